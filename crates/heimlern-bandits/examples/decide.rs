@@ -1,7 +1,7 @@
 use std::io::{self, Read};
 
 use heimlern_bandits::RemindBandit;
-use heimlern_core::{Chosen, Context, Decision, Policy};
+use heimlern_core::{kind, Chosen, Context, Decision, Policy};
 use serde::Serialize;
 use serde_json::{json, Value};
 use time::{format_description::well_known::Rfc3339, OffsetDateTime};
@@ -15,7 +15,7 @@ fn iso8601_now() -> String {
 fn parse_context(input: &str) -> Context {
     if input.trim().is_empty() {
         return Context {
-            kind: "reminder".into(),
+            kind: kind::REMINDER.into(),
             features: json!({}),
         };
     }
@@ -28,7 +28,7 @@ fn parse_context(input: &str) -> Context {
         let kind = obj
             .remove("kind")
             .and_then(|v| v.as_str().map(std::borrow::ToOwned::to_owned))
-            .unwrap_or_else(|| "reminder".to_string());
+            .unwrap_or_else(|| kind::REMINDER.to_string());
 
         let features = match obj.remove("features") {
             Some(value) => value,
@@ -72,9 +72,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Populate the 'chosen' field for strict schema compliance
     if decision.chosen.is_none() {
-        decision.chosen = Some(Chosen {
-            action: decision.action.clone(),
-        });
+        decision.chosen = Some(Chosen::new(decision.action.clone()));
     }
 
     let record = PolicyDecisionRecord {
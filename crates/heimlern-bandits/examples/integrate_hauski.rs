@@ -1,11 +1,11 @@
 use heimlern_bandits::RemindBandit;
-use heimlern_core::{Context, Policy};
+use heimlern_core::{kind, Context, Policy};
 use time::{format_description::well_known::Rfc3339, OffsetDateTime};
 
 fn main() {
     let mut p = RemindBandit::default();
     let ctx = Context {
-        kind: "reminder".into(),
+        kind: kind::REMINDER.into(),
         features: serde_json::json!({"load": 0.3}),
     };
     let d = p.decide(&ctx);
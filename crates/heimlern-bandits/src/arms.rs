@@ -0,0 +1,110 @@
+//! Protokoll der Arm-Verwaltung (hinzufügen, entfernen, umbenennen).
+//!
+//! Änderungen über [`crate::RemindBandit::add_arm`] und Verwandte landen als
+//! [`ArmChange`] im Snapshot unter `arm_log`, damit nachvollziehbar bleibt,
+//! warum Statistiken eines Arms fehlen oder unter neuem Namen weiterlaufen.
+//!
+//! Daneben trägt jeder Arm optional [`ArmMeta`] – Anzeigename, Beschreibung,
+//! Tags –, damit Oberflächen Slots nicht selbst übersetzen müssen.
+
+use crate::EvictionStrategy;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Höchstzahl protokollierter Änderungen; ältere Einträge fallen heraus.
+pub const MAX_ARM_LOG: usize = 256;
+
+/// Höchstlänge von Anzeigename, Beschreibung und einzelnen Tags in Bytes.
+pub const MAX_ARM_META_LEN: usize = 256;
+/// Höchstzahl an Tags je Arm.
+pub const MAX_ARM_TAGS: usize = 16;
+
+/// Beschreibende Angaben zu einem Arm; fließen nicht in die Schätzung ein.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArmMeta {
+    /// Anzeigename, z. B. „Morgens“ für `morning`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl ArmMeta {
+    /// Metadaten nur mit Anzeigename.
+    #[must_use]
+    pub fn label(label: impl Into<String>) -> Self {
+        Self {
+            label: Some(label.into()),
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// `true`, wenn alle Angaben die Grenzen einhalten.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        let fits = |s: &String| s.len() <= MAX_ARM_META_LEN;
+        self.label.as_ref().is_none_or(fits)
+            && self.description.as_ref().is_none_or(fits)
+            && self.tags.len() <= MAX_ARM_TAGS
+            && self.tags.iter().all(|t| !t.is_empty() && fits(t))
+    }
+
+    /// Einträge für `Decision.why`.
+    pub(crate) fn why(&self) -> impl Iterator<Item = String> + '_ {
+        let label = self.label.as_ref().map(|l| format!("label: {l}"));
+        let tags = (!self.tags.is_empty()).then(|| format!("tags: {}", self.tags.join(", ")));
+        label.into_iter().chain(tags)
+    }
+}
+
+/// Eine Änderung an der Arm-Menge.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum ArmChange {
+    /// Neuer Arm ohne Statistik.
+    Added { arm: String, ts: String },
+    /// Arm samt Statistik entfernt; `pulls` hält fest, wie viel Wissen verloren ging.
+    Removed { arm: String, pulls: u64, ts: String },
+    /// Arm an der Kapazitätsgrenze nach `strategy` verdrängt.
+    Evicted {
+        arm: String,
+        pulls: u64,
+        strategy: EvictionStrategy,
+        ts: String,
+    },
+    /// Arm umbenannt, die Statistik läuft unter `to` weiter.
+    Renamed {
+        from: String,
+        to: String,
+        ts: String,
+    },
+}
+
+/// Hängt `change` an und kürzt das Protokoll auf [`MAX_ARM_LOG`].
+pub(crate) fn record(log: &mut Vec<ArmChange>, change: ArmChange) {
+    log.push(change);
+    if log.len() > MAX_ARM_LOG {
+        log.drain(..log.len() - MAX_ARM_LOG);
+    }
+}
+
+/// Verschiebt den Eintrag `from` nach `to` (falls vorhanden).
+pub(crate) fn rename_key<V>(map: &mut BTreeMap<String, V>, from: &str, to: &str) {
+    if let Some(value) = map.remove(from) {
+        map.insert(to.to_string(), value);
+    }
+}
@@ -0,0 +1,74 @@
+//! Protokoll der jüngsten Entscheidungen.
+//!
+//! Mit [`AuditConfig`] hält der Bandit für die letzten `capacity`
+//! Entscheidungen fest, welcher Arm auf welchem Weg (Aufwärmphase, Exploration,
+//! Ausnutzung) gewählt wurde und welche Schätzwerte alle Arme in diesem Moment
+//! hatten. So lässt sich im Nachhinein beantworten, warum an einer Stelle
+//! exploriert wurde. In den Snapshot wandert das Protokoll nur mit `persist`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Größte zulässige Protokolllänge.
+pub const MAX_AUDIT_ENTRIES: usize = 1024;
+
+/// Konfiguration des Entscheidungsprotokolls.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditConfig {
+    /// Anzahl der aufbewahrten Entscheidungen (1..=[`MAX_AUDIT_ENTRIES`]).
+    pub capacity: usize,
+    /// Protokoll auch im Snapshot ablegen.
+    #[serde(default)]
+    pub persist: bool,
+}
+
+impl AuditConfig {
+    /// Hält die letzten `capacity` Entscheidungen nur zur Laufzeit.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            persist: false,
+        }
+    }
+
+    /// Legt das Protokoll zusätzlich im Snapshot ab.
+    #[must_use]
+    pub fn persisted(mut self) -> Self {
+        self.persist = true;
+        self
+    }
+}
+
+/// Weg, auf dem ein Arm gewählt wurde.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionMode {
+    Warmup,
+    Explore,
+    Exploit,
+}
+
+/// Eine protokollierte Entscheidung.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecisionRecord {
+    /// Zeitpunkt (RFC 3339, UTC).
+    pub ts: String,
+    pub arm: String,
+    pub mode: DecisionMode,
+    /// Schätzwerte aller Arme vor der Wahl (ohne Ermüdungsabschlag).
+    pub estimates: BTreeMap<String, f32>,
+}
+
+/// Hängt `record` an und kürzt `log` auf `capacity` Einträge.
+pub(crate) fn record(log: &mut Vec<DecisionRecord>, capacity: usize, record: DecisionRecord) {
+    log.push(record);
+    trim(log, capacity);
+}
+
+/// Verwirft die ältesten Einträge jenseits von `capacity`.
+pub(crate) fn trim(log: &mut Vec<DecisionRecord>, capacity: usize) {
+    if log.len() > capacity {
+        log.drain(..log.len() - capacity);
+    }
+}
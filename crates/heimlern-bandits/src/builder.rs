@@ -0,0 +1,366 @@
+//! Builder für [`RemindBandit`].
+//!
+//! Bündelt die optionalen Erweiterungen (Aufwärmphase, Ermüdung, Abkühlzeit,
+//! Timing, Fenster, Recency, Zerfall), Vorwissen und Metadaten je Arm und den
+//! Seed in einer Aufrufkette und prüft Slots, Priors und Konfigurationen beim
+//! Bauen, statt sie still zu bereinigen. Geladene Snapshots durchlaufen nach der
+//! Migration dieselben Konfigurationsprüfungen ([`Extensions`]).
+
+use crate::error::{BanditError, Result};
+use crate::seed::PolicyRng;
+use crate::{
+    ArmMeta, ArmPrior, AuditConfig, CooldownConfig, DecayConfig, EvictionStrategy, FatigueConfig,
+    RecencyConfig, RemindBandit, TimingConfig, WarmupConfig, WindowConfig, MAX_ARMS,
+    MAX_ARM_NAME_LEN, MAX_AUDIT_ENTRIES, MAX_WINDOW,
+};
+
+/// Aufrufkette für einen [`RemindBandit`], siehe [`RemindBandit::builder`].
+#[derive(Debug, Clone, Default)]
+#[must_use]
+pub struct RemindBanditBuilder {
+    epsilon: Option<f32>,
+    epsilon_by_kind: Vec<(String, f32)>,
+    namespace: Option<String>,
+    slots: Option<Vec<String>>,
+    seed: Option<u64>,
+    warmup: Option<WarmupConfig>,
+    fatigue: Option<FatigueConfig>,
+    cooldown: Option<CooldownConfig>,
+    timing: Option<TimingConfig>,
+    window: Option<WindowConfig>,
+    recency: Option<RecencyConfig>,
+    decay: Option<DecayConfig>,
+    priors: Vec<(String, ArmPrior)>,
+    meta: Vec<(String, ArmMeta)>,
+    eviction: Option<EvictionStrategy>,
+    optimistic_init: Option<f32>,
+    audit: Option<AuditConfig>,
+}
+
+impl RemindBanditBuilder {
+    /// Explorationsrate (0.0..=1.0, Standard 0.2).
+    pub fn epsilon(mut self, epsilon: f32) -> Self {
+        self.epsilon = Some(epsilon);
+        self
+    }
+
+    /// Eigene Explorationsrate für Kontexte der Art `kind`.
+    pub fn epsilon_for(mut self, kind: impl Into<String>, epsilon: f32) -> Self {
+        self.epsilon_by_kind.push((kind.into(), epsilon));
+        self
+    }
+
+    /// Namensraum der Aktionen; Standard ist `remind`.
+    pub fn namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// Slots (Arme); Standard sind `morning`, `afternoon`, `evening`.
+    pub fn slots<I, S>(mut self, slots: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.slots = Some(slots.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Fester Seed für reproduzierbare Entscheidungen.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn warmup(mut self, config: WarmupConfig) -> Self {
+        self.warmup = Some(config);
+        self
+    }
+
+    pub fn fatigue(mut self, config: FatigueConfig) -> Self {
+        self.fatigue = Some(config);
+        self
+    }
+
+    pub fn cooldown(mut self, config: CooldownConfig) -> Self {
+        self.cooldown = Some(config);
+        self
+    }
+
+    pub fn timing(mut self, config: TimingConfig) -> Self {
+        self.timing = Some(config);
+        self
+    }
+
+    pub fn window(mut self, config: WindowConfig) -> Self {
+        self.window = Some(config);
+        self
+    }
+
+    pub fn recency(mut self, config: RecencyConfig) -> Self {
+        self.recency = Some(config);
+        self
+    }
+
+    /// Lässt Ziehungen und Reward-Summen mit der Zeit zerfallen, z. B. `DecayConfig::daily(0.99)`.
+    pub fn decay(mut self, config: DecayConfig) -> Self {
+        self.decay = Some(config);
+        self
+    }
+
+    /// Verdrängt an der Kapazitätsgrenze einen Arm nach `strategy`, statt neue Slots zu verwerfen.
+    pub fn eviction(mut self, strategy: EvictionStrategy) -> Self {
+        self.eviction = Some(strategy);
+        self
+    }
+
+    /// Startwert für Slots ohne Beobachtung, z. B. 1.0 bei Rewards aus `[0, 1]`.
+    pub fn optimistic_init(mut self, value: f32) -> Self {
+        self.optimistic_init = Some(value);
+        self
+    }
+
+    /// Protokolliert die jüngsten Entscheidungen, siehe [`RemindBandit::decision_log`].
+    pub fn audit(mut self, config: AuditConfig) -> Self {
+        self.audit = Some(config);
+        self
+    }
+
+    /// Warmstart für `arm`: wirkt wie `pseudo_count` Beobachtungen mit Mittelwert `mean`.
+    pub fn prior(mut self, arm: impl Into<String>, pseudo_count: f64, mean: f64) -> Self {
+        self.priors
+            .push((arm.into(), ArmPrior::new(pseudo_count, mean)));
+        self
+    }
+
+    /// Anzeigename, Beschreibung und Tags für `arm`.
+    pub fn arm_meta(mut self, arm: impl Into<String>, meta: ArmMeta) -> Self {
+        self.meta.push((arm.into(), meta));
+        self
+    }
+
+    /// Baut den Banditen.
+    ///
+    /// # Errors
+    /// [`BanditError::InvalidArm`] bei leerer Slot-Liste, doppelten, leeren
+    /// oder zu langen Slot-Namen oder mehr als der zulässigen Zahl an Slots;
+    /// [`BanditError::InvalidParameter`] bei einem `epsilon` außerhalb von 0.0..=1.0
+    /// oder für eine leere Kontextart, einer Erweiterung mit Werten, die sonst still
+    /// korrigiert würden (Fenstergröße, Halbwertszeiten, Timing-Raster) oder die
+    /// nichts bewirken (Abkühlzeit ohne Grenze), mehr als einer der sich
+    /// ausschließenden Schätzungen Fenster, Recency und Zerfall, Aufwärmgewichten
+    /// oder Priors für unbekannte Slots oder einem ungültigen Prior; Metadaten wie
+    /// bei [`RemindBandit::set_arm_meta`].
+    pub fn build(self) -> Result<RemindBandit> {
+        let mut bandit = RemindBandit::default();
+        if let Some(epsilon) = self.epsilon {
+            if !(0.0..=1.0).contains(&epsilon) {
+                return Err(BanditError::InvalidParameter(format!(
+                    "epsilon must be within 0.0..=1.0, got {epsilon}"
+                )));
+            }
+            bandit.epsilon = epsilon;
+        }
+        for (kind, epsilon) in self.epsilon_by_kind {
+            if kind.is_empty() {
+                return Err(BanditError::InvalidParameter(
+                    "epsilon_for needs a non-empty context kind".into(),
+                ));
+            }
+            if !(0.0..=1.0).contains(&epsilon) {
+                return Err(BanditError::InvalidParameter(format!(
+                    "epsilon for '{kind}' must be within 0.0..=1.0, got {epsilon}"
+                )));
+            }
+            bandit.epsilon_by_kind.insert(kind, epsilon);
+        }
+        if let Some(namespace) = &self.namespace {
+            bandit.set_namespace(namespace)?;
+        }
+        if let Some(slots) = self.slots {
+            check_slots(&slots)?;
+            bandit.values = crate::empty_values(&slots);
+            bandit.slots = slots;
+        }
+        Extensions {
+            slots: &bandit.slots,
+            optimistic_init: self.optimistic_init,
+            warmup: self.warmup.as_ref(),
+            fatigue: self.fatigue.as_ref(),
+            cooldown: self.cooldown.as_ref(),
+            timing: self.timing.as_ref(),
+            window: self.window.as_ref(),
+            recency: self.recency.as_ref(),
+            decay: self.decay.as_ref(),
+            audit: self.audit.as_ref(),
+        }
+        .check()?;
+        bandit.warmup = self.warmup;
+        bandit.fatigue = self.fatigue;
+        bandit.cooldown = self.cooldown;
+        bandit.timing = self.timing;
+        bandit.window = self.window;
+        bandit.recency = self.recency;
+        bandit.decay = self.decay;
+        bandit.eviction = self.eviction;
+        bandit.optimistic_init = self.optimistic_init;
+        bandit.audit = self.audit;
+        for (arm, prior) in self.priors {
+            if !prior.is_valid() {
+                return Err(BanditError::InvalidParameter(format!(
+                    "prior for '{arm}' needs a finite mean and a finite, non-negative pseudo count"
+                )));
+            }
+            if !bandit.slots.contains(&arm) {
+                return Err(BanditError::InvalidParameter(format!(
+                    "prior for unknown slot '{arm}'"
+                )));
+            }
+            bandit.priors.insert(arm, prior);
+        }
+        for (arm, meta) in self.meta {
+            bandit.set_arm_meta(&arm, meta)?;
+        }
+        bandit.rng = PolicyRng::new(self.seed);
+        Ok(bandit)
+    }
+}
+
+/// Erweiterungen eines Banditen, wie sie der Builder und das Laden eines
+/// Snapshots gleichermaßen prüfen.
+pub(crate) struct Extensions<'a> {
+    pub(crate) slots: &'a [String],
+    pub(crate) optimistic_init: Option<f32>,
+    pub(crate) warmup: Option<&'a WarmupConfig>,
+    pub(crate) fatigue: Option<&'a FatigueConfig>,
+    pub(crate) cooldown: Option<&'a CooldownConfig>,
+    pub(crate) timing: Option<&'a TimingConfig>,
+    pub(crate) window: Option<&'a WindowConfig>,
+    pub(crate) recency: Option<&'a RecencyConfig>,
+    pub(crate) decay: Option<&'a DecayConfig>,
+    pub(crate) audit: Option<&'a AuditConfig>,
+}
+
+impl Extensions<'_> {
+    /// Lehnt Werte ab, die sonst still korrigiert würden oder nichts bewirken.
+    ///
+    /// # Errors
+    /// [`BanditError::InvalidParameter`] mit der ersten verletzten Bedingung.
+    pub(crate) fn check(&self) -> Result<()> {
+        if let Some(value) = self.optimistic_init {
+            if !value.is_finite() {
+                return Err(BanditError::InvalidParameter(format!(
+                    "optimistic_init must be finite, got {value}"
+                )));
+            }
+        }
+        if let Some(cfg) = self.warmup {
+            if let Some(arm) = cfg.weights.keys().find(|arm| !self.slots.contains(arm)) {
+                return Err(BanditError::InvalidParameter(format!(
+                    "warmup weight for unknown slot '{arm}'"
+                )));
+            }
+        }
+        if let Some(cfg) = self.cooldown {
+            let limits = [cfg.decisions, cfg.seconds];
+            if limits.iter().all(Option::is_none) || limits.contains(&Some(0)) {
+                return Err(BanditError::InvalidParameter(
+                    "cooldown needs decisions and/or seconds, each > 0".into(),
+                ));
+            }
+        }
+        // `observed_mean` nutzt nur die erste dieser Schätzungen; die übrigen
+        // liefen unbemerkt mit.
+        let estimators = [
+            self.window.is_some().then_some("window"),
+            self.recency.is_some().then_some("recency"),
+            self.decay.is_some().then_some("decay"),
+        ];
+        let estimators: Vec<&str> = estimators.into_iter().flatten().collect();
+        if estimators.len() > 1 {
+            return Err(BanditError::InvalidParameter(format!(
+                "{} cannot be combined; configure at most one",
+                estimators.join(", ")
+            )));
+        }
+        if let Some(cfg) = self.fatigue {
+            if !(cfg.half_life.is_finite() && cfg.half_life > 0.0 && cfg.penalty.is_finite()) {
+                return Err(BanditError::InvalidParameter(format!(
+                    "fatigue needs a positive half_life and a finite penalty, got {} and {}",
+                    cfg.half_life, cfg.penalty
+                )));
+            }
+        }
+        if let Some(cfg) = self.timing {
+            if cfg.step_minutes == 0
+                || !(cfg.bandwidth_minutes.is_finite() && cfg.bandwidth_minutes > 0.0)
+            {
+                return Err(BanditError::InvalidParameter(
+                    "timing needs step_minutes > 0 and a positive bandwidth".into(),
+                ));
+            }
+        }
+        if let Some(cfg) = self.window {
+            if !(1..=MAX_WINDOW).contains(&cfg.size) {
+                return Err(BanditError::InvalidParameter(format!(
+                    "window size must be within 1..={MAX_WINDOW}, got {}",
+                    cfg.size
+                )));
+            }
+        }
+        if let Some(cfg) = self.recency {
+            let range = RecencyConfig::MIN_HALF_LIFE..=RecencyConfig::MAX_HALF_LIFE;
+            if !range.contains(&cfg.half_life) {
+                return Err(BanditError::InvalidParameter(format!(
+                    "recency half_life must be within {}..={}, got {}",
+                    RecencyConfig::MIN_HALF_LIFE,
+                    RecencyConfig::MAX_HALF_LIFE,
+                    cfg.half_life
+                )));
+            }
+        }
+        if let Some(cfg) = self.decay {
+            if !cfg.is_valid() {
+                return Err(BanditError::InvalidParameter(format!(
+                    "decay needs a factor within (0.0, 1.0] and period_seconds > 0, got {} and {}",
+                    cfg.factor, cfg.period_seconds
+                )));
+            }
+        }
+        if let Some(cfg) = self.audit {
+            if !(1..=MAX_AUDIT_ENTRIES).contains(&cfg.capacity) {
+                return Err(BanditError::InvalidParameter(format!(
+                    "audit capacity must be within 1..={MAX_AUDIT_ENTRIES}, got {}",
+                    cfg.capacity
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn check_slots(slots: &[String]) -> Result<()> {
+    if slots.is_empty() {
+        return Err(BanditError::InvalidArm(
+            "at least one slot is required".into(),
+        ));
+    }
+    if slots.len() > MAX_ARMS {
+        return Err(BanditError::InvalidArm(format!(
+            "{} slots exceed the limit of {MAX_ARMS}",
+            slots.len()
+        )));
+    }
+    for (i, slot) in slots.iter().enumerate() {
+        if slot.is_empty() || slot.len() > MAX_ARM_NAME_LEN {
+            return Err(BanditError::InvalidArm(format!(
+                "slot name must have 1 to {MAX_ARM_NAME_LEN} bytes, got {}",
+                slot.len()
+            )));
+        }
+        if slots[..i].contains(slot) {
+            return Err(BanditError::InvalidArm(format!("duplicate slot '{slot}'")));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,127 @@
+//! Abkühlzeit je Arm.
+//!
+//! Nach einer Wahl ist ein Arm für eine feste Zahl von Entscheidungen und/oder
+//! eine feste Dauer gesperrt – etwa damit dieselbe Erinnerung nicht zweimal
+//! hintereinander feuert. Anders als beim Ermüdungsmodell gibt es keine
+//! Abstufung: Ein Arm ist gesperrt oder wählbar. Der Zustand hält je Arm den
+//! Entscheidungszähler und den Zeitpunkt (Unix-Sekunden) der letzten Wahl fest.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Konfiguration der Abkühlzeit; gesperrt wird, solange eine der Grenzen greift.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CooldownConfig {
+    /// Anzahl der auf eine Wahl folgenden Entscheidungen, in denen der Arm gesperrt ist.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decisions: Option<u64>,
+    /// Sperrdauer nach einer Wahl in Sekunden.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seconds: Option<u64>,
+}
+
+impl CooldownConfig {
+    /// Sperre für die nächsten `decisions` Entscheidungen.
+    #[must_use]
+    pub fn decisions(decisions: u64) -> Self {
+        Self {
+            decisions: Some(decisions),
+            seconds: None,
+        }
+    }
+
+    /// Sperre für `seconds` Sekunden.
+    #[must_use]
+    pub fn seconds(seconds: u64) -> Self {
+        Self {
+            decisions: None,
+            seconds: Some(seconds),
+        }
+    }
+
+    /// Ergänzt eine Sperrdauer in Sekunden.
+    #[must_use]
+    pub fn and_seconds(mut self, seconds: u64) -> Self {
+        self.seconds = Some(seconds);
+        self
+    }
+}
+
+/// Letzte Wahl eines Arms.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArmCooldown {
+    /// Entscheidungszähler der letzten Wahl.
+    pub last_tick: u64,
+    /// Zeitpunkt der letzten Wahl (Unix-Sekunden, UTC).
+    pub last_chosen: i64,
+}
+
+/// Laufender Abkühlzustand aller Arme.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CooldownState {
+    /// Anzahl der bisher protokollierten Wahlen (logische Uhr).
+    pub tick: u64,
+    #[serde(default)]
+    pub arms: BTreeMap<String, ArmCooldown>,
+}
+
+impl CooldownState {
+    /// `true`, wenn `arm` zum Zeitpunkt `now` (Unix-Sekunden) gesperrt ist.
+    #[must_use]
+    pub fn is_cooling(&self, config: &CooldownConfig, arm: &str, now: i64) -> bool {
+        let Some(last) = self.arms.get(arm) else {
+            return false;
+        };
+        let by_decisions = config
+            .decisions
+            .is_some_and(|n| self.tick.saturating_sub(last.last_tick) < n);
+        let by_time = config.seconds.is_some_and(|s| {
+            let elapsed = now.saturating_sub(last.last_chosen);
+            u64::try_from(elapsed).map_or(true, |elapsed| elapsed < s)
+        });
+        by_decisions || by_time
+    }
+
+    /// Protokolliert die Wahl von `arm` zum Zeitpunkt `now`.
+    pub fn choose(&mut self, arm: &str, now: i64) {
+        self.tick = self.tick.saturating_add(1);
+        self.arms.insert(
+            arm.to_string(),
+            ArmCooldown {
+                last_tick: self.tick,
+                last_chosen: now,
+            },
+        );
+    }
+
+    /// Entfernt Einträge für Arme, die nicht mehr existieren.
+    pub fn retain_arms(&mut self, arms: &[String]) {
+        self.arms.retain(|name, _| arms.contains(name));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arm_is_blocked_for_decisions_and_duration() {
+        let cfg = CooldownConfig::decisions(2);
+        let mut state = CooldownState::default();
+        state.choose("a", 0);
+        assert!(state.is_cooling(&cfg, "a", 0));
+        assert!(!state.is_cooling(&cfg, "b", 0));
+        state.choose("b", 0);
+        assert!(state.is_cooling(&cfg, "a", 0));
+        state.choose("c", 0);
+        // Zwei Entscheidungen nach der Wahl ist "a" wieder frei.
+        assert!(!state.is_cooling(&cfg, "a", 0));
+
+        let cfg = CooldownConfig::seconds(60);
+        state.choose("a", 1_000);
+        assert!(state.is_cooling(&cfg, "a", 1_059));
+        assert!(!state.is_cooling(&cfg, "a", 1_060));
+        // Uhr rückwärts: lieber gesperrt lassen.
+        assert!(state.is_cooling(&cfg, "a", 900));
+    }
+}
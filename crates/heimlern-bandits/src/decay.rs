@@ -0,0 +1,175 @@
+//! Zeitbasierter Zerfall der Statistiken je Arm.
+//!
+//! Ziehungen und Reward-Summe eines Arms schrumpfen je abgelaufener Periode um
+//! den Faktor `factor`, z. B. um 1 % pro Tag. So kann eine lang laufende Policy
+//! nicht auf ewig von sehr alten Beobachtungen beherrscht werden. Anders als
+//! bei Recency ([`crate::RecencyConfig`]) zählt die verstrichene Zeit, nicht
+//! die Zahl weiterer Feedbacks – auch ein Arm, der lange kein Feedback bekommt,
+//! verliert an Gewicht.
+//!
+//! Der Zerfall wird verzögert angewandt: Je Arm hält der Zustand den Zeitpunkt
+//! (Unix-Sekunden) der letzten Fortschreibung fest; beim nächsten Feedback
+//! wird der Faktor für die gesamte Zwischenzeit auf einmal nachgeholt.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Sekunden pro Tag, die Standardperiode.
+const DAY_SECONDS: u64 = 86_400;
+
+/// Konfiguration des Zerfalls.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecayConfig {
+    /// Faktor je Periode (`0.0 < factor <= 1.0`).
+    pub factor: f64,
+    /// Länge einer Periode in Sekunden (> 0).
+    #[serde(default = "default_period")]
+    pub period_seconds: u64,
+}
+
+fn default_period() -> u64 {
+    DAY_SECONDS
+}
+
+impl DecayConfig {
+    #[must_use]
+    pub fn new(factor: f64, period_seconds: u64) -> Self {
+        Self {
+            factor,
+            period_seconds,
+        }
+    }
+
+    /// Zerfall um `factor` je Tag.
+    #[must_use]
+    pub fn daily(factor: f64) -> Self {
+        Self::new(factor, DAY_SECONDS)
+    }
+
+    /// `true`, wenn Faktor und Periode zulässig sind.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.factor.is_finite()
+            && self.factor > 0.0
+            && self.factor <= 1.0
+            && self.period_seconds > 0
+    }
+
+    /// Gesamtfaktor für `elapsed` Sekunden (1.0 bei ungültiger Konfiguration
+    /// oder rückwärts laufender Uhr).
+    #[must_use]
+    pub fn multiplier(&self, elapsed: i64) -> f64 {
+        if !self.is_valid() || elapsed <= 0 {
+            return 1.0;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let periods = elapsed as f64 / self.period_seconds as f64;
+        self.factor.powf(periods)
+    }
+}
+
+/// Zerfallende Statistik eines Arms.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct DecayedArm {
+    /// Zerfallene Anzahl der Ziehungen.
+    pub pulls: f64,
+    /// Zerfallene Reward-Summe.
+    pub sum: f64,
+    /// Zeitpunkt der letzten Fortschreibung (Unix-Sekunden, UTC).
+    pub updated: i64,
+}
+
+/// Zerfallende Statistiken aller Arme.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DecayState {
+    #[serde(default)]
+    pub arms: BTreeMap<String, DecayedArm>,
+}
+
+impl DecayState {
+    /// Holt den Zerfall von `arm` bis `now` nach und nimmt `reward` auf.
+    pub fn observe(&mut self, config: &DecayConfig, arm: &str, reward: f64, now: i64) {
+        let entry = self.arms.entry(arm.to_string()).or_insert(DecayedArm {
+            updated: now,
+            ..DecayedArm::default()
+        });
+        let m = config.multiplier(now.saturating_sub(entry.updated));
+        entry.pulls = entry.pulls * m + 1.0;
+        entry.sum = entry.sum * m + reward;
+        entry.updated = entry.updated.max(now);
+    }
+
+    /// Zerfallene Anzahl der Ziehungen von `arm` zum Zeitpunkt `now`.
+    #[must_use]
+    pub fn pulls(&self, config: &DecayConfig, arm: &str, now: i64) -> f64 {
+        self.arms.get(arm).map_or(0.0, |a| {
+            a.pulls * config.multiplier(now.saturating_sub(a.updated))
+        })
+    }
+
+    /// Mittelwert von `arm` (`None` ohne Beobachtungen); der Zerfall trifft
+    /// Ziehungen und Summe gleichermaßen und ändert ihn daher nicht.
+    #[must_use]
+    pub fn mean(&self, arm: &str) -> Option<f64> {
+        self.arms
+            .get(arm)
+            .filter(|a| a.pulls > 0.0)
+            .map(|a| a.sum / a.pulls)
+    }
+
+    /// Übernimmt kumulierte Statistiken `(pulls, summe)` mit Stand `now` für
+    /// Arme ohne Zerfallszustand, etwa wenn der Zerfall nachträglich aktiviert wird.
+    pub fn seed_missing(&mut self, totals: &BTreeMap<String, (u64, f64)>, now: i64) {
+        for (arm, (pulls, sum)) in totals {
+            if *pulls == 0 || self.arms.contains_key(arm) {
+                continue;
+            }
+            #[allow(clippy::cast_precision_loss)]
+            self.arms.insert(
+                arm.clone(),
+                DecayedArm {
+                    pulls: *pulls as f64,
+                    sum: *sum,
+                    updated: now,
+                },
+            );
+        }
+    }
+
+    /// Verwirft ungültige Einträge und Arme, die nicht mehr existieren.
+    pub fn sanitize(&mut self, arms: &[String]) {
+        self.arms.retain(|name, a| {
+            arms.contains(name) && a.pulls.is_finite() && a.pulls >= 0.0 && a.sum.is_finite()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statistics_decay_lazily_with_elapsed_time() {
+        let cfg = DecayConfig::daily(0.5);
+        let mut state = DecayState::default();
+        let day = 86_400;
+        state.observe(&cfg, "a", 1.0, 0);
+        state.observe(&cfg, "a", 1.0, 0);
+        assert!((state.pulls(&cfg, "a", 2 * day) - 0.5).abs() < 1e-9);
+        // Der Mittelwert bleibt beim reinen Altern gleich.
+        assert_eq!(state.mean("a"), Some(1.0));
+
+        // Nach zwei Tagen zählen die alten Einsen nur noch ein Viertel.
+        state.observe(&cfg, "a", 0.0, 2 * day);
+        let Some(mean) = state.mean("a") else {
+            panic!("mean missing");
+        };
+        assert!((mean - 0.5 / 1.5).abs() < 1e-9);
+
+        // Uhr rückwärts: kein Zerfall, kein Zurückdatieren.
+        state.observe(&cfg, "a", 0.0, day);
+        assert_eq!(state.arms["a"].updated, 2 * day);
+        assert!(!DecayConfig::daily(1.5).is_valid());
+        assert!(!DecayConfig::new(0.9, 0).is_valid());
+    }
+}
@@ -0,0 +1,71 @@
+//! Stichproben aus Normal-, Gamma- und Beta-Verteilungen.
+//!
+//! `rand` 0.8 bringt ohne `rand_distr` nur Gleichverteilungen mit; für
+//! Thompson Sampling genügen diese drei Verteilungen, daher werden sie hier
+//! direkt implementiert (Box-Muller bzw. Marsaglia-Tsang).
+
+use rand::Rng;
+
+/// Standardnormalverteilte Stichprobe (Box-Muller).
+pub(crate) fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    // `1 - gen()` liegt in (0, 1], der Logarithmus ist damit endlich.
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+}
+
+/// Gamma(`shape`, 1)-verteilte Stichprobe (Marsaglia-Tsang).
+pub(crate) fn gamma<R: Rng + ?Sized>(rng: &mut R, shape: f64) -> f64 {
+    if !(shape.is_finite() && shape > 0.0) {
+        return 0.0;
+    }
+    if shape < 1.0 {
+        // Boost: Gamma(a) = Gamma(a + 1) · U^(1/a)
+        let u: f64 = 1.0 - rng.gen::<f64>();
+        return gamma(rng, shape + 1.0) * u.powf(1.0 / shape);
+    }
+    let d = shape - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+    loop {
+        let x = standard_normal(rng);
+        let v = (1.0 + c * x).powi(3);
+        if v <= 0.0 {
+            continue;
+        }
+        let u: f64 = 1.0 - rng.gen::<f64>();
+        if u.ln() < 0.5 * x * x + d - d * v + d * v.ln() {
+            return d * v;
+        }
+    }
+}
+
+/// Beta(`alpha`, `beta`)-verteilte Stichprobe.
+pub(crate) fn beta<R: Rng + ?Sized>(rng: &mut R, alpha: f64, beta: f64) -> f64 {
+    let x = gamma(rng, alpha);
+    let y = gamma(rng, beta);
+    if x + y > 0.0 {
+        x / (x + y)
+    } else {
+        0.5
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn sample_means_match_distributions() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let n = 20_000;
+        #[allow(clippy::cast_precision_loss)]
+        let mean = |f: &mut dyn FnMut() -> f64| (0..n).map(|_| f()).sum::<f64>() / n as f64;
+
+        assert!(mean(&mut || standard_normal(&mut rng)).abs() < 0.05);
+        assert!((mean(&mut || gamma(&mut rng, 0.5)) - 0.5).abs() < 0.05);
+        assert!((mean(&mut || gamma(&mut rng, 3.0)) - 3.0).abs() < 0.1);
+        assert!((mean(&mut || beta(&mut rng, 2.0, 6.0)) - 0.25).abs() < 0.02);
+    }
+}
@@ -6,8 +6,61 @@ pub enum BanditError {
     Snapshot(#[from] serde_json::Error),
     #[error("Invalid action: {0}")]
     InvalidAction(String),
+    #[error("Invalid arm: {0}")]
+    InvalidArm(String),
+    #[error("Invalid parameter: {0}")]
+    InvalidParameter(String),
+    #[error("Snapshot belongs to policy '{found}', expected '{expected}'")]
+    WrongPolicy {
+        expected: &'static str,
+        found: String,
+    },
+    #[error("Invalid snapshot: {0}")]
+    InvalidSnapshot(String),
+    #[error("Unsupported snapshot version '{found}' (this build reads up to {supported})")]
+    UnsupportedVersion {
+        found: String,
+        supported: &'static str,
+    },
     #[error("Internal error: {0}")]
     Internal(&'static str),
 }
 
 pub type Result<T> = std::result::Result<T, BanditError>;
+
+impl From<BanditError> for heimlern_core::PolicyError {
+    fn from(err: BanditError) -> Self {
+        match err {
+            BanditError::WrongPolicy { expected, found } => Self::WrongPolicy {
+                expected: expected.to_string(),
+                found,
+            },
+            BanditError::UnsupportedVersion { found, supported } => {
+                Self::UnsupportedVersion(format!("'{found}' (this build reads up to {supported})"))
+            }
+            BanditError::Snapshot(e) => Self::InvalidSnapshot(e.to_string()),
+            BanditError::InvalidSnapshot(reason) => Self::InvalidSnapshot(reason),
+            BanditError::InvalidAction(reason) | BanditError::InvalidArm(reason) => {
+                Self::InvalidAction(reason)
+            }
+            BanditError::InvalidParameter(reason) => Self::InvalidSnapshot(reason),
+            BanditError::Internal(reason) => Self::NoDecision(reason.to_string()),
+        }
+    }
+}
+
+impl From<BanditError> for heimlern_core::HeimlernError {
+    fn from(err: BanditError) -> Self {
+        let base = match &err {
+            BanditError::Snapshot(_)
+            | BanditError::WrongPolicy { .. }
+            | BanditError::InvalidSnapshot(_)
+            | BanditError::UnsupportedVersion { .. } => Self::contract(err.to_string()),
+            BanditError::InvalidAction(_)
+            | BanditError::InvalidArm(_)
+            | BanditError::InvalidParameter(_)
+            | BanditError::Internal(_) => Self::policy(err.to_string()),
+        };
+        base.with_source(err)
+    }
+}
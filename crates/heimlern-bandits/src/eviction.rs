@@ -0,0 +1,86 @@
+//! Verdrängung von Armen, wenn die Höchstzahl an Armen erreicht ist.
+//!
+//! Ohne Strategie werden neue Slots an der Kapazitätsgrenze verworfen – eine
+//! langlaufende Installation mit vielen dynamischen Aktionen friert ihre
+//! Arm-Menge dann ein. Mit einer [`EvictionStrategy`] macht der Bandit Platz:
+//! Er entfernt den am längsten ungenutzten Arm, den mit den wenigsten Ziehungen
+//! oder den mit dem niedrigsten Schätzwert. Jede Verdrängung landet als
+//! [`crate::ArmChange::Evicted`] im Arm-Protokoll.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Welcher Arm bei voller Kapazität weichen muss.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EvictionStrategy {
+    /// Am längsten nicht gewählter oder bewerteter Arm.
+    Lru,
+    /// Arm mit den wenigsten Ziehungen.
+    LowestPulls,
+    /// Arm mit dem niedrigsten mittleren Reward.
+    LowestValue,
+}
+
+/// Letzte Nutzung je Arm (logische Uhr über Entscheidungen und Feedbacks).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageState {
+    pub tick: u64,
+    #[serde(default)]
+    pub last_used: BTreeMap<String, u64>,
+}
+
+impl UsageState {
+    /// Vermerkt eine Nutzung von `arm`.
+    pub fn touch(&mut self, arm: &str) {
+        self.tick = self.tick.saturating_add(1);
+        match self.last_used.get_mut(arm) {
+            Some(last) => *last = self.tick,
+            None => {
+                self.last_used.insert(arm.to_string(), self.tick);
+            }
+        }
+    }
+
+    /// Zeitpunkt der letzten Nutzung (0 für nie genutzte Arme).
+    #[must_use]
+    pub fn last_used(&self, arm: &str) -> u64 {
+        self.last_used.get(arm).copied().unwrap_or(0)
+    }
+
+    /// Entfernt Einträge für Arme, die nicht mehr existieren.
+    pub fn retain_arms(&mut self, arms: &[String]) {
+        self.last_used.retain(|name, _| arms.contains(name));
+    }
+}
+
+/// Wählt das Opfer unter `arms` nach `key` (kleinster Wert verliert, bei
+/// Gleichstand der früheste Arm).
+pub(crate) fn victim<K: PartialOrd>(arms: &[String], key: impl Fn(&str) -> K) -> Option<&str> {
+    let mut best: Option<(&str, K)> = None;
+    for arm in arms {
+        let k = key(arm);
+        if best.as_ref().is_none_or(|(_, b)| k < *b) {
+            best = Some((arm, k));
+        }
+    }
+    best.map(|(arm, _)| arm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn victim_prefers_smallest_key_and_earliest_on_ties() {
+        let arms: Vec<String> = ["a", "b", "c"].iter().map(ToString::to_string).collect();
+        let mut usage = UsageState::default();
+        usage.touch("a");
+        usage.touch("c");
+        assert_eq!(victim(&arms, |a| usage.last_used(a)), Some("b"));
+        usage.touch("b");
+        assert_eq!(victim(&arms, |a| usage.last_used(a)), Some("a"));
+        assert_eq!(victim(&arms, |_| 1), Some("a"));
+        assert_eq!(victim(&[], |_: &str| 1), None);
+    }
+}
@@ -0,0 +1,142 @@
+//! Ermüdungsmodell je Arm.
+//!
+//! Jede Auslösung eines Arms erhöht dessen Ermüdungsniveau um 1; das Niveau
+//! zerfällt exponentiell mit der Halbwertszeit `half_life` (gemessen in
+//! Entscheidungen). Beim Ausnutzen wird der mittlere Reward um
+//! `penalty * niveau` gemindert, oberhalb von `suppress_above` wird der Arm
+//! vorübergehend gar nicht mehr gewählt. So werden häufig und kürzlich
+//! gefeuerte Erinnerungen gedämpft, ohne ihre gelernten Werte zu verändern.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Konfiguration des Ermüdungsmodells.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FatigueConfig {
+    /// Halbwertszeit des Ermüdungsniveaus in Entscheidungen (> 0).
+    pub half_life: f64,
+    /// Abzug vom mittleren Reward je Ermüdungseinheit.
+    pub penalty: f64,
+    /// Ab diesem Niveau wird ein Arm übersprungen (sofern Alternativen existieren).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suppress_above: Option<f64>,
+}
+
+impl FatigueConfig {
+    #[must_use]
+    pub fn new(half_life: f64, penalty: f64) -> Self {
+        Self {
+            half_life,
+            penalty,
+            suppress_above: None,
+        }
+    }
+
+    /// Setzt die Schwelle, ab der ein Arm unterdrückt wird.
+    #[must_use]
+    pub fn suppress_above(mut self, level: f64) -> Self {
+        self.suppress_above = Some(level);
+        self
+    }
+}
+
+/// Ermüdungszustand eines einzelnen Arms.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArmFatigue {
+    /// Niveau zum Zeitpunkt `last_fired`.
+    pub level: f64,
+    /// Entscheidungszähler der letzten Auslösung.
+    pub last_fired: u64,
+    /// Gesamtzahl der Auslösungen.
+    pub fired: u64,
+}
+
+/// Laufender Ermüdungszustand aller Arme.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FatigueState {
+    /// Anzahl der bisher protokollierten Auslösungen (logische Uhr).
+    pub tick: u64,
+    #[serde(default)]
+    pub arms: BTreeMap<String, ArmFatigue>,
+}
+
+impl FatigueState {
+    /// Aktuelles (zerfallenes) Ermüdungsniveau von `arm`.
+    #[must_use]
+    pub fn level(&self, config: &FatigueConfig, arm: &str) -> f64 {
+        let Some(state) = self.arms.get(arm) else {
+            return 0.0;
+        };
+        if !(config.half_life.is_finite() && config.half_life > 0.0) {
+            return 0.0;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let age = self.tick.saturating_sub(state.last_fired) as f64;
+        state.level * 0.5_f64.powf(age / config.half_life)
+    }
+
+    /// Reward-Abzug für `arm`.
+    #[must_use]
+    pub fn penalty(&self, config: &FatigueConfig, arm: &str) -> f64 {
+        let penalty = config.penalty * self.level(config, arm);
+        if penalty.is_finite() {
+            penalty
+        } else {
+            0.0
+        }
+    }
+
+    /// `true`, wenn `arm` aktuell unterdrückt ist.
+    #[must_use]
+    pub fn is_suppressed(&self, config: &FatigueConfig, arm: &str) -> bool {
+        config
+            .suppress_above
+            .is_some_and(|limit| self.level(config, arm) > limit)
+    }
+
+    /// Protokolliert eine Auslösung von `arm`.
+    pub fn fire(&mut self, config: &FatigueConfig, arm: &str) {
+        self.tick = self.tick.saturating_add(1);
+        let level = self.level(config, arm) + 1.0;
+        let entry = self.arms.entry(arm.to_string()).or_default();
+        entry.level = level;
+        entry.last_fired = self.tick;
+        entry.fired = entry.fired.saturating_add(1);
+    }
+
+    /// Entfernt Einträge für Arme, die nicht mehr existieren.
+    pub fn retain_arms(&mut self, arms: &[String]) {
+        self.arms.retain(|name, _| arms.contains(name));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn level_decays_with_half_life() {
+        let cfg = FatigueConfig::new(2.0, 1.0);
+        let mut state = FatigueState::default();
+        state.fire(&cfg, "a");
+        assert!((state.level(&cfg, "a") - 1.0).abs() < 1e-9);
+
+        state.fire(&cfg, "b");
+        state.fire(&cfg, "b");
+        // "a" ist zwei Entscheidungen alt → halbes Niveau.
+        assert!((state.level(&cfg, "a") - 0.5).abs() < 1e-9);
+        assert!(state.level(&cfg, "b") > 1.5);
+        assert_eq!(state.arms["b"].fired, 2);
+    }
+
+    #[test]
+    fn suppression_threshold_applies_to_decayed_level() {
+        let cfg = FatigueConfig::new(1.0, 0.1).suppress_above(1.2);
+        let mut state = FatigueState::default();
+        state.fire(&cfg, "a");
+        state.fire(&cfg, "a");
+        assert!(state.is_suppressed(&cfg, "a"));
+        state.fire(&cfg, "b");
+        assert!(!state.is_suppressed(&cfg, "a"));
+    }
+}
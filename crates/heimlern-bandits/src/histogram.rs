@@ -0,0 +1,190 @@
+//! Reward-Verteilungen je Arm als Histogramm.
+//!
+//! Neben den gemittelten Rewards im Snapshot lassen sich pro Arm
+//! Reward-Histogramme mit frei wählbaren Bucket-Grenzen mitführen. Der Export
+//! erfolgt im OpenMetrics-Textformat, sodass Dashboards die Streuung je Slot
+//! direkt aus den Metriken ablesen können. Histogramme sind Laufzeitmetriken
+//! und werden nicht im Snapshot persistiert.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+/// Standard-Bucket-Grenzen für Rewards im Bereich `0.0..=1.0`.
+pub const DEFAULT_REWARD_BUCKETS: &[f64] = &[0.0, 0.25, 0.5, 0.75, 1.0];
+
+/// Histogramm eines einzelnen Arms.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RewardHistogram {
+    /// Nicht-kumulative Zählwerte; der letzte Eintrag ist der `+Inf`-Bucket.
+    buckets: Vec<u64>,
+    count: u64,
+    sum: f64,
+}
+
+impl RewardHistogram {
+    /// Anzahl der Beobachtungen.
+    #[must_use]
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Summe aller beobachteten Rewards.
+    #[must_use]
+    pub fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    /// Kumulative Zählwerte je Bucket-Grenze (letzter Eintrag = `+Inf`).
+    #[must_use]
+    pub fn cumulative(&self) -> Vec<u64> {
+        self.buckets
+            .iter()
+            .scan(0_u64, |acc, n| {
+                *acc += n;
+                Some(*acc)
+            })
+            .collect()
+    }
+}
+
+/// Reward-Histogramme für alle Arme mit gemeinsamen Bucket-Grenzen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArmHistograms {
+    bounds: Vec<f64>,
+    arms: BTreeMap<String, RewardHistogram>,
+}
+
+impl Default for ArmHistograms {
+    fn default() -> Self {
+        Self::new(DEFAULT_REWARD_BUCKETS.to_vec())
+    }
+}
+
+impl ArmHistograms {
+    /// Legt Histogramme mit den angegebenen oberen Bucket-Grenzen an.
+    ///
+    /// Nicht-endliche Grenzen werden verworfen, der Rest sortiert und
+    /// dedupliziert. Der `+Inf`-Bucket wird immer implizit ergänzt.
+    #[must_use]
+    pub fn new(mut bounds: Vec<f64>) -> Self {
+        bounds.retain(|b| b.is_finite());
+        bounds.sort_by(f64::total_cmp);
+        bounds.dedup();
+        Self {
+            bounds,
+            arms: BTreeMap::new(),
+        }
+    }
+
+    /// Obere Bucket-Grenzen (ohne `+Inf`).
+    #[must_use]
+    pub fn bounds(&self) -> &[f64] {
+        &self.bounds
+    }
+
+    /// Histogramm eines Arms, falls bereits Beobachtungen vorliegen.
+    #[must_use]
+    pub fn get(&self, arm: &str) -> Option<&RewardHistogram> {
+        self.arms.get(arm)
+    }
+
+    /// Führt das Histogramm von `from` unter dem Namen `to` weiter.
+    pub fn rename_arm(&mut self, from: &str, to: &str) {
+        crate::arms::rename_key(&mut self.arms, from, to);
+    }
+
+    /// Verwirft das Histogramm von `arm`.
+    pub fn remove_arm(&mut self, arm: &str) {
+        self.arms.remove(arm);
+    }
+
+    /// Erfasst einen Reward für `arm`. Nicht-endliche Werte werden ignoriert.
+    pub fn observe(&mut self, arm: &str, reward: f64) {
+        if !reward.is_finite() {
+            return;
+        }
+        let slots = self.bounds.len() + 1;
+        let hist = self
+            .arms
+            .entry(arm.to_string())
+            .or_insert_with(|| RewardHistogram {
+                buckets: vec![0; slots],
+                ..RewardHistogram::default()
+            });
+        let idx = self
+            .bounds
+            .iter()
+            .position(|b| reward <= *b)
+            .unwrap_or(self.bounds.len());
+        hist.buckets[idx] += 1;
+        hist.count += 1;
+        hist.sum += reward;
+    }
+
+    /// Exportiert alle Histogramme im OpenMetrics-Textformat.
+    ///
+    /// `name` ist der Metrikname ohne Suffix, z. B. `heimlern_arm_reward`.
+    #[must_use]
+    pub fn to_openmetrics(&self, name: &str) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# TYPE {name} histogram");
+        let _ = writeln!(out, "# HELP {name} Reward distribution per arm.");
+        for (arm, hist) in &self.arms {
+            let arm = escape_label(arm);
+            for (bound, cum) in self.bounds.iter().zip(hist.cumulative()) {
+                let _ = writeln!(out, "{name}_bucket{{arm=\"{arm}\",le=\"{bound:?}\"}} {cum}");
+            }
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{arm=\"{arm}\",le=\"+Inf\"}} {}",
+                hist.count
+            );
+            let _ = writeln!(out, "{name}_sum{{arm=\"{arm}\"}} {:?}", hist.sum);
+            let _ = writeln!(out, "{name}_count{{arm=\"{arm}\"}} {}", hist.count);
+        }
+        out.push_str("# EOF\n");
+        out
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn observations_land_in_upper_inclusive_buckets() {
+        let mut h = ArmHistograms::new(vec![1.0, 0.5, f64::NAN, 0.5]);
+        assert_eq!(h.bounds(), &[0.5, 1.0]);
+        h.observe("a", 0.5);
+        h.observe("a", 0.7);
+        h.observe("a", 3.0);
+        h.observe("a", f64::INFINITY);
+        let Some(a) = h.get("a") else {
+            panic!("histogram for 'a' missing");
+        };
+        assert_eq!(a.cumulative(), vec![1, 2, 3]);
+        assert_eq!(a.count(), 3);
+        assert!((a.sum() - 4.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn openmetrics_export_is_cumulative_and_escaped() {
+        let mut h = ArmHistograms::new(vec![0.0, 1.0]);
+        h.observe("mo\"rning", 0.0);
+        h.observe("mo\"rning", 1.0);
+        let text = h.to_openmetrics("heimlern_arm_reward");
+        assert!(text.starts_with("# TYPE heimlern_arm_reward histogram\n"));
+        assert!(text.contains("heimlern_arm_reward_bucket{arm=\"mo\\\"rning\",le=\"0.0\"} 1\n"));
+        assert!(text.contains("heimlern_arm_reward_bucket{arm=\"mo\\\"rning\",le=\"1.0\"} 2\n"));
+        assert!(text.contains("heimlern_arm_reward_bucket{arm=\"mo\\\"rning\",le=\"+Inf\"} 2\n"));
+        assert!(text.contains("heimlern_arm_reward_count{arm=\"mo\\\"rning\"} 2\n"));
+        assert!(text.ends_with("# EOF\n"));
+    }
+}
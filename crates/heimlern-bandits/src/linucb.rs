@@ -0,0 +1,568 @@
+//! Kontextueller Bandit (LinUCB) für Erinnerungs-Slots.
+//!
+//! Im Gegensatz zu den übrigen Banditen nutzt der [`LinUcbBandit`]
+//! `Context.features`: Aus dem Feature-JSON wird ein numerischer Vektor `x`
+//! extrahiert ([`FeatureConfig`]), und je Slot wird ein lineares Modell
+//! `reward ≈ θᵀx` per Ridge-Regression geführt (`A = λI + Σ xxᵀ`,
+//! `b = Σ r·x`). Gewählt wird der Slot mit der höchsten oberen Schranke
+//! `θᵀx + alpha · sqrt(xᵀA⁻¹x)`.
+//!
+//! Feedback wird mit dem in `feedback` übergebenen Kontext verbucht; Aufrufer
+//! sollten dort denselben Kontext wie bei der Entscheidung übergeben.
+//!
+//! Der Snapshot folgt dem Contract (`counts`/`values` sind Ziehungen und
+//! mittlerer Reward) und trägt Feature-Konfiguration sowie die Matrizen `A`
+//! und Vektoren `b` unter `linear`. Snapshots des `RemindBandit` lassen sich
+//! laden; ist ein Bias-Feature konfiguriert, wird der mittlere Reward als
+//! Startwert des Achsenabschnitts übernommen.
+
+use crate::{
+    admit_slot, chosen, contract_is_loadable, contract_snapshot, default_slots, fallback_decision,
+    log_warn, serialize_context, to_value_or_null, ContractEnvelope, ContractSnapshot,
+    DEFAULT_NAMESPACE, SNAPSHOT_VERSION,
+};
+use heimlern_core::{Context, Decision, Policy, PolicyDescriptor, Uncertainty};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Kennung im Contract-Snapshot.
+const POLICY_ID: &str = "linucb-bandit";
+/// Präfix der Log-Meldungen.
+const TAG: &str = "linucb";
+/// Obergrenze der Feature-Dimension; die Modelle wachsen quadratisch mit ihr.
+pub const MAX_FEATURES: usize = 64;
+
+/// Welche Werte aus `Context.features` in den Feature-Vektor eingehen.
+///
+/// Pfade sind punktgetrennte Schlüssel in das Feature-Objekt
+/// (`"weather.temp"`), gelesen mit [`Context::feature_f64`]; fehlende oder
+/// nicht-numerische Werte zählen als `0`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeatureConfig {
+    /// Feature-Pfade in fester Reihenfolge.
+    pub paths: Vec<String>,
+    /// Konstantes Feature `1.0` voranstellen (Achsenabschnitt).
+    #[serde(default = "default_bias")]
+    pub bias: bool,
+}
+
+fn default_bias() -> bool {
+    true
+}
+
+impl Default for FeatureConfig {
+    fn default() -> Self {
+        Self {
+            paths: Vec::new(),
+            bias: true,
+        }
+    }
+}
+
+impl FeatureConfig {
+    /// Konfiguration über die angegebenen Pfade, mit Bias.
+    #[must_use]
+    pub fn with_paths<I, S>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            paths: paths.into_iter().map(Into::into).collect(),
+            bias: true,
+        }
+    }
+
+    /// Dimension des Feature-Vektors.
+    #[must_use]
+    pub fn dimension(&self) -> usize {
+        self.paths.len() + usize::from(self.bias)
+    }
+
+    /// Extrahiert den Feature-Vektor aus `ctx`.
+    #[must_use]
+    pub fn extract(&self, ctx: &Context) -> Vec<f64> {
+        let bias = self.bias.then_some(1.0);
+        bias.into_iter()
+            .chain(self.paths.iter().map(|p| ctx.feature_f64(p).unwrap_or(0.0)))
+            .collect()
+    }
+}
+
+/// Ridge-Regression eines Slots.
+#[derive(Debug, Clone, PartialEq)]
+struct LinearModel {
+    /// `λI + Σ xxᵀ`, zeilenweise.
+    a: Vec<Vec<f64>>,
+    /// `Σ r·x`.
+    b: Vec<f64>,
+    pulls: u64,
+    reward_sum: f64,
+}
+
+impl LinearModel {
+    fn new(dim: usize, ridge: f64) -> Self {
+        let a = (0..dim)
+            .map(|i| (0..dim).map(|j| if i == j { ridge } else { 0.0 }).collect())
+            .collect();
+        Self {
+            a,
+            b: vec![0.0; dim],
+            pulls: 0,
+            reward_sum: 0.0,
+        }
+    }
+
+    fn dimension_matches(&self, dim: usize) -> bool {
+        self.b.len() == dim && self.a.len() == dim && self.a.iter().all(|row| row.len() == dim)
+    }
+
+    fn update(&mut self, x: &[f64], reward: f64) {
+        for (i, xi) in x.iter().enumerate() {
+            for (j, xj) in x.iter().enumerate() {
+                self.a[i][j] += xi * xj;
+            }
+            self.b[i] += reward * xi;
+        }
+        self.pulls = self.pulls.saturating_add(1);
+        self.reward_sum += reward;
+    }
+
+    fn mean_reward(&self) -> f64 {
+        if self.pulls == 0 {
+            return 0.0;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let mean = self.reward_sum / self.pulls as f64;
+        mean
+    }
+
+    /// Vorhersage `θᵀx` und Standardabweichung `sqrt(xᵀA⁻¹x)`.
+    fn predict(&self, x: &[f64]) -> Option<(f64, f64)> {
+        let l = cholesky(&self.a)?;
+        let theta = solve(&l, &self.b);
+        let a_inv_x = solve(&l, x);
+        let mean = dot(&theta, x);
+        let variance = dot(x, &a_inv_x).max(0.0);
+        Some((mean, variance.sqrt())).filter(|(m, s)| m.is_finite() && s.is_finite())
+    }
+}
+
+fn dot(u: &[f64], v: &[f64]) -> f64 {
+    u.iter().zip(v).map(|(a, b)| a * b).sum()
+}
+
+/// Cholesky-Zerlegung `A = LLᵀ`; `None`, wenn `A` nicht positiv definit ist.
+fn cholesky(a: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let n = a.len();
+    let mut l = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in 0..=i {
+            let sum = a[i][j] - dot(&l[i][..j], &l[j][..j]);
+            if i == j {
+                if !(sum.is_finite() && sum > 0.0) {
+                    return None;
+                }
+                l[i][i] = sum.sqrt();
+            } else {
+                l[i][j] = sum / l[j][j];
+            }
+        }
+    }
+    Some(l)
+}
+
+/// Löst `LLᵀy = v` durch Vorwärts- und Rückwärtseinsetzen.
+fn solve(l: &[Vec<f64>], v: &[f64]) -> Vec<f64> {
+    let n = l.len();
+    let mut z = vec![0.0; n];
+    for i in 0..n {
+        z[i] = (v[i] - dot(&l[i][..i], &z[..i])) / l[i][i];
+    }
+    let mut y = vec![0.0; n];
+    for i in (0..n).rev() {
+        let tail: f64 = (i + 1..n).map(|k| l[k][i] * y[k]).sum();
+        y[i] = (z[i] - tail) / l[i][i];
+    }
+    y
+}
+
+/// Erweiterung des Contract-Snapshots um Feature-Konfiguration und Modelle.
+#[derive(Debug, Serialize, Deserialize)]
+struct LinearSnapshot {
+    features: FeatureConfig,
+    alpha: f64,
+    ridge: f64,
+    /// Matrix `A` je Arm (Reihenfolge wie `arms`).
+    a: Vec<Vec<Vec<f64>>>,
+    /// Vektor `b` je Arm.
+    b: Vec<Vec<f64>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LinUcbSnapshot {
+    #[serde(flatten)]
+    contract: ContractEnvelope,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    linear: Option<LinearSnapshot>,
+}
+
+/// LinUCB-Policy für Erinnerungen.
+#[derive(Debug, Clone)]
+pub struct LinUcbBandit {
+    /// Verfügbare Zeit-Slots (Arme).
+    pub slots: Vec<String>,
+    /// Feature-Extraktion aus dem Kontext.
+    pub features: FeatureConfig,
+    /// Gewicht des Unsicherheitsbonus (0 = rein gierig).
+    pub alpha: f64,
+    /// Ridge-Regularisierung `λ` (> 0).
+    pub ridge: f64,
+    models: BTreeMap<String, LinearModel>,
+}
+
+impl Default for LinUcbBandit {
+    fn default() -> Self {
+        Self {
+            slots: default_slots(),
+            features: FeatureConfig::default(),
+            alpha: 1.0,
+            ridge: 1.0,
+            models: BTreeMap::new(),
+        }
+    }
+}
+
+impl LinUcbBandit {
+    /// Bandit über die angegebenen Slots und Features.
+    #[must_use]
+    pub fn new<I, S>(slots: I, features: FeatureConfig) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            slots: slots.into_iter().map(Into::into).collect(),
+            features,
+            ..Self::default()
+        }
+    }
+
+    fn ridge(&self) -> f64 {
+        if self.ridge.is_finite() && self.ridge > 0.0 {
+            self.ridge
+        } else {
+            1.0
+        }
+    }
+
+    fn alpha(&self) -> f64 {
+        if self.alpha.is_finite() {
+            self.alpha.max(0.0)
+        } else {
+            1.0
+        }
+    }
+
+    /// Feature-Vektor für `ctx`; `None`, wenn die Dimension das Limit überschreitet.
+    fn feature_vector(&self, ctx: &Context) -> Option<Vec<f64>> {
+        let dim = self.features.dimension();
+        if dim == 0 || dim > MAX_FEATURES {
+            log_warn(&format!(
+                "{TAG}: ungültige Feature-Dimension {dim} (erlaubt 1..={MAX_FEATURES})"
+            ));
+            return None;
+        }
+        Some(self.features.extract(ctx))
+    }
+
+    fn model(&self, slot: &str) -> LinearModel {
+        let dim = self.features.dimension();
+        self.models
+            .get(slot)
+            .filter(|m| m.dimension_matches(dim))
+            .cloned()
+            .unwrap_or_else(|| LinearModel::new(dim, self.ridge()))
+    }
+
+    /// Vorhergesagter Reward und dessen Standardabweichung für `slot` im Kontext `ctx`.
+    #[must_use]
+    pub fn predict(&self, slot: &str, ctx: &Context) -> Option<(f64, f64)> {
+        let x = self.feature_vector(ctx)?;
+        self.model(slot).predict(&x)
+    }
+
+    /// Persistiert Zustand als Contract-Snapshot mit Modell-Erweiterung.
+    #[must_use]
+    pub fn to_contract_snapshot(&self) -> serde_json::Value {
+        let arms = if self.slots.is_empty() {
+            default_slots()
+        } else {
+            self.slots.clone()
+        };
+        let models: Vec<LinearModel> = arms.iter().map(|a| self.model(a)).collect();
+        let counts = models.iter().map(|m| m.pulls).collect();
+        let values = models.iter().map(LinearModel::mean_reward).collect();
+        to_value_or_null(
+            TAG,
+            &LinUcbSnapshot {
+                contract: contract_snapshot(POLICY_ID, arms, counts, values),
+                linear: Some(LinearSnapshot {
+                    features: self.features.clone(),
+                    alpha: self.alpha,
+                    ridge: self.ridge,
+                    b: models.iter().map(|m| m.b.clone()).collect(),
+                    a: models.into_iter().map(|m| m.a).collect(),
+                }),
+            },
+        )
+    }
+
+    /// Modelle aus einem Snapshot ohne `linear`-Erweiterung: nur der
+    /// Achsenabschnitt (falls vorhanden) übernimmt den mittleren Reward.
+    fn bootstrap_models(&self, contract: &ContractSnapshot) -> BTreeMap<String, LinearModel> {
+        let dim = self.features.dimension();
+        let ridge = self.ridge();
+        contract
+            .arms
+            .iter()
+            .enumerate()
+            .map(|(i, arm)| {
+                let mut model = LinearModel::new(dim, ridge);
+                let pulls = contract.counts[i];
+                let mean = if contract.values[i].is_finite() {
+                    contract.values[i]
+                } else {
+                    0.0
+                };
+                #[allow(clippy::cast_precision_loss)]
+                let n = pulls as f64;
+                if self.features.bias && dim > 0 {
+                    model.a[0][0] += n;
+                    model.b[0] += mean * n;
+                }
+                model.pulls = pulls;
+                model.reward_sum = mean * n;
+                (arm.clone(), model)
+            })
+            .collect()
+    }
+}
+
+impl Policy for LinUcbBandit {
+    /// Wählt den Slot mit der höchsten oberen Schranke im aktuellen Kontext.
+    fn decide(&mut self, ctx: &Context) -> Decision {
+        if self.slots.is_empty() {
+            self.slots = default_slots();
+        }
+        let Some(x) = self.feature_vector(ctx) else {
+            return fallback_decision(
+                POLICY_ID,
+                DEFAULT_NAMESPACE,
+                "invalid feature configuration",
+                ctx,
+            );
+        };
+        let alpha = self.alpha();
+        let Some((slot, mean, sd, bound)) = self
+            .slots
+            .iter()
+            .filter_map(|s| {
+                let (mean, sd) = self.model(s).predict(&x)?;
+                Some((s, mean, sd, mean + alpha * sd))
+            })
+            .max_by(|a, b| a.3.total_cmp(&b.3))
+        else {
+            return fallback_decision(POLICY_ID, DEFAULT_NAMESPACE, "no slots available", ctx);
+        };
+        let pulls = self.models.get(slot).map_or(0, |m| m.pulls);
+        let action = format!("remind.{slot}");
+        #[allow(clippy::cast_possible_truncation)]
+        Decision {
+            chosen: chosen(POLICY_ID, &action, Uncertainty::normal(pulls, mean, sd)),
+            action,
+            score: mean as f32,
+            why: vec![format!("linucb: upper bound {bound:.3}")],
+            context: serialize_context(ctx),
+        }
+    }
+
+    fn feedback(&mut self, ctx: &Context, action: &str, reward: f32) {
+        let Some(x) = self.feature_vector(ctx) else {
+            return;
+        };
+        let Some(slot) = admit_slot(TAG, DEFAULT_NAMESPACE, &mut self.slots, action, reward) else {
+            return;
+        };
+        let mut model = self.model(slot);
+        model.update(&x, f64::from(reward));
+        self.models.insert(slot.to_string(), model);
+    }
+
+    fn snapshot(&self) -> serde_json::Value {
+        self.to_contract_snapshot()
+    }
+
+    fn load(&mut self, v: serde_json::Value) {
+        let snap = match serde_json::from_value::<LinUcbSnapshot>(v) {
+            Ok(snap) => snap,
+            Err(e) => {
+                log_warn(&format!("{TAG}: Snapshot konnte nicht geladen werden: {e}"));
+                return;
+            }
+        };
+        if !contract_is_loadable(TAG, &snap.contract, &[POLICY_ID, crate::POLICY_ID]) {
+            return;
+        }
+        let contract = snap.contract.payload;
+        let n = contract.arms.len();
+
+        let models = match snap.linear {
+            Some(linear) => {
+                let dim = linear.features.dimension();
+                if dim == 0 || dim > MAX_FEATURES || linear.a.len() != n || linear.b.len() != n {
+                    log_warn(&format!(
+                        "{TAG}: Snapshot mit ungültigen Modellen – verworfen"
+                    ));
+                    return;
+                }
+                let models: BTreeMap<String, LinearModel> = contract
+                    .arms
+                    .iter()
+                    .zip(linear.a.into_iter().zip(linear.b))
+                    .enumerate()
+                    .map(|(i, (arm, (a, b)))| {
+                        let pulls = contract.counts[i];
+                        #[allow(clippy::cast_precision_loss)]
+                        let reward_sum = if contract.values[i].is_finite() {
+                            contract.values[i] * pulls as f64
+                        } else {
+                            0.0
+                        };
+                        let model = LinearModel {
+                            a,
+                            b,
+                            pulls,
+                            reward_sum,
+                        };
+                        (arm.clone(), model)
+                    })
+                    .collect();
+                if models
+                    .values()
+                    .any(|m| !m.dimension_matches(dim) || cholesky(&m.a).is_none())
+                {
+                    log_warn(&format!(
+                        "{TAG}: Snapshot mit ungültigen Modellen – verworfen"
+                    ));
+                    return;
+                }
+                self.features = linear.features;
+                self.alpha = linear.alpha;
+                self.ridge = linear.ridge;
+                models
+            }
+            None => {
+                log_warn(&format!(
+                    "{TAG}: keine Modelle im Snapshot – aus counts/values initialisiert"
+                ));
+                self.bootstrap_models(&contract)
+            }
+        };
+        self.slots = contract.arms;
+        self.models = models;
+    }
+
+    fn descriptor(&self) -> PolicyDescriptor {
+        PolicyDescriptor::new(POLICY_ID)
+            .kind(heimlern_core::kind::REMINDER)
+            .snapshot_version(SNAPSHOT_VERSION)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RemindBandit;
+    use serde_json::json;
+
+    fn ctx(features: serde_json::Value) -> Context {
+        Context {
+            kind: "reminder".into(),
+            features,
+        }
+    }
+
+    #[test]
+    fn extracts_nested_numbers_and_flags() {
+        let cfg = FeatureConfig::with_paths(["weather.temp", "home", "missing", "label"]);
+        let x = cfg.extract(&ctx(json!({
+            "weather": { "temp": 21.5 },
+            "home": true,
+            "label": "x",
+        })));
+        assert_eq!(x, vec![1.0, 21.5, 1.0, 0.0, 0.0]);
+        assert_eq!(cfg.dimension(), 5);
+    }
+
+    #[test]
+    fn learns_context_dependent_choice() {
+        let mut bandit =
+            LinUcbBandit::new(["morning", "evening"], FeatureConfig::with_paths(["home"]));
+        bandit.alpha = 0.1;
+        let at_home = ctx(json!({ "home": true }));
+        let away = ctx(json!({ "home": false }));
+        // Zu Hause wirkt der Abend, unterwegs der Morgen.
+        for _ in 0..30 {
+            bandit.feedback(&at_home, "remind.evening", 1.0);
+            bandit.feedback(&at_home, "remind.morning", 0.0);
+            bandit.feedback(&away, "remind.evening", 0.0);
+            bandit.feedback(&away, "remind.morning", 1.0);
+        }
+        assert_eq!(bandit.decide(&at_home).action, "remind.evening");
+        assert_eq!(bandit.decide(&away).action, "remind.morning");
+
+        let Some((mean, _)) = bandit.predict("evening", &at_home) else {
+            panic!("no prediction");
+        };
+        assert!((mean - 1.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn snapshot_roundtrips_models_and_accepts_remind_bandit_state() {
+        let mut bandit = LinUcbBandit::new(["a", "b"], FeatureConfig::with_paths(["x"]));
+        let c = ctx(json!({ "x": 2.0 }));
+        bandit.feedback(&c, "remind.a", 1.0);
+        bandit.feedback(&c, "remind.b", 0.0);
+
+        let snap = bandit.snapshot();
+        assert_eq!(snap["policy_id"], "linucb-bandit");
+        assert_eq!(snap["counts"], json!([1, 1]));
+        assert_eq!(snap["linear"]["a"][0], json!([[2.0, 2.0], [2.0, 5.0]]));
+        assert_eq!(snap["linear"]["b"][0], json!([1.0, 2.0]));
+
+        let mut restored = LinUcbBandit::default();
+        restored.load(snap);
+        assert_eq!(restored.slots, bandit.slots);
+        assert_eq!(restored.features, bandit.features);
+        assert_eq!(restored.predict("a", &c), bandit.predict("a", &c));
+
+        let mut remind = RemindBandit::default();
+        let empty = ctx(json!({}));
+        for _ in 0..10 {
+            remind.feedback(&empty, "remind.evening", 1.0);
+        }
+        let mut swapped = LinUcbBandit::default();
+        swapped.load(remind.snapshot());
+        let Some((mean, _)) = swapped.predict("evening", &empty) else {
+            panic!("no prediction");
+        };
+        assert!(mean > 0.9);
+
+        let mut rejected = LinUcbBandit::new(["x"], FeatureConfig::default());
+        let mut broken = bandit.snapshot();
+        broken["linear"]["a"][0] = json!([[-1.0, 0.0], [0.0, 1.0]]);
+        rejected.load(broken);
+        assert_eq!(rejected.slots, vec!["x".to_string()]);
+    }
+}
@@ -0,0 +1,56 @@
+//! Vorwissen je Arm für einen Warmstart.
+//!
+//! Ein [`ArmPrior`] wirkt wie `pseudo_count` bereits beobachtete Rewards mit
+//! Mittelwert `mean`: Die Schätzung eines Arms ist der gewichtete Mittelwert aus
+//! Prior und Beobachtungen und nähert sich mit wachsender Datenlage den
+//! Beobachtungen an. Priors stehen im Snapshot getrennt unter `priors`;
+//! `counts` und `values` enthalten weiterhin nur tatsächlich beobachtete Daten.
+
+use serde::{Deserialize, Serialize};
+
+/// Vorwissen über einen Arm.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ArmPrior {
+    /// Gewicht des Priors in Beobachtungen (>= 0).
+    pub pseudo_count: f64,
+    /// Angenommener mittlerer Reward.
+    pub mean: f64,
+}
+
+impl ArmPrior {
+    #[must_use]
+    pub fn new(pseudo_count: f64, mean: f64) -> Self {
+        Self { pseudo_count, mean }
+    }
+
+    /// `true`, wenn beide Werte endlich sind und `pseudo_count` nicht negativ ist.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.pseudo_count.is_finite() && self.pseudo_count >= 0.0 && self.mean.is_finite()
+    }
+
+    /// Mischt den Prior mit `weight` Beobachtungen vom Mittelwert `observed`.
+    #[must_use]
+    pub fn blend(&self, weight: f64, observed: f64) -> f64 {
+        let total = weight + self.pseudo_count;
+        if !self.is_valid() || total <= 0.0 {
+            return observed;
+        }
+        (weight * observed + self.pseudo_count * self.mean) / total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prior_fades_as_observations_accumulate() {
+        let prior = ArmPrior::new(10.0, 0.8);
+        assert!((prior.blend(0.0, 0.0) - 0.8).abs() < 1e-12);
+        assert!((prior.blend(10.0, 0.2) - 0.5).abs() < 1e-12);
+        assert!((prior.blend(990.0, 0.2) - 0.206).abs() < 1e-12);
+        assert!(!ArmPrior::new(-1.0, 0.5).is_valid());
+        assert!((ArmPrior::new(f64::NAN, 0.5).blend(3.0, 0.1) - 0.1).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,164 @@
+//! Aktualitätsgewichtete (diskontierte) Reward-Schätzung je Arm.
+//!
+//! Bei jedem Feedback für einen Arm werden dessen bisheriges Gewicht und
+//! Reward-Summe mit `γ = 0.5^(1 / half_life)` multipliziert, bevor der neue
+//! Reward mit Gewicht 1 hinzukommt. Ein Reward zählt also nach `half_life`
+//! weiteren Feedbacks desselben Arms nur noch halb. Anders als das gleitende
+//! Fenster ([`crate::WindowConfig`]) vergisst die Schätzung weich statt abrupt.
+//!
+//! Im Snapshot liegt die Konfiguration unter `recency.half_life` – derselbe
+//! Schlüssel, den Vorschläge aus `heimlern-feedback` als Delta verwenden.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Konfiguration der diskontierten Updates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecencyConfig {
+    /// Halbwertszeit eines Rewards in Feedbacks desselben Arms (> 0).
+    pub half_life: f64,
+}
+
+impl RecencyConfig {
+    /// Kleinste zulässige Halbwertszeit.
+    pub const MIN_HALF_LIFE: f64 = 1.0;
+    /// Größte zulässige Halbwertszeit.
+    pub const MAX_HALF_LIFE: f64 = 100_000.0;
+
+    #[must_use]
+    pub fn new(half_life: f64) -> Self {
+        Self { half_life }
+    }
+
+    /// Diskontfaktor `γ` je Feedback (1.0 bei ungültiger Halbwertszeit).
+    #[must_use]
+    pub fn discount(&self) -> f64 {
+        if self.half_life.is_finite() && self.half_life > 0.0 {
+            0.5_f64.powf(1.0 / self.half_life)
+        } else {
+            1.0
+        }
+    }
+
+    /// Grenzgewicht `1 / (1 - γ)`, dem sich ein oft gezogener Arm annähert.
+    #[must_use]
+    pub fn horizon(&self) -> f64 {
+        let gamma = self.discount();
+        if gamma < 1.0 {
+            1.0 / (1.0 - gamma)
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    /// Begrenzt die Halbwertszeit auf den zulässigen Bereich.
+    pub fn sanitize(&mut self) {
+        self.half_life = if self.half_life.is_finite() {
+            self.half_life
+                .clamp(Self::MIN_HALF_LIFE, Self::MAX_HALF_LIFE)
+        } else {
+            Self::MIN_HALF_LIFE
+        };
+    }
+}
+
+/// Diskontierte Statistik eines Arms.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct DiscountedArm {
+    /// Summe der Gewichte aller bisherigen Rewards.
+    pub weight: f64,
+    /// Gewichtete Reward-Summe.
+    pub sum: f64,
+}
+
+/// Diskontierte Statistiken aller Arme.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecencyState {
+    #[serde(default)]
+    pub arms: BTreeMap<String, DiscountedArm>,
+}
+
+impl RecencyState {
+    /// Diskontiert `arm` und nimmt `reward` mit Gewicht 1 auf.
+    pub fn observe(&mut self, config: &RecencyConfig, arm: &str, reward: f64) {
+        let gamma = config.discount();
+        let entry = self.arms.entry(arm.to_string()).or_default();
+        entry.weight = entry.weight * gamma + 1.0;
+        entry.sum = entry.sum * gamma + reward;
+    }
+
+    /// Diskontierter Mittelwert von `arm` (`None` ohne Beobachtungen).
+    #[must_use]
+    pub fn mean(&self, arm: &str) -> Option<f64> {
+        self.arms
+            .get(arm)
+            .filter(|a| a.weight > 0.0)
+            .map(|a| a.sum / a.weight)
+    }
+
+    /// Effektive Anzahl an Beobachtungen von `arm`.
+    #[must_use]
+    pub fn weight(&self, arm: &str) -> f64 {
+        self.arms.get(arm).map_or(0.0, |a| a.weight)
+    }
+
+    /// Übernimmt kumulierte Statistiken `(pulls, summe)` für Arme ohne
+    /// diskontierten Zustand, etwa wenn Recency nachträglich aktiviert wird.
+    ///
+    /// Das Gewicht wird dabei auf den Horizont der Halbwertszeit begrenzt.
+    pub fn seed_missing(&mut self, config: &RecencyConfig, totals: &BTreeMap<String, (u64, f64)>) {
+        for (arm, (pulls, sum)) in totals {
+            if *pulls == 0 || self.arms.contains_key(arm) {
+                continue;
+            }
+            #[allow(clippy::cast_precision_loss)]
+            let n = *pulls as f64;
+            let weight = n.min(config.horizon());
+            self.arms.insert(
+                arm.clone(),
+                DiscountedArm {
+                    weight,
+                    sum: sum / n * weight,
+                },
+            );
+        }
+    }
+
+    /// Verwirft ungültige Einträge und Arme, die nicht mehr existieren.
+    pub fn sanitize(&mut self, arms: &[String]) {
+        self.arms.retain(|name, a| {
+            arms.contains(name) && a.weight.is_finite() && a.weight >= 0.0 && a.sum.is_finite()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reward_weight_halves_after_half_life() {
+        let cfg = RecencyConfig::new(2.0);
+        let mut state = RecencyState::default();
+        state.observe(&cfg, "a", 1.0);
+        state.observe(&cfg, "a", 0.0);
+        state.observe(&cfg, "a", 0.0);
+        // Gewichte: 0.5 (Reward 1.0), 1/√2, 1 → Mittel 0.5 / (0.5 + 1/√2 + 1).
+        let expected = 0.5 / (1.5 + 0.5_f64.sqrt());
+        let Some(mean) = state.mean("a") else {
+            panic!("mean missing");
+        };
+        assert!((mean - expected).abs() < 1e-9);
+        assert!(state.weight("a") < cfg.horizon());
+    }
+
+    #[test]
+    fn seeding_caps_weight_at_horizon() {
+        let cfg = RecencyConfig::new(1.0);
+        let mut state = RecencyState::default();
+        let totals = BTreeMap::from([("a".to_string(), (100, 80.0))]);
+        state.seed_missing(&cfg, &totals);
+        assert!((state.weight("a") - 2.0).abs() < 1e-9);
+        assert_eq!(state.mean("a"), Some(0.8));
+    }
+}
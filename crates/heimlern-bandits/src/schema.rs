@@ -0,0 +1,133 @@
+//! Versionen des Snapshot-Schemas und Migrationen zwischen ihnen.
+//!
+//! - **v1** (`0.1.0`): der Contract-Snapshot aus
+//!   `contracts/policy.snapshot.schema.json` – `arms`, `counts`, `values`,
+//!   `epsilon`, optional `seed`.
+//! - **v2** (`0.2.0`): v1 plus die optionalen Erweiterungen der Policies
+//!   (Aufwärmphase, Ermüdung, Priors, …), beschrieben in
+//!   `crates/heimlern-core/schemas/policy.snapshot.v2.schema.json`. Jeder
+//!   v1-Snapshot ist
+//!   inhaltlich ein gültiger v2-Snapshot. Das v2-Schema ist aktiv und gehört
+//!   deshalb nicht in das eingefrorene Archiv unter `contracts/`.
+//!
+//! Beim Laden hebt [`migrate`] einen Snapshot Schritt für Schritt auf die
+//! aktuelle Version. Eine unbekannte – etwa von einer neueren Version
+//! geschriebene – Schemaversion wird mit
+//! [`BanditError::UnsupportedVersion`] abgelehnt, statt sie zu raten.
+
+use crate::error::{BanditError, Result};
+use serde_json::Value;
+use std::fmt;
+
+/// Bekannte Versionen des Snapshot-Schemas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SnapshotVersion {
+    /// Reiner Contract-Snapshot.
+    V1,
+    /// Contract-Snapshot mit Erweiterungen.
+    V2,
+}
+
+impl SnapshotVersion {
+    /// Version, die beim Schreiben verwendet wird.
+    pub const CURRENT: Self = Self::V2;
+
+    /// Wert des `version`-Felds.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::V1 => "0.1.0",
+            Self::V2 => "0.2.0",
+        }
+    }
+
+    /// Liest das `version`-Feld; die Patch-Stelle wird ignoriert.
+    ///
+    /// # Errors
+    /// [`BanditError::UnsupportedVersion`] bei unbekannter oder unlesbarer Version.
+    pub fn parse(version: &str) -> Result<Self> {
+        let mut parts = version.split('.');
+        let major = parts.next().and_then(|p| p.parse::<u64>().ok());
+        let minor = parts.next().and_then(|p| p.parse::<u64>().ok());
+        match (major, minor) {
+            (Some(0), Some(1)) => Ok(Self::V1),
+            (Some(0), Some(2)) => Ok(Self::V2),
+            _ => Err(BanditError::UnsupportedVersion {
+                found: version.to_string(),
+                supported: Self::CURRENT.as_str(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for SnapshotVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Hebt `snapshot` auf [`SnapshotVersion::CURRENT`] und liefert die
+/// ursprüngliche Version (`None` ohne `version`-Feld, etwa bei der
+/// direkt serialisierten Struct-Form).
+///
+/// # Errors
+/// [`BanditError::UnsupportedVersion`], wenn `version` keine bekannte Version ist.
+pub fn migrate(snapshot: &mut Value) -> Result<Option<SnapshotVersion>> {
+    let Some(found) = snapshot.get("version") else {
+        return Ok(None);
+    };
+    let original = match found.as_str() {
+        Some(version) => SnapshotVersion::parse(version)?,
+        None => {
+            return Err(BanditError::UnsupportedVersion {
+                found: found.to_string(),
+                supported: SnapshotVersion::CURRENT.as_str(),
+            })
+        }
+    };
+    let mut version = original;
+    while version < SnapshotVersion::CURRENT {
+        version = match version {
+            SnapshotVersion::V1 => v1_to_v2(snapshot),
+            SnapshotVersion::V2 => break,
+        };
+    }
+    Ok(Some(original))
+}
+
+/// v1 → v2: die Erweiterungen sind optional, es ändert sich nur die Version.
+fn v1_to_v2(snapshot: &mut Value) -> SnapshotVersion {
+    if let Some(obj) = snapshot.as_object_mut() {
+        obj.insert("version".into(), Value::from(SnapshotVersion::V2.as_str()));
+    }
+    SnapshotVersion::V2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn old_versions_are_upgraded_and_future_ones_rejected() {
+        let mut v1 = json!({ "version": "0.1.0", "arms": ["a"] });
+        assert!(matches!(migrate(&mut v1), Ok(Some(SnapshotVersion::V1))));
+        assert_eq!(v1["version"], "0.2.0");
+        assert_eq!(v1["arms"], json!(["a"]));
+
+        let mut legacy = json!({ "slots": ["a"] });
+        assert!(matches!(migrate(&mut legacy), Ok(None)));
+
+        for future in [json!("0.3.0"), json!("1.0.0"), json!("v2"), json!(2)] {
+            let mut snap = json!({ "version": future });
+            let Err(BanditError::UnsupportedVersion { supported, .. }) = migrate(&mut snap) else {
+                panic!("{future} should be rejected");
+            };
+            assert_eq!(supported, "0.2.0");
+        }
+        assert!(matches!(
+            SnapshotVersion::parse("0.1.7"),
+            Ok(SnapshotVersion::V1)
+        ));
+    }
+}
@@ -0,0 +1,317 @@
+//! Simulationsumgebungen und Hyperparameter-Suche.
+//!
+//! Eine [`SimEnv`] lässt eine Policy eine Reihe von Entscheidungen treffen und
+//! liefert den mittleren Reward. Mitgeliefert werden eine synthetische
+//! Bernoulli-Umgebung ([`BernoulliEnv`]) und eine Replay-Umgebung über
+//! geloggte Outcomes ([`ReplayEnv`], Rejection Sampling).
+//!
+//! [`search`] bewertet jede Konfiguration eines [`SearchSpace`] (Grid oder
+//! Zufallsstichprobe) über alle Umgebungen und liefert einen nach mittlerem
+//! Reward sortierten [`SearchReport`]. Hyperparameter sind benannte Zahlen
+//! (z. B. `"epsilon"`), die eine Fabrikfunktion in eine Policy übersetzt.
+
+use heimlern_core::{Context, Policy};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::BTreeMap;
+
+/// Benannte Hyperparameter einer Konfiguration.
+pub type HyperParams = BTreeMap<String, f64>;
+
+/// Umgebung, in der eine Policy bewertet wird.
+pub trait SimEnv {
+    /// Name für den Report.
+    fn name(&self) -> &str;
+
+    /// Lässt `policy` entscheiden und liefert den mittleren Reward.
+    fn run(&self, policy: &mut dyn Policy, rng: &mut StdRng) -> f64;
+}
+
+/// Synthetische Umgebung: jede Aktion liefert mit fester Wahrscheinlichkeit Reward 1.
+#[derive(Debug, Clone)]
+pub struct BernoulliEnv {
+    pub name: String,
+    /// Erfolgswahrscheinlichkeit je Aktion; unbekannte Aktionen liefern 0.
+    pub arms: BTreeMap<String, f64>,
+    pub rounds: usize,
+    pub kind: String,
+}
+
+impl BernoulliEnv {
+    #[must_use]
+    pub fn new(name: impl Into<String>, rounds: usize) -> Self {
+        Self {
+            name: name.into(),
+            arms: BTreeMap::new(),
+            rounds,
+            kind: heimlern_core::kind::REMINDER.to_string(),
+        }
+    }
+
+    /// Fügt eine Aktion mit Erfolgswahrscheinlichkeit `p` hinzu.
+    #[must_use]
+    pub fn arm(mut self, action: impl Into<String>, p: f64) -> Self {
+        self.arms.insert(action.into(), p.clamp(0.0, 1.0));
+        self
+    }
+}
+
+impl SimEnv for BernoulliEnv {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&self, policy: &mut dyn Policy, rng: &mut StdRng) -> f64 {
+        if self.rounds == 0 {
+            return 0.0;
+        }
+        let ctx = Context {
+            kind: self.kind.clone(),
+            features: json!({}),
+        };
+        let mut total = 0.0;
+        for _ in 0..self.rounds {
+            let decision = policy.decide(&ctx);
+            let p = self.arms.get(&decision.action).copied().unwrap_or(0.0);
+            let reward = if rng.gen::<f64>() < p { 1.0 } else { 0.0 };
+            policy.feedback(&ctx, &decision.action, reward);
+            total += f64::from(reward);
+        }
+        #[allow(clippy::cast_precision_loss)]
+        {
+            total / self.rounds as f64
+        }
+    }
+}
+
+/// Replay geloggter `(Aktion, Reward)`-Paare per Rejection Sampling.
+///
+/// Nur Ereignisse, bei denen die Policy dieselbe Aktion wählt wie im Log,
+/// zählen und werden zurückgemeldet. Das ist für gleichverteilt explorierte
+/// Logs unverzerrt.
+#[derive(Debug, Clone)]
+pub struct ReplayEnv {
+    pub name: String,
+    pub kind: String,
+    pub events: Vec<(String, f32)>,
+}
+
+impl ReplayEnv {
+    #[must_use]
+    pub fn new(name: impl Into<String>, events: Vec<(String, f32)>) -> Self {
+        Self {
+            name: name.into(),
+            kind: heimlern_core::kind::REMINDER.to_string(),
+            events,
+        }
+    }
+}
+
+impl SimEnv for ReplayEnv {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&self, policy: &mut dyn Policy, _rng: &mut StdRng) -> f64 {
+        let ctx = Context {
+            kind: self.kind.clone(),
+            features: json!({}),
+        };
+        let mut matched = 0_u32;
+        let mut total = 0.0;
+        for (action, reward) in &self.events {
+            if !reward.is_finite() {
+                continue;
+            }
+            if policy.decide(&ctx).action == *action {
+                policy.feedback(&ctx, action, *reward);
+                matched += 1;
+                total += f64::from(*reward);
+            }
+        }
+        if matched == 0 {
+            0.0
+        } else {
+            total / f64::from(matched)
+        }
+    }
+}
+
+/// Suchraum über benannte Hyperparameter.
+#[derive(Debug, Clone)]
+pub enum SearchSpace {
+    /// Kartesisches Produkt aller Werte.
+    Grid(BTreeMap<String, Vec<f64>>),
+    /// `samples` gleichverteilte Stichproben aus `[min, max]` je Parameter.
+    Random {
+        ranges: BTreeMap<String, (f64, f64)>,
+        samples: usize,
+    },
+}
+
+impl SearchSpace {
+    /// Alle zu prüfenden Konfigurationen.
+    #[must_use]
+    pub fn configurations(&self, rng: &mut StdRng) -> Vec<HyperParams> {
+        match self {
+            Self::Grid(axes) => axes
+                .iter()
+                .fold(vec![HyperParams::new()], |acc, (key, values)| {
+                    acc.iter()
+                        .flat_map(|partial| {
+                            values.iter().map(move |v| {
+                                let mut next = partial.clone();
+                                next.insert(key.clone(), *v);
+                                next
+                            })
+                        })
+                        .collect()
+                }),
+            Self::Random { ranges, samples } => (0..*samples)
+                .map(|_| {
+                    ranges
+                        .iter()
+                        .map(|(key, (lo, hi))| {
+                            let v = if hi > lo {
+                                rng.gen_range(*lo..=*hi)
+                            } else {
+                                *lo
+                            };
+                            (key.clone(), v)
+                        })
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Ergebnis einer Konfiguration.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Trial {
+    pub params: HyperParams,
+    /// Mittlerer Reward über alle Umgebungen.
+    pub mean_reward: f64,
+    /// Mittlerer Reward je Umgebung.
+    pub per_env: BTreeMap<String, f64>,
+}
+
+/// Nach `mean_reward` absteigend sortierte Ergebnisse.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SearchReport {
+    pub trials: Vec<Trial>,
+}
+
+impl SearchReport {
+    /// Beste Konfiguration, falls mindestens eine geprüft wurde.
+    #[must_use]
+    pub fn best(&self) -> Option<&Trial> {
+        self.trials.first()
+    }
+
+    /// Snapshot einer frisch erzeugten Policy mit der besten Konfiguration.
+    pub fn bootstrap_snapshot<P: Policy>(
+        &self,
+        factory: impl Fn(&HyperParams) -> P,
+    ) -> Option<serde_json::Value> {
+        self.best().map(|trial| factory(&trial.params).snapshot())
+    }
+}
+
+/// Bewertet alle Konfigurationen aus `space` in allen `envs`.
+///
+/// Jede Konfiguration wird je Umgebung `repeats`-mal mit frischer Policy
+/// ausgeführt. Der Zufallsgenerator der Umgebungen ist über `seed`
+/// reproduzierbar; die Exploration der Policy selbst bleibt ihre Sache.
+pub fn search<P: Policy>(
+    space: &SearchSpace,
+    envs: &[&dyn SimEnv],
+    repeats: usize,
+    seed: u64,
+    factory: impl Fn(&HyperParams) -> P,
+) -> SearchReport {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let repeats = repeats.max(1);
+    let mut trials: Vec<Trial> = space
+        .configurations(&mut rng)
+        .into_iter()
+        .map(|params| {
+            let per_env: BTreeMap<String, f64> = envs
+                .iter()
+                .map(|env| {
+                    let sum: f64 = (0..repeats)
+                        .map(|_| {
+                            let mut policy = factory(&params);
+                            env.run(&mut policy, &mut rng)
+                        })
+                        .sum();
+                    #[allow(clippy::cast_precision_loss)]
+                    (env.name().to_string(), sum / repeats as f64)
+                })
+                .collect();
+            #[allow(clippy::cast_precision_loss)]
+            let mean_reward = if per_env.is_empty() {
+                0.0
+            } else {
+                per_env.values().sum::<f64>() / per_env.len() as f64
+            };
+            Trial {
+                params,
+                mean_reward,
+                per_env,
+            }
+        })
+        .collect();
+    trials.sort_by(|a, b| b.mean_reward.total_cmp(&a.mean_reward));
+    SearchReport { trials }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RemindBandit;
+
+    fn bandit(params: &HyperParams) -> RemindBandit {
+        #[allow(clippy::cast_possible_truncation)]
+        let epsilon = params.get("epsilon").copied().unwrap_or(0.2) as f32;
+        RemindBandit {
+            epsilon,
+            ..RemindBandit::default()
+        }
+    }
+
+    #[test]
+    fn grid_enumerates_cartesian_product() {
+        let space = SearchSpace::Grid(BTreeMap::from([
+            ("a".to_string(), vec![1.0, 2.0]),
+            ("b".to_string(), vec![0.1, 0.2, 0.3]),
+        ]));
+        let configs = space.configurations(&mut StdRng::seed_from_u64(0));
+        assert_eq!(configs.len(), 6);
+        assert!(configs.iter().all(|c| c.len() == 2));
+    }
+
+    #[test]
+    fn pure_exploration_ranks_below_mostly_greedy() {
+        let env = BernoulliEnv::new("morning-person", 1500)
+            .arm("remind.morning", 0.9)
+            .arm("remind.afternoon", 0.1)
+            .arm("remind.evening", 0.1);
+        let space = SearchSpace::Grid(BTreeMap::from([("epsilon".to_string(), vec![0.1, 1.0])]));
+        let report = search(&space, &[&env], 2, 7, bandit);
+
+        assert_eq!(report.trials.len(), 2);
+        let Some(best) = report.best() else {
+            panic!("report must not be empty");
+        };
+        assert_eq!(best.params.get("epsilon"), Some(&0.1));
+        assert!(best.mean_reward > report.trials[1].mean_reward);
+
+        let Some(snapshot) = report.bootstrap_snapshot(bandit) else {
+            panic!("bootstrap snapshot missing");
+        };
+        assert!((snapshot["epsilon"].as_f64().unwrap_or_default() - 0.1).abs() < 1e-6);
+    }
+}
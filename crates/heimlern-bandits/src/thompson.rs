@@ -0,0 +1,796 @@
+//! Thompson Sampling für Erinnerungs-Slots.
+//!
+//! Zwei Varianten: [`ThompsonBandit`] für Erfolgsquoten (Beta-Bernoulli) und
+//! [`GaussianThompsonBandit`] für kontinuierliche Rewards.
+//!
+//! Der [`ThompsonBandit`] führt je Slot eine Beta-Posterior `(alpha, beta)`.
+//! In `decide` wird aus jeder Posterior eine Erfolgswahrscheinlichkeit gezogen
+//! und der Slot mit dem größten Wert gewählt; Exploration ergibt sich damit aus
+//! der Unsicherheit statt aus einem festen `epsilon`. Rewards aus `[0, 1]`
+//! fließen anteilig ein (`alpha += r`, `beta += 1 - r`).
+//!
+//! Der Snapshot folgt dem Contract (`counts`/`values` sind Ziehungen und
+//! mittlerer Reward) und trägt die Posterior-Parameter zusätzlich unter
+//! `posterior`; fehlt sie, wird sie aus `counts`/`values` und dem eigenen Prior
+//! rekonstruiert. Snapshots anderer Policies (auch des `RemindBandit`) werden
+//! abgelehnt. Aktionen tragen den Namensraum des Bandits (Standard `remind`),
+//! der im Snapshot mitgeführt wird.
+//!
+//! Der [`GaussianThompsonBandit`] schätzt je Slot Mittelwert und Varianz des
+//! Rewards (Welford) und zieht aus der Normal-Posterior des Mittelwerts. Ein
+//! Slot mit Reward 0.9 schlägt so einen mit 0.6, auch wenn beide stets als
+//! „Erfolg“ zählen würden. Die Varianzen stehen im Snapshot unter `variance`.
+
+use crate::seed::PolicyRng;
+use crate::{
+    admit_slot, check_namespace, chosen, contract_is_loadable, contract_snapshot, default_slots,
+    dist, fallback_decision, log_warn, serialize_context, snapshot_namespace, to_value_or_null,
+    ContractEnvelope, ContractSnapshot, Result, DEFAULT_NAMESPACE, SNAPSHOT_VERSION,
+};
+use heimlern_core::{Context, Decision, Policy, PolicyDescriptor, Uncertainty};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Kennung im Contract-Snapshot.
+const POLICY_ID: &str = "thompson-bandit";
+/// Kennung der Gauß-Variante im Contract-Snapshot.
+const GAUSSIAN_POLICY_ID: &str = "gaussian-thompson-bandit";
+/// Präfix der Log-Meldungen.
+const TAG: &str = "thompson";
+
+/// Beta-Posterior und Ziehungen eines Slots.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct BetaArm {
+    alpha: f64,
+    beta: f64,
+    pulls: u64,
+}
+
+/// Erweiterung des Contract-Snapshots um die Posterior-Parameter.
+#[derive(Debug, Serialize, Deserialize)]
+struct BetaPosteriors {
+    alpha: Vec<f64>,
+    beta: Vec<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ThompsonSnapshot {
+    #[serde(flatten)]
+    contract: ContractEnvelope,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    posterior: Option<BetaPosteriors>,
+}
+
+/// Beta-Bernoulli-Thompson-Sampling-Policy für Erinnerungen.
+#[derive(Debug, Clone)]
+pub struct ThompsonBandit {
+    /// Verfügbare Zeit-Slots (Arme).
+    pub slots: Vec<String>,
+    /// Prior-Parameter `alpha` für neue Slots (> 0).
+    pub prior_alpha: f64,
+    /// Prior-Parameter `beta` für neue Slots (> 0).
+    pub prior_beta: f64,
+    namespace: String,
+    arms: BTreeMap<String, BetaArm>,
+    rng: PolicyRng,
+}
+
+impl Default for ThompsonBandit {
+    fn default() -> Self {
+        Self {
+            slots: default_slots(),
+            prior_alpha: 1.0,
+            prior_beta: 1.0,
+            namespace: DEFAULT_NAMESPACE.to_string(),
+            arms: BTreeMap::new(),
+            rng: PolicyRng::default(),
+        }
+    }
+}
+
+impl ThompsonBandit {
+    /// Bandit über die angegebenen Slots mit uniformem Prior.
+    #[must_use]
+    pub fn with_slots<I, S>(slots: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            slots: slots.into_iter().map(Into::into).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Zieht in [`Policy::decide`] aus einem mit `seed` initialisierten
+    /// `StdRng` statt aus `thread_rng()`; der Seed wird im Snapshot mitgeführt.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = PolicyRng::new(Some(seed));
+        self
+    }
+
+    /// Seed des Zufallsgenerators, falls gesetzt.
+    #[must_use]
+    pub fn seed(&self) -> Option<u64> {
+        self.rng.seed()
+    }
+
+    /// Namensraum der Aktionen (Standard `remind`).
+    #[must_use]
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Setzt den Namensraum der Aktionen.
+    ///
+    /// # Errors
+    /// [`crate::BanditError::InvalidParameter`], wenn `namespace` leer, zu lang ist
+    /// oder andere Zeichen als ASCII-Buchstaben, Ziffern, `_` und `-` enthält.
+    pub fn set_namespace(&mut self, namespace: &str) -> Result<()> {
+        check_namespace(namespace)?;
+        self.namespace = namespace.to_string();
+        Ok(())
+    }
+
+    fn prior(&self) -> BetaArm {
+        let valid = |p: f64| if p.is_finite() && p > 0.0 { p } else { 1.0 };
+        BetaArm {
+            alpha: valid(self.prior_alpha),
+            beta: valid(self.prior_beta),
+            pulls: 0,
+        }
+    }
+
+    fn arm(&self, slot: &str) -> BetaArm {
+        self.arms.get(slot).copied().unwrap_or_else(|| self.prior())
+    }
+
+    /// Posterior-Parameter `(alpha, beta)` von `slot`.
+    #[must_use]
+    pub fn posterior(&self, slot: &str) -> (f64, f64) {
+        let arm = self.arm(slot);
+        (arm.alpha, arm.beta)
+    }
+
+    /// Wie [`Policy::decide`], aber mit vorgegebenem Zufallsgenerator.
+    pub fn decide_with<R: Rng + ?Sized>(&mut self, ctx: &Context, rng: &mut R) -> Decision {
+        if self.slots.is_empty() {
+            self.slots = default_slots();
+        }
+        let Some((slot, sample)) = self
+            .slots
+            .iter()
+            .map(|s| {
+                let arm = self.arm(s);
+                (s, dist::beta(rng, arm.alpha, arm.beta))
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        else {
+            return fallback_decision(POLICY_ID, &self.namespace, "no slots available", ctx);
+        };
+        let arm = self.arm(slot);
+        let total = arm.alpha + arm.beta;
+        let mean = arm.alpha / total;
+        let sd = (arm.alpha * arm.beta / (total * total * (total + 1.0))).sqrt();
+        let mut uncertainty = Uncertainty::normal(arm.pulls, mean, sd);
+        uncertainty.lower = uncertainty.lower.max(0.0);
+        uncertainty.upper = uncertainty.upper.min(1.0);
+        let action = format!("{}.{slot}", self.namespace);
+        #[allow(clippy::cast_possible_truncation)]
+        Decision {
+            chosen: chosen(POLICY_ID, &action, uncertainty),
+            action,
+            score: mean as f32,
+            why: vec![format!("thompson sample {sample:.3}")],
+            context: serialize_context(ctx),
+        }
+    }
+
+    fn mean_reward(&self, arm: &BetaArm) -> f64 {
+        if arm.pulls == 0 {
+            return 0.0;
+        }
+        let prior = self.prior();
+        #[allow(clippy::cast_precision_loss)]
+        let mean = (arm.alpha - prior.alpha) / arm.pulls as f64;
+        mean.clamp(0.0, 1.0)
+    }
+
+    /// Persistiert Zustand als Contract-Snapshot mit Posterior-Erweiterung.
+    #[must_use]
+    pub fn to_contract_snapshot(&self) -> serde_json::Value {
+        let arms = if self.slots.is_empty() {
+            default_slots()
+        } else {
+            self.slots.clone()
+        };
+        let states: Vec<BetaArm> = arms.iter().map(|a| self.arm(a)).collect();
+        let counts = states.iter().map(|s| s.pulls).collect();
+        let values = states.iter().map(|s| self.mean_reward(s)).collect();
+        to_value_or_null(
+            TAG,
+            &ThompsonSnapshot {
+                contract: contract_snapshot(POLICY_ID, arms, counts, values).map(|c| {
+                    ContractSnapshot {
+                        namespace: (self.namespace != DEFAULT_NAMESPACE)
+                            .then(|| self.namespace.clone()),
+                        seed: self.rng.seed(),
+                        ..c
+                    }
+                }),
+                posterior: Some(BetaPosteriors {
+                    alpha: states.iter().map(|s| s.alpha).collect(),
+                    beta: states.iter().map(|s| s.beta).collect(),
+                }),
+            },
+        )
+    }
+}
+
+impl Policy for ThompsonBandit {
+    /// Zieht je Slot aus der Beta-Posterior und wählt den größten Wert.
+    fn decide(&mut self, ctx: &Context) -> Decision {
+        let mut rng = std::mem::take(&mut self.rng);
+        let decision = rng.with(|r| self.decide_with(ctx, r));
+        self.rng = rng;
+        decision
+    }
+
+    /// Zieht alle Stichproben aus einem Generator, in Reihenfolge der Kontexte.
+    fn decide_batch(&mut self, ctxs: &[Context]) -> Vec<Decision> {
+        let mut rng = std::mem::take(&mut self.rng);
+        let decisions = rng.with(|r| ctxs.iter().map(|ctx| self.decide_with(ctx, r)).collect());
+        self.rng = rng;
+        decisions
+    }
+
+    fn feedback(&mut self, _ctx: &Context, action: &str, reward: f32) {
+        let Some(slot) = admit_slot(TAG, &self.namespace, &mut self.slots, action, reward) else {
+            return;
+        };
+        let r = f64::from(reward).clamp(0.0, 1.0);
+        let prior = self.prior();
+        let arm = self.arms.entry(slot.to_string()).or_insert(prior);
+        arm.alpha += r;
+        arm.beta += 1.0 - r;
+        arm.pulls = arm.pulls.saturating_add(1);
+    }
+
+    fn snapshot(&self) -> serde_json::Value {
+        self.to_contract_snapshot()
+    }
+
+    fn load(&mut self, v: serde_json::Value) {
+        let snap = match serde_json::from_value::<ThompsonSnapshot>(v) {
+            Ok(snap) => snap,
+            Err(e) => {
+                log_warn(&format!(
+                    "thompson: Snapshot konnte nicht geladen werden: {e}"
+                ));
+                return;
+            }
+        };
+        if !contract_is_loadable(TAG, &snap.contract, &[POLICY_ID]) {
+            return;
+        }
+        let Some(namespace) = snapshot_namespace(TAG, snap.contract.payload.namespace.as_deref())
+        else {
+            return;
+        };
+        let contract = snap.contract.payload;
+        let n = contract.arms.len();
+
+        let valid = |p: &f64| p.is_finite() && *p > 0.0;
+        let posterior = snap.posterior.filter(|p| {
+            p.alpha.len() == n
+                && p.beta.len() == n
+                && p.alpha.iter().all(valid)
+                && p.beta.iter().all(valid)
+        });
+        if posterior.is_none() {
+            log_warn("thompson: keine Posterior im Snapshot – aus counts/values rekonstruiert");
+        }
+
+        let prior = self.prior();
+        let mut arms = BTreeMap::new();
+        for (i, arm) in contract.arms.iter().enumerate() {
+            let pulls = contract.counts[i];
+            let state = match &posterior {
+                Some(p) => BetaArm {
+                    alpha: p.alpha[i],
+                    beta: p.beta[i],
+                    pulls,
+                },
+                None => {
+                    let mean = if contract.values[i].is_finite() {
+                        contract.values[i].clamp(0.0, 1.0)
+                    } else {
+                        0.0
+                    };
+                    #[allow(clippy::cast_precision_loss)]
+                    let n = pulls as f64;
+                    BetaArm {
+                        alpha: prior.alpha + mean * n,
+                        beta: prior.beta + (1.0 - mean) * n,
+                        pulls,
+                    }
+                }
+            };
+            arms.insert(arm.clone(), state);
+        }
+        self.rng = PolicyRng::new(contract.seed);
+        self.namespace = namespace;
+        self.slots = contract.arms;
+        self.arms = arms;
+    }
+
+    fn descriptor(&self) -> PolicyDescriptor {
+        PolicyDescriptor::new(POLICY_ID)
+            .kind(heimlern_core::kind::REMINDER)
+            .snapshot_version(SNAPSHOT_VERSION)
+    }
+}
+
+/// Laufende Reward-Statistik eines Slots (Welford).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct GaussianArm {
+    pulls: u64,
+    mean: f64,
+    /// Summe der quadrierten Abweichungen vom Mittelwert.
+    m2: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct GaussianSnapshot {
+    #[serde(flatten)]
+    contract: ContractEnvelope,
+    /// Stichprobenvarianz je Arm.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    variance: Option<Vec<f64>>,
+}
+
+/// Thompson-Sampling-Policy mit Normal-Posterior für kontinuierliche Rewards.
+#[derive(Debug, Clone)]
+pub struct GaussianThompsonBandit {
+    /// Verfügbare Zeit-Slots (Arme).
+    pub slots: Vec<String>,
+    /// Prior-Mittelwert des Rewards.
+    pub prior_mean: f64,
+    /// Prior-Varianz des Rewards (> 0); bestimmt die Exploration wenig gezogener Slots.
+    pub prior_variance: f64,
+    namespace: String,
+    arms: BTreeMap<String, GaussianArm>,
+    rng: PolicyRng,
+}
+
+impl Default for GaussianThompsonBandit {
+    fn default() -> Self {
+        Self {
+            slots: default_slots(),
+            prior_mean: 0.5,
+            prior_variance: 0.25,
+            namespace: DEFAULT_NAMESPACE.to_string(),
+            arms: BTreeMap::new(),
+            rng: PolicyRng::default(),
+        }
+    }
+}
+
+impl GaussianThompsonBandit {
+    /// Bandit über die angegebenen Slots mit Standard-Prior.
+    #[must_use]
+    pub fn with_slots<I, S>(slots: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            slots: slots.into_iter().map(Into::into).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Zieht in [`Policy::decide`] aus einem mit `seed` initialisierten
+    /// `StdRng` statt aus `thread_rng()`; der Seed wird im Snapshot mitgeführt.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = PolicyRng::new(Some(seed));
+        self
+    }
+
+    /// Seed des Zufallsgenerators, falls gesetzt.
+    #[must_use]
+    pub fn seed(&self) -> Option<u64> {
+        self.rng.seed()
+    }
+
+    /// Namensraum der Aktionen (Standard `remind`).
+    #[must_use]
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Setzt den Namensraum der Aktionen.
+    ///
+    /// # Errors
+    /// [`crate::BanditError::InvalidParameter`], wenn `namespace` leer, zu lang ist
+    /// oder andere Zeichen als ASCII-Buchstaben, Ziffern, `_` und `-` enthält.
+    pub fn set_namespace(&mut self, namespace: &str) -> Result<()> {
+        check_namespace(namespace)?;
+        self.namespace = namespace.to_string();
+        Ok(())
+    }
+
+    fn prior_variance(&self) -> f64 {
+        if self.prior_variance.is_finite() && self.prior_variance > 0.0 {
+            self.prior_variance
+        } else {
+            0.25
+        }
+    }
+
+    /// Posterior des mittleren Rewards von `slot` als `(Mittelwert, Varianz)`.
+    ///
+    /// Der Prior zählt als eine Pseudo-Beobachtung; die Reward-Varianz wird aus
+    /// Prior-Varianz und Stichprobe gemischt, damit wenige gleiche Rewards die
+    /// Unsicherheit nicht auf null drücken.
+    #[must_use]
+    pub fn posterior(&self, slot: &str) -> (f64, f64) {
+        let arm = self.arms.get(slot).copied().unwrap_or_default();
+        let prior_mean = if self.prior_mean.is_finite() {
+            self.prior_mean
+        } else {
+            0.5
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let n = arm.pulls as f64;
+        let weight = n + 1.0;
+        let mean = (prior_mean + arm.mean * n) / weight;
+        let reward_variance = (self.prior_variance() + arm.m2) / weight;
+        (mean, reward_variance / weight)
+    }
+
+    /// Wie [`Policy::decide`], aber mit vorgegebenem Zufallsgenerator.
+    pub fn decide_with<R: Rng + ?Sized>(&mut self, ctx: &Context, rng: &mut R) -> Decision {
+        if self.slots.is_empty() {
+            self.slots = default_slots();
+        }
+        let Some((slot, sample)) = self
+            .slots
+            .iter()
+            .map(|s| {
+                let (mean, variance) = self.posterior(s);
+                (s, mean + variance.sqrt() * dist::standard_normal(rng))
+            })
+            .filter(|(_, sample)| sample.is_finite())
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        else {
+            return fallback_decision(
+                GAUSSIAN_POLICY_ID,
+                &self.namespace,
+                "no slots available",
+                ctx,
+            );
+        };
+        let (mean, variance) = self.posterior(slot);
+        let pulls = self.arms.get(slot).map_or(0, |a| a.pulls);
+        let action = format!("{}.{slot}", self.namespace);
+        #[allow(clippy::cast_possible_truncation)]
+        Decision {
+            chosen: chosen(
+                GAUSSIAN_POLICY_ID,
+                &action,
+                Uncertainty::normal(pulls, mean, variance.sqrt()),
+            ),
+            action,
+            score: mean as f32,
+            why: vec![format!("gaussian thompson sample {sample:.3}")],
+            context: serialize_context(ctx),
+        }
+    }
+
+    /// Persistiert Zustand als Contract-Snapshot mit Varianz-Erweiterung.
+    #[must_use]
+    pub fn to_contract_snapshot(&self) -> serde_json::Value {
+        let arms = if self.slots.is_empty() {
+            default_slots()
+        } else {
+            self.slots.clone()
+        };
+        let states: Vec<GaussianArm> = arms
+            .iter()
+            .map(|a| self.arms.get(a).copied().unwrap_or_default())
+            .collect();
+        #[allow(clippy::cast_precision_loss)]
+        let variance = states
+            .iter()
+            .map(|s| {
+                if s.pulls > 1 {
+                    s.m2 / (s.pulls - 1) as f64
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        let counts = states.iter().map(|s| s.pulls).collect();
+        let values = states.iter().map(|s| s.mean).collect();
+        to_value_or_null(
+            TAG,
+            &GaussianSnapshot {
+                contract: contract_snapshot(GAUSSIAN_POLICY_ID, arms, counts, values).map(|c| {
+                    ContractSnapshot {
+                        namespace: (self.namespace != DEFAULT_NAMESPACE)
+                            .then(|| self.namespace.clone()),
+                        seed: self.rng.seed(),
+                        ..c
+                    }
+                }),
+                variance: Some(variance),
+            },
+        )
+    }
+}
+
+impl Policy for GaussianThompsonBandit {
+    /// Zieht je Slot aus der Normal-Posterior des Mittelwerts und wählt den größten Wert.
+    fn decide(&mut self, ctx: &Context) -> Decision {
+        let mut rng = std::mem::take(&mut self.rng);
+        let decision = rng.with(|r| self.decide_with(ctx, r));
+        self.rng = rng;
+        decision
+    }
+
+    /// Zieht alle Stichproben aus einem Generator, in Reihenfolge der Kontexte.
+    fn decide_batch(&mut self, ctxs: &[Context]) -> Vec<Decision> {
+        let mut rng = std::mem::take(&mut self.rng);
+        let decisions = rng.with(|r| ctxs.iter().map(|ctx| self.decide_with(ctx, r)).collect());
+        self.rng = rng;
+        decisions
+    }
+
+    fn feedback(&mut self, _ctx: &Context, action: &str, reward: f32) {
+        let Some(slot) = admit_slot(TAG, &self.namespace, &mut self.slots, action, reward) else {
+            return;
+        };
+        let r = f64::from(reward);
+        let arm = self.arms.entry(slot.to_string()).or_default();
+        arm.pulls = arm.pulls.saturating_add(1);
+        #[allow(clippy::cast_precision_loss)]
+        let n = arm.pulls as f64;
+        let delta = r - arm.mean;
+        arm.mean += delta / n;
+        arm.m2 += delta * (r - arm.mean);
+    }
+
+    fn snapshot(&self) -> serde_json::Value {
+        self.to_contract_snapshot()
+    }
+
+    fn load(&mut self, v: serde_json::Value) {
+        let snap = match serde_json::from_value::<GaussianSnapshot>(v) {
+            Ok(snap) => snap,
+            Err(e) => {
+                log_warn(&format!(
+                    "thompson: Snapshot konnte nicht geladen werden: {e}"
+                ));
+                return;
+            }
+        };
+        if !contract_is_loadable(TAG, &snap.contract, &[GAUSSIAN_POLICY_ID]) {
+            return;
+        }
+        let Some(namespace) = snapshot_namespace(TAG, snap.contract.payload.namespace.as_deref())
+        else {
+            return;
+        };
+        let contract = snap.contract.payload;
+        let variance = snap
+            .variance
+            .filter(|v| v.len() == contract.arms.len())
+            .unwrap_or_else(|| {
+                log_warn("thompson: keine Varianzen im Snapshot – Prior-Varianz angenommen");
+                vec![self.prior_variance(); contract.arms.len()]
+            });
+
+        let mut arms = BTreeMap::new();
+        for (i, arm) in contract.arms.iter().enumerate() {
+            let pulls = contract.counts[i];
+            let finite = |x: f64| if x.is_finite() { x } else { 0.0 };
+            #[allow(clippy::cast_precision_loss)]
+            let m2 = finite(variance[i]).max(0.0) * pulls.saturating_sub(1) as f64;
+            arms.insert(
+                arm.clone(),
+                GaussianArm {
+                    pulls,
+                    mean: finite(contract.values[i]),
+                    m2,
+                },
+            );
+        }
+        self.rng = PolicyRng::new(contract.seed);
+        self.namespace = namespace;
+        self.slots = contract.arms;
+        self.arms = arms;
+    }
+
+    fn descriptor(&self) -> PolicyDescriptor {
+        PolicyDescriptor::new(GAUSSIAN_POLICY_ID)
+            .kind(heimlern_core::kind::REMINDER)
+            .snapshot_version(SNAPSHOT_VERSION)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RemindBandit;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use serde_json::json;
+
+    fn ctx() -> Context {
+        Context {
+            kind: "reminder".into(),
+            features: json!({}),
+        }
+    }
+
+    #[test]
+    fn concentrates_on_best_arm() {
+        let mut bandit = ThompsonBandit::with_slots(["good", "bad"]);
+        let mut rng = StdRng::seed_from_u64(3);
+        let ctx = ctx();
+        let mut good = 0;
+        for _ in 0..500 {
+            let decision = bandit.decide_with(&ctx, &mut rng);
+            let reward = if decision.action == "remind.good" {
+                good += 1;
+                f32::from(u8::from(rng.gen::<f64>() < 0.8))
+            } else {
+                f32::from(u8::from(rng.gen::<f64>() < 0.2))
+            };
+            bandit.feedback(&ctx, &decision.action, reward);
+        }
+        assert!(good > 400, "good arm chosen only {good} times");
+    }
+
+    #[test]
+    fn snapshot_round_trips_posterior_and_rejects_remind_bandit() {
+        let mut bandit = ThompsonBandit::default();
+        let ctx = ctx();
+        bandit.feedback(&ctx, "remind.morning", 1.0);
+        bandit.feedback(&ctx, "remind.morning", 0.25);
+
+        let snap = bandit.snapshot();
+        assert_eq!(snap["policy_id"], "thompson-bandit");
+        assert_eq!(snap["counts"], json!([2, 0, 0]));
+        assert_eq!(snap["posterior"]["alpha"], json!([2.25, 1.0, 1.0]));
+
+        let mut restored = ThompsonBandit::with_slots(["x"]);
+        restored.load(snap);
+        assert_eq!(restored.posterior("morning"), (2.25, 1.75));
+        assert_eq!(restored.slots, bandit.slots);
+
+        let mut remind = RemindBandit::default();
+        for _ in 0..4 {
+            remind.feedback(&ctx, "remind.evening", 1.0);
+        }
+        let mut swapped = ThompsonBandit::with_slots(["x"]);
+        swapped.load(remind.snapshot());
+        assert_eq!(swapped.slots, vec!["x".to_string()]);
+        assert_eq!(swapped.posterior("evening"), (1.0, 1.0));
+    }
+
+    #[test]
+    fn namespace_drives_actions_and_survives_snapshots() {
+        let ctx = ctx();
+        let mut bandit = ThompsonBandit::with_slots(["morning"]);
+        assert!(bandit.set_namespace("no.dots").is_err());
+        assert!(bandit.set_namespace("notify").is_ok());
+        let decision = bandit.decide(&ctx);
+        assert_eq!(decision.action, "notify.morning");
+        assert_eq!(
+            decision.chosen.and_then(|c| c.policy_id),
+            Some(POLICY_ID.to_string())
+        );
+        bandit.feedback(&ctx, "remind.morning", 1.0);
+        bandit.feedback(&ctx, "notify.morning", 1.0);
+        assert_eq!(bandit.posterior("morning"), (2.0, 1.0));
+
+        let snap = bandit.snapshot();
+        assert_eq!(snap["namespace"], "notify");
+        let mut restored = ThompsonBandit::default();
+        restored.load(snap.clone());
+        assert_eq!(restored.namespace(), "notify");
+        assert!(ThompsonBandit::default()
+            .snapshot()
+            .get("namespace")
+            .is_none());
+
+        let mut broken = snap;
+        broken["namespace"] = "no.dots".into();
+        let mut untouched = ThompsonBandit::default();
+        untouched.load(broken);
+        assert_eq!(untouched.namespace(), DEFAULT_NAMESPACE);
+        assert_eq!(untouched.posterior("morning"), (1.0, 1.0));
+
+        let mut gaussian = GaussianThompsonBandit::with_slots(["evening"]);
+        assert!(gaussian.set_namespace("notify").is_ok());
+        assert_eq!(gaussian.decide(&ctx).action, "notify.evening");
+        let mut restored = GaussianThompsonBandit::default();
+        restored.load(gaussian.snapshot());
+        assert_eq!(restored.namespace(), "notify");
+    }
+
+    #[test]
+    fn seed_makes_sampling_reproducible_across_snapshots() {
+        let ctx = ctx();
+        let mut bandit = ThompsonBandit::default().with_seed(5);
+        let expected: Vec<String> = (0..10).map(|_| bandit.decide(&ctx).action).collect();
+
+        let mut restored = ThompsonBandit::default();
+        restored.load(ThompsonBandit::default().with_seed(5).snapshot());
+        assert_eq!(restored.seed(), Some(5));
+        let replayed: Vec<String> = (0..10).map(|_| restored.decide(&ctx).action).collect();
+        assert_eq!(replayed, expected);
+
+        let gaussian = GaussianThompsonBandit::default().with_seed(9);
+        assert_eq!(gaussian.snapshot()["seed"], 9);
+        let mut restored = GaussianThompsonBandit::default();
+        restored.load(gaussian.snapshot());
+        assert_eq!(restored.seed(), Some(9));
+    }
+
+    #[test]
+    fn decide_batch_matches_sequential_draws() {
+        let ctxs = vec![ctx(); 10];
+        let mut sequential = ThompsonBandit::default().with_seed(5);
+        let expected: Vec<String> = ctxs.iter().map(|c| sequential.decide(c).action).collect();
+        let mut batched = ThompsonBandit::default().with_seed(5);
+        let actions: Vec<String> = batched
+            .decide_batch(&ctxs)
+            .into_iter()
+            .map(|d| d.action)
+            .collect();
+        assert_eq!(actions, expected);
+        assert_eq!(
+            batched.decide(&ctxs[0]).action,
+            sequential.decide(&ctxs[0]).action
+        );
+    }
+
+    #[test]
+    fn gaussian_variant_prefers_higher_reward_magnitude() {
+        let mut bandit = GaussianThompsonBandit::with_slots(["high", "low"]);
+        let mut rng = StdRng::seed_from_u64(11);
+        let ctx = ctx();
+        let mut high = 0;
+        for _ in 0..400 {
+            let decision = bandit.decide_with(&ctx, &mut rng);
+            // Beide Slots „gelingen“ immer, unterscheiden sich aber im Reward.
+            let reward = if decision.action == "remind.high" {
+                high += 1;
+                0.9
+            } else {
+                0.6
+            };
+            let noise = (rng.gen::<f32>() - 0.5) * 0.1;
+            bandit.feedback(&ctx, &decision.action, reward + noise);
+        }
+        assert!(high > 340, "high-reward arm chosen only {high} times");
+
+        let mut restored = GaussianThompsonBandit::with_slots(["x"]);
+        restored.load(bandit.snapshot());
+        let (before, after) = (bandit.posterior("high"), restored.posterior("high"));
+        assert!((before.0 - after.0).abs() < 1e-9);
+        assert!((before.1 - after.1).abs() < 1e-9);
+
+        let mut remind = RemindBandit::default();
+        remind.feedback(&ctx, "remind.evening", 0.8);
+        let mut swapped = GaussianThompsonBandit::default();
+        swapped.load(remind.snapshot());
+        assert_eq!(swapped.posterior("evening"), swapped.posterior("morning"));
+    }
+}
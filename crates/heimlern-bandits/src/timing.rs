@@ -0,0 +1,209 @@
+//! Feinabstimmung des Auslösezeitpunkts innerhalb eines Slots.
+//!
+//! Der Bandit wählt nur den Slot; wann genau innerhalb des Slots erinnert
+//! wird, lernt dieses Modul aus der Quittierungslatenz. Kandidaten sind die
+//! Minuten `0, step, 2·step, …, window` nach Slot-Beginn. Für jeden Kandidaten
+//! schätzt eine Kernel-Regression über die beobachteten `(Offset, Latenz)`-Paare
+//! die erwartete Latenz samt Unsicherheit; gewählt wird die untere
+//! Konfidenzschranke (kleine Bayes'sche Optimierung auf einem Raster).
+//!
+//! Der „amtierende“ Offset je Slot (`offset_minutes`) ist der Kandidat mit
+//! der kleinsten geschätzten Latenz. Er ist als Parameter
+//! `timing.<slot>.offset_minutes` justierbar; solange wenige Daten vorliegen,
+//! bestimmt er, wo der Lerner sucht.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Geringer Zug zum amtierenden Offset, der Gleichstände auflöst.
+const INCUMBENT_PULL: f64 = 1e-3;
+
+/// Konfiguration des Timing-Lerners.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimingConfig {
+    /// Länge des Suchfensters ab Slot-Beginn in Minuten.
+    pub window_minutes: u32,
+    /// Rasterabstand der Kandidaten in Minuten (> 0).
+    pub step_minutes: u32,
+    /// Kernel-Bandbreite in Minuten: wie weit eine Beobachtung auf Nachbarn ausstrahlt.
+    pub bandwidth_minutes: f64,
+    /// Gewicht des Unsicherheitsbonus (0 = rein gierig).
+    pub exploration: f64,
+    /// Maximal gespeicherte Beobachtungen je Slot (älteste fallen heraus).
+    pub max_samples: usize,
+}
+
+impl Default for TimingConfig {
+    fn default() -> Self {
+        Self {
+            window_minutes: 60,
+            step_minutes: 5,
+            bandwidth_minutes: 10.0,
+            exploration: 1.0,
+            max_samples: 200,
+        }
+    }
+}
+
+impl TimingConfig {
+    fn candidates(&self) -> impl Iterator<Item = f64> + '_ {
+        let step = self.step_minutes.max(1);
+        (0..=self.window_minutes / step).map(move |i| f64::from(i * step))
+    }
+
+    pub(crate) fn clamp(&self, minutes: f64) -> f64 {
+        if minutes.is_finite() {
+            minutes.clamp(0.0, f64::from(self.window_minutes))
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Gelernter Zustand eines Slots.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SlotTiming {
+    /// Amtierender Offset in Minuten nach Slot-Beginn.
+    pub offset_minutes: f64,
+    /// Beobachtungen als `(Offset, Latenz)` in Minuten.
+    #[serde(default)]
+    samples: Vec<(f64, f64)>,
+}
+
+impl SlotTiming {
+    /// Geschätzte Latenz bei `offset` und effektive Stichprobengröße.
+    fn posterior(&self, cfg: &TimingConfig, offset: f64) -> (f64, f64) {
+        #[allow(clippy::cast_precision_loss)]
+        let prior_mean = if self.samples.is_empty() {
+            0.0
+        } else {
+            self.samples.iter().map(|(_, y)| y).sum::<f64>() / self.samples.len() as f64
+        };
+        let h = cfg.bandwidth_minutes.max(f64::EPSILON);
+        let (weight, weighted) =
+            self.samples
+                .iter()
+                .fold((1.0, prior_mean), |(w_sum, y_sum), (x, y)| {
+                    let w = (-(offset - x).powi(2) / (2.0 * h * h)).exp();
+                    (w_sum + w, y_sum + w * y)
+                });
+        (weighted / weight, weight)
+    }
+
+    fn spread(&self) -> f64 {
+        if self.samples.len() < 2 {
+            return 1.0;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let n = self.samples.len() as f64;
+        let mean = self.samples.iter().map(|(_, y)| y).sum::<f64>() / n;
+        let var = self
+            .samples
+            .iter()
+            .map(|(_, y)| (y - mean).powi(2))
+            .sum::<f64>()
+            / (n - 1.0);
+        var.sqrt().max(1.0)
+    }
+
+    fn argmin(&self, cfg: &TimingConfig, score: impl Fn(f64) -> f64) -> f64 {
+        cfg.candidates()
+            .map(|c| {
+                (
+                    c,
+                    score(c) + INCUMBENT_PULL * (c - self.offset_minutes).abs(),
+                )
+            })
+            .filter(|(_, s)| s.is_finite())
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map_or(self.offset_minutes, |(c, _)| c)
+    }
+
+    /// Offset für die nächste Auslösung (untere Konfidenzschranke).
+    fn next_offset(&self, cfg: &TimingConfig) -> f64 {
+        let spread = self.spread();
+        self.argmin(cfg, |c| {
+            let (mean, weight) = self.posterior(cfg, c);
+            mean - cfg.exploration * spread / weight.sqrt()
+        })
+    }
+}
+
+/// Timing-Zustand aller Slots.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TimingState {
+    slots: BTreeMap<String, SlotTiming>,
+}
+
+impl TimingState {
+    /// Amtierender Offset von `slot`.
+    #[must_use]
+    pub fn offset(&self, slot: &str) -> f64 {
+        self.slots.get(slot).map_or(0.0, |s| s.offset_minutes)
+    }
+
+    /// Offset, zu dem die nächste Erinnerung in `slot` ausgelöst werden soll.
+    #[must_use]
+    pub fn next_offset(&self, cfg: &TimingConfig, slot: &str) -> f64 {
+        self.slots.get(slot).map_or(0.0, |s| s.next_offset(cfg))
+    }
+
+    /// Verbucht eine Quittierung nach `latency` Minuten bei Auslösung zu `offset`.
+    pub fn observe(&mut self, cfg: &TimingConfig, slot: &str, offset: f64, latency: f64) {
+        if !(offset.is_finite() && latency.is_finite() && latency >= 0.0) {
+            return;
+        }
+        let state = self.slots.entry(slot.to_string()).or_default();
+        state.samples.push((cfg.clamp(offset), latency));
+        let excess = state.samples.len().saturating_sub(cfg.max_samples.max(1));
+        state.samples.drain(..excess);
+        state.offset_minutes = state.argmin(cfg, |c| state.posterior(cfg, c).0);
+    }
+
+    /// Setzt den amtierenden Offset (z. B. aus einem angenommenen Vorschlag).
+    pub fn set_offset(&mut self, cfg: &TimingConfig, slot: &str, minutes: f64) {
+        self.slots
+            .entry(slot.to_string())
+            .or_default()
+            .offset_minutes = cfg.clamp(minutes);
+    }
+
+    /// Verwirft Zustand für Slots, die es nicht mehr gibt.
+    pub fn retain_arms(&mut self, arms: &[String]) {
+        self.slots.retain(|slot, _| arms.contains(slot));
+    }
+
+    /// Führt den Zustand von `from` unter dem Namen `to` weiter.
+    pub fn rename_arm(&mut self, from: &str, to: &str) {
+        crate::arms::rename_key(&mut self.slots, from, to);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converges_on_offset_with_lowest_latency() {
+        let cfg = TimingConfig::default();
+        let mut state = TimingState::default();
+        // Wahre Latenz: minimal bei 30 Minuten nach Slot-Beginn.
+        let latency = |offset: f64| 5.0 + (offset - 30.0).abs();
+        for _ in 0..40 {
+            let offset = state.next_offset(&cfg, "evening");
+            state.observe(&cfg, "evening", offset, latency(offset));
+        }
+        assert!((state.offset("evening") - 30.0).abs() <= 5.0);
+    }
+
+    #[test]
+    fn incumbent_guides_search_without_data() {
+        let cfg = TimingConfig::default();
+        let mut state = TimingState::default();
+        state.set_offset(&cfg, "morning", 20.0);
+        assert!((state.next_offset(&cfg, "morning") - 20.0).abs() < 1e-9);
+        state.set_offset(&cfg, "morning", 500.0);
+        assert!((state.offset("morning") - 60.0).abs() < 1e-9);
+    }
+}
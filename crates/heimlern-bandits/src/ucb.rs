@@ -0,0 +1,296 @@
+//! UCB1-Bandit für Erinnerungs-Slots.
+//!
+//! Statt zufälliger Exploration wählt der [`UcbBandit`] den Slot mit der
+//! höchsten oberen Konfidenzschranke `mean + sqrt(2 ln N / n)`; Slots ohne
+//! Ziehung kommen zuerst an die Reihe. Die Entscheidung ist damit
+//! deterministisch.
+//!
+//! Der Snapshot folgt demselben Contract wie beim [`RemindBandit`](crate::RemindBandit)
+//! (`arms`/`counts`/`values`, `epsilon` ist stets `0.0`), sodass Aufrufer die
+//! Policy tauschen können, ohne ihre Persistenz anzupassen. `load` akzeptiert
+//! auch Snapshots des `RemindBandit` und übernimmt dessen Statistiken als
+//! Startwerte.
+
+use crate::{
+    bernoulli_uncertainty, chosen, contract_is_loadable, contract_snapshot, default_slots,
+    fallback_decision, log_warn, serialize_context, ContractEnvelope, DEFAULT_NAMESPACE, MAX_ARMS,
+    MAX_ARM_NAME_LEN, SNAPSHOT_VERSION,
+};
+use heimlern_core::{Context, Decision, Policy, PolicyDescriptor};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Kennung im Contract-Snapshot.
+const POLICY_ID: &str = "ucb-bandit";
+
+/// UCB1-Policy für Erinnerungen.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UcbBandit {
+    /// Verfügbare Zeit-Slots (Arme).
+    pub slots: Vec<String>,
+    /// Statistiken je Slot: (Anzahl Ziehungen, summierte Rewards).
+    values: BTreeMap<String, (u64, f64)>,
+}
+
+impl Default for UcbBandit {
+    fn default() -> Self {
+        Self {
+            slots: default_slots(),
+            values: BTreeMap::new(),
+        }
+    }
+}
+
+impl UcbBandit {
+    /// Bandit über die angegebenen Slots.
+    #[must_use]
+    pub fn with_slots<I, S>(slots: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            slots: slots.into_iter().map(Into::into).collect(),
+            values: BTreeMap::new(),
+        }
+    }
+
+    fn pulls(&self, slot: &str) -> u64 {
+        self.values.get(slot).map_or(0, |(n, _)| *n)
+    }
+
+    fn mean(&self, slot: &str) -> f64 {
+        #[allow(clippy::cast_precision_loss)]
+        self.values
+            .get(slot)
+            .filter(|(n, _)| *n > 0)
+            .map_or(0.0, |(n, sum)| sum / *n as f64)
+    }
+
+    /// Obere Konfidenzschranke von `slot`; `None` für Slots ohne Ziehung.
+    #[must_use]
+    pub fn upper_bound(&self, slot: &str) -> Option<f64> {
+        let n = self.pulls(slot);
+        if n == 0 {
+            return None;
+        }
+        let total: u64 = self.slots.iter().map(|s| self.pulls(s)).sum();
+        #[allow(clippy::cast_precision_loss)]
+        let bonus = (2.0 * (total.max(1) as f64).ln() / n as f64).sqrt();
+        Some(self.mean(slot) + bonus)
+    }
+
+    fn sanitize(&mut self) {
+        for (_, sum) in self.values.values_mut() {
+            if !sum.is_finite() {
+                *sum = 0.0;
+            }
+        }
+        if self.slots.is_empty() {
+            self.slots = default_slots();
+        }
+    }
+
+    fn decide_sanitized(&self, ctx: &Context) -> Decision {
+        if let Some(slot) = self.slots.iter().find(|s| self.pulls(s) == 0) {
+            let action = format!("remind.{slot}");
+            return Decision {
+                chosen: chosen(POLICY_ID, &action, bernoulli_uncertainty(0, 0.0)),
+                action,
+                score: 0.0,
+                why: vec!["ucb: untried arm".into()],
+                context: serialize_context(ctx),
+            };
+        }
+
+        let Some((slot, bound)) = self
+            .slots
+            .iter()
+            .filter_map(|s| self.upper_bound(s).map(|b| (s, b)))
+            .filter(|(_, b)| b.is_finite())
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        else {
+            return fallback_decision(POLICY_ID, DEFAULT_NAMESPACE, "no slots available", ctx);
+        };
+
+        let action = format!("remind.{slot}");
+        #[allow(clippy::cast_possible_truncation)]
+        Decision {
+            chosen: chosen(
+                POLICY_ID,
+                &action,
+                bernoulli_uncertainty(self.pulls(slot), self.mean(slot)),
+            ),
+            action,
+            score: self.mean(slot) as f32,
+            why: vec![format!("ucb: upper bound {bound:.3}")],
+            context: serialize_context(ctx),
+        }
+    }
+
+    /// Persistiert Zustand als Contract-Snapshot.
+    #[must_use]
+    pub fn to_contract_snapshot(&self) -> serde_json::Value {
+        let arms = if self.slots.is_empty() {
+            default_slots()
+        } else {
+            self.slots.clone()
+        };
+        let counts = arms.iter().map(|a| self.pulls(a)).collect();
+        let values = arms.iter().map(|a| self.mean(a)).collect();
+        let snap = contract_snapshot(POLICY_ID, arms, counts, values);
+        serde_json::to_value(snap).unwrap_or_else(|e| {
+            log_warn(&format!(
+                "ucb: Snapshot konnte nicht serialisiert werden: {e}"
+            ));
+            serde_json::Value::Null
+        })
+    }
+}
+
+impl Policy for UcbBandit {
+    /// Wählt den Slot mit der höchsten oberen Konfidenzschranke.
+    fn decide(&mut self, ctx: &Context) -> Decision {
+        self.sanitize();
+        self.decide_sanitized(ctx)
+    }
+
+    /// Bereinigt den Zustand nur einmal für alle Kontexte.
+    fn decide_batch(&mut self, ctxs: &[Context]) -> Vec<Decision> {
+        self.sanitize();
+        ctxs.iter().map(|ctx| self.decide_sanitized(ctx)).collect()
+    }
+
+    fn feedback(&mut self, _ctx: &Context, action: &str, reward: f32) {
+        if !reward.is_finite() {
+            log_warn(&format!(
+                "ucb: ungültiger Reward '{reward}' für Aktion '{action}' – ignoriert"
+            ));
+            return;
+        }
+        let Some(slot) = action.strip_prefix("remind.") else {
+            log_warn(&format!(
+                "ucb: Aktion ohne erwartetes Präfix 'remind.': '{action}' – ignoriert"
+            ));
+            return;
+        };
+        if !self.slots.iter().any(|s| s == slot) {
+            if slot.len() > MAX_ARM_NAME_LEN || self.slots.len() >= MAX_ARMS {
+                log_warn("ucb: neuer Slot überschreitet die Grenzen – ignoriert");
+                return;
+            }
+            self.slots.push(slot.to_string());
+        }
+        let entry = self.values.entry(slot.to_string()).or_insert((0, 0.0));
+        entry.0 = entry.0.saturating_add(1);
+        entry.1 += f64::from(reward);
+    }
+
+    fn snapshot(&self) -> serde_json::Value {
+        self.to_contract_snapshot()
+    }
+
+    fn load(&mut self, v: serde_json::Value) {
+        let envelope = match serde_json::from_value::<ContractEnvelope>(v) {
+            Ok(envelope) => envelope,
+            Err(e) => {
+                log_warn(&format!("ucb: Snapshot konnte nicht geladen werden: {e}"));
+                return;
+            }
+        };
+        if !contract_is_loadable("ucb", &envelope, &[POLICY_ID, crate::POLICY_ID]) {
+            return;
+        }
+        let snap = envelope.payload;
+
+        #[allow(clippy::cast_precision_loss)]
+        let values = snap
+            .arms
+            .iter()
+            .zip(snap.counts.iter().zip(&snap.values))
+            .map(|(arm, (count, avg))| {
+                let total = if avg.is_finite() {
+                    avg * *count as f64
+                } else {
+                    0.0
+                };
+                (arm.clone(), (*count, total))
+            })
+            .collect();
+        self.slots = snap.arms;
+        self.values = values;
+        self.sanitize();
+    }
+
+    fn descriptor(&self) -> PolicyDescriptor {
+        PolicyDescriptor::new(POLICY_ID)
+            .kind(heimlern_core::kind::REMINDER)
+            .snapshot_version(SNAPSHOT_VERSION)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RemindBandit;
+    use serde_json::json;
+
+    fn ctx() -> Context {
+        Context {
+            kind: "reminder".into(),
+            features: json!({}),
+        }
+    }
+
+    #[test]
+    fn tries_every_arm_then_balances_mean_and_bonus() {
+        let mut bandit = UcbBandit::with_slots(["a", "b"]);
+        let ctx = ctx();
+        for expected in ["remind.a", "remind.b"] {
+            let decision = bandit.decide(&ctx);
+            assert_eq!(decision.action, expected);
+            bandit.feedback(&ctx, &decision.action, 0.0);
+        }
+        // Gleiche Mittelwerte, aber "a" wurde seltener gezogen → größerer Bonus.
+        bandit.feedback(&ctx, "remind.b", 0.0);
+        assert_eq!(bandit.decide(&ctx).action, "remind.a");
+
+        // Ein klar besserer Arm setzt sich durch.
+        for _ in 0..20 {
+            bandit.feedback(&ctx, "remind.a", 0.0);
+            bandit.feedback(&ctx, "remind.b", 1.0);
+        }
+        assert_eq!(bandit.decide(&ctx).action, "remind.b");
+    }
+
+    #[test]
+    fn snapshot_follows_contract_and_accepts_remind_bandit_state() {
+        let mut bandit = UcbBandit::default();
+        let ctx = ctx();
+        bandit.feedback(&ctx, "remind.morning", 1.0);
+        bandit.feedback(&ctx, "remind.morning", 0.0);
+
+        let snap = bandit.snapshot();
+        assert_eq!(snap["policy_id"], "ucb-bandit");
+        assert_eq!(snap["counts"], json!([2, 0, 0]));
+        assert_eq!(snap["values"], json!([0.5, 0.0, 0.0]));
+        assert_eq!(snap["epsilon"], json!(0.0));
+
+        let mut restored = UcbBandit::with_slots(["x"]);
+        restored.load(snap);
+        assert_eq!(restored.slots, bandit.slots);
+        assert_eq!(restored.pulls("morning"), 2);
+
+        let mut remind = RemindBandit::default();
+        remind.feedback(&ctx, "remind.evening", 1.0);
+        let mut swapped = UcbBandit::with_slots(["x"]);
+        swapped.load(remind.snapshot());
+        assert_eq!(swapped.pulls("evening"), 1);
+
+        let mut rejected = UcbBandit::with_slots(["x"]);
+        let mut foreign = bandit.snapshot();
+        foreign["policy_id"] = json!("other");
+        rejected.load(foreign);
+        assert_eq!(rejected.slots, vec!["x".to_string()]);
+    }
+}
@@ -0,0 +1,189 @@
+//! Aufwärmphase für frisch angelegte Policies.
+//!
+//! Während der ersten `decisions` Entscheidungen werden die Arme im gewichteten
+//! Round-Robin-Verfahren reihum gezogen, damit jeder Arm eine Mindestanzahl an
+//! Stichproben erhält, bevor die eigentliche Strategie (z. B. ε-greedy) übernimmt.
+//!
+//! Ein Zyklus umfasst `W` Ziehungen (Summe aller Gewichte) und besteht aus
+//! Runden: In Runde `r` kommt jeder Arm mit Gewicht größer `r` einmal vor, in
+//! der Reihenfolge der Arme. Schwere Arme treten so verteilt über den Zyklus
+//! statt in Blöcken auf.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Konfiguration der Aufwärmphase.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WarmupConfig {
+    /// Anzahl der Entscheidungen, die im Round-Robin-Modus getroffen werden.
+    pub decisions: u64,
+    /// Optionale Gewichte je Arm (Default: 1). Ein Gewicht von 0 nimmt den Arm
+    /// aus der Aufwärmphase heraus.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub weights: BTreeMap<String, u32>,
+}
+
+impl WarmupConfig {
+    /// Aufwärmphase über `decisions` Entscheidungen mit gleichen Gewichten.
+    #[must_use]
+    pub fn new(decisions: u64) -> Self {
+        Self {
+            decisions,
+            weights: BTreeMap::new(),
+        }
+    }
+
+    /// Setzt das Gewicht eines einzelnen Arms.
+    #[must_use]
+    pub fn weight(mut self, arm: impl Into<String>, weight: u32) -> Self {
+        self.weights.insert(arm.into(), weight);
+        self
+    }
+
+    fn weight_of(&self, arm: &str) -> u32 {
+        self.weights.get(arm).copied().unwrap_or(1)
+    }
+
+    /// Mindestanzahl an Warm-up-Ziehungen, die `arm` bei `arms` erhält.
+    ///
+    /// Jeder volle Zyklus bringt `w` Ziehungen; hinzu kommen die Ziehungen im
+    /// angebrochenen letzten Zyklus. Arme außerhalb von `arms` erhalten keine.
+    #[must_use]
+    pub fn min_samples(&self, arms: &[String], arm: &str) -> u64 {
+        let weights = self.weights_of(arms);
+        let total: u64 = weights.iter().sum();
+        let Some(index) = arms.iter().position(|a| a == arm) else {
+            return 0;
+        };
+        if total == 0 {
+            return 0;
+        }
+        let weight = weights[index];
+        let rest = self.decisions % total;
+        let partial = match rest.checked_sub(1).and_then(|last| locate(&weights, last)) {
+            Some((round, rank)) => {
+                let before = weights[..index].iter().filter(|&&w| w > round).count();
+                round.min(weight) + u64::from(weight > round && before as u64 <= rank)
+            }
+            None => 0,
+        };
+        (self.decisions / total) * weight + partial
+    }
+
+    fn weights_of(&self, arms: &[String]) -> Vec<u64> {
+        arms.iter().map(|a| u64::from(self.weight_of(a))).collect()
+    }
+}
+
+/// Ordnet die Zyklus-Position `position` ihrer Runde und dem Rang darin zu.
+///
+/// Die Runden zwischen zwei aufeinanderfolgenden Gewichtsstufen sind gleich
+/// lang; ein Durchlauf über die kumulierten Abschnittslängen genügt daher.
+fn locate(weights: &[u64], mut position: u64) -> Option<(u64, u64)> {
+    let mut levels = weights.to_vec();
+    levels.sort_unstable();
+    let mut floor = 0;
+    for (i, &level) in levels.iter().enumerate() {
+        if level == floor {
+            continue;
+        }
+        // `levels` ist aufsteigend sortiert: ab `i` haben alle Arme Gewicht >= `level`.
+        let width = (levels.len() - i) as u64;
+        let span = (level - floor) * width;
+        if position < span {
+            return Some((floor + position / width, position % width));
+        }
+        position -= span;
+        floor = level;
+    }
+    None
+}
+
+/// Liefert den Index des Arms, der im Warm-up-Schritt `step` gezogen wird.
+///
+/// Der Aufwand hängt nur von der Anzahl der Arme ab, nicht von `step`.
+/// Gibt `None` zurück, wenn alle Gewichte 0 sind.
+pub(crate) fn schedule_index(config: &WarmupConfig, arms: &[String], step: u64) -> Option<usize> {
+    let weights = config.weights_of(arms);
+    let total: u64 = weights.iter().sum();
+    if total == 0 {
+        return None;
+    }
+    let (round, rank) = locate(&weights, step % total)?;
+    weights
+        .iter()
+        .enumerate()
+        .filter(|(_, &w)| w > round)
+        .nth(usize::try_from(rank).ok()?)
+        .map(|(idx, _)| idx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arms(names: &[&str]) -> Vec<String> {
+        names.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn equal_weights_cycle_in_order() {
+        let cfg = WarmupConfig::new(6);
+        let arms = arms(&["a", "b", "c"]);
+        let order: Vec<Option<usize>> = (0..6).map(|s| schedule_index(&cfg, &arms, s)).collect();
+        assert_eq!(
+            order,
+            vec![Some(0), Some(1), Some(2), Some(0), Some(1), Some(2)]
+        );
+    }
+
+    #[test]
+    fn weights_are_spread_smoothly() {
+        let cfg = WarmupConfig::new(4).weight("a", 2);
+        let arms = arms(&["a", "b", "c"]);
+        let order: Vec<Option<usize>> = (0..4).map(|s| schedule_index(&cfg, &arms, s)).collect();
+        assert_eq!(order, vec![Some(0), Some(1), Some(2), Some(0)]);
+        assert_eq!(cfg.min_samples(&arms, "a"), 2);
+        assert_eq!(cfg.min_samples(&arms, "b"), 1);
+    }
+
+    #[test]
+    fn heavy_arms_spread_over_rounds_and_min_samples_match() {
+        let cfg = WarmupConfig::new(7)
+            .weight("a", 3)
+            .weight("c", 0)
+            .weight("d", 2);
+        let arms = arms(&["a", "b", "c", "d"]);
+        let order: Vec<Option<usize>> = (0..7).map(|s| schedule_index(&cfg, &arms, s)).collect();
+        assert_eq!(
+            order,
+            vec![
+                Some(0),
+                Some(1),
+                Some(3),
+                Some(0),
+                Some(3),
+                Some(0),
+                Some(0)
+            ]
+        );
+        for (idx, arm) in arms.iter().enumerate() {
+            let drawn = order.iter().filter(|o| **o == Some(idx)).count() as u64;
+            assert_eq!(cfg.min_samples(&arms, arm), drawn, "arm {arm}");
+        }
+        assert_eq!(cfg.min_samples(&arms, "x"), 0);
+        assert_eq!(
+            schedule_index(&cfg, &arms, u64::MAX),
+            schedule_index(&cfg, &arms, u64::MAX % 6)
+        );
+    }
+
+    #[test]
+    fn zero_weights_are_skipped() {
+        let cfg = WarmupConfig::new(3)
+            .weight("a", 0)
+            .weight("b", 0)
+            .weight("c", 0);
+        assert_eq!(schedule_index(&cfg, &arms(&["a", "b", "c"]), 0), None);
+    }
+}
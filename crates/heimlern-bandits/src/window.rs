@@ -0,0 +1,101 @@
+//! Gleitendes Fenster über die jüngsten Rewards je Arm.
+//!
+//! Haushalte ändern ihr Verhalten über Wochen; kumulierte Mittelwerte halten
+//! an längst überholten Beobachtungen fest. Mit einem Fenster der Größe
+//! `size` berücksichtigt der Bandit für seine Schätzung nur die letzten
+//! `size` Rewards eines Arms (Ringpuffer), ältere Beobachtungen fallen heraus.
+//! Die kumulierten Zähler im Contract-Snapshot bleiben davon unberührt.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+
+/// Obergrenze für die Fenstergröße, um Snapshots klein zu halten.
+pub const MAX_WINDOW: usize = 10_000;
+
+/// Konfiguration des gleitenden Fensters.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowConfig {
+    /// Anzahl der jüngsten Rewards je Arm (1..=[`MAX_WINDOW`]).
+    pub size: usize,
+}
+
+impl WindowConfig {
+    #[must_use]
+    pub fn new(size: usize) -> Self {
+        Self { size }
+    }
+
+    /// Fenstergröße, auf den gültigen Bereich begrenzt.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.size.clamp(1, MAX_WINDOW)
+    }
+}
+
+/// Jüngste Rewards aller Arme, älteste zuerst.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WindowState {
+    #[serde(default)]
+    pub samples: BTreeMap<String, VecDeque<f64>>,
+}
+
+impl WindowState {
+    /// Nimmt einen Reward für `arm` auf und verdrängt bei vollem Fenster den ältesten.
+    pub fn push(&mut self, config: &WindowConfig, arm: &str, reward: f64) {
+        let capacity = config.capacity();
+        let buffer = self.samples.entry(arm.to_string()).or_default();
+        while buffer.len() >= capacity {
+            buffer.pop_front();
+        }
+        buffer.push_back(reward);
+    }
+
+    /// Anzahl der Rewards von `arm` im Fenster.
+    #[must_use]
+    pub fn len(&self, arm: &str) -> usize {
+        self.samples.get(arm).map_or(0, VecDeque::len)
+    }
+
+    /// Mittlerer Reward von `arm` im Fenster (`None` ohne Beobachtungen).
+    #[must_use]
+    pub fn mean(&self, arm: &str) -> Option<f64> {
+        let buffer = self.samples.get(arm).filter(|b| !b.is_empty())?;
+        #[allow(clippy::cast_precision_loss)]
+        Some(buffer.iter().sum::<f64>() / buffer.len() as f64)
+    }
+
+    /// Kürzt überlange Puffer (etwa nach Verkleinerung des Fensters), verwirft
+    /// ungültige Rewards und Einträge für Arme, die nicht mehr existieren.
+    pub fn sanitize(&mut self, config: &WindowConfig, arms: &[String]) {
+        let capacity = config.capacity();
+        self.samples.retain(|name, _| arms.contains(name));
+        for buffer in self.samples.values_mut() {
+            buffer.retain(|r| r.is_finite());
+            while buffer.len() > capacity {
+                buffer.pop_front();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ring_buffer_keeps_only_latest_rewards() {
+        let cfg = WindowConfig::new(3);
+        let mut state = WindowState::default();
+        for r in [0.0, 0.0, 1.0, 1.0, 1.0] {
+            state.push(&cfg, "a", r);
+        }
+        assert_eq!(state.len("a"), 3);
+        assert_eq!(state.mean("a"), Some(1.0));
+        assert_eq!(state.mean("b"), None);
+
+        state.sanitize(&WindowConfig::new(1), &["a".to_string()]);
+        assert_eq!(state.len("a"), 1);
+        state.sanitize(&WindowConfig::new(1), &[]);
+        assert!(state.samples.is_empty());
+    }
+}
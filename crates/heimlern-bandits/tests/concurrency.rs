@@ -0,0 +1,93 @@
+//! Stress tests for shared bandit state.
+//!
+//! There is no dedicated concurrent policy host yet; integrators share a
+//! policy behind `Arc<Mutex<_>>`. These tests hammer that setup from many
+//! threads and check that no feedback is lost, including while another thread
+//! keeps swapping the state via snapshot/load.
+//!
+//! Loom model tests for snapshot-swap vs. feedback races and WAL ordering are
+//! left out on purpose: without a host there are no synchronization
+//! primitives of our own for loom to permute, and there is no WAL. They belong
+//! next to the host once it exists.
+
+use heimlern_bandits::RemindBandit;
+use heimlern_core::{Context, Policy};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+const THREADS: usize = 8;
+const ROUNDS: usize = 250;
+
+fn ctx() -> Context {
+    Context {
+        kind: "reminder".into(),
+        features: json!({}),
+    }
+}
+
+fn total_count(snapshot: &Value) -> u64 {
+    snapshot["counts"]
+        .as_array()
+        .expect("counts array")
+        .iter()
+        .map(|c| c.as_u64().expect("count is u64"))
+        .sum()
+}
+
+fn hammer(policy: &Arc<Mutex<RemindBandit>>) -> Vec<thread::JoinHandle<()>> {
+    (0..THREADS)
+        .map(|_| {
+            let policy = Arc::clone(policy);
+            thread::spawn(move || {
+                let ctx = ctx();
+                for _ in 0..ROUNDS {
+                    let action = policy.lock().expect("lock").decide(&ctx).action;
+                    policy.lock().expect("lock").feedback(&ctx, &action, 1.0);
+                }
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn concurrent_decide_feedback_conserves_counts() {
+    let policy = Arc::new(Mutex::new(RemindBandit::default()));
+    for handle in hammer(&policy) {
+        handle.join().expect("worker panicked");
+    }
+
+    let snapshot = policy.lock().expect("lock").snapshot();
+    assert_eq!(total_count(&snapshot), (THREADS * ROUNDS) as u64);
+    for value in snapshot["values"].as_array().expect("values array") {
+        let avg = value.as_f64().expect("value is f64");
+        assert!(avg == 0.0 || (avg - 1.0).abs() < 1e-9, "avg = {avg}");
+    }
+}
+
+#[test]
+fn snapshot_swap_during_feedback_conserves_counts() {
+    let policy = Arc::new(Mutex::new(RemindBandit::default()));
+    let workers = hammer(&policy);
+
+    let swapper = {
+        let policy = Arc::clone(&policy);
+        thread::spawn(move || {
+            for _ in 0..ROUNDS {
+                let mut guard = policy.lock().expect("lock");
+                let snap = guard.snapshot();
+                let mut fresh = RemindBandit::default();
+                fresh.load(snap);
+                *guard = fresh;
+            }
+        })
+    };
+
+    for handle in workers {
+        handle.join().expect("worker panicked");
+    }
+    swapper.join().expect("swapper panicked");
+
+    let snapshot = policy.lock().expect("lock").snapshot();
+    assert_eq!(total_count(&snapshot), (THREADS * ROUNDS) as u64);
+}
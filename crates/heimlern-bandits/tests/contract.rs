@@ -0,0 +1,98 @@
+//! Snapshots of fully configured policies against the published contract.
+//!
+//! Every extension a policy writes must be declared by the snapshot schema of
+//! its version; `validate_snapshot` picks that schema from `version`.
+
+use heimlern_bandits::{
+    ArmMeta, AuditConfig, CooldownConfig, DecayConfig, EvictionStrategy, FatigueConfig,
+    GaussianThompsonBandit, LinUcbBandit, RecencyConfig, RemindBandit, RemindBanditBuilder,
+    ThompsonBandit, TimingConfig, UcbBandit, WarmupConfig, WindowConfig,
+};
+use heimlern_core::contracts::{snapshot_schema, validate_snapshot, Schema};
+use heimlern_core::{Context, Policy};
+use serde_json::{json, Value};
+
+fn ctx() -> Context {
+    Context {
+        kind: "reminder".into(),
+        features: json!({ "hour": 8 }),
+    }
+}
+
+/// Decides and rewards a few times so every extension carries state.
+fn exercise(policy: &mut dyn Policy) -> Value {
+    let ctx = ctx();
+    for i in 0..12 {
+        let action = policy.decide(&ctx).action;
+        policy.feedback(&ctx, &action, if i % 3 == 0 { 0.0 } else { 1.0 });
+    }
+    policy.snapshot()
+}
+
+fn configured() -> RemindBanditBuilder {
+    RemindBandit::builder()
+        .namespace("care")
+        .epsilon(0.1)
+        .epsilon_for("reminder", 0.3)
+        .seed(7)
+        .warmup(WarmupConfig::new(4).weight("morning", 2))
+        .fatigue(FatigueConfig::new(5.0, 0.1))
+        .cooldown(CooldownConfig::decisions(1))
+        .timing(TimingConfig::default())
+        .eviction(EvictionStrategy::Lru)
+        .optimistic_init(1.0)
+        .audit(AuditConfig::new(8).persisted())
+        .prior("evening", 3.0, 0.6)
+        .arm_meta("morning", ArmMeta::label("Morgens"))
+}
+
+#[test]
+fn fully_configured_remind_bandit_matches_v2_schema() {
+    let builders = [
+        configured().window(WindowConfig::new(5)),
+        configured().recency(RecencyConfig::new(10.0)),
+        configured().decay(DecayConfig::daily(0.99)),
+    ];
+    for builder in builders {
+        let mut bandit = builder.build().expect("valid configuration");
+        bandit.add_arm("night").expect("arm can be added");
+        let snapshot = exercise(&mut bandit);
+        assert_eq!(snapshot_schema(&snapshot), Schema::PolicySnapshotV2);
+        for key in [
+            "namespace",
+            "warmup",
+            "fatigue",
+            "timing",
+            "audit",
+            "arm_log",
+        ] {
+            assert!(snapshot.get(key).is_some(), "{key} missing in {snapshot}");
+        }
+        if let Err(e) = validate_snapshot(&snapshot) {
+            panic!("{e}");
+        }
+    }
+}
+
+#[test]
+fn other_policies_match_v2_schema() {
+    let policies: [Box<dyn Policy>; 4] = [
+        Box::new(UcbBandit::default()),
+        Box::new(ThompsonBandit::default()),
+        Box::new(GaussianThompsonBandit::default()),
+        Box::new(LinUcbBandit::default()),
+    ];
+    for mut policy in policies {
+        let snapshot = exercise(policy.as_mut());
+        if let Err(e) = validate_snapshot(&snapshot) {
+            panic!("{e}");
+        }
+    }
+}
+
+#[test]
+fn undeclared_extension_is_rejected() {
+    let mut snapshot = RemindBandit::default().snapshot();
+    snapshot["unknown_extension"] = json!(true);
+    assert!(validate_snapshot(&snapshot).is_err());
+}
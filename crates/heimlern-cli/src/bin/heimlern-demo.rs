@@ -0,0 +1,350 @@
+//! End-to-end demo of the heimlern learn loop.
+//!
+//! One run wires every stage of the propose/review/apply architecture:
+//!
+//! 1. **ingest** `AussenEvent`s from a JSONL file (generated if none is given),
+//! 2. **context**: map each event to a [`Context`] via a [`KindMapper`],
+//! 3. **decide** with a seeded [`RemindBandit`],
+//! 4. **outcome**: a synthetic household answers each reminder,
+//! 5. **ledger**: decision records go to `decisions.jsonl`, outcomes to
+//!    `outcomes.jsonl`, decision and feedback counters end up in
+//!    `metrics.json`,
+//! 6. **analyze/propose**: the [`FeedbackAnalyzer`] inspects the round and
+//!    replays it against a copy of the bandit with the proposed deltas,
+//! 7. **review/apply**: proposals are validated against the policy
+//!    descriptor and applied via [`apply_to_params`] or rejected,
+//!
+//! and repeats for a number of rounds. The household answers most reminders
+//! outside the evening with a shrug, so heavy exploration fails often; the
+//! analyzer proposes less exploration, the bandit learns the evening slot, and
+//! the success rate climbs. The run fails unless the last third of the rounds
+//! beats the first third, so it doubles as a regression test.
+//!
+//! Events read, decision records written and the final snapshot are checked
+//! against their contracts in `contracts/`; a violation aborts the run.
+
+use anyhow::{ensure, Context as _, Result};
+use clap::Parser;
+use heimlern_bandits::RemindBandit;
+use heimlern_core::contracts;
+use heimlern_core::event::AussenEvent;
+use heimlern_core::kind::{self, KindMapper};
+use heimlern_core::mapping::ContextMapping;
+use heimlern_core::metrics::PolicyMetrics;
+use heimlern_core::{Context, Policy};
+use heimlern_feedback::apply::apply_to_params;
+use heimlern_feedback::sink::{JsonlSink, OutcomeSink};
+use heimlern_feedback::{DecisionOutcome, FeedbackAnalyzer, OutcomeType, ProposalStatus};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::json;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+/// Success probability of a reminder per slot in the synthetic household.
+const HOUSEHOLD: &[(&str, f64)] = &[("morning", 0.1), ("afternoon", 0.2), ("evening", 0.65)];
+
+#[derive(Parser)]
+#[command(
+    author,
+    version,
+    about = "Run the full heimlern learn loop against a synthetic household",
+    long_about = None
+)]
+struct Cli {
+    /// Directory for events, ledger, proposals and the final snapshot
+    #[arg(long, default_value = "data/demo")]
+    out: PathBuf,
+
+    /// JSONL file with AussenEvents (generated into --out if omitted)
+    #[arg(long)]
+    events: Option<PathBuf>,
+
+    /// Number of learn-loop rounds
+    #[arg(long, default_value = "12")]
+    rounds: usize,
+
+    /// Decisions per round
+    #[arg(long, default_value = "100")]
+    per_round: usize,
+
+    /// Initial exploration rate of the bandit
+    #[arg(long, default_value = "0.9")]
+    epsilon: f32,
+
+    /// Seed for the bandit and the synthetic household
+    #[arg(long, default_value = "7")]
+    seed: u64,
+}
+
+/// Per-round summary printed as a table.
+struct Round {
+    epsilon: f64,
+    success_rate: f64,
+    proposal: Option<ProposalStatus>,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    ensure!(
+        cli.rounds >= 3 && cli.per_round > 0,
+        "need at least three rounds with decisions"
+    );
+    fs::create_dir_all(&cli.out)
+        .with_context(|| format!("Failed to create {}", cli.out.display()))?;
+
+    // 1. ingest
+    let events_path = match &cli.events {
+        Some(path) => path.clone(),
+        None => {
+            let path = cli.out.join("events.jsonl");
+            write_events(&path, cli.per_round)?;
+            path
+        }
+    };
+    let events = read_events(&events_path)?;
+    ensure!(
+        !events.is_empty(),
+        "{} has no events",
+        events_path.display()
+    );
+
+    // 2. context building
+    let mapping = ContextMapping::default().kinds(
+        KindMapper::new(kind::GENERIC)
+            .rule("reminder.*", kind::REMINDER)
+            .rule("sensor.*", kind::ENVIRONMENT),
+    );
+
+    // The default slots of the bandit match the household.
+    let mut bandit = RemindBandit::default().with_seed(cli.seed);
+    bandit.epsilon = cli.epsilon;
+    let mut household = StdRng::seed_from_u64(cli.seed.wrapping_add(1));
+    let mut metrics = PolicyMetrics::default();
+
+    let decisions_path = cli.out.join("decisions.jsonl");
+    let ledger_path = cli.out.join("outcomes.jsonl");
+    let proposals_path = cli.out.join("proposals.jsonl");
+    for path in [&decisions_path, &ledger_path, &proposals_path] {
+        if path.exists() {
+            fs::remove_file(path).with_context(|| format!("Failed to reset {}", path.display()))?;
+        }
+    }
+    let mut decisions = File::create(&decisions_path)
+        .with_context(|| format!("Failed to create {}", decisions_path.display()))?;
+    let mut ledger = JsonlSink::append(&ledger_path)?;
+    let mut proposals = File::create(&proposals_path)
+        .with_context(|| format!("Failed to create {}", proposals_path.display()))?;
+    let analyzer = FeedbackAnalyzer::default();
+
+    let mut rounds = Vec::with_capacity(cli.rounds);
+    let mut stream = events.iter().cycle();
+    for round in 0..cli.rounds {
+        let epsilon = f64::from(bandit.epsilon);
+        let mut outcomes = Vec::with_capacity(cli.per_round);
+        for i in 0..cli.per_round {
+            let Some(event) = stream.next() else {
+                break;
+            };
+            let ctx = Context::from_aussen_event_with(event, &mapping);
+
+            // 3. decide, 4. synthetic outcome
+            let start = Instant::now();
+            let decision = bandit.decide(&ctx);
+            metrics.record_decision(&decision, start.elapsed());
+            let slot = decision.action.trim_start_matches("remind.");
+            let p = HOUSEHOLD
+                .iter()
+                .find(|(s, _)| *s == slot)
+                .map_or(0.0, |(_, p)| *p);
+            let success = household.gen::<f64>() < p;
+            let reward = if success { 1.0 } else { 0.0 };
+            metrics.record_feedback(&decision.action, reward);
+            bandit.feedback(&ctx, &decision.action, reward);
+
+            // 5. ledger
+            let record = json!({
+                "ts": now(),
+                "policy_id": bandit.descriptor().policy_id,
+                "decision": &decision,
+            });
+            contracts::validate_decision(&record)
+                .with_context(|| format!("round {}: invalid decision record", round + 1))?;
+            serde_json::to_writer(&mut decisions, &record)?;
+            decisions.write_all(b"\n")?;
+            let outcome = DecisionOutcome {
+                decision_id: format!("demo-{round}-{i}"),
+                ts: now(),
+                policy_id: Some(bandit.descriptor().policy_id),
+                action: Some(decision.action.clone()),
+                outcome: if success {
+                    OutcomeType::Success
+                } else {
+                    OutcomeType::Failure
+                },
+                success,
+                reward: Some(reward),
+                context: Some(json!(ctx)),
+                metadata: Some(json!({ "why": decision.why })),
+            };
+            ledger.send(&outcome)?;
+            outcomes.push(outcome);
+        }
+        ledger.flush()?;
+
+        // 6. analyze/propose, 7. review/apply
+        let descriptor = bandit.descriptor();
+        let mut status = None;
+        if let Some(mut proposal) = analyzer.propose_for(&descriptor, &outcomes) {
+            // Replay the round against a copy of the bandit with the deltas applied.
+            if !proposal.deltas.is_empty() {
+                let mut replica = RemindBandit::default();
+                replica.load(bandit.snapshot());
+                let _ = analyzer.simulate_replay(
+                    &mut proposal,
+                    &descriptor,
+                    &bandit.tunable_params(),
+                    replica,
+                    RemindBandit::set_param,
+                    &outcomes,
+                );
+            }
+            // Patterns without deltas stay `proposed`: there is nothing to apply.
+            let review = (!proposal.deltas.is_empty())
+                .then(|| apply_to_params(&proposal, &descriptor, &bandit.tunable_params()));
+            match review {
+                None => {}
+                Some(Ok(params)) => {
+                    for (key, value) in params {
+                        bandit.set_param(&key, value);
+                    }
+                    proposal.status = ProposalStatus::Accepted;
+                }
+                Some(Err(violations)) => {
+                    for v in violations {
+                        eprintln!("round {}: rejected delta: {v}", round + 1);
+                    }
+                    proposal.status = ProposalStatus::Rejected;
+                }
+            }
+            status = Some(proposal.status);
+            // Pattern-only findings are no weight adjustment and stay out of the file.
+            if !proposal.deltas.is_empty() {
+                let value = serde_json::to_value(&proposal)?;
+                contracts::validate_weight_adjustment(&value).with_context(|| {
+                    format!("round {}: proposal violates its contract", round + 1)
+                })?;
+                serde_json::to_writer(&mut proposals, &value)?;
+                proposals.write_all(b"\n")?;
+            }
+        }
+
+        let successes = outcomes.iter().filter(|o| o.success).count();
+        #[allow(clippy::cast_precision_loss)]
+        let success_rate = successes as f64 / outcomes.len().max(1) as f64;
+        rounds.push(Round {
+            epsilon,
+            success_rate,
+            proposal: status,
+        });
+    }
+
+    let snapshot_path = cli.out.join("snapshot.json");
+    let snapshot = bandit.snapshot();
+    contracts::validate_snapshot(&snapshot).context("final snapshot violates its contract")?;
+    fs::write(&snapshot_path, serde_json::to_string_pretty(&snapshot)?)
+        .with_context(|| format!("Failed to write {}", snapshot_path.display()))?;
+    let metrics_path = cli.out.join("metrics.json");
+    fs::write(
+        &metrics_path,
+        serde_json::to_string_pretty(&metrics.to_json()?)?,
+    )
+    .with_context(|| format!("Failed to write {}", metrics_path.display()))?;
+
+    println!("round  epsilon  success  proposal");
+    for (i, r) in rounds.iter().enumerate() {
+        let proposal = r
+            .proposal
+            .map_or_else(|| "-".to_string(), |s| format!("{s:?}").to_lowercase());
+        println!(
+            "{:>5}  {:>7.3}  {:>6.1}%  {proposal}",
+            i + 1,
+            r.epsilon,
+            r.success_rate * 100.0
+        );
+    }
+
+    let third = rounds.len() / 3;
+    let first = mean_success(&rounds[..third]);
+    let last = mean_success(&rounds[rounds.len() - third..]);
+    ensure!(
+        last > first,
+        "success rate did not improve: {:.1}% -> {:.1}%",
+        first * 100.0,
+        last * 100.0
+    );
+    println!(
+        "Success rate improved from {:.1}% to {:.1}%; artifacts in {}",
+        first * 100.0,
+        last * 100.0,
+        cli.out.display()
+    );
+    Ok(())
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn mean_success(rounds: &[Round]) -> f64 {
+    rounds.iter().map(|r| r.success_rate).sum::<f64>() / rounds.len().max(1) as f64
+}
+
+/// Write `count` synthetic reminder events as JSONL.
+fn write_events(path: &Path, count: usize) -> Result<()> {
+    let mut out = String::new();
+    for i in 0..count {
+        let event = AussenEvent {
+            id: Some(format!("demo-event-{i}")),
+            r#type: "reminder.due".into(),
+            source: "demo-haushalt".into(),
+            title: Some(["Müll rausbringen", "Pflanzen gießen", "Wäsche"][i % 3].into()),
+            summary: None,
+            url: None,
+            tags: Some(vec!["haushalt".into()]),
+            ts: Some(now()),
+            features: None,
+            meta: None,
+        };
+        out.push_str(&serde_json::to_string(&event)?);
+        out.push('\n');
+    }
+    fs::write(path, out).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Read AussenEvents from a JSONL file, skipping blank lines.
+fn read_events(path: &Path) -> Result<Vec<AussenEvent>> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut events = Vec::new();
+    for (idx, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let event = serde_json::from_str::<serde_json::Value>(&line)
+            .map_err(anyhow::Error::from)
+            .and_then(|value| {
+                contracts::validate_event(&value)?;
+                Ok(serde_json::from_value(value)?)
+            })
+            .with_context(|| format!("{}:{}: invalid event", path.display(), idx + 1))?;
+        events.push(event);
+    }
+    Ok(events)
+}
+
+fn now() -> String {
+    OffsetDateTime::now_utc()
+        .format(&Rfc3339)
+        .unwrap_or_else(|_| "1970-01-01T00:00:00Z".to_string())
+}
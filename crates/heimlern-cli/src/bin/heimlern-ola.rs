@@ -2,6 +2,7 @@
 
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand, ValueEnum};
+use heimlern_core::kind::KindMapper;
 use heimlern_core::ola;
 use serde_json::{json, Value};
 use std::fs::File;
@@ -32,6 +33,13 @@ enum Commands {
         /// Policy id to embed when emitting a decision outcome.
         #[arg(long, default_value = "grabowski-routing-v0")]
         policy_id: String,
+        /// JSON KindMapper inferring the context kind from the task class.
+        #[arg(long)]
+        kinds: Option<PathBuf>,
+        /// Rhai script computing the reward instead of the built-in rules.
+        #[cfg(feature = "scripting")]
+        #[arg(long)]
+        reward_script: Option<PathBuf>,
     },
     /// Convert a routing outcome JSON record into decision-outcome JSON.
     DecisionOutcome {
@@ -41,6 +49,9 @@ enum Commands {
         /// Policy id to embed in the decision outcome.
         #[arg(long, default_value = "grabowski-routing-v0")]
         policy_id: String,
+        /// JSON KindMapper inferring the context kind from the task class.
+        #[arg(long)]
+        kinds: Option<PathBuf>,
     },
     /// Derive the safe policy delta key for a route action.
     RouteDeltaKey {
@@ -60,6 +71,29 @@ fn read_json(path: &PathBuf) -> Result<Value> {
     serde_json::from_reader(file).with_context(|| format!("failed to parse {}", path.display()))
 }
 
+#[cfg(feature = "scripting")]
+fn adapt_with_script(input_record: &Value, path: &PathBuf) -> Result<Value> {
+    use heimlern_core::reward_script::{adapt_scripted, RewardScript};
+
+    let source = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let script = RewardScript::compile(&source)
+        .with_context(|| format!("invalid reward script {}", path.display()))?;
+    Ok(adapt_scripted(
+        input_record,
+        &script,
+        &path.display().to_string(),
+    )?)
+}
+
+fn read_kinds(path: Option<&PathBuf>) -> Result<KindMapper> {
+    match path {
+        Some(path) => Ok(serde_json::from_value(read_json(path)?)
+            .with_context(|| format!("invalid kind mapper {}", path.display()))?),
+        None => Ok(KindMapper::default()),
+    }
+}
+
 fn print_json(value: &Value) -> Result<()> {
     println!("{}", serde_json::to_string_pretty(value)?);
     Ok(())
@@ -72,19 +106,34 @@ fn main() -> Result<()> {
             input,
             emit,
             policy_id,
+            kinds,
+            #[cfg(feature = "scripting")]
+            reward_script,
         } => {
             let input_record = read_json(&input)?;
+            #[cfg(feature = "scripting")]
+            let routing_outcome = match reward_script {
+                Some(path) => adapt_with_script(&input_record, &path)?,
+                None => ola::adapt(&input_record),
+            };
+            #[cfg(not(feature = "scripting"))]
             let routing_outcome = ola::adapt(&input_record);
             let payload = if emit == Emit::DecisionOutcome {
-                ola::to_decision_outcome(&routing_outcome, &policy_id)
+                let kinds = read_kinds(kinds.as_ref())?;
+                ola::to_decision_outcome_with(&routing_outcome, &policy_id, &kinds)
             } else {
                 routing_outcome
             };
             print_json(&payload)?;
         }
-        Commands::DecisionOutcome { input, policy_id } => {
+        Commands::DecisionOutcome {
+            input,
+            policy_id,
+            kinds,
+        } => {
             let routing_outcome = read_json(&input)?;
-            let payload = ola::to_decision_outcome(&routing_outcome, &policy_id);
+            let kinds = read_kinds(kinds.as_ref())?;
+            let payload = ola::to_decision_outcome_with(&routing_outcome, &policy_id, &kinds);
             print_json(&payload)?;
         }
         Commands::RouteDeltaKey { action } => match ola::route_delta_key(&action) {
@@ -0,0 +1,607 @@
+//! `heimlern contracts` — keep the Rust artifact types in lockstep with `contracts/`.
+//!
+//! `gen` turns every `*.schema.json` below the contracts directory into serde
+//! structs and enums and writes them to `heimlern_core::contracts`. With
+//! `--check` nothing is written; the command fails if the checked-in module is
+//! out of date, which is what CI runs.
+//!
+//! `validate` checks JSON or JSONL payloads against one of the schemas that
+//! `heimlern_core::contracts` embeds, at runtime and without Python. The
+//! other commands run the same checks through [`ensure`] wherever a snapshot,
+//! decision record or event crosses the file boundary.
+//!
+//! The generator covers the subset of JSON Schema the contracts use: typed
+//! properties, `required`, closed objects (`additionalProperties: false` →
+//! `deny_unknown_fields`), string enums, `const` and nullable type lists.
+//! Anything it cannot express as a Rust type (`oneOf`, mixed type lists) becomes
+//! `serde_json::Value`; conditional requirements (`if`/`then`) are not enforced.
+
+use anyhow::{bail, Context, Result};
+use clap::Subcommand;
+use heimlern_core::contracts::{self, validate_event, Schema};
+use heimlern_core::event::AussenEvent;
+use heimlern_core::HeimlernError;
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Checked-in output, relative to the repository root.
+const DEFAULT_OUT: &str = "crates/heimlern-core/src/contracts/generated.rs";
+
+#[derive(Subcommand)]
+pub(crate) enum ContractsCommand {
+    /// Generate serde types from the JSON Schemas
+    Gen {
+        /// Directory containing the schemas (searched recursively)
+        #[arg(long, default_value = "contracts")]
+        schemas: PathBuf,
+
+        /// Generated Rust module
+        #[arg(long, default_value = DEFAULT_OUT)]
+        out: PathBuf,
+
+        /// Fail if the module is out of date instead of writing it
+        #[arg(long)]
+        check: bool,
+    },
+    /// Validate JSON/JSONL payloads against an embedded contract schema
+    Validate {
+        /// Schema file name, e.g. `policy.snapshot.schema.json`
+        #[arg(long)]
+        schema: String,
+
+        /// Payload files; `.jsonl` files are checked line by line
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+}
+
+pub(crate) fn run(command: ContractsCommand) -> Result<()> {
+    match command {
+        ContractsCommand::Gen {
+            schemas,
+            out,
+            check,
+        } => {
+            let generated = generate(&schemas)?;
+            if check {
+                let current = fs::read_to_string(&out).unwrap_or_default();
+                if current != generated {
+                    bail!(
+                        "{} is out of date with {}; run `heimlern contracts gen`",
+                        out.display(),
+                        schemas.display()
+                    );
+                }
+                println!("{} is up to date", out.display());
+            } else {
+                fs::write(&out, generated)
+                    .with_context(|| format!("Failed to write {}", out.display()))?;
+                println!("Wrote {}", out.display());
+            }
+        }
+        ContractsCommand::Validate { schema, files } => {
+            let Some(schema) = Schema::from_path(&schema) else {
+                let known: Vec<&str> = Schema::ALL.iter().map(|s| s.path()).collect();
+                bail!("Unknown schema {schema}; known: {}", known.join(", "));
+            };
+            let mut failed = 0;
+            for file in &files {
+                let errors = validate_file(schema, file)?;
+                if errors.is_empty() {
+                    println!("✓ {} valid against {schema}", file.display());
+                }
+                for error in &errors {
+                    eprintln!("✗ {}{error}", file.display());
+                }
+                failed += errors.len();
+            }
+            if failed > 0 {
+                bail!("{failed} payload(s) do not match {schema}");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Fail unless `value`, read from or written to `origin`, passes `check`.
+pub(crate) fn ensure(
+    check: fn(&Value) -> Result<(), HeimlernError>,
+    value: &Value,
+    origin: &Path,
+) -> Result<()> {
+    check(value).with_context(|| format!("{} violates its contract", origin.display()))
+}
+
+/// Decode an `aussen.event` payload after checking it against the contract.
+///
+/// Both ingest sources call this per payload; an `Err` carries every
+/// violation in one message so the caller can skip and count the payload
+/// instead of aborting the batch.
+pub(crate) fn decode_event(value: Value) -> std::result::Result<AussenEvent, String> {
+    validate_event(&value).map_err(|e| e.message().to_string())?;
+    serde_json::from_value(value).map_err(|e| e.to_string())
+}
+
+/// Validate one file; returns one message per invalid payload (`:line: …` for JSONL).
+fn validate_file(schema: Schema, path: &Path) -> Result<Vec<String>> {
+    let raw =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let check = |payload: &str| -> String {
+        match serde_json::from_str::<Value>(payload) {
+            Ok(value) => contracts::violations(schema, &value)
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("; "),
+            Err(e) => format!("not valid JSON: {e}"),
+        }
+    };
+    let mut errors = Vec::new();
+    if path.extension().is_some_and(|e| e == "jsonl") {
+        for (idx, line) in raw.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let error = check(line);
+            if !error.is_empty() {
+                errors.push(format!(":{}: {error}", idx + 1));
+            }
+        }
+    } else {
+        let error = check(&raw);
+        if !error.is_empty() {
+            errors.push(format!(": {error}"));
+        }
+    }
+    Ok(errors)
+}
+
+/// Render the Rust module for all schemas below `dir`.
+pub(crate) fn generate(dir: &Path) -> Result<String> {
+    let mut files = Vec::new();
+    collect_schemas(dir, &mut files)?;
+    let mut relative: Vec<(String, PathBuf)> = files
+        .into_iter()
+        .map(|path| {
+            let rel = path
+                .strip_prefix(dir)
+                .unwrap_or(&path)
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join("/");
+            (rel, path)
+        })
+        .collect();
+    relative.sort();
+    if relative.is_empty() {
+        bail!("No *.schema.json files below {}", dir.display());
+    }
+
+    let mut gen = Generator::default();
+    for (rel, path) in &relative {
+        let raw = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let schema: Value = serde_json::from_str(&raw)
+            .with_context(|| format!("Invalid JSON in {}", path.display()))?;
+        gen.root(rel, &schema)
+            .with_context(|| format!("Unsupported schema {}", path.display()))?;
+    }
+
+    let mut out = String::from(
+        "//! Aus den JSON-Schemas in `contracts/` erzeugte Serde-Typen.\n\
+         //!\n\
+         //! @generated by `heimlern contracts gen` – nicht von Hand bearbeiten.\n\
+         //! Nach Schemaänderungen neu erzeugen; ein Test in `heimlern-cli` schlägt\n\
+         //! fehl, solange diese Datei und die Schemas auseinanderlaufen.\n\
+         \n\
+         use serde::{Deserialize, Serialize};\n",
+    );
+    for item in gen.items {
+        out.push('\n');
+        out.push_str(&item);
+    }
+    Ok(out)
+}
+
+fn collect_schemas(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let entries = fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_schemas(&path, files)?;
+        } else if path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.ends_with(".schema.json"))
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[derive(Default)]
+struct Generator {
+    /// Rendered items in output order.
+    items: Vec<String>,
+    names: BTreeSet<String>,
+}
+
+impl Generator {
+    fn root(&mut self, rel: &str, schema: &Value) -> Result<()> {
+        let Some(obj) = schema.as_object() else {
+            bail!("schema is not an object");
+        };
+        if !obj.contains_key("properties") {
+            bail!("top-level schema has no properties");
+        }
+        let stem = rel.rsplit('/').next().unwrap_or(rel);
+        let name = pascal(stem.trim_end_matches(".schema.json"));
+        let mut docs = doc_lines(obj);
+        if !docs.is_empty() {
+            docs.push(String::new());
+        }
+        docs.push(format!("Erzeugt aus `contracts/{rel}`."));
+        let slot = self.items.len();
+        let name = self.object(&name, obj, docs);
+
+        let mut consts = format!("impl {name} {{\n");
+        let _ = writeln!(consts, "    /// Schema-Datei relativ zu `contracts/`.");
+        let _ = writeln!(consts, "    pub const SCHEMA_PATH: &str = {rel:?};");
+        if let Some(id) = obj.get("$id").and_then(Value::as_str) {
+            let _ = writeln!(consts, "    /// `$id` des Schemas.");
+            let _ = writeln!(consts, "    pub const SCHEMA_ID: &str = {id:?};");
+        }
+        consts.push_str("}\n");
+        self.items.insert(slot + 1, consts);
+        Ok(())
+    }
+
+    fn unique(&mut self, name: &str) -> String {
+        let mut candidate = name.to_string();
+        let mut n = 2;
+        while !self.names.insert(candidate.clone()) {
+            candidate = format!("{name}{n}");
+            n += 1;
+        }
+        candidate
+    }
+
+    /// Render a struct for an object schema with properties; returns its name.
+    fn object(&mut self, name: &str, obj: &Map<String, Value>, docs: Vec<String>) -> String {
+        let name = self.unique(name);
+        let slot = self.items.len();
+        self.items.push(String::new());
+
+        let required: BTreeSet<&str> = obj
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|r| r.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        let closed = obj.get("additionalProperties") == Some(&Value::Bool(false));
+        let empty = Map::new();
+        let properties = obj
+            .get("properties")
+            .and_then(Value::as_object)
+            .unwrap_or(&empty);
+        let mut keys: Vec<&String> = properties.keys().collect();
+        keys.sort();
+
+        let mut body = String::new();
+        for key in keys {
+            let prop = &properties[key];
+            let ty = self.ty(prop, &format!("{name}{}", pascal(key)));
+            let (ident, renamed) = field_ident(key);
+            if let Some(p) = prop.as_object() {
+                for line in doc_lines(p) {
+                    push_doc(&mut body, "    ", &line);
+                }
+            }
+            if renamed {
+                let _ = writeln!(body, "    #[serde(rename = {key:?})]");
+            }
+            if required.contains(key.as_str()) {
+                let _ = writeln!(body, "    pub {ident}: {ty},");
+            } else {
+                let ty = if ty.starts_with("Option<") {
+                    ty
+                } else {
+                    format!("Option<{ty}>")
+                };
+                let _ = writeln!(
+                    body,
+                    "    #[serde(default, skip_serializing_if = \"Option::is_none\")]"
+                );
+                let _ = writeln!(body, "    pub {ident}: {ty},");
+            }
+        }
+        if !closed && !properties.contains_key("extra") {
+            push_doc(
+                &mut body,
+                "    ",
+                "Weitere, vom Schema nicht benannte Felder.",
+            );
+            let _ = writeln!(body, "    #[serde(flatten)]");
+            let _ = writeln!(
+                body,
+                "    pub extra: serde_json::Map<String, serde_json::Value>,"
+            );
+        }
+
+        let mut item = String::new();
+        for line in &docs {
+            push_doc(&mut item, "", line);
+        }
+        item.push_str("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n");
+        if closed {
+            item.push_str("#[serde(deny_unknown_fields)]\n");
+        }
+        let _ = writeln!(item, "pub struct {name} {{");
+        item.push_str(&body);
+        item.push_str("}\n");
+        self.items[slot] = item;
+        name
+    }
+
+    /// Render a string enum; returns its name.
+    fn string_enum(&mut self, name: &str, values: &[&str], docs: Vec<String>) -> String {
+        let name = self.unique(name);
+        let mut item = String::new();
+        for line in &docs {
+            push_doc(&mut item, "", line);
+        }
+        item.push_str(
+            "#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]\n",
+        );
+        let _ = writeln!(item, "pub enum {name} {{");
+        let mut variants = BTreeSet::new();
+        for value in values {
+            let mut variant = pascal(value);
+            if variant.is_empty() || variant.starts_with(|c: char| c.is_ascii_digit()) {
+                variant.insert(0, 'V');
+            }
+            let base = variant.clone();
+            let mut n = 2;
+            while !variants.insert(variant.clone()) {
+                variant = format!("{base}{n}");
+                n += 1;
+            }
+            let _ = writeln!(item, "    #[serde(rename = {value:?})]");
+            let _ = writeln!(item, "    {variant},");
+        }
+        item.push_str("}\n");
+        self.items.push(item);
+        name
+    }
+
+    /// Rust type for a property schema; nested types are named after `hint`.
+    fn ty(&mut self, node: &Value, hint: &str) -> String {
+        const ANY: &str = "serde_json::Value";
+        let Some(obj) = node.as_object() else {
+            return ANY.into();
+        };
+        if let Some(values) = obj.get("enum").and_then(Value::as_array) {
+            let strings: Vec<&str> = values.iter().filter_map(Value::as_str).collect();
+            if strings.is_empty() || strings.len() != values.len() {
+                return ANY.into();
+            }
+            return self.string_enum(hint, &strings, doc_lines(obj));
+        }
+        if obj.contains_key("oneOf") || obj.contains_key("anyOf") {
+            return ANY.into();
+        }
+        if let Some(constant) = obj.get("const") {
+            return match constant {
+                Value::String(_) => "String".into(),
+                Value::Bool(_) => "bool".into(),
+                Value::Number(n) if n.is_u64() => "u64".into(),
+                Value::Number(n) if n.is_i64() => "i64".into(),
+                Value::Number(_) => "f64".into(),
+                _ => ANY.into(),
+            };
+        }
+
+        let mut types: Vec<&str> = match obj.get("type") {
+            Some(Value::String(t)) => vec![t.as_str()],
+            Some(Value::Array(ts)) => ts.iter().filter_map(Value::as_str).collect(),
+            _ if obj.contains_key("properties") => vec!["object"],
+            _ => Vec::new(),
+        };
+        let nullable = types.contains(&"null");
+        types.retain(|t| *t != "null");
+        let base = match types.as_slice() {
+            ["string"] => "String".to_string(),
+            ["integer"] => {
+                let unsigned = obj
+                    .get("minimum")
+                    .and_then(Value::as_f64)
+                    .is_some_and(|m| m >= 0.0);
+                if unsigned { "u64" } else { "i64" }.to_string()
+            }
+            ["number"] | ["integer", "number"] | ["number", "integer"] => "f64".to_string(),
+            ["boolean"] => "bool".to_string(),
+            ["array"] => {
+                let item = obj.get("items").cloned().unwrap_or(Value::Bool(true));
+                format!("Vec<{}>", self.ty(&item, &format!("{hint}Item")))
+            }
+            ["object"] => self.object_ty(obj, hint),
+            _ => return ANY.into(),
+        };
+        if nullable {
+            format!("Option<{base}>")
+        } else {
+            base
+        }
+    }
+
+    fn object_ty(&mut self, obj: &Map<String, Value>, hint: &str) -> String {
+        let has_properties = obj
+            .get("properties")
+            .and_then(Value::as_object)
+            .is_some_and(|p| !p.is_empty());
+        if has_properties {
+            return self.object(hint, obj, doc_lines(obj));
+        }
+        match obj.get("additionalProperties") {
+            Some(schema @ Value::Object(ap)) if !ap.is_empty() => format!(
+                "std::collections::BTreeMap<String, {}>",
+                self.ty(schema, &format!("{hint}Value"))
+            ),
+            _ => "serde_json::Map<String, serde_json::Value>".into(),
+        }
+    }
+}
+
+/// Doc lines from `title` and `description`.
+fn doc_lines(obj: &Map<String, Value>) -> Vec<String> {
+    let mut lines = Vec::new();
+    for key in ["title", "description"] {
+        if let Some(text) = obj.get(key).and_then(Value::as_str) {
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+            lines.extend(text.lines().map(|l| l.trim_end().to_string()));
+        }
+    }
+    lines
+}
+
+fn push_doc(out: &mut String, indent: &str, line: &str) {
+    if line.is_empty() {
+        let _ = writeln!(out, "{indent}///");
+    } else {
+        let _ = writeln!(out, "{indent}/// {line}");
+    }
+}
+
+/// `UpperCamelCase` from any separator-delimited name.
+fn pascal(raw: &str) -> String {
+    raw.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_ascii_uppercase().to_string() + chars.as_str()
+            })
+        })
+        .collect()
+}
+
+/// Field identifier for a property key, and whether serde needs a rename.
+fn field_ident(key: &str) -> (String, bool) {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+        "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in",
+        "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+        "return", "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe",
+        "unsized", "use", "virtual", "where", "while", "yield",
+    ];
+    let mut ident = String::with_capacity(key.len());
+    let mut prev_lower = false;
+    for c in key.chars() {
+        if c.is_ascii_uppercase() {
+            if prev_lower {
+                ident.push('_');
+            }
+            ident.push(c.to_ascii_lowercase());
+            prev_lower = false;
+        } else if c.is_ascii_alphanumeric() {
+            ident.push(c);
+            prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        } else {
+            ident.push('_');
+            prev_lower = false;
+        }
+    }
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    if ["self", "super", "crate", "extra"].contains(&ident.as_str()) {
+        ident.push('_');
+    }
+    let renamed = ident != key;
+    if KEYWORDS.contains(&ident.as_str()) {
+        ident.insert_str(0, "r#");
+    }
+    (ident, renamed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn repo_root() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")
+    }
+
+    #[test]
+    fn checked_in_types_match_contracts() {
+        let root = repo_root();
+        let generated = generate(&root.join("contracts")).expect("generate");
+        let current = fs::read_to_string(root.join(DEFAULT_OUT)).expect("checked-in module");
+        assert!(
+            generated == current,
+            "{DEFAULT_OUT} is out of date; run `cargo run -p heimlern-cli -- contracts gen`"
+        );
+    }
+
+    #[test]
+    fn validate_reports_invalid_jsonl_lines() {
+        let root = repo_root();
+        let sample = root.join("data/samples/policy.decision.sample.jsonl");
+        assert!(validate_file(Schema::PolicyDecision, &sample)
+            .expect("read sample")
+            .is_empty());
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("decisions.jsonl");
+        let first = fs::read_to_string(&sample).expect("sample");
+        let first = first.lines().next().expect("sample line");
+        fs::write(&path, format!("{first}\n\n{{\"ts\": 1}}\nnope\n")).expect("write");
+        let errors = validate_file(Schema::PolicyDecision, &path).expect("read");
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert!(errors[0].starts_with(":3: "));
+        assert!(errors[1].starts_with(":4: not valid JSON"));
+    }
+
+    #[test]
+    fn generator_maps_schema_features() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let schema = json!({
+            "title": "Demo",
+            "type": "object",
+            "additionalProperties": false,
+            "required": ["type", "level"],
+            "properties": {
+                "type": { "type": "string" },
+                "level": { "enum": ["low", "not_applicable"] },
+                "count": { "type": "integer", "minimum": 0 },
+                "note": { "type": ["string", "null"] },
+                "why": { "oneOf": [{ "type": "string" }, { "type": "array" }] },
+                "inner": { "type": "object", "properties": { "ok": { "type": "boolean" } } }
+            }
+        });
+        fs::write(
+            dir.path().join("demo.thing.schema.json"),
+            schema.to_string(),
+        )
+        .expect("write schema");
+        let out = generate(dir.path()).expect("generate");
+
+        assert!(out.contains("#[serde(deny_unknown_fields)]\npub struct DemoThing {"));
+        assert!(out.contains("    pub r#type: String,"));
+        assert!(out.contains("    pub level: DemoThingLevel,"));
+        assert!(out.contains("    #[serde(rename = \"not_applicable\")]\n    NotApplicable,"));
+        assert!(out.contains("    pub count: Option<u64>,"));
+        assert!(out.contains("    pub note: Option<String>,"));
+        assert!(out.contains("    pub why: Option<serde_json::Value>,"));
+        assert!(out.contains("pub struct DemoThingInner {"));
+        assert!(out.contains("    pub extra: serde_json::Map<String, serde_json::Value>,"));
+        assert!(out.contains("pub const SCHEMA_PATH: &str = \"demo.thing.schema.json\";"));
+    }
+}
@@ -0,0 +1,282 @@
+//! `heimlern federation` — exchange anonymized statistics between households.
+//!
+//! `share` turns a local snapshot into noised shared statistics, `aggregate`
+//! combines shared documents (files or an exchange endpoint) into consensus
+//! priors, and `seed` writes a starting snapshot from such a prior. Seeding
+//! appends an `external_prior` entry to the snapshot lineage so the
+//! pseudo-counts are never mistaken for local experience.
+
+use crate::contracts;
+use anyhow::{bail, Context, Result};
+use clap::Subcommand;
+use heimlern_core::contracts::validate_snapshot;
+use heimlern_feedback::federation::{
+    consensus, ConsensusPrior, SharedStatistics, EXTERNAL_PRIOR_OPERATION,
+};
+use heimlern_feedback::lineage::{append_lineage, lineage_path, LineageEntry};
+use heimlern_feedback::privacy::privatize;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+/// Default privacy parameter for shared statistics.
+const SHARE_DP_EPSILON: f64 = 1.0;
+/// Version written into seeded snapshots.
+const SNAPSHOT_VERSION: &str = "0.1.0";
+
+#[derive(Subcommand)]
+pub(crate) enum FederationCommand {
+    /// Write noised per-arm statistics of a local snapshot for sharing
+    Share {
+        /// Policy snapshot (policy.snapshot JSON)
+        #[arg(long)]
+        snapshot: PathBuf,
+
+        /// Task the snapshot's arms are chosen for
+        #[arg(long)]
+        task: String,
+
+        /// Output path for the shared statistics
+        #[arg(long)]
+        out: PathBuf,
+
+        /// Differential-privacy epsilon (smaller = more noise)
+        #[arg(long, default_value_t = SHARE_DP_EPSILON)]
+        dp_epsilon: f64,
+    },
+    /// Combine shared statistics into consensus priors per task
+    Aggregate {
+        /// Files or http(s) exchange URLs, each holding one document or an array
+        #[arg(required = true)]
+        inputs: Vec<String>,
+
+        /// Minimum number of households per task
+        #[arg(long, default_value_t = 3)]
+        min_households: usize,
+
+        /// Output path for the priors (JSON array)
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Write a starting snapshot from a consensus prior
+    Seed {
+        /// Priors written by `aggregate`
+        #[arg(long)]
+        priors: PathBuf,
+
+        /// Task to seed
+        #[arg(long)]
+        task: String,
+
+        /// Output path for the snapshot
+        #[arg(long)]
+        out: PathBuf,
+
+        /// Policy id of the seeded snapshot
+        #[arg(long, default_value = "remind-bandit")]
+        policy_id: String,
+
+        /// Pseudo-pulls per arm; local feedback outweighs the prior beyond this
+        #[arg(long, default_value_t = 2)]
+        weight: u64,
+
+        /// Exploration rate of the seeded snapshot
+        #[arg(long, default_value_t = 0.2)]
+        epsilon: f32,
+    },
+}
+
+pub(crate) fn run(command: FederationCommand) -> Result<()> {
+    match command {
+        FederationCommand::Share {
+            snapshot,
+            task,
+            out,
+            dp_epsilon,
+        } => {
+            let value = read_json(&snapshot)?;
+            let Some(mut stats) = SharedStatistics::from_snapshot(&task, &value) else {
+                bail!("{} lacks arms/counts/values", snapshot.display());
+            };
+            privatize(&mut stats, dp_epsilon)?;
+            fs::write(&out, serde_json::to_string_pretty(&stats)?)
+                .with_context(|| format!("Failed to write {}", out.display()))?;
+            println!(
+                "Shared {} arms for task '{task}' to {}",
+                stats.arms.len(),
+                out.display()
+            );
+        }
+        FederationCommand::Aggregate {
+            inputs,
+            min_households,
+            out,
+        } => {
+            let mut shared = Vec::new();
+            for input in &inputs {
+                shared.extend(load_shared(input)?);
+            }
+            let priors = consensus(&shared, min_households);
+            for prior in &priors {
+                let favorite = prior
+                    .favorite()
+                    .map(|(arm, a)| format!(", best in {} households: '{arm}'", a.best_in))
+                    .unwrap_or_default();
+                println!(
+                    "Task '{}': {} households{favorite}",
+                    prior.task, prior.households
+                );
+            }
+            fs::write(&out, serde_json::to_string_pretty(&priors)?)
+                .with_context(|| format!("Failed to write {}", out.display()))?;
+        }
+        FederationCommand::Seed {
+            priors,
+            task,
+            out,
+            policy_id,
+            weight,
+            epsilon,
+        } => {
+            let all: Vec<ConsensusPrior> = serde_json::from_value(read_json(&priors)?)
+                .with_context(|| format!("Invalid priors {}", priors.display()))?;
+            let Some(prior) = all.iter().find(|p| p.task == task) else {
+                bail!("No prior for task '{task}' in {}", priors.display());
+            };
+            seed(prior, &priors, &out, &policy_id, weight, epsilon)?;
+            println!(
+                "Seeded {} arms for task '{task}' from {} households into {}",
+                prior.arms.len(),
+                prior.households,
+                out.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn read_json(path: &Path) -> Result<Value> {
+    let raw =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&raw).with_context(|| format!("Invalid JSON in {}", path.display()))
+}
+
+/// Shared statistics from a file or an exchange endpoint.
+fn load_shared(input: &str) -> Result<Vec<SharedStatistics>> {
+    let value: Value = if input.starts_with("http://") || input.starts_with("https://") {
+        ureq::get(input)
+            .timeout(Duration::from_secs(10))
+            .call()
+            .with_context(|| format!("Failed to fetch from {input}"))?
+            .into_json()?
+    } else {
+        read_json(Path::new(input))?
+    };
+    let documents = match value {
+        Value::Array(items) => items,
+        other => vec![other],
+    };
+    documents
+        .into_iter()
+        .map(|doc| {
+            serde_json::from_value(doc)
+                .with_context(|| format!("Invalid shared statistics in {input}"))
+        })
+        .collect()
+}
+
+fn seed(
+    prior: &ConsensusPrior,
+    source: &Path,
+    out: &Path,
+    policy_id: &str,
+    weight: u64,
+    epsilon: f32,
+) -> Result<()> {
+    if prior.arms.is_empty() {
+        bail!("Prior for task '{}' has no arms", prior.task);
+    }
+    let (arms, counts, values) = prior.seed(weight);
+    let ts = OffsetDateTime::now_utc().format(&Rfc3339)?;
+    let snapshot = json!({
+        "version": SNAPSHOT_VERSION,
+        "policy_id": policy_id,
+        "ts": ts,
+        "arms": arms,
+        "counts": counts,
+        "values": values,
+        "epsilon": epsilon.clamp(0.0, 1.0),
+    });
+    contracts::ensure(validate_snapshot, &snapshot, out)?;
+    fs::write(out, serde_json::to_string_pretty(&snapshot)?)
+        .with_context(|| format!("Failed to write {}", out.display()))?;
+    let entry = LineageEntry::now(EXTERNAL_PRIOR_OPERATION, policy_id).with_details(json!({
+        "task": prior.task,
+        "households": prior.households,
+        "pseudo_pulls_per_arm": weight,
+        "source": source.display().to_string(),
+    }));
+    let lineage = lineage_path(out);
+    append_lineage(&lineage, &entry)
+        .with_context(|| format!("Failed to record lineage in {}", lineage.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heimlern_feedback::lineage::read_lineage;
+
+    #[test]
+    fn share_aggregate_seed_records_external_prior() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut shared = Vec::new();
+        for (i, evening) in [0.9, 0.8, 0.7].into_iter().enumerate() {
+            let snapshot = dir.path().join(format!("home{i}.json"));
+            let value = json!({
+                "version": "0.1.0", "policy_id": "remind-bandit", "ts": "2026-01-01T00:00:00Z",
+                "arms": ["morning", "evening"], "counts": [50, 50],
+                "values": [0.1, evening], "epsilon": 0.2,
+            });
+            fs::write(&snapshot, value.to_string()).expect("write snapshot");
+            let out = dir.path().join(format!("shared{i}.json"));
+            run(FederationCommand::Share {
+                snapshot,
+                task: "chores".into(),
+                out: out.clone(),
+                dp_epsilon: 10.0,
+            })
+            .expect("share");
+            shared.push(out.display().to_string());
+        }
+
+        let priors = dir.path().join("priors.json");
+        run(FederationCommand::Aggregate {
+            inputs: shared,
+            min_households: 3,
+            out: priors.clone(),
+        })
+        .expect("aggregate");
+
+        let seeded = dir.path().join("seeded.json");
+        run(FederationCommand::Seed {
+            priors,
+            task: "chores".into(),
+            out: seeded.clone(),
+            policy_id: "remind-bandit".into(),
+            weight: 2,
+            epsilon: 0.2,
+        })
+        .expect("seed");
+
+        let snapshot = read_json(&seeded).expect("snapshot");
+        assert_eq!(snapshot["arms"], json!(["evening", "morning"]));
+        assert_eq!(snapshot["counts"], json!([2, 2]));
+        let lineage = read_lineage(&lineage_path(&seeded)).expect("lineage");
+        assert_eq!(lineage.len(), 1);
+        assert_eq!(lineage[0].operation, EXTERNAL_PRIOR_OPERATION);
+        assert_eq!(lineage[0].details["households"], 3);
+    }
+}
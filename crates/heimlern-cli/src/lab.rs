@@ -0,0 +1,323 @@
+//! `heimlern lab` — sandboxed what-if session.
+//!
+//! Loads a policy snapshot and an outcome log into memory and lets the
+//! operator tweak epsilon and per-arm priors, re-run the simulation and compare
+//! the result against the loaded baseline. Nothing is written back; `propose`
+//! only prints what the analyzer would formalize.
+
+use anyhow::{bail, Context, Result};
+use heimlern_feedback::{
+    DecisionOutcome, DeltaValue, Evidence, FeedbackAnalyzer, OutcomeStatistics, ProposalStatus,
+    WeightAdjustmentProposal,
+};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::{BufRead, Write};
+
+const HELP: &str = "\
+commands:
+  show                  current parameters and metrics
+  set epsilon <value>   change exploration rate (0..1)
+  prior <arm> <value>   override the value estimate of an arm
+  clear <arm>           remove a prior override
+  compare               baseline vs. current metrics
+  reset                 discard all tweaks
+  propose               print the analyzer's proposal and the decisions behind it
+  help                  this text
+  quit                  leave the lab";
+
+/// Subset of `policy.snapshot` the lab needs.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct LabSnapshot {
+    #[serde(default = "default_policy_id")]
+    policy_id: String,
+    arms: Vec<String>,
+    values: Vec<f64>,
+    epsilon: f32,
+}
+
+fn default_policy_id() -> String {
+    "unknown".to_string()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Metrics {
+    epsilon: f32,
+    best_arm: Option<String>,
+    expected_reward: f64,
+    sim_success_rate: f32,
+}
+
+pub(crate) struct Lab {
+    snapshot: LabSnapshot,
+    outcomes: Vec<DecisionOutcome>,
+    epsilon: f32,
+    priors: BTreeMap<String, f64>,
+    analyzer: FeedbackAnalyzer,
+}
+
+impl Lab {
+    pub(crate) fn new(snapshot: LabSnapshot, outcomes: Vec<DecisionOutcome>) -> Result<Self> {
+        if snapshot.arms.is_empty() || snapshot.arms.len() != snapshot.values.len() {
+            bail!("Snapshot arms/values are empty or of different length");
+        }
+        Ok(Self {
+            epsilon: snapshot.epsilon.clamp(0.0, 1.0),
+            snapshot,
+            outcomes,
+            priors: BTreeMap::new(),
+            analyzer: FeedbackAnalyzer::default(),
+        })
+    }
+
+    fn metrics(&self, epsilon: f32, priors: &BTreeMap<String, f64>) -> Metrics {
+        let values: Vec<f64> = self
+            .snapshot
+            .arms
+            .iter()
+            .zip(&self.snapshot.values)
+            .map(|(arm, v)| priors.get(arm).copied().unwrap_or(*v))
+            .collect();
+        let best = values
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(i, v)| (i, *v));
+        #[allow(clippy::cast_precision_loss)]
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        let expected_reward = best.map_or(0.0, |(_, best)| {
+            (1.0 - f64::from(epsilon)) * best + f64::from(epsilon) * mean
+        });
+
+        let probe = WeightAdjustmentProposal {
+            version: "v1".into(),
+            basis_policy: self.snapshot.policy_id.clone(),
+            ts: String::new(),
+            deltas: BTreeMap::from([(
+                "epsilon".to_string(),
+                DeltaValue::Absolute { value: epsilon },
+            )]),
+            confidence: 0.0,
+            evidence: Evidence::default(),
+            reasoning: None,
+            status: ProposalStatus::Proposed,
+        };
+
+        Metrics {
+            epsilon,
+            best_arm: best.map(|(i, _)| self.snapshot.arms[i].clone()),
+            expected_reward,
+            sim_success_rate: self.analyzer.simulate_adjustment(&probe, &self.outcomes),
+        }
+    }
+
+    fn baseline(&self) -> Metrics {
+        self.metrics(self.snapshot.epsilon.clamp(0.0, 1.0), &BTreeMap::new())
+    }
+
+    fn current(&self) -> Metrics {
+        self.metrics(self.epsilon, &self.priors)
+    }
+
+    /// Execute one command. Returns `None` when the session should end.
+    pub(crate) fn execute(&mut self, line: &str) -> Result<Option<String>> {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let out = match parts.as_slice() {
+            [] => String::new(),
+            ["quit" | "exit"] => return Ok(None),
+            ["help"] => HELP.to_string(),
+            ["show"] => self.show(),
+            ["set", "epsilon", value] => {
+                let value: f32 = value.parse().context("epsilon must be a number")?;
+                if !(0.0..=1.0).contains(&value) {
+                    bail!("epsilon must be within 0..1");
+                }
+                self.epsilon = value;
+                format!("epsilon = {value}")
+            }
+            ["prior", arm, value] => {
+                if !self.snapshot.arms.iter().any(|a| a == arm) {
+                    bail!("unknown arm '{arm}'");
+                }
+                let value: f64 = value.parse().context("prior must be a number")?;
+                if !value.is_finite() {
+                    bail!("prior must be finite");
+                }
+                self.priors.insert((*arm).to_string(), value);
+                format!("prior {arm} = {value}")
+            }
+            ["clear", arm] => match self.priors.remove(*arm) {
+                Some(_) => format!("prior {arm} cleared"),
+                None => format!("no prior set for '{arm}'"),
+            },
+            ["compare"] => self.compare(),
+            ["reset"] => {
+                self.epsilon = self.snapshot.epsilon.clamp(0.0, 1.0);
+                self.priors.clear();
+                "reset to snapshot".to_string()
+            }
+            ["propose"] => match self
+                .analyzer
+                .propose_with_samples(&self.snapshot.policy_id, &self.outcomes)
+            {
+                Some((p, samples)) => {
+                    let mut out = serde_json::to_string_pretty(&p)?;
+                    for support in &samples.patterns {
+                        let _ = write!(
+                            out,
+                            "\n{}: {} of {} decisions: {}",
+                            support.pattern,
+                            support.decision_ids.len(),
+                            support.matching,
+                            support.decision_ids.join(", ")
+                        );
+                    }
+                    out
+                }
+                None => "analyzer has no proposal for this outcome log".to_string(),
+            },
+            _ => bail!("unknown command '{line}' (try 'help')"),
+        };
+        Ok(Some(out))
+    }
+
+    fn show(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "policy: {}", self.snapshot.policy_id);
+        let _ = writeln!(out, "epsilon: {}", self.epsilon);
+        for (arm, value) in self.snapshot.arms.iter().zip(&self.snapshot.values) {
+            match self.priors.get(arm) {
+                Some(prior) => {
+                    let _ = writeln!(out, "  {arm}: {value:.3} (prior {prior:.3})");
+                }
+                None => {
+                    let _ = writeln!(out, "  {arm}: {value:.3}");
+                }
+            }
+        }
+        let mut stats = OutcomeStatistics::default();
+        self.outcomes.iter().for_each(|o| stats.record(o));
+        let _ = write!(
+            out,
+            "outcomes: {} (success rate {:.1}%)",
+            self.outcomes.len(),
+            stats.success_rate() * 100.0
+        );
+        out
+    }
+
+    fn compare(&self) -> String {
+        let base = self.baseline();
+        let cur = self.current();
+        let mut out = String::new();
+        let _ = writeln!(out, "{:<18} {:>10} {:>10}", "metric", "baseline", "current");
+        let _ = writeln!(
+            out,
+            "{:<18} {:>10.3} {:>10.3}",
+            "epsilon", base.epsilon, cur.epsilon
+        );
+        let _ = writeln!(
+            out,
+            "{:<18} {:>10} {:>10}",
+            "best arm",
+            base.best_arm.as_deref().unwrap_or("-"),
+            cur.best_arm.as_deref().unwrap_or("-")
+        );
+        let _ = writeln!(
+            out,
+            "{:<18} {:>10.3} {:>10.3}",
+            "expected reward", base.expected_reward, cur.expected_reward
+        );
+        let _ = write!(
+            out,
+            "{:<18} {:>9.1}% {:>9.1}%",
+            "sim success rate",
+            base.sim_success_rate * 100.0,
+            cur.sim_success_rate * 100.0
+        );
+        out
+    }
+}
+
+/// Run an interactive session reading commands from `input`.
+pub(crate) fn run(lab: &mut Lab, input: impl BufRead, mut output: impl Write) -> Result<()> {
+    writeln!(output, "heimlern lab — type 'help' for commands")?;
+    for line in input.lines() {
+        let line = line?;
+        match lab.execute(line.trim()) {
+            Ok(Some(text)) if text.is_empty() => {}
+            Ok(Some(text)) => writeln!(output, "{text}")?,
+            Ok(None) => break,
+            Err(err) => writeln!(output, "error: {err:#}")?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heimlern_feedback::OutcomeType;
+
+    fn lab() -> Lab {
+        let snapshot = LabSnapshot {
+            policy_id: "remind-bandit".into(),
+            arms: vec!["morning".into(), "evening".into()],
+            values: vec![0.8, 0.2],
+            epsilon: 0.2,
+        };
+        let outcomes = (0..10)
+            .map(|i| DecisionOutcome {
+                decision_id: i.to_string(),
+                ts: "2026-01-01T00:00:00Z".into(),
+                policy_id: None,
+                action: None,
+                outcome: if i % 2 == 0 {
+                    OutcomeType::Success
+                } else {
+                    OutcomeType::Failure
+                },
+                success: i % 2 == 0,
+                reward: None,
+                context: None,
+                metadata: Some(serde_json::json!({
+                    "why": if i < 2 { "explore ε" } else { "exploit" }
+                })),
+            })
+            .collect();
+        Lab::new(snapshot, outcomes).expect("lab")
+    }
+
+    #[test]
+    fn tweaks_change_current_but_not_baseline() {
+        let mut lab = lab();
+        lab.execute("set epsilon 0.0").expect("set");
+        lab.execute("prior evening 0.9").expect("prior");
+        let base = lab.baseline();
+        let cur = lab.current();
+        assert_eq!(base.best_arm.as_deref(), Some("morning"));
+        assert_eq!(cur.best_arm.as_deref(), Some("evening"));
+        assert!((cur.expected_reward - 0.9).abs() < 1e-9);
+        assert!((base.expected_reward - 0.74).abs() < 1e-9);
+
+        lab.execute("reset").expect("reset");
+        assert_eq!(lab.current(), lab.baseline());
+    }
+
+    #[test]
+    fn invalid_commands_are_reported_and_session_continues() {
+        let mut lab = lab();
+        let input = b"set epsilon 2\nprior night 0.5\ncompare\nquit\nshow\n";
+        let mut out = Vec::new();
+        run(&mut lab, &input[..], &mut out).expect("run");
+        let text = String::from_utf8(out).expect("utf8");
+        assert!(text.contains("error: epsilon must be within 0..1"));
+        assert!(text.contains("error: unknown arm 'night'"));
+        assert!(text.contains("sim success rate"));
+        assert!(
+            !text.contains("policy: remind-bandit"),
+            "quit ends the session"
+        );
+    }
+}
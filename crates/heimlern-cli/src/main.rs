@@ -3,11 +3,22 @@
 //! Provides commands for ingesting events from Chronik or local files, managing state and stats,
 //! and performing drift checks. It serves as the operational interface for the policy framework.
 
+mod contracts;
+mod federation;
+mod lab;
+mod migrate;
+mod outcomes;
+mod profile;
+mod proposals;
+mod snapshot;
+
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
+use heimlern_core::contracts::validate_snapshot;
+use heimlern_core::dedup::DedupFilter;
 use heimlern_core::event::{is_valid_event_domain, AussenEvent};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::env;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
@@ -34,6 +45,51 @@ enum Commands {
         #[command(subcommand)]
         path: LearningPathCommand,
     },
+    /// Generate Rust types from the JSON Schemas in contracts/
+    Contracts {
+        #[command(subcommand)]
+        command: contracts::ContractsCommand,
+    },
+    /// Share statistics with other households and seed snapshots from consensus priors
+    Federation {
+        #[command(subcommand)]
+        command: federation::FederationCommand,
+    },
+    /// Interactive what-if session on a snapshot and an outcome log
+    Lab {
+        /// Policy snapshot (policy.snapshot JSON)
+        #[arg(long)]
+        snapshot: PathBuf,
+
+        /// JSONL outcome log
+        #[arg(long)]
+        outcomes: PathBuf,
+    },
+    /// Upgrade artifacts written by older heimlern versions
+    Migrate {
+        #[command(subcommand)]
+        command: migrate::MigrateCommand,
+    },
+    /// Manage decision outcome logs
+    Outcomes {
+        #[command(subcommand)]
+        command: outcomes::OutcomesCommand,
+    },
+    /// Inspect ingest schema profiles
+    Profile {
+        #[command(subcommand)]
+        command: profile::ProfileCommand,
+    },
+    /// Inspect and verify weight adjustment proposals
+    Proposals {
+        #[command(subcommand)]
+        command: proposals::ProposalsCommand,
+    },
+    /// Maintain policy snapshots
+    Snapshot {
+        #[command(subcommand)]
+        command: snapshot::SnapshotCommand,
+    },
 }
 
 #[derive(Subcommand)]
@@ -69,6 +125,10 @@ enum IngestSource {
         /// Path to the stats file
         #[arg(long, default_value = "data/heimlern.stats.json")]
         stats_file: PathBuf,
+
+        /// Record a per-event-type schema profile in this file
+        #[arg(long)]
+        profile_file: Option<PathBuf>,
     },
     /// Ingest from local file (Simulation mode)
     File {
@@ -87,6 +147,10 @@ enum IngestSource {
         /// Path to the stats file
         #[arg(long, default_value = "data/heimlern.stats.json")]
         stats_file: PathBuf,
+
+        /// Record a per-event-type schema profile in this file
+        #[arg(long)]
+        profile_file: Option<PathBuf>,
     },
 }
 
@@ -193,19 +257,31 @@ impl IngestState {
 #[derive(Serialize, Deserialize, Debug)]
 struct EventStats {
     total_processed: u64,
-    by_type: HashMap<String, u64>,
-    by_source: HashMap<String, u64>,
+    by_type: BTreeMap<String, u64>,
+    by_source: BTreeMap<String, u64>,
     #[serde(with = "time::serde::iso8601")]
     last_updated: OffsetDateTime,
+    /// Events dropped because their id was already counted (e.g. Chronik replays).
+    #[serde(default)]
+    duplicates_skipped: u64,
+    /// Events dropped because they violate the aussen.event contract.
+    #[serde(default)]
+    invalid_skipped: u64,
+    /// Recently counted event ids.
+    #[serde(default)]
+    seen: DedupFilter,
 }
 
 impl Default for EventStats {
     fn default() -> Self {
         Self {
             total_processed: 0,
-            by_type: HashMap::new(),
-            by_source: HashMap::new(),
+            by_type: BTreeMap::new(),
+            by_source: BTreeMap::new(),
             last_updated: OffsetDateTime::now_utc(),
+            duplicates_skipped: 0,
+            invalid_skipped: 0,
+            seen: DedupFilter::default(),
         }
     }
 }
@@ -229,11 +305,17 @@ impl EventStats {
         Ok(())
     }
 
-    fn update(&mut self, event: AussenEvent) {
+    /// Counts `event` unless its id was seen recently; returns whether it was counted.
+    fn update(&mut self, event: AussenEvent) -> bool {
+        if !self.seen.admit(&event) {
+            self.duplicates_skipped += 1;
+            return false;
+        }
         self.total_processed += 1;
         *self.by_type.entry(event.r#type).or_insert(0) += 1;
         *self.by_source.entry(event.source).or_insert(0) += 1;
         self.last_updated = OffsetDateTime::now_utc();
+        true
     }
 }
 
@@ -241,7 +323,8 @@ impl EventStats {
 struct ChronikEvent {
     #[allow(dead_code)]
     r#type: Option<String>,
-    payload: AussenEvent,
+    /// Checked against the aussen.event contract before it is decoded.
+    payload: serde_json::Value,
 }
 
 #[derive(Deserialize, Debug)]
@@ -265,6 +348,8 @@ struct FetchResult {
     events: Vec<AussenEvent>,
     next_cursor: Option<u64>, // Relaxed to Option<u64>
     has_more: bool,
+    /// Payloads dropped at the source because they violate the aussen.event contract.
+    invalid: u64,
 }
 
 fn record_state_error(
@@ -398,16 +483,23 @@ fn fetch_chronik(cursor: Option<u64>, domain: &str, limit: u32) -> Result<FetchR
 
     let response_body: ChronikEventsResponse = resp.into_json()?;
 
-    let events = response_body
-        .events
-        .into_iter()
-        .map(|env| env.payload)
-        .collect();
+    let mut events = Vec::with_capacity(response_body.events.len());
+    let mut invalid = 0;
+    for (idx, envelope) in response_body.events.into_iter().enumerate() {
+        match contracts::decode_event(envelope.payload) {
+            Ok(event) => events.push(event),
+            Err(e) => {
+                eprintln!("Warning: skipping invalid Chronik event #{}: {e}", idx + 1);
+                invalid += 1;
+            }
+        }
+    }
 
     Ok(FetchResult {
         events,
         next_cursor: response_body.next_cursor,
         has_more: response_body.has_more,
+        invalid,
     })
 }
 
@@ -416,6 +508,7 @@ fn fetch_file(path: &Path, offset: u64) -> Result<FetchResult> {
     let reader = BufReader::new(f);
     let mut events = Vec::new();
     let mut lines_read = 0;
+    let mut invalid = 0;
 
     for (idx, line) in reader.lines().enumerate() {
         let line = line?;
@@ -426,8 +519,20 @@ fn fetch_file(path: &Path, offset: u64) -> Result<FetchResult> {
             lines_read += 1;
             continue;
         }
-        let event: AussenEvent = serde_json::from_str(&line)?;
-        events.push(event);
+        let decoded = serde_json::from_str(&line)
+            .map_err(|e| format!("not valid JSON: {e}"))
+            .and_then(contracts::decode_event);
+        match decoded {
+            Ok(event) => events.push(event),
+            Err(e) => {
+                eprintln!(
+                    "Warning: skipping invalid event at {}:{}: {e}",
+                    path.display(),
+                    idx + 1
+                );
+                invalid += 1;
+            }
+        }
         lines_read += 1;
     }
 
@@ -437,6 +542,7 @@ fn fetch_file(path: &Path, offset: u64) -> Result<FetchResult> {
         events,
         next_cursor: Some(next_offset),
         has_more: false,
+        invalid,
     })
 }
 
@@ -444,6 +550,7 @@ fn process_ingest(
     source_result: Result<FetchResult>,
     state_file: &Path,
     stats_file: &Path,
+    profile_file: Option<&Path>,
     current_cursor: &mut u64,
     mode: IngestMode,
 ) -> Result<bool> {
@@ -456,18 +563,42 @@ fn process_ingest(
                 );
                 EventStats::default()
             });
-            let count = fetch_result.events.len();
+            let mut fresh = Vec::with_capacity(fetch_result.events.len());
+            let mut duplicates = 0;
+            let mut invalid = fetch_result.invalid;
+            for mut event in fetch_result.events {
+                event.normalize();
+                if let Err(issues) = event.validate() {
+                    let issues: Vec<String> = issues.iter().map(ToString::to_string).collect();
+                    eprintln!(
+                        "Warning: skipping invalid event {}: {}",
+                        event.id.as_deref().unwrap_or("<without id>"),
+                        issues.join("; ")
+                    );
+                    invalid += 1;
+                    continue;
+                }
+                if stats.update(event.clone()) {
+                    fresh.push(event);
+                } else {
+                    duplicates += 1;
+                }
+            }
 
-            for event in fetch_result.events {
-                stats.update(event);
+            if let Some(profile_file) = profile_file {
+                profile::update_profile(profile_file, &fresh)?;
             }
 
+            stats.invalid_skipped += invalid;
             // Always update last_updated to reflect the check time
             stats.last_updated = OffsetDateTime::now_utc();
 
             println!(
-                "Processed {} events. (Stats updated at {})",
-                count, stats.last_updated
+                "Processed {} events, skipped {} duplicates and {} invalid. (Stats updated at {})",
+                fresh.len(),
+                duplicates,
+                invalid,
+                stats.last_updated
             );
             stats.save(stats_file).context("Failed to save stats")?;
 
@@ -539,6 +670,25 @@ fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
+        Commands::Migrate { command } => migrate::run(command)?,
+        Commands::Outcomes { command } => outcomes::run(command)?,
+        Commands::Profile { command } => profile::run(command)?,
+        Commands::Contracts { command } => contracts::run(command)?,
+        Commands::Federation { command } => federation::run(command)?,
+        Commands::Proposals { command } => proposals::run(command)?,
+        Commands::Snapshot { command } => snapshot::run(command)?,
+        Commands::Lab { snapshot, outcomes } => {
+            let raw = std::fs::read_to_string(&snapshot)
+                .with_context(|| format!("Failed to read {}", snapshot.display()))?;
+            let value: serde_json::Value = serde_json::from_str(&raw)
+                .with_context(|| format!("Invalid snapshot {}", snapshot.display()))?;
+            contracts::ensure(validate_snapshot, &value, &snapshot)?;
+            let snapshot = serde_json::from_value(value)
+                .with_context(|| format!("Invalid snapshot {}", snapshot.display()))?;
+            let outcomes = outcomes::read_outcomes(&outcomes)?;
+            let mut lab = lab::Lab::new(snapshot, outcomes)?;
+            lab::run(&mut lab, std::io::stdin().lock(), std::io::stdout().lock())?;
+        }
         Commands::LearningPath { path } => match path {
             LearningPathCommand::Offline => {
                 let artifact = offline_learning_path_artifact();
@@ -553,6 +703,7 @@ fn main() -> Result<()> {
                 max_batches,
                 state_file,
                 stats_file,
+                profile_file,
             } => {
                 let mut batches_processed = 0;
                 let mut current_cursor = cursor.unwrap_or(0);
@@ -574,6 +725,7 @@ fn main() -> Result<()> {
                         fetch_chronik(Some(current_cursor), &domain, limit),
                         &state_file,
                         &stats_file,
+                        profile_file.as_deref(),
                         &mut current_cursor,
                         IngestMode::Chronik,
                     )?;
@@ -589,6 +741,7 @@ fn main() -> Result<()> {
                 line_offset,
                 state_file,
                 stats_file,
+                profile_file,
             } => {
                 let mut current_cursor = line_offset.unwrap_or(0);
 
@@ -603,6 +756,7 @@ fn main() -> Result<()> {
                     fetch_file(&path, current_cursor),
                     &state_file,
                     &stats_file,
+                    profile_file.as_deref(),
                     &mut current_cursor,
                     IngestMode::File,
                 )?;
@@ -661,6 +815,7 @@ mod tests {
             events: vec![],
             next_cursor: None,
             has_more: true,
+            invalid: 0,
         };
         let mut cursor = 0;
 
@@ -668,6 +823,7 @@ mod tests {
             Ok(fetch_result),
             &state_file,
             &stats_file,
+            None,
             &mut cursor,
             IngestMode::Chronik,
         );
@@ -694,6 +850,7 @@ mod tests {
             events: vec![],
             next_cursor: Some(10),
             has_more: true,
+            invalid: 0,
         };
         let mut cursor = 10; // Same as next
 
@@ -701,6 +858,7 @@ mod tests {
             Ok(fetch_result),
             &state_file,
             &stats_file,
+            None,
             &mut cursor,
             IngestMode::Chronik,
         );
@@ -723,6 +881,7 @@ mod tests {
             events: vec![],
             next_cursor: Some(20),
             has_more: true,
+            invalid: 0,
         };
         let mut cursor = 10;
 
@@ -730,6 +889,7 @@ mod tests {
             Ok(fetch_result),
             &state_file,
             &stats_file,
+            None,
             &mut cursor,
             IngestMode::Chronik,
         );
@@ -745,6 +905,55 @@ mod tests {
         assert!(state.last_error.is_none());
     }
 
+    #[test]
+    fn test_process_ingest_skips_replayed_and_invalid_events() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let state_file = dir.path().join("state.json");
+        let stats_file = dir.path().join("stats.json");
+        let batch: Vec<AussenEvent> = ["evt-1", "evt-2", "evt-3"]
+            .into_iter()
+            .map(|id| {
+                // evt-3 is a link without url and violates the contract.
+                let kind = if id == "evt-3" {
+                    "link"
+                } else {
+                    "sensor.reading"
+                };
+                serde_json::from_value(serde_json::json!({
+                    "id": id, "type": kind, "source": "chronik"
+                }))
+                .expect("valid event")
+            })
+            .collect();
+
+        // The same batch arrives twice, as after a crash before the cursor was saved.
+        let mut cursor = 0;
+        for _ in 0..2 {
+            let fetch_result = FetchResult {
+                events: batch.clone(),
+                next_cursor: Some(2),
+                has_more: false,
+                invalid: 0,
+            };
+            process_ingest(
+                Ok(fetch_result),
+                &state_file,
+                &stats_file,
+                None,
+                &mut cursor,
+                IngestMode::Chronik,
+            )
+            .expect("ingest succeeds");
+            cursor = 0;
+        }
+
+        let stats = EventStats::load(&stats_file).expect("stats readable");
+        assert_eq!(stats.total_processed, 2);
+        assert_eq!(stats.duplicates_skipped, 2);
+        assert_eq!(stats.invalid_skipped, 2);
+        assert_eq!(stats.by_source.get("chronik"), Some(&2));
+    }
+
     /// This test uses Unix-specific permission handling (chmod) to simulate IO errors.
     /// It is gated with #[cfg(unix)] to prevent failures on non-Unix systems (e.g., Windows).
     #[test]
@@ -798,6 +1007,7 @@ mod tests {
             events: vec![],
             next_cursor: None,
             has_more: true, // Protocol error condition
+            invalid: 0,
         };
         let mut cursor = 0;
 
@@ -805,6 +1015,7 @@ mod tests {
             Ok(fetch_result),
             &state_file,       // This save should fail
             &valid_stats_file, // This save should succeed
+            None,
             &mut cursor,
             IngestMode::Chronik,
         );
@@ -0,0 +1,261 @@
+//! `heimlern migrate` — upgrade artifacts written by older heimlern versions.
+//!
+//! `all` walks a data directory and rewrites every artifact it recognizes in
+//! the current format: bandit snapshots in the legacy struct layout become
+//! contract snapshots, decision records get a `why` list and a `chosen`
+//! object. Files that need no change are left untouched. Every upgrade is
+//! recorded as a deprecation in a compatibility report, which can be written
+//! as JSON for review.
+
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use heimlern_bandits::RemindBandit;
+use heimlern_core::compat::{self, upgrade_decision, CompatReport};
+use heimlern_core::Policy;
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Subcommand)]
+pub(crate) enum MigrateCommand {
+    /// Upgrade every artifact below a data directory in one pass
+    All {
+        /// Data directory (searched recursively for *.json and *.jsonl)
+        #[arg(default_value = "data")]
+        dir: PathBuf,
+
+        /// Only report what would change
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Write the compatibility report (JSON) here
+        #[arg(long)]
+        report: Option<PathBuf>,
+    },
+}
+
+/// Outcome of a migration run.
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct Migration {
+    pub(crate) files_scanned: usize,
+    pub(crate) files_upgraded: Vec<String>,
+    /// Files or lines that could not be parsed and were left as they are.
+    pub(crate) unreadable: Vec<String>,
+    #[serde(flatten)]
+    pub(crate) report: CompatReport,
+}
+
+pub(crate) fn run(command: MigrateCommand) -> Result<()> {
+    match command {
+        MigrateCommand::All {
+            dir,
+            dry_run,
+            report,
+        } => {
+            let migration = migrate_all(&dir, dry_run)?;
+            print_summary(&migration, dry_run);
+            if let Some(path) = report {
+                fs::write(&path, serde_json::to_string_pretty(&migration)?)
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_summary(migration: &Migration, dry_run: bool) {
+    let verb = if dry_run { "would upgrade" } else { "upgraded" };
+    println!(
+        "Scanned {} files, {verb} {}, {} deprecations.",
+        migration.files_scanned,
+        migration.files_upgraded.len(),
+        migration.report.len()
+    );
+    for (code, count) in migration.report.counts() {
+        println!("  {code}: {count}");
+    }
+    for path in &migration.unreadable {
+        println!("  skipped unreadable {path}");
+    }
+}
+
+/// Upgrade all `*.json` and `*.jsonl` files below `dir`.
+pub(crate) fn migrate_all(dir: &Path, dry_run: bool) -> Result<Migration> {
+    let mut files = Vec::new();
+    collect(dir, &mut files)?;
+    files.sort();
+
+    let mut migration = Migration::default();
+    for path in files {
+        migration.files_scanned += 1;
+        let raw = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let name = path.display().to_string();
+        let upgraded = if name.ends_with(".jsonl") {
+            migrate_lines(&raw, &name, &mut migration)
+        } else {
+            migrate_document(&raw, &name, &mut migration)
+        };
+        if let Some(content) = upgraded {
+            if !dry_run {
+                fs::write(&path, content)
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+            }
+            migration.files_upgraded.push(name);
+        }
+    }
+    Ok(migration)
+}
+
+fn collect(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let entries = fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect(&path, files)?;
+        } else if matches!(
+            path.extension().and_then(|e| e.to_str()),
+            Some("json" | "jsonl")
+        ) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn migrate_document(raw: &str, name: &str, migration: &mut Migration) -> Option<String> {
+    let Ok(mut value) = serde_json::from_str::<Value>(raw) else {
+        migration.unreadable.push(name.to_string());
+        return None;
+    };
+    let mut report = CompatReport::default();
+    let changed = upgrade(&mut value, &mut report);
+    migration.report.absorb(report, name);
+    if !changed {
+        return None;
+    }
+    serde_json::to_string_pretty(&value).ok().map(|s| s + "\n")
+}
+
+fn migrate_lines(raw: &str, name: &str, migration: &mut Migration) -> Option<String> {
+    let mut changed = false;
+    let mut out = String::with_capacity(raw.len());
+    for (i, line) in raw.lines().enumerate() {
+        let location = format!("{name}:{}", i + 1);
+        let parsed = (!line.trim().is_empty())
+            .then(|| serde_json::from_str::<Value>(line))
+            .transpose();
+        match parsed {
+            Ok(Some(mut value)) => {
+                let mut report = CompatReport::default();
+                if upgrade(&mut value, &mut report) {
+                    changed = true;
+                    out.push_str(&value.to_string());
+                } else {
+                    out.push_str(line);
+                }
+                migration.report.absorb(report, &location);
+            }
+            Ok(None) => out.push_str(line),
+            Err(_) => {
+                migration.unreadable.push(location);
+                out.push_str(line);
+            }
+        }
+        out.push('\n');
+    }
+    changed.then_some(out)
+}
+
+/// Upgrade one artifact in place; `true` if it changed.
+fn upgrade(value: &mut Value, report: &mut CompatReport) -> bool {
+    let legacy_snapshot = value.get("policy_id").is_none()
+        && value.get("slots").is_some_and(Value::is_array)
+        && value.get("values").is_some_and(Value::is_object);
+    if legacy_snapshot {
+        let mut bandit = RemindBandit::default();
+        let mut loaded = CompatReport::default();
+        bandit.load_with_report(value.clone(), &mut loaded);
+        if !loaded.counts().contains_key(compat::LEGACY_SNAPSHOT) {
+            return false;
+        }
+        *value = bandit.snapshot();
+        report.deprecations.extend(loaded.deprecations);
+        return true;
+    }
+    upgrade_decision(value, report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn migrate_all_upgrades_legacy_artifacts_once() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let nested = dir.path().join("policies");
+        fs::create_dir(&nested).expect("mkdir");
+        fs::write(
+            nested.join("remind.json"),
+            json!({
+                "epsilon": 0.3,
+                "slots": ["morning", "evening"],
+                "values": { "morning": [4, 3.0] }
+            })
+            .to_string(),
+        )
+        .expect("write snapshot");
+        let decisions = concat!(
+            r#"{"ts":"t","policy_id":"p","decision":{"action":"a","score":0.5,"why":"w"}}"#,
+            "\n",
+            r#"{"ts":"t","policy_id":"p","decision":{"action":"b","score":0.5,"why":["w"],"chosen":{"action":"b"}}}"#,
+            "\n",
+        );
+        fs::write(dir.path().join("decisions.jsonl"), decisions).expect("write decisions");
+        let outcomes = r#"{"decision_id":"d","ts":"t","action":"a","outcome":"success"}"#;
+        fs::write(dir.path().join("outcomes.jsonl"), outcomes).expect("write outcomes");
+
+        let dry = migrate_all(dir.path(), true).expect("dry run");
+        assert_eq!(dry.files_upgraded.len(), 2);
+        let unchanged = fs::read_to_string(nested.join("remind.json")).expect("read");
+        assert!(!unchanged.contains("policy_id"));
+
+        let report = dir.path().join("compat.json");
+        run(MigrateCommand::All {
+            dir: dir.path().to_path_buf(),
+            dry_run: false,
+            report: Some(report.clone()),
+        })
+        .expect("migrate");
+
+        let snapshot: Value =
+            serde_json::from_str(&fs::read_to_string(nested.join("remind.json")).expect("read"))
+                .expect("json");
+        assert_eq!(snapshot["policy_id"], "remind-bandit");
+        assert_eq!(snapshot["counts"], json!([4, 0]));
+        let lines: Vec<Value> = fs::read_to_string(dir.path().join("decisions.jsonl"))
+            .expect("read")
+            .lines()
+            .map(|l| serde_json::from_str(l).expect("json line"))
+            .collect();
+        assert_eq!(lines[0]["decision"]["why"], json!(["w"]));
+        assert_eq!(lines[0]["decision"]["chosen"]["action"], "a");
+        assert_eq!(
+            fs::read_to_string(dir.path().join("outcomes.jsonl")).expect("read"),
+            outcomes
+        );
+
+        let written: Value =
+            serde_json::from_str(&fs::read_to_string(&report).expect("report")).expect("json");
+        assert_eq!(written["deprecations"].as_array().map(Vec::len), Some(3));
+        assert!(written["deprecations"][0]["location"]
+            .as_str()
+            .is_some_and(|l| l.ends_with("decisions.jsonl:1")));
+
+        let again = migrate_all(dir.path(), false).expect("second run");
+        assert!(again.files_upgraded.is_empty());
+        assert!(again.report.is_empty());
+    }
+}
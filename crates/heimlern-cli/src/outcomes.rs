@@ -0,0 +1,436 @@
+//! `heimlern outcomes` subcommands and helpers for outcome logs (JSONL, one
+//! `DecisionOutcome` per line).
+
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use heimlern_feedback::anomaly::{
+    EwmaChart, DEFAULT_CONTROL_LIMIT, DEFAULT_EWMA_LAMBDA, DEFAULT_WARMUP_DAYS,
+};
+use heimlern_feedback::drift::{DriftReport, DEFAULT_DRIFT_THRESHOLD};
+use heimlern_feedback::idempotency::IdempotencyStore;
+use heimlern_feedback::latency::LatencyReport;
+use heimlern_feedback::merge::{merge_outcomes, MergeStats};
+use heimlern_feedback::overrides::analyze_overrides;
+use heimlern_feedback::privacy::privatize;
+use heimlern_feedback::skew::{correct_skew, estimate_skew, SkewEstimate};
+use heimlern_feedback::{DecisionOutcome, FeedbackAnalyzer, OutcomeStatistics};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Subcommand)]
+pub(crate) enum OutcomesCommand {
+    /// Append outcomes to a log, skipping already-seen idempotency keys
+    Append {
+        /// JSONL file with incoming outcomes
+        #[arg(long)]
+        input: PathBuf,
+
+        /// JSONL outcome log to append to
+        #[arg(long, default_value = "data/heimlern.outcomes.jsonl")]
+        log: PathBuf,
+
+        /// File recording admitted idempotency keys
+        #[arg(long, default_value = "data/heimlern.outcomes.keys")]
+        keys: PathBuf,
+    },
+    /// Merge outcome logs from several machines into one
+    Merge {
+        /// JSONL outcome logs to merge
+        #[arg(required = true)]
+        inputs: Vec<PathBuf>,
+
+        /// Output path for the merged log
+        #[arg(long)]
+        out: PathBuf,
+
+        /// Correct clock skew of other sources against this `metadata.source`
+        #[arg(long)]
+        skew_reference: Option<String>,
+
+        /// Offsets below this many seconds are left uncorrected
+        #[arg(long, default_value_t = 1.0)]
+        skew_tolerance: f64,
+    },
+    /// Compare the most recent outcomes against the history before them
+    Drift {
+        /// JSONL outcome log
+        #[arg(long, default_value = "data/heimlern.outcomes.jsonl")]
+        log: PathBuf,
+
+        /// Length of the recent window in days, counted back from the newest outcome
+        #[arg(long, default_value_t = 7)]
+        recent_days: u32,
+
+        /// PSI above which a distribution counts as drifted
+        #[arg(long, default_value_t = DEFAULT_DRIFT_THRESHOLD)]
+        threshold: f64,
+    },
+    /// List days whose failure rate leaves the EWMA control limits, as JSON
+    Anomalies {
+        /// JSONL outcome log
+        #[arg(long, default_value = "data/heimlern.outcomes.jsonl")]
+        log: PathBuf,
+
+        /// Weight of the newest day in the moving average
+        #[arg(long, default_value_t = DEFAULT_EWMA_LAMBDA)]
+        lambda: f64,
+
+        /// Control limit in standard deviations
+        #[arg(long, default_value_t = DEFAULT_CONTROL_LIMIT)]
+        limit: f64,
+
+        /// Leading days that only seed the average
+        #[arg(long, default_value_t = DEFAULT_WARMUP_DAYS)]
+        warmup: usize,
+    },
+    /// Print decision-to-outcome latency statistics per action as JSON
+    Latency {
+        /// JSONL outcome log
+        #[arg(long, default_value = "data/heimlern.outcomes.jsonl")]
+        log: PathBuf,
+    },
+    /// List the actions people override by hand most often
+    Overrides {
+        /// JSONL outcome log
+        #[arg(long, default_value = "data/heimlern.outcomes.jsonl")]
+        log: PathBuf,
+
+        /// Number of actions to list
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+
+        /// Add differential-privacy noise with this epsilon before printing
+        #[arg(long)]
+        dp_epsilon: Option<f64>,
+    },
+    /// Analyze every policy in the log and compare them per context kind, as JSON
+    Portfolio {
+        /// JSONL outcome log
+        #[arg(long, default_value = "data/heimlern.outcomes.jsonl")]
+        log: PathBuf,
+    },
+    /// Print success statistics per action as JSON, streaming the log
+    Stats {
+        /// JSONL outcome log
+        #[arg(long, default_value = "data/heimlern.outcomes.jsonl")]
+        log: PathBuf,
+    },
+    /// Estimate clock skew between outcome sources without changing anything
+    Skew {
+        /// JSONL outcome logs to inspect
+        #[arg(required = true)]
+        inputs: Vec<PathBuf>,
+
+        /// `metadata.source` whose clock is trusted
+        #[arg(long)]
+        reference: String,
+
+        /// Minimum number of shared decisions per source
+        #[arg(long, default_value_t = SKEW_MIN_SAMPLES)]
+        min_samples: usize,
+    },
+}
+
+/// Minimum number of shared decisions before a skew estimate is trusted.
+const SKEW_MIN_SAMPLES: usize = 3;
+
+pub(crate) fn run(command: OutcomesCommand) -> Result<()> {
+    match command {
+        OutcomesCommand::Append { input, log, keys } => {
+            let (appended, skipped) = append(&input, &log, &keys)?;
+            println!("Appended {appended} outcomes, skipped {skipped} duplicates.");
+        }
+        OutcomesCommand::Merge {
+            inputs,
+            out,
+            skew_reference,
+            skew_tolerance,
+        } => {
+            let skew = skew_reference.as_deref().map(|r| (r, skew_tolerance));
+            let (stats, corrections) = merge(&inputs, &out, skew)?;
+            for estimate in &corrections {
+                print_estimate(estimate);
+            }
+            println!(
+                "Merged {} inputs: {} read, {} written, {} duplicates, {} conflicts resolved.",
+                stats.inputs, stats.read, stats.merged, stats.duplicates, stats.conflicts
+            );
+            if stats.unparseable_ts > 0 {
+                eprintln!(
+                    "Warning: {} outcomes have unparseable timestamps and were placed last.",
+                    stats.unparseable_ts
+                );
+            }
+        }
+        OutcomesCommand::Drift {
+            log,
+            recent_days,
+            threshold,
+        } => {
+            let report = DriftReport::compare_recent(
+                &read_outcomes(&log)?,
+                i64::from(recent_days) * 86_400,
+                threshold,
+            );
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            if report.drifted {
+                eprintln!(
+                    "Warning: outcomes drifted (action PSI {:.3}, reward PSI {:.3}).",
+                    report.action_psi, report.reward_psi
+                );
+            }
+        }
+        OutcomesCommand::Anomalies {
+            log,
+            lambda,
+            limit,
+            warmup,
+        } => {
+            let chart = EwmaChart {
+                lambda,
+                limit,
+                warmup,
+            };
+            let analyzer = FeedbackAnalyzer::builder().exclude_anomalies(chart).build();
+            let anomalies = analyzer.detect_anomalies(&read_outcomes(&log)?);
+            println!("{}", serde_json::to_string_pretty(&anomalies)?);
+            if !anomalies.is_empty() {
+                eprintln!(
+                    "Warning: {} day(s) deviate from the usual failure rate; check them for data-quality issues.",
+                    anomalies.len()
+                );
+            }
+        }
+        OutcomesCommand::Latency { log } => {
+            let report = LatencyReport::compute(&read_outcomes(&log)?);
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        OutcomesCommand::Portfolio { log } => {
+            let report = FeedbackAnalyzer::default().analyze_portfolio(&read_outcomes(&log)?);
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        OutcomesCommand::Stats { log } => {
+            let file =
+                File::open(&log).with_context(|| format!("Failed to open {}", log.display()))?;
+            let mut aggregator = FeedbackAnalyzer::default().aggregator();
+            aggregator
+                .read_jsonl(BufReader::new(file))
+                .with_context(|| format!("Failed to read {}", log.display()))?;
+            let by_action: serde_json::Map<String, serde_json::Value> = aggregator
+                .by_action()
+                .iter()
+                .map(|(action, stats)| (action.clone(), stats_json(stats)))
+                .collect();
+            let report = serde_json::json!({
+                "records": aggregator.records(),
+                "overall": stats_json(aggregator.overall()),
+                "by_action": by_action,
+            });
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        OutcomesCommand::Overrides {
+            log,
+            top,
+            dp_epsilon,
+        } => {
+            let mut report = analyze_overrides(&read_outcomes(&log)?);
+            if let Some(epsilon) = dp_epsilon {
+                privatize(&mut report, epsilon)?;
+            }
+            println!(
+                "{} of {} decisions were overridden by hand.",
+                report.overridden, report.decisions
+            );
+            for (action, stats) in report.most_overridden(top) {
+                let replacement = stats
+                    .top_replacement()
+                    .map(|(r, n)| format!(", mostly to '{r}' ({n}x)"))
+                    .unwrap_or_default();
+                println!(
+                    "  {action}: {} of {} ({:.1}%){replacement}",
+                    stats.overridden,
+                    stats.decisions,
+                    stats.override_rate() * 100.0
+                );
+            }
+        }
+        OutcomesCommand::Skew {
+            inputs,
+            reference,
+            min_samples,
+        } => {
+            let outcomes = read_all(&inputs)?.into_iter().flatten().collect::<Vec<_>>();
+            let estimates = estimate_skew(&outcomes, &reference, min_samples);
+            if estimates.is_empty() {
+                println!("No source shares enough decisions with '{reference}'.");
+            }
+            for estimate in &estimates {
+                print_estimate(estimate);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Appends admitted outcomes from `input` to `log`; returns (appended, skipped).
+fn append(input: &Path, log: &Path, keys: &Path) -> Result<(usize, usize)> {
+    let incoming = read_outcomes(input)?;
+    for path in [log, keys] {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+    let mut store = IdempotencyStore::open(keys)
+        .with_context(|| format!("Failed to open {}", keys.display()))?;
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log)
+        .with_context(|| format!("Failed to open {}", log.display()))?;
+    let mut writer = BufWriter::new(file);
+
+    let mut appended = 0;
+    let mut skipped = 0;
+    for outcome in &incoming {
+        if !store
+            .admit(outcome)
+            .with_context(|| format!("Failed to record key in {}", keys.display()))?
+        {
+            skipped += 1;
+            continue;
+        }
+        serde_json::to_writer(&mut writer, outcome)?;
+        writer.write_all(b"\n")?;
+        appended += 1;
+    }
+    writer.flush()?;
+    Ok((appended, skipped))
+}
+
+fn print_estimate(estimate: &SkewEstimate) {
+    println!(
+        "Source '{}' is {:+.1}s off ({} shared decisions).",
+        estimate.source, estimate.offset_seconds, estimate.samples
+    );
+}
+
+fn read_all(inputs: &[PathBuf]) -> Result<Vec<Vec<DecisionOutcome>>> {
+    inputs.iter().map(|path| read_outcomes(path)).collect()
+}
+
+/// Merges the logs at `inputs` into `out`, see [`merge_outcomes`].
+///
+/// With `skew` set to `(reference, tolerance)`, timestamps of sources whose
+/// clock is off against the reference are corrected first; the applied
+/// estimates are returned alongside the merge statistics.
+fn merge(
+    inputs: &[PathBuf],
+    out: &Path,
+    skew: Option<(&str, f64)>,
+) -> Result<(MergeStats, Vec<SkewEstimate>)> {
+    let mut logs = read_all(inputs)?;
+    let mut applied = Vec::new();
+    if let Some((reference, tolerance)) = skew {
+        let all: Vec<DecisionOutcome> = logs.iter().flatten().cloned().collect();
+        applied = estimate_skew(&all, reference, SKEW_MIN_SAMPLES);
+        applied.retain(|e| e.offset_seconds.abs() >= tolerance);
+        for log in &mut logs {
+            correct_skew(log, &applied, tolerance);
+        }
+    }
+    let (merged, stats) = merge_outcomes(logs);
+    if let Some(parent) = out.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = File::create(out).with_context(|| format!("Failed to create {}", out.display()))?;
+    let mut writer = BufWriter::new(file);
+    for outcome in &merged {
+        serde_json::to_writer(&mut writer, outcome)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok((stats, applied))
+}
+
+fn stats_json(stats: &OutcomeStatistics) -> serde_json::Value {
+    serde_json::json!({
+        "total": stats.total,
+        "successes": stats.successes,
+        "failures": stats.failures,
+        "censored": stats.censored,
+        "overrides": stats.overrides,
+        "success_rate": stats.success_rate(),
+        "average_reward": stats.average_reward(),
+    })
+}
+
+pub(crate) fn read_outcomes(path: &Path) -> Result<Vec<DecisionOutcome>> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut outcomes = Vec::new();
+    for (idx, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.with_context(|| format!("Failed to read {}", path.display()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let outcome = serde_json::from_str(&line)
+            .with_context(|| format!("Invalid outcome at {}:{}", path.display(), idx + 1))?;
+        outcomes.push(outcome);
+    }
+    Ok(outcomes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retried_deliveries_are_not_appended_twice() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let input = dir.path().join("in.jsonl");
+        let log = dir.path().join("data/outcomes.jsonl");
+        let keys = dir.path().join("data/outcomes.keys");
+        std::fs::write(
+            &input,
+            concat!(
+                r#"{"decision_id":"a","ts":"2026-01-01T00:00:00Z","outcome":"success","success":true,"metadata":{"idempotency_key":"k1"}}"#,
+                "\n",
+                r#"{"decision_id":"a","ts":"2026-01-01T00:00:00Z","outcome":"success","success":true,"metadata":{"idempotency_key":"k1"}}"#,
+                "\n",
+                r#"{"decision_id":"b","ts":"2026-01-01T00:00:00Z","outcome":"failure","success":false}"#,
+                "\n",
+            ),
+        )
+        .expect("write input");
+
+        assert_eq!(append(&input, &log, &keys).expect("first run"), (2, 1));
+        // A restart replaying the same delivery only re-admits the unkeyed outcome.
+        assert_eq!(append(&input, &log, &keys).expect("second run"), (1, 2));
+        assert_eq!(read_outcomes(&log).expect("log").len(), 3);
+    }
+
+    #[test]
+    fn merge_writes_sorted_log_without_duplicates() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let laptop = dir.path().join("laptop.jsonl");
+        let server = dir.path().join("server.jsonl");
+        let out = dir.path().join("merged/outcomes.jsonl");
+        let shared =
+            r#"{"decision_id":"a","ts":"2026-01-02T00:00:00Z","outcome":"success","success":true}"#;
+        std::fs::write(&laptop, format!("{shared}\n")).expect("write laptop");
+        std::fs::write(
+            &server,
+            format!(
+                "{}\n{shared}\n",
+                r#"{"decision_id":"b","ts":"2026-01-01T00:00:00Z","outcome":"failure","success":false}"#
+            ),
+        )
+        .expect("write server");
+
+        let (stats, _) = merge(&[laptop, server], &out, None).expect("merge");
+        assert_eq!((stats.read, stats.merged, stats.duplicates), (3, 2, 1));
+        let merged = read_outcomes(&out).expect("merged log");
+        assert_eq!(merged[0].decision_id, "b");
+        assert_eq!(merged[1].decision_id, "a");
+    }
+}
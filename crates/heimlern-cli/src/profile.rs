@@ -0,0 +1,254 @@
+//! Per-event-type schema profiling during ingest.
+//!
+//! For every event type the profile records how many events were seen and, per
+//! field, in how many of them the field was present and with which JSON value
+//! types. Top-level fields are profiled by name; entries of `features` and
+//! `meta` as `features.<key>` / `meta.<key>`. The artifact guides feature
+//! extractor design, and [`SchemaProfile::drift`] compares two profiles to spot
+//! upstream schema changes.
+
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use heimlern_core::event::AussenEvent;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use time::OffsetDateTime;
+
+#[derive(Subcommand)]
+pub(crate) enum ProfileCommand {
+    /// Compare a schema profile against a baseline and list drifted fields
+    Drift {
+        /// Current profile
+        #[arg(long, default_value = "data/heimlern.schema-profile.json")]
+        current: PathBuf,
+
+        /// Baseline profile to compare against
+        #[arg(long)]
+        baseline: PathBuf,
+
+        /// Minimum change in presence fraction to report (0.0 to 1.0)
+        #[arg(long, default_value_t = 0.2)]
+        threshold: f64,
+    },
+}
+
+pub(crate) fn run(command: ProfileCommand) -> Result<()> {
+    match command {
+        ProfileCommand::Drift {
+            current,
+            baseline,
+            threshold,
+        } => {
+            let read = |path: &Path| -> Result<SchemaProfile> {
+                let file = File::open(path)
+                    .with_context(|| format!("Failed to open {}", path.display()))?;
+                serde_json::from_reader(file)
+                    .with_context(|| format!("Invalid schema profile {}", path.display()))
+            };
+            let findings = read(&current)?.drift(&read(&baseline)?, threshold);
+            if findings.is_empty() {
+                println!("No schema drift detected.");
+            }
+            for finding in findings {
+                println!("{finding}");
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub(crate) struct FieldProfile {
+    /// Number of events in which the field was present and not null.
+    pub(crate) present: u64,
+    /// Count per JSON value type ("string", "number", "bool", "array", "object").
+    pub(crate) types: BTreeMap<String, u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub(crate) struct TypeProfile {
+    pub(crate) events: u64,
+    pub(crate) fields: BTreeMap<String, FieldProfile>,
+}
+
+impl TypeProfile {
+    /// Share of events of this type that carried `field` (0.0 to 1.0).
+    pub(crate) fn presence(&self, field: &str) -> f64 {
+        if self.events == 0 {
+            return 0.0;
+        }
+        let present = self.fields.get(field).map_or(0, |f| f.present);
+        present as f64 / self.events as f64
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct SchemaProfile {
+    pub(crate) by_type: BTreeMap<String, TypeProfile>,
+    #[serde(with = "time::serde::iso8601")]
+    pub(crate) last_updated: OffsetDateTime,
+}
+
+impl Default for SchemaProfile {
+    fn default() -> Self {
+        Self {
+            by_type: BTreeMap::new(),
+            last_updated: OffsetDateTime::now_utc(),
+        }
+    }
+}
+
+fn type_name(value: &Value) -> Option<&'static str> {
+    match value {
+        Value::Null => None,
+        Value::Bool(_) => Some("bool"),
+        Value::Number(_) => Some("number"),
+        Value::String(_) => Some("string"),
+        Value::Array(_) => Some("array"),
+        Value::Object(_) => Some("object"),
+    }
+}
+
+impl SchemaProfile {
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let file = File::open(path)?;
+        Ok(serde_json::from_reader(file)?)
+    }
+
+    pub(crate) fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = File::create(path)?;
+        serde_json::to_writer_pretty(file, self)?;
+        Ok(())
+    }
+
+    pub(crate) fn observe(&mut self, event: &AussenEvent) {
+        let profile = self.by_type.entry(event.r#type.clone()).or_default();
+        profile.events += 1;
+
+        let mut record = |field: String, value: &Value| {
+            if let Some(ty) = type_name(value) {
+                let entry = profile.fields.entry(field).or_default();
+                entry.present += 1;
+                *entry.types.entry(ty.to_string()).or_default() += 1;
+            }
+        };
+
+        // Serialize once so top-level fields are profiled by their JSON names.
+        if let Ok(Value::Object(map)) = serde_json::to_value(event) {
+            for (key, value) in &map {
+                match (key.as_str(), value) {
+                    ("type", _) => {}
+                    ("features" | "meta", Value::Object(inner)) => {
+                        record(key.clone(), value);
+                        for (sub, v) in inner {
+                            record(format!("{key}.{sub}"), v);
+                        }
+                    }
+                    _ => record(key.clone(), value),
+                }
+            }
+        }
+        self.last_updated = OffsetDateTime::now_utc();
+    }
+
+    /// Differences to `baseline`: new or vanished fields, presence shifts above
+    /// `threshold`, and value types not seen in the baseline.
+    pub(crate) fn drift(&self, baseline: &Self, threshold: f64) -> Vec<String> {
+        let mut findings = Vec::new();
+        for (ty, current) in &self.by_type {
+            let Some(base) = baseline.by_type.get(ty) else {
+                findings.push(format!("{ty}: new event type"));
+                continue;
+            };
+            let fields: std::collections::BTreeSet<&String> =
+                current.fields.keys().chain(base.fields.keys()).collect();
+            for field in fields {
+                let (now, before) = (current.presence(field), base.presence(field));
+                if (now - before).abs() > threshold {
+                    findings.push(format!(
+                        "{ty}.{field}: presence {:.0}% -> {:.0}%",
+                        before * 100.0,
+                        now * 100.0
+                    ));
+                }
+                if let (Some(cur), Some(old)) = (current.fields.get(field), base.fields.get(field))
+                {
+                    for new_type in cur.types.keys().filter(|t| !old.types.contains_key(*t)) {
+                        findings.push(format!("{ty}.{field}: new value type '{new_type}'"));
+                    }
+                }
+            }
+        }
+        findings
+    }
+}
+
+/// Adds `events` to the profile stored at `path`.
+pub(crate) fn update_profile(path: &Path, events: &[AussenEvent]) -> Result<()> {
+    let mut profile = SchemaProfile::load(path).unwrap_or_else(|e| {
+        eprintln!(
+            "Warning: failed to read schema profile from {:?}; starting fresh: {}",
+            path, e
+        );
+        SchemaProfile::default()
+    });
+    for event in events {
+        profile.observe(event);
+    }
+    profile.save(path).context("Failed to save schema profile")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event(features: Value) -> AussenEvent {
+        serde_json::from_value(json!({
+            "type": "sensor.reading",
+            "source": "hub",
+            "features": features
+        }))
+        .expect("valid event")
+    }
+
+    #[test]
+    fn profiles_presence_and_types_per_event_type() {
+        let mut profile = SchemaProfile::default();
+        profile.observe(&event(json!({"temp": 21.5})));
+        profile.observe(&event(json!({"temp": "21.5", "humidity": 40})));
+
+        let readings = &profile.by_type["sensor.reading"];
+        assert_eq!(readings.events, 2);
+        assert_eq!(readings.fields["features.temp"].types["number"], 1);
+        assert_eq!(readings.fields["features.temp"].types["string"], 1);
+        assert!((readings.presence("features.humidity") - 0.5).abs() < 1e-9);
+        assert!((readings.presence("source") - 1.0).abs() < 1e-9);
+        assert_eq!(readings.presence("title"), 0.0);
+    }
+
+    #[test]
+    fn drift_reports_presence_shift_and_new_types() {
+        let mut baseline = SchemaProfile::default();
+        for _ in 0..4 {
+            baseline.observe(&event(json!({"temp": 21.5, "humidity": 40})));
+        }
+        let mut current = SchemaProfile::default();
+        for _ in 0..4 {
+            current.observe(&event(json!({"temp": "21.5"})));
+        }
+
+        let findings = current.drift(&baseline, 0.2);
+        assert!(findings.contains(&"sensor.reading.features.humidity: presence 100% -> 0%".into()));
+        assert!(findings.contains(&"sensor.reading.features.temp: new value type 'string'".into()));
+    }
+}
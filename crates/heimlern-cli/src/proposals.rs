@@ -0,0 +1,531 @@
+//! `heimlern proposals` subcommands.
+//!
+//! Proposals live as `<dir>/<id>.json`; their provenance sidecar as
+//! `<dir>/<id>.provenance.json`. Outcomes are read from a JSONL file with one
+//! `DecisionOutcome` per line. The meta tuner that picks analyzer sensitivity
+//! profiles keeps its state and audit log in `<dir>/meta_tuner.json`.
+//! Proposals added with `store` are indexed in `<dir>/history.jsonl`, which
+//! links each one to the proposal it superseded, and every change to them is
+//! recorded in the hash-chained audit log `<dir>/audit.jsonl`.
+
+use crate::contracts;
+use crate::outcomes::read_outcomes;
+use anyhow::{bail, Context, Result};
+use clap::Subcommand;
+use heimlern_core::contracts::validate_weight_adjustment;
+use heimlern_feedback::audit::AuditIssue;
+use heimlern_feedback::expiry::{Staleness, DEFAULT_TTL_SECONDS};
+use heimlern_feedback::meta::{MetaTuner, ProposalFate};
+use heimlern_feedback::privacy::privatize;
+use heimlern_feedback::provenance::{ProposalProvenance, Verification};
+use heimlern_feedback::sink::{JsonlSink, OutcomeSink};
+use heimlern_feedback::store::ProposalStore;
+use heimlern_feedback::{FeedbackAnalyzer, WeightAdjustmentProposal};
+use std::fs;
+use std::path::{Path, PathBuf};
+use time::OffsetDateTime;
+
+#[derive(Subcommand)]
+pub(crate) enum ProposalsCommand {
+    /// Store a proposal under a new id, superseding the pending one for its policy
+    Store {
+        /// Proposal JSON file
+        #[arg(long)]
+        file: PathBuf,
+
+        /// Days after its `ts` the proposal expires
+        #[arg(long, default_value_t = DEFAULT_TTL_DAYS)]
+        ttl_days: i64,
+
+        /// Directory holding proposals
+        #[arg(long, default_value = "data/proposals")]
+        dir: PathBuf,
+    },
+    /// List stored proposals for a policy, newest first
+    History {
+        /// Basis policy of the proposals
+        #[arg(long)]
+        policy: String,
+
+        /// Directory holding proposals
+        #[arg(long, default_value = "data/proposals")]
+        dir: PathBuf,
+    },
+    /// Retire pending proposals that expired or were overtaken by newer outcomes
+    Expire {
+        /// JSONL outcome log
+        #[arg(long, default_value = "data/heimlern.outcomes.jsonl")]
+        outcomes: PathBuf,
+
+        /// Directory holding proposals
+        #[arg(long, default_value = "data/proposals")]
+        dir: PathBuf,
+    },
+    /// Check the audit chain and the stored proposals for tampering
+    Audit {
+        /// Directory holding proposals
+        #[arg(long, default_value = "data/proposals")]
+        dir: PathBuf,
+    },
+    /// Record the outcome hashes a proposal was derived from
+    Attest {
+        /// Proposal id (file stem inside --dir)
+        #[arg(long)]
+        id: String,
+
+        /// JSONL file with the outcomes the proposal was derived from
+        #[arg(long)]
+        outcomes: PathBuf,
+
+        /// Directory holding proposals
+        #[arg(long, default_value = "data/proposals")]
+        dir: PathBuf,
+    },
+    /// Recompute hashes from stored outcomes and compare them with the sidecar
+    Verify {
+        /// Proposal id (file stem inside --dir)
+        #[arg(long)]
+        id: String,
+
+        /// JSONL file with the stored outcomes
+        #[arg(long)]
+        outcomes: PathBuf,
+
+        /// Directory holding proposals
+        #[arg(long, default_value = "data/proposals")]
+        dir: PathBuf,
+    },
+    /// Project success rate and regret with and without the proposal
+    Forecast {
+        /// Proposal id (file stem inside --dir)
+        #[arg(long)]
+        id: String,
+
+        /// JSONL file with the outcome history
+        #[arg(long)]
+        outcomes: PathBuf,
+
+        /// Number of weeks to project
+        #[arg(long, default_value_t = 4)]
+        weeks: usize,
+
+        /// Directory holding proposals
+        #[arg(long, default_value = "data/proposals")]
+        dir: PathBuf,
+    },
+    /// Write a copy of a proposal with noised evidence for sharing outside the household
+    Export {
+        /// Proposal id (file stem inside --dir)
+        #[arg(long)]
+        id: String,
+
+        /// Output path for the shareable proposal
+        #[arg(long)]
+        out: PathBuf,
+
+        /// Differential-privacy epsilon (smaller = more noise)
+        #[arg(long, default_value_t = EXPORT_DP_EPSILON)]
+        dp_epsilon: f64,
+
+        /// Directory holding proposals
+        #[arg(long, default_value = "data/proposals")]
+        dir: PathBuf,
+    },
+    /// Record what happened to a proposal and let the meta tuner pick the next profile
+    Fate {
+        /// Proposal id (file stem inside --dir)
+        #[arg(long)]
+        id: String,
+
+        /// Sensitivity profile the proposal was generated with
+        #[arg(long)]
+        profile: String,
+
+        /// improved, rolled_back or rejected
+        #[arg(long)]
+        fate: ProposalFate,
+
+        /// JSONL ledger the fate is appended to as an analyzer outcome
+        #[arg(long)]
+        ledger: Option<PathBuf>,
+
+        /// Directory holding proposals
+        #[arg(long, default_value = "data/proposals")]
+        dir: PathBuf,
+    },
+}
+
+/// Default time to live of stored proposals.
+const DEFAULT_TTL_DAYS: i64 = DEFAULT_TTL_SECONDS / 86_400;
+
+/// Default privacy parameter for exported proposals.
+const EXPORT_DP_EPSILON: f64 = 1.0;
+
+pub(crate) fn run(command: ProposalsCommand) -> Result<()> {
+    match command {
+        ProposalsCommand::Store {
+            file,
+            ttl_days,
+            dir,
+        } => {
+            let raw = fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read {}", file.display()))?;
+            let value: serde_json::Value = serde_json::from_str(&raw)
+                .with_context(|| format!("Invalid proposal {}", file.display()))?;
+            contracts::ensure(validate_weight_adjustment, &value, &file)?;
+            let proposal: WeightAdjustmentProposal = serde_json::from_value(value)
+                .with_context(|| format!("Invalid proposal {}", file.display()))?;
+            let mut store = ProposalStore::open(&dir)?.with_ttl(ttl_days * 86_400);
+            let id = store.add(&proposal)?;
+            let supersedes = store.chain(&id).get(1).map(|r| r.id.clone());
+            match supersedes {
+                Some(previous) => println!("Stored proposal '{id}' (supersedes '{previous}')"),
+                None => println!("Stored proposal '{id}'"),
+            }
+        }
+        ProposalsCommand::History { policy, dir } => {
+            let store = ProposalStore::open(&dir)?;
+            let Some(latest) = store.latest(&policy) else {
+                bail!("No stored proposals for policy '{policy}'");
+            };
+            for record in store.chain(&latest.id) {
+                let status = serde_json::to_value(store.get(&record.id)?.status)?;
+                println!(
+                    "{}\t{}\t{}",
+                    record.id,
+                    record.created,
+                    status.as_str().unwrap_or_default()
+                );
+            }
+        }
+        ProposalsCommand::Expire { outcomes, dir } => {
+            let outcomes = read_outcomes(&outcomes)?;
+            let mut store = ProposalStore::open(&dir)?;
+            let retired = store.expire_stale(&outcomes, OffsetDateTime::now_utc())?;
+            for (id, staleness) in &retired {
+                match staleness {
+                    Staleness::Expired { expires_at } => {
+                        println!("Retired proposal '{id}': expired at {expires_at}");
+                    }
+                    Staleness::Outdated {
+                        newer_outcomes,
+                        decisions_analyzed,
+                    } => println!(
+                        "Retired proposal '{id}': {newer_outcomes} newer outcomes outweigh the {decisions_analyzed} it was derived from"
+                    ),
+                    Staleness::Fresh => {}
+                }
+            }
+            if retired.is_empty() {
+                println!("No stale proposals.");
+            }
+        }
+        ProposalsCommand::Audit { dir } => {
+            let store = ProposalStore::open(&dir)?;
+            let issues = store.verify()?;
+            for issue in &issues {
+                eprintln!("{}", describe_issue(issue));
+            }
+            if !issues.is_empty() {
+                bail!("Audit of {} found {} issue(s)", dir.display(), issues.len());
+            }
+            println!(
+                "Audit intact: {} entries, {} proposals, head {}",
+                store.audit().entries().len(),
+                store.records().len(),
+                store.audit().head()
+            );
+        }
+        ProposalsCommand::Attest { id, outcomes, dir } => {
+            let proposal = load_proposal(&dir, &id)?;
+            let outcomes = read_outcomes(&outcomes)?;
+            let sidecar = ProposalProvenance::compute(&proposal, &outcomes)
+                .context("Failed to hash proposal evidence")?;
+            let path = provenance_path(&dir, &id);
+            fs::write(&path, serde_json::to_string_pretty(&sidecar)?)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            println!(
+                "Attested proposal '{id}': {} outcomes, root {}",
+                sidecar.outcome_count, sidecar.outcomes_root
+            );
+        }
+        ProposalsCommand::Verify { id, outcomes, dir } => {
+            let proposal = load_proposal(&dir, &id)?;
+            let outcomes = read_outcomes(&outcomes)?;
+            let path = provenance_path(&dir, &id);
+            let sidecar: ProposalProvenance = serde_json::from_str(
+                &fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?,
+            )
+            .with_context(|| format!("Invalid provenance file {}", path.display()))?;
+
+            match sidecar.verify(&proposal, &outcomes)? {
+                Verification::Valid => println!("Proposal '{id}' verified."),
+                Verification::ProposalMismatch { expected, actual } => bail!(
+                    "Proposal '{id}' was modified after attestation (expected {expected}, got {actual})"
+                ),
+                Verification::OutcomesMismatch {
+                    expected_root,
+                    actual_root,
+                    expected_count,
+                    actual_count,
+                } => bail!(
+                    "Outcomes do not match proposal '{id}': expected {expected_count} records with root {expected_root}, got {actual_count} with root {actual_root}"
+                ),
+            }
+        }
+        ProposalsCommand::Forecast {
+            id,
+            outcomes,
+            weeks,
+            dir,
+        } => {
+            let proposal = load_proposal(&dir, &id)?;
+            let outcomes = read_outcomes(&outcomes)?;
+            let Some(forecast) =
+                FeedbackAnalyzer::default().forecast(&outcomes, weeks, Some(&proposal))
+            else {
+                bail!("No timestamped outcomes to forecast from");
+            };
+            println!("{}", serde_json::to_string_pretty(&forecast)?);
+        }
+        ProposalsCommand::Export {
+            id,
+            out,
+            dp_epsilon,
+            dir,
+        } => {
+            let mut proposal = load_proposal(&dir, &id)?;
+            privatize(&mut proposal, dp_epsilon)?;
+            let value = serde_json::to_value(&proposal)?;
+            contracts::ensure(validate_weight_adjustment, &value, &out)?;
+            fs::write(&out, serde_json::to_string_pretty(&value)?)
+                .with_context(|| format!("Failed to write {}", out.display()))?;
+            println!(
+                "Exported proposal '{id}' with epsilon {dp_epsilon} to {}",
+                out.display()
+            );
+        }
+        ProposalsCommand::Fate {
+            id,
+            profile,
+            fate,
+            ledger,
+            dir,
+        } => {
+            load_proposal(&dir, &id)?;
+            let path = meta_tuner_path(&dir);
+            let mut tuner: MetaTuner = if path.exists() {
+                serde_json::from_str(
+                    &fs::read_to_string(&path)
+                        .with_context(|| format!("Failed to read {}", path.display()))?,
+                )
+                .with_context(|| format!("Invalid meta tuner state {}", path.display()))?
+            } else {
+                MetaTuner::default()
+            };
+            let outcome = tuner.record(&id, &profile, fate)?;
+            if let Some(ledger) = ledger {
+                let mut sink = JsonlSink::append(&ledger)?;
+                sink.send(&outcome)?;
+                sink.flush()?;
+            }
+            let next = tuner.select()?;
+            fs::write(&path, serde_json::to_string_pretty(&tuner)?)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            println!(
+                "Recorded fate '{fate}' of proposal '{id}' for profile '{profile}'; next profile: '{}' (min_decisions {}, min_confidence {})",
+                next.name, next.min_decisions, next.min_confidence
+            );
+        }
+    }
+    Ok(())
+}
+
+fn proposal_path(dir: &Path, id: &str) -> PathBuf {
+    dir.join(format!("{id}.json"))
+}
+
+fn provenance_path(dir: &Path, id: &str) -> PathBuf {
+    dir.join(format!("{id}.provenance.json"))
+}
+
+fn meta_tuner_path(dir: &Path) -> PathBuf {
+    dir.join("meta_tuner.json")
+}
+
+fn describe_issue(issue: &AuditIssue) -> String {
+    match issue {
+        AuditIssue::HashMismatch { seq, .. } => format!("Entry {seq} was altered"),
+        AuditIssue::BrokenLink { seq, .. } => {
+            format!("Entry {seq} does not follow the entry before it")
+        }
+        AuditIssue::ProposalChanged { id, .. } => {
+            format!("Proposal '{id}' was modified outside the store")
+        }
+        AuditIssue::StatusChanged {
+            id,
+            expected,
+            actual,
+        } => format!("Proposal '{id}' has status {actual:?}, audited as {expected:?}"),
+        AuditIssue::Unaudited { id } => format!("Proposal '{id}' has no audit entry"),
+    }
+}
+
+fn load_proposal(dir: &Path, id: &str) -> Result<WeightAdjustmentProposal> {
+    let path = proposal_path(dir, id);
+    let raw =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&raw).with_context(|| format!("Invalid proposal {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heimlern_feedback::ProposalStatus;
+    use std::fs::File;
+    use std::io::Write;
+
+    const PROPOSAL: &str = r#"{
+        "version": "v1",
+        "basis_policy": "remind-bandit",
+        "ts": "2026-01-01T00:00:00Z",
+        "deltas": { "epsilon": { "kind": "relative", "value": -5.0, "unit": "percent" } },
+        "confidence": 0.7,
+        "evidence": { "decisions_analyzed": 2 }
+    }"#;
+
+    fn write_outcomes(path: &Path, success: bool) {
+        let mut f = File::create(path).expect("create outcomes");
+        for id in ["a", "b"] {
+            writeln!(
+                f,
+                r#"{{"decision_id":"{id}","ts":"2026-01-01T00:00:00Z","outcome":"{}","success":{success}}}"#,
+                if success { "success" } else { "failure" }
+            )
+            .expect("write outcome");
+        }
+    }
+
+    #[test]
+    fn attest_then_verify_detects_altered_outcomes() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::write(dir.path().join("p1.json"), PROPOSAL).expect("write proposal");
+        let outcomes = dir.path().join("outcomes.jsonl");
+        write_outcomes(&outcomes, true);
+
+        let attest = ProposalsCommand::Attest {
+            id: "p1".into(),
+            outcomes: outcomes.clone(),
+            dir: dir.path().to_path_buf(),
+        };
+        run(attest).expect("attest");
+        assert!(dir.path().join("p1.provenance.json").exists());
+
+        let verify = || ProposalsCommand::Verify {
+            id: "p1".into(),
+            outcomes: outcomes.clone(),
+            dir: dir.path().to_path_buf(),
+        };
+        run(verify()).expect("unaltered outcomes verify");
+
+        write_outcomes(&outcomes, false);
+        let err = run(verify()).expect_err("altered outcomes must fail");
+        assert!(err.to_string().contains("Outcomes do not match"));
+    }
+
+    #[test]
+    fn store_supersedes_pending_proposal() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let file = dir.path().join("new.json");
+        fs::write(&file, PROPOSAL).expect("write proposal");
+        let store = || ProposalsCommand::Store {
+            file: file.clone(),
+            ttl_days: DEFAULT_TTL_DAYS,
+            dir: dir.path().join("store"),
+        };
+        run(store()).expect("store first");
+        run(store()).expect("store second");
+
+        let first = load_proposal(&dir.path().join("store"), "p000001").expect("first");
+        assert_eq!(first.status, ProposalStatus::Superseded);
+        run(ProposalsCommand::History {
+            policy: "remind-bandit".into(),
+            dir: dir.path().join("store"),
+        })
+        .expect("history");
+        assert!(run(ProposalsCommand::History {
+            policy: "ucb".into(),
+            dir: dir.path().join("store"),
+        })
+        .is_err());
+
+        let audit = || ProposalsCommand::Audit {
+            dir: dir.path().join("store"),
+        };
+        let log = dir.path().join("outcomes.jsonl");
+        fs::write(&log, "").expect("write outcomes");
+        run(ProposalsCommand::Expire {
+            outcomes: log,
+            dir: dir.path().join("store"),
+        })
+        .expect("expire");
+        let second = load_proposal(&dir.path().join("store"), "p000002").expect("second");
+        assert_eq!(second.status, ProposalStatus::Superseded);
+
+        run(audit()).expect("untouched store passes the audit");
+        fs::write(dir.path().join("store/p000001.json"), PROPOSAL).expect("overwrite");
+        let err = run(audit()).expect_err("edited status must fail");
+        assert!(err.to_string().contains("1 issue(s)"));
+    }
+
+    #[test]
+    fn fate_updates_meta_tuner_and_ledger() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::write(dir.path().join("p1.json"), PROPOSAL).expect("write proposal");
+        let ledger = dir.path().join("meta.jsonl");
+
+        let fate = |profile: &str| ProposalsCommand::Fate {
+            id: "p1".into(),
+            profile: profile.into(),
+            fate: ProposalFate::RolledBack,
+            ledger: Some(ledger.clone()),
+            dir: dir.path().to_path_buf(),
+        };
+        run(fate("balanced")).expect("record fate");
+        assert!(run(fate("reckless")).is_err());
+
+        let tuner: MetaTuner = serde_json::from_str(
+            &fs::read_to_string(dir.path().join("meta_tuner.json")).expect("read state"),
+        )
+        .expect("state json");
+        assert_eq!(tuner.stats("balanced").pulls, 1);
+        assert_eq!(tuner.log().len(), 2);
+        let outcomes = read_outcomes(&ledger).expect("ledger");
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(outcomes[0].action.as_deref(), Some("balanced"));
+    }
+
+    #[test]
+    fn export_strips_free_text_and_rejects_invalid_epsilon() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut proposal: serde_json::Value = serde_json::from_str(PROPOSAL).expect("json");
+        proposal["reasoning"] = serde_json::json!("Reduce exploration due to high failure rate");
+        fs::write(dir.path().join("p1.json"), proposal.to_string()).expect("write proposal");
+        let out = dir.path().join("shared.json");
+
+        let export = |dp_epsilon| ProposalsCommand::Export {
+            id: "p1".into(),
+            out: out.clone(),
+            dp_epsilon,
+            dir: dir.path().to_path_buf(),
+        };
+        run(export(0.5)).expect("export");
+        let shared: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&out).expect("read")).expect("json");
+        assert!(shared.get("reasoning").is_none());
+        assert_eq!(shared["basis_policy"], "remind-bandit");
+
+        assert!(run(export(0.0)).is_err());
+    }
+}
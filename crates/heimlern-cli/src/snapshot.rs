@@ -0,0 +1,335 @@
+//! `heimlern snapshot` subcommands.
+//!
+//! Snapshots are handled as plain `policy.snapshot` JSON so that every bandit
+//! flavour can be maintained without loading it into a policy. Besides the
+//! contract fields (`arms`, `counts`, `values`) the per-arm extensions written
+//! by the bandits are understood: parallel arrays (`posterior.alpha`,
+//! `posterior.beta`, `variance`, `linear.a`, `linear.b`) and maps keyed by arm
+//! (`warmup.weights`, `fatigue.arms`, `timing.slots`, `window.samples`,
+//! `recency.arms`).
+
+use anyhow::{bail, Context, Result};
+use clap::Subcommand;
+use heimlern_feedback::lineage::{append_lineage, lineage_path, LineageEntry};
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+/// Per-arm arrays inside extension objects, as `(object, field)`; an empty
+/// object name addresses a top-level array.
+const ARM_ARRAYS: &[(&str, &str)] = &[
+    ("posterior", "alpha"),
+    ("posterior", "beta"),
+    ("", "variance"),
+    ("linear", "a"),
+    ("linear", "b"),
+];
+
+/// Maps keyed by arm name, as `(object, field)`.
+const ARM_MAPS: &[(&str, &str)] = &[
+    ("warmup", "weights"),
+    ("fatigue", "arms"),
+    ("timing", "slots"),
+    ("window", "samples"),
+    ("recency", "arms"),
+];
+
+#[derive(Subcommand)]
+pub(crate) enum SnapshotCommand {
+    /// Drop arms that were never pulled and metadata of arms that no longer exist
+    Compact {
+        /// Policy snapshot (policy.snapshot JSON), rewritten in place unless --out is given
+        snapshot: PathBuf,
+
+        /// Write the compacted snapshot here instead
+        #[arg(long)]
+        out: Option<PathBuf>,
+
+        /// Arms to keep even without pulls (repeatable)
+        #[arg(long = "keep")]
+        keep: Vec<String>,
+
+        /// Only report what would be removed
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// What a compaction removed.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Compaction {
+    pub(crate) policy_id: String,
+    pub(crate) arms_before: usize,
+    pub(crate) removed_arms: Vec<String>,
+    /// Stale per-arm metadata entries, as `"object.field.arm"`.
+    pub(crate) stale_metadata: Vec<String>,
+    /// Non-finite values that were reset to `0.0`.
+    pub(crate) reset_values: usize,
+}
+
+impl Compaction {
+    fn is_noop(&self) -> bool {
+        self.removed_arms.is_empty() && self.stale_metadata.is_empty() && self.reset_values == 0
+    }
+
+    fn lineage_entry(&self) -> LineageEntry {
+        LineageEntry::now("compact", &self.policy_id).with_details(json!({
+            "arms_before": self.arms_before,
+            "arms_after": self.arms_before - self.removed_arms.len(),
+            "removed_arms": self.removed_arms,
+            "stale_metadata": self.stale_metadata,
+            "reset_values": self.reset_values,
+        }))
+    }
+}
+
+pub(crate) fn run(command: SnapshotCommand) -> Result<()> {
+    match command {
+        SnapshotCommand::Compact {
+            snapshot,
+            out,
+            keep,
+            dry_run,
+        } => {
+            let raw = fs::read_to_string(&snapshot)
+                .with_context(|| format!("Failed to read {}", snapshot.display()))?;
+            let mut value: Value = serde_json::from_str(&raw)
+                .with_context(|| format!("Invalid snapshot {}", snapshot.display()))?;
+            let report = compact(&mut value, &keep)?;
+            print_report(&report);
+            if dry_run || report.is_noop() {
+                return Ok(());
+            }
+            let target = out.as_deref().unwrap_or(&snapshot);
+            write_compacted(target, &value, &report)?;
+            println!("Wrote {}", target.display());
+        }
+    }
+    Ok(())
+}
+
+fn print_report(report: &Compaction) {
+    println!(
+        "Snapshot '{}': {} arms, {} removed, {} stale metadata entries, {} values reset.",
+        report.policy_id,
+        report.arms_before,
+        report.removed_arms.len(),
+        report.stale_metadata.len(),
+        report.reset_values
+    );
+    for arm in &report.removed_arms {
+        println!("  - arm {arm}");
+    }
+    for entry in &report.stale_metadata {
+        println!("  - metadata {entry}");
+    }
+}
+
+fn write_compacted(target: &Path, value: &Value, report: &Compaction) -> Result<()> {
+    fs::write(target, serde_json::to_string_pretty(value)?)
+        .with_context(|| format!("Failed to write {}", target.display()))?;
+    let lineage = lineage_path(target);
+    append_lineage(&lineage, &report.lineage_entry())
+        .with_context(|| format!("Failed to record lineage in {}", lineage.display()))?;
+    Ok(())
+}
+
+/// Compact `snapshot` in place.
+///
+/// Arms with a count of zero are removed unless listed in `keep`; if no arm
+/// has been pulled yet, all arms are kept. Parallel per-arm arrays are
+/// filtered alongside, arm-keyed maps lose entries of arms that are gone, and
+/// non-finite averages are reset to `0.0`. `values` are per-arm averages, so
+/// the remaining ones need no rescaling after eviction.
+pub(crate) fn compact(snapshot: &mut Value, keep: &[String]) -> Result<Compaction> {
+    let Some(root) = snapshot.as_object_mut() else {
+        bail!("Snapshot is not a JSON object");
+    };
+    let policy_id = root
+        .get("policy_id")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown")
+        .to_string();
+    let arms: Vec<String> = field(root, "arms")?;
+    let counts: Vec<u64> = field(root, "counts")?;
+    let values: Vec<Option<f64>> = field(root, "values")?;
+    if counts.len() != arms.len() || values.len() != arms.len() {
+        bail!(
+            "arms/counts/values lengths differ ({}, {}, {})",
+            arms.len(),
+            counts.len(),
+            values.len()
+        );
+    }
+
+    let any_pulled = counts.iter().any(|c| *c > 0);
+    let retained: Vec<bool> = arms
+        .iter()
+        .zip(&counts)
+        .map(|(arm, count)| !any_pulled || *count > 0 || keep.contains(arm))
+        .collect();
+    let mut report = Compaction {
+        policy_id,
+        arms_before: arms.len(),
+        removed_arms: arms
+            .iter()
+            .zip(&retained)
+            .filter(|(_, r)| !**r)
+            .map(|(a, _)| a.clone())
+            .collect(),
+        ..Compaction::default()
+    };
+
+    let filter = |items: Vec<Value>| -> Vec<Value> {
+        items
+            .into_iter()
+            .zip(&retained)
+            .filter_map(|(item, r)| r.then_some(item))
+            .collect()
+    };
+    let remaining: Vec<String> = arms
+        .iter()
+        .zip(&retained)
+        .filter(|(_, r)| **r)
+        .map(|(a, _)| a.clone())
+        .collect();
+
+    let values: Vec<Value> = values
+        .iter()
+        .zip(&retained)
+        .filter(|(_, r)| **r)
+        .map(|(v, _)| match v {
+            Some(x) if x.is_finite() => json!(x),
+            _ => {
+                report.reset_values += 1;
+                json!(0.0)
+            }
+        })
+        .collect();
+    root.insert("arms".into(), json!(remaining));
+    root.insert(
+        "counts".into(),
+        Value::Array(filter(counts.iter().map(|c| json!(c)).collect())),
+    );
+    root.insert("values".into(), Value::Array(values));
+
+    for (object, name) in ARM_ARRAYS {
+        let Some(Value::Array(items)) = nested_mut(root, object, name) else {
+            continue;
+        };
+        if items.len() == arms.len() {
+            *items = filter(std::mem::take(items));
+        }
+    }
+    for (object, name) in ARM_MAPS {
+        let Some(Value::Object(map)) = nested_mut(root, object, name) else {
+            continue;
+        };
+        map.retain(|arm, _| {
+            let live = remaining.contains(arm);
+            if !live && !report.removed_arms.contains(arm) {
+                report.stale_metadata.push(format!("{object}.{name}.{arm}"));
+            }
+            live
+        });
+    }
+
+    if !report.is_noop() {
+        if let Ok(ts) = OffsetDateTime::now_utc().format(&Rfc3339) {
+            root.insert("ts".into(), json!(ts));
+        }
+    }
+    Ok(report)
+}
+
+fn field<T: serde::de::DeserializeOwned>(root: &Map<String, Value>, name: &str) -> Result<T> {
+    let value = root.get(name).cloned().unwrap_or(Value::Null);
+    serde_json::from_value(value).with_context(|| format!("Snapshot field '{name}' is invalid"))
+}
+
+fn nested_mut<'a>(
+    root: &'a mut Map<String, Value>,
+    object: &str,
+    name: &str,
+) -> Option<&'a mut Value> {
+    if object.is_empty() {
+        root.get_mut(name)
+    } else {
+        root.get_mut(object)?.get_mut(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heimlern_feedback::lineage::read_lineage;
+
+    fn snapshot() -> Value {
+        json!({
+            "version": "0.1.0",
+            "policy_id": "thompson-bandit",
+            "ts": "2026-01-01T00:00:00Z",
+            "arms": ["morning", "old_routine", "evening"],
+            "counts": [4, 0, 2],
+            "values": [0.5, 0.0, null],
+            "epsilon": 0.0,
+            "posterior": { "alpha": [3.0, 1.0, 1.0], "beta": [3.0, 1.0, 3.0] },
+            "timing": {
+                "window_minutes": 60,
+                "slots": { "morning": {}, "old_routine": {}, "renamed_long_ago": {} }
+            }
+        })
+    }
+
+    #[test]
+    fn compaction_prunes_dead_arms_and_stale_metadata() {
+        let mut snap = snapshot();
+        let report = compact(&mut snap, &[]).expect("compact");
+
+        assert_eq!(report.removed_arms, vec!["old_routine".to_string()]);
+        assert_eq!(
+            report.stale_metadata,
+            vec!["timing.slots.renamed_long_ago".to_string()]
+        );
+        assert_eq!(report.reset_values, 1);
+        assert_eq!(snap["arms"], json!(["morning", "evening"]));
+        assert_eq!(snap["counts"], json!([4, 2]));
+        assert_eq!(snap["values"], json!([0.5, 0.0]));
+        assert_eq!(snap["posterior"]["alpha"], json!([3.0, 1.0]));
+        assert_eq!(
+            snap["timing"]["slots"]
+                .as_object()
+                .map(|m| m.keys().cloned().collect::<Vec<_>>()),
+            Some(vec!["morning".to_string()])
+        );
+
+        let mut kept = snapshot();
+        let report = compact(&mut kept, &["old_routine".to_string()]).expect("compact");
+        assert!(report.removed_arms.is_empty());
+        assert_eq!(kept["arms"].as_array().map(Vec::len), Some(3));
+    }
+
+    #[test]
+    fn compact_command_writes_snapshot_and_lineage() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("policy.json");
+        fs::write(&path, snapshot().to_string()).expect("write snapshot");
+
+        run(SnapshotCommand::Compact {
+            snapshot: path.clone(),
+            out: None,
+            keep: Vec::new(),
+            dry_run: false,
+        })
+        .expect("compact");
+
+        let written: Value =
+            serde_json::from_str(&fs::read_to_string(&path).expect("read")).expect("json");
+        assert_eq!(written["arms"], json!(["morning", "evening"]));
+        let lineage = read_lineage(&lineage_path(&path)).expect("lineage");
+        assert_eq!(lineage.len(), 1);
+        assert_eq!(lineage[0].operation, "compact");
+        assert_eq!(lineage[0].details["removed_arms"], json!(["old_routine"]));
+    }
+}
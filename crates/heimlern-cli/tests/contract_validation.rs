@@ -0,0 +1,71 @@
+use assert_cmd::Command;
+use std::fs;
+
+fn stderr(output: &std::process::Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[allow(deprecated)]
+#[test]
+fn malformed_payloads_are_skipped_or_rejected_at_the_boundary() {
+    let temp = tempfile::tempdir().expect("tempdir");
+
+    let events = temp.path().join("events.jsonl");
+    fs::write(
+        &events,
+        "{\"type\":\"reminder.due\",\"source\":\"demo\",\"title\":\"ok\"}\n\
+         {\"type\":\"reminder.due\",\"source\":\"demo\",\"url\":null}\n",
+    )
+    .expect("write events");
+    let output = Command::cargo_bin("heimlern")
+        .expect("binary")
+        .args(["ingest", "file", "--path"])
+        .arg(&events)
+        .arg("--state-file")
+        .arg(temp.path().join("state.json"))
+        .arg("--stats-file")
+        .arg(temp.path().join("stats.json"))
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    let message = stderr(&output);
+    assert!(message.contains("events.jsonl:2"), "{message}");
+
+    // The invalid line is skipped and counted, and the cursor moves past it.
+    let stats: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(temp.path().join("stats.json")).unwrap())
+            .expect("stats json");
+    assert_eq!(stats["total_processed"], 1);
+    assert_eq!(stats["invalid_skipped"], 1);
+    let state: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(temp.path().join("state.json")).unwrap())
+            .expect("state json");
+    assert_eq!(state["cursor"], 2);
+
+    let snapshot = temp.path().join("snapshot.json");
+    fs::write(
+        &snapshot,
+        r#"{"version":"0.1.0","policy_id":"remind-bandit","ts":"2026-01-01T00:00:00Z",
+            "arms":["remind.morning"],"counts":[1],"values":[0.5],"epsilon":0.2,
+            "warmup":{"decisions":3,"issued":0}}"#,
+    )
+    .expect("write snapshot");
+    let outcomes = temp.path().join("outcomes.jsonl");
+    fs::write(&outcomes, "").expect("write outcomes");
+    let output = Command::cargo_bin("heimlern")
+        .expect("binary")
+        .arg("lab")
+        .arg("--snapshot")
+        .arg(&snapshot)
+        .arg("--outcomes")
+        .arg(&outcomes)
+        .write_stdin("")
+        .assert()
+        .failure()
+        .get_output()
+        .clone();
+    let message = stderr(&output);
+    assert!(message.contains("violates its contract"), "{message}");
+    assert!(message.contains("warmup"), "{message}");
+}
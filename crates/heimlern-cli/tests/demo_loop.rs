@@ -0,0 +1,41 @@
+use assert_cmd::Command;
+use serde_json::Value;
+use std::fs;
+
+#[allow(deprecated)]
+#[test]
+fn demo_learn_loop_improves_and_writes_artifacts() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let output = Command::cargo_bin("heimlern-demo")
+        .expect("binary")
+        .args(["--out"])
+        .arg(temp.path())
+        .args(["--rounds", "9", "--per-round", "80", "--seed", "3"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let stdout = String::from_utf8(output).expect("utf8");
+    assert!(stdout.contains("Success rate improved"), "{stdout}");
+
+    let ledger = fs::read_to_string(temp.path().join("outcomes.jsonl")).expect("ledger");
+    assert_eq!(ledger.lines().count(), 9 * 80);
+    let decisions = fs::read_to_string(temp.path().join("decisions.jsonl")).expect("decisions");
+    assert_eq!(decisions.lines().count(), 9 * 80);
+
+    let proposals: Vec<Value> = fs::read_to_string(temp.path().join("proposals.jsonl"))
+        .expect("proposals")
+        .lines()
+        .map(|l| serde_json::from_str(l).expect("proposal json"))
+        .collect();
+    assert!(proposals.iter().any(|p| p["status"] == "accepted"));
+
+    let snapshot: Value = serde_json::from_str(
+        &fs::read_to_string(temp.path().join("snapshot.json")).expect("snapshot"),
+    )
+    .expect("snapshot json");
+    assert_eq!(snapshot["policy_id"], "remind-bandit");
+    assert_eq!(snapshot["seed"], 3);
+    assert!(snapshot["epsilon"].as_f64().expect("epsilon") < 0.9);
+}
@@ -0,0 +1,214 @@
+//! Gemeinsamer Kontextspeicher ("Blackboard") für unabhängige Policies.
+//!
+//! Middleware schreibt kurzlebige Fakten (z. B. `"speaker.busy_until"`), Policies
+//! lesen sie während `decide`, ohne voneinander zu wissen. Jeder Eintrag kann
+//! eine Ablaufzeit in Unix-Sekunden tragen; abgelaufene Einträge sind unsichtbar
+//! und werden beim Persistieren verworfen. Zeitpunkte werden explizit übergeben,
+//! damit das Verhalten deterministisch testbar bleibt.
+
+use crate::Context;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Schlüssel, unter dem [`Blackboard::annotate`] die Einträge in
+/// `Context::features` ablegt.
+pub const FEATURE_KEY: &str = "blackboard";
+
+/// Aktuelle Zeit in Unix-Sekunden (0, falls die Systemuhr vor 1970 steht).
+#[must_use]
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Einzelner Eintrag mit optionaler Ablaufzeit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub value: Value,
+    /// Ablaufzeitpunkt in Unix-Sekunden (exklusiv). `None` = unbegrenzt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+}
+
+impl Entry {
+    fn is_live(&self, now: u64) -> bool {
+        self.expires_at.is_none_or(|t| now < t)
+    }
+}
+
+/// Schlüssel-Wert-Speicher mit TTL.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Blackboard {
+    #[serde(default)]
+    entries: BTreeMap<String, Entry>,
+}
+
+impl Blackboard {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Schreibt `value` unter `key`; mit `ttl` läuft der Eintrag ab `now + ttl` ab.
+    pub fn put(&mut self, key: impl Into<String>, value: Value, ttl: Option<Duration>, now: u64) {
+        let expires_at = ttl.map(|ttl| now.saturating_add(ttl.as_secs()));
+        self.entries.insert(key.into(), Entry { value, expires_at });
+    }
+
+    /// Liest einen noch gültigen Eintrag.
+    #[must_use]
+    pub fn get(&self, key: &str, now: u64) -> Option<&Value> {
+        self.entries
+            .get(key)
+            .filter(|e| e.is_live(now))
+            .map(|e| &e.value)
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        self.entries.remove(key).map(|e| e.value)
+    }
+
+    /// Entfernt abgelaufene Einträge und liefert deren Anzahl.
+    pub fn purge_expired(&mut self, now: u64) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|_, e| e.is_live(now));
+        before - self.entries.len()
+    }
+
+    /// Alle gültigen Einträge in Schlüsselreihenfolge.
+    pub fn live(&self, now: u64) -> impl Iterator<Item = (&str, &Value)> {
+        self.entries
+            .iter()
+            .filter(move |(_, e)| e.is_live(now))
+            .map(|(k, e)| (k.as_str(), &e.value))
+    }
+
+    /// Legt alle gültigen Einträge als Objekt unter `features.blackboard` ab.
+    ///
+    /// `null`-Features werden zu einem leeren Objekt; andere Nicht-Objekte bleiben
+    /// unverändert.
+    pub fn annotate(&self, ctx: &mut Context, now: u64) {
+        if ctx.features.is_null() {
+            ctx.features = Value::Object(Map::new());
+        }
+        if let Value::Object(features) = &mut ctx.features {
+            let board: Map<String, Value> = self
+                .live(now)
+                .map(|(k, v)| (k.to_string(), v.clone()))
+                .collect();
+            features.insert(FEATURE_KEY.to_string(), Value::Object(board));
+        }
+    }
+
+    /// Persistierbare Form ohne abgelaufene Einträge.
+    #[must_use]
+    pub fn to_persisted(&self, now: u64) -> Value {
+        let mut copy = self.clone();
+        copy.purge_expired(now);
+        serde_json::to_value(copy).unwrap_or(Value::Null)
+    }
+}
+
+/// Thread-sicher teilbarer Handle auf ein [`Blackboard`].
+#[derive(Debug, Clone, Default)]
+pub struct SharedBlackboard(Arc<RwLock<Blackboard>>);
+
+impl SharedBlackboard {
+    #[must_use]
+    pub fn new(board: Blackboard) -> Self {
+        Self(Arc::new(RwLock::new(board)))
+    }
+
+    pub fn put(&self, key: impl Into<String>, value: Value, ttl: Option<Duration>) {
+        self.0
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .put(key, value, ttl, unix_now());
+    }
+
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<Value> {
+        self.0
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(key, unix_now())
+            .cloned()
+    }
+
+    pub fn annotate(&self, ctx: &mut Context) {
+        self.0
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .annotate(ctx, unix_now());
+    }
+
+    /// Kopie des aktuellen Zustands (z. B. zum Persistieren).
+    #[must_use]
+    pub fn snapshot(&self) -> Blackboard {
+        self.0
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn entries_expire_after_ttl() {
+        let mut board = Blackboard::new();
+        board.put(
+            "speaker.busy",
+            json!(true),
+            Some(Duration::from_secs(60)),
+            1_000,
+        );
+        board.put("household.mode", json!("away"), None, 1_000);
+
+        assert_eq!(board.get("speaker.busy", 1_059), Some(&json!(true)));
+        assert_eq!(board.get("speaker.busy", 1_060), None);
+        assert_eq!(board.get("household.mode", u64::MAX), Some(&json!("away")));
+        assert_eq!(board.purge_expired(2_000), 1);
+    }
+
+    #[test]
+    fn annotate_exposes_live_entries_to_policies() {
+        let mut board = Blackboard::new();
+        board.put(
+            "speaker.busy",
+            json!(true),
+            Some(Duration::from_secs(10)),
+            0,
+        );
+        board.put("stale", json!(1), Some(Duration::from_secs(1)), 0);
+
+        let mut ctx = Context {
+            kind: "reminder".into(),
+            features: json!({ "load": 0.3 }),
+        };
+        board.annotate(&mut ctx, 5);
+        assert_eq!(ctx.features["load"], json!(0.3));
+        assert_eq!(ctx.features[FEATURE_KEY], json!({ "speaker.busy": true }));
+    }
+
+    #[test]
+    fn persisted_form_drops_expired_entries() {
+        let mut board = Blackboard::new();
+        board.put("a", json!(1), Some(Duration::from_secs(1)), 0);
+        board.put("b", json!(2), None, 0);
+
+        let restored: Blackboard = match serde_json::from_value(board.to_persisted(10)) {
+            Ok(b) => b,
+            Err(err) => panic!("roundtrip failed: {err}"),
+        };
+        assert_eq!(restored.live(10).count(), 1);
+        assert_eq!(restored.get("b", 10), Some(&json!(2)));
+    }
+}
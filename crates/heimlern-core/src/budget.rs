@@ -0,0 +1,269 @@
+//! Latenzbudget und Messung pro Entscheidung.
+//!
+//! [`BudgetedPolicy`] umhüllt eine beliebige [`Policy`], misst die Laufzeit
+//! jedes `decide`-Aufrufs und zählt Budgetüberschreitungen. Überschreitet die
+//! Policy ihr Budget wiederholt, schaltet der Wrapper optional auf einen
+//! degradierten Schnellpfad um: Für eine begrenzte Zahl von Aufrufen wird die
+//! zuletzt berechnete Entscheidung desselben `kind` ohne serialisierten Kontext
+//! zurückgegeben, danach wird die Policy erneut geprüft.
+
+use crate::{Context, Decision, Policy, PolicyDescriptor};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Budgetkonfiguration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyBudget {
+    /// Maximale Laufzeit eines `decide`-Aufrufs.
+    pub decide: Duration,
+    /// Anzahl aufeinanderfolgender Überschreitungen, ab der der Schnellpfad
+    /// aktiviert wird. `0` deaktiviert den Schnellpfad.
+    pub degrade_after: u32,
+    /// Anzahl der Aufrufe, die im Schnellpfad beantwortet werden, bevor die
+    /// Policy erneut befragt wird.
+    pub degraded_calls: u32,
+}
+
+impl LatencyBudget {
+    /// Budget ohne Schnellpfad (nur Messung und Zählung).
+    #[must_use]
+    pub fn new(decide: Duration) -> Self {
+        Self {
+            decide,
+            degrade_after: 0,
+            degraded_calls: 0,
+        }
+    }
+
+    /// Aktiviert den degradierten Schnellpfad.
+    #[must_use]
+    pub fn with_fast_path(mut self, degrade_after: u32, degraded_calls: u32) -> Self {
+        self.degrade_after = degrade_after;
+        self.degraded_calls = degraded_calls;
+        self
+    }
+}
+
+/// Laufzeitkennzahlen des Wrappers.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LatencyStats {
+    /// Anzahl gemessener `decide`-Aufrufe der inneren Policy.
+    pub calls: u64,
+    /// Anzahl der Aufrufe, die das Budget überschritten haben.
+    pub breaches: u64,
+    /// Anzahl der Antworten aus dem Schnellpfad.
+    pub degraded: u64,
+    /// Summe der gemessenen Laufzeiten in Mikrosekunden.
+    pub total_micros: u64,
+    /// Längste gemessene Laufzeit in Mikrosekunden.
+    pub max_micros: u64,
+}
+
+impl LatencyStats {
+    /// Mittlere Laufzeit in Mikrosekunden (0 ohne Messungen).
+    #[must_use]
+    pub fn mean_micros(&self) -> u64 {
+        self.total_micros.checked_div(self.calls).unwrap_or(0)
+    }
+
+    fn record(&mut self, elapsed: Duration, breached: bool) {
+        let micros = u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX);
+        self.calls += 1;
+        self.total_micros = self.total_micros.saturating_add(micros);
+        self.max_micros = self.max_micros.max(micros);
+        if breached {
+            self.breaches += 1;
+        }
+    }
+}
+
+/// Policy-Wrapper mit Latenzbudget.
+#[derive(Debug)]
+pub struct BudgetedPolicy<P> {
+    inner: P,
+    budget: LatencyBudget,
+    stats: LatencyStats,
+    consecutive_breaches: u32,
+    degraded_remaining: u32,
+    cache: HashMap<String, Decision>,
+}
+
+impl<P: Policy> BudgetedPolicy<P> {
+    pub fn new(inner: P, budget: LatencyBudget) -> Self {
+        Self {
+            inner,
+            budget,
+            stats: LatencyStats::default(),
+            consecutive_breaches: 0,
+            degraded_remaining: 0,
+            cache: HashMap::new(),
+        }
+    }
+
+    pub fn stats(&self) -> &LatencyStats {
+        &self.stats
+    }
+
+    pub fn budget(&self) -> LatencyBudget {
+        self.budget
+    }
+
+    /// `true`, solange Antworten aus dem Schnellpfad kommen.
+    pub fn is_degraded(&self) -> bool {
+        self.degraded_remaining > 0
+    }
+
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+
+    fn fast_path(&mut self, ctx: &Context) -> Option<Decision> {
+        if self.degraded_remaining == 0 {
+            return None;
+        }
+        let mut decision = self.cache.get(&ctx.kind)?.clone();
+        self.degraded_remaining -= 1;
+        self.stats.degraded += 1;
+        decision.context = None;
+        decision
+            .why
+            .push("degraded fast-path (latency budget)".into());
+        Some(decision)
+    }
+}
+
+impl<P: Policy> Policy for BudgetedPolicy<P> {
+    fn decide(&mut self, ctx: &Context) -> Decision {
+        if let Some(decision) = self.fast_path(ctx) {
+            return decision;
+        }
+
+        let started = Instant::now();
+        let decision = self.inner.decide(ctx);
+        let elapsed = started.elapsed();
+        let breached = elapsed > self.budget.decide;
+        self.stats.record(elapsed, breached);
+
+        if breached {
+            self.consecutive_breaches = self.consecutive_breaches.saturating_add(1);
+            if self.budget.degrade_after > 0
+                && self.consecutive_breaches >= self.budget.degrade_after
+            {
+                self.degraded_remaining = self.budget.degraded_calls;
+                self.consecutive_breaches = 0;
+            }
+        } else {
+            self.consecutive_breaches = 0;
+        }
+
+        if self.budget.degrade_after > 0 {
+            self.cache.insert(ctx.kind.clone(), decision.clone());
+        }
+        decision
+    }
+
+    fn feedback(&mut self, ctx: &Context, action: &str, reward: f32) {
+        self.inner.feedback(ctx, action, reward);
+    }
+
+    fn feedback_batch(&mut self, items: &[(Context, String, f32)]) {
+        self.inner.feedback_batch(items);
+    }
+
+    fn snapshot(&self) -> Value {
+        self.inner.snapshot()
+    }
+
+    fn load(&mut self, snapshot: Value) {
+        self.cache.clear();
+        self.inner.load(snapshot);
+    }
+
+    fn descriptor(&self) -> PolicyDescriptor {
+        self.inner.descriptor()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    struct Slow {
+        delay: Duration,
+        calls: u32,
+    }
+
+    impl Policy for Slow {
+        fn decide(&mut self, ctx: &Context) -> Decision {
+            self.calls += 1;
+            std::thread::sleep(self.delay);
+            Decision {
+                action: format!("slow.{}", self.calls),
+                score: 0.0,
+                why: vec!["slow".into()],
+                context: Some(json!({ "kind": ctx.kind })),
+                chosen: None,
+            }
+        }
+        fn feedback(&mut self, _: &Context, _: &str, _: f32) {}
+        fn snapshot(&self) -> Value {
+            Value::Null
+        }
+        fn load(&mut self, _: Value) {}
+    }
+
+    fn ctx() -> Context {
+        Context {
+            kind: "reminder".into(),
+            features: json!({}),
+        }
+    }
+
+    #[test]
+    fn counts_breaches_without_fast_path() {
+        let slow = Slow {
+            delay: Duration::from_millis(2),
+            calls: 0,
+        };
+        let mut policy = BudgetedPolicy::new(slow, LatencyBudget::new(Duration::from_micros(1)));
+        for _ in 0..3 {
+            policy.decide(&ctx());
+        }
+        assert_eq!(policy.stats().calls, 3);
+        assert_eq!(policy.stats().breaches, 3);
+        assert_eq!(policy.stats().degraded, 0);
+        assert!(policy.stats().max_micros >= 2_000);
+    }
+
+    #[test]
+    fn fast_path_serves_cached_decision_then_probes_again() {
+        let slow = Slow {
+            delay: Duration::from_millis(2),
+            calls: 0,
+        };
+        let budget = LatencyBudget::new(Duration::from_micros(1)).with_fast_path(2, 2);
+        let mut policy = BudgetedPolicy::new(slow, budget);
+
+        policy.decide(&ctx());
+        let second = policy.decide(&ctx());
+        assert!(policy.is_degraded());
+
+        let cached = policy.decide(&ctx());
+        assert_eq!(cached.action, second.action);
+        assert!(cached.context.is_none());
+        assert!(cached.why.iter().any(|w| w.contains("degraded")));
+        policy.decide(&ctx());
+        assert!(!policy.is_degraded());
+
+        policy.decide(&ctx());
+        assert_eq!(policy.inner().calls, 3);
+        assert_eq!(policy.stats().degraded, 2);
+    }
+}
@@ -0,0 +1,90 @@
+//! Kanonische JSON-Serialisierung.
+//!
+//! Artefakte, die gehasht, gedifft oder als Golden-Files verglichen werden,
+//! müssen byte-genau reproduzierbar sein. Die Funktionen hier sortieren alle
+//! Objekt-Schlüssel rekursiv und schreiben kompaktes JSON ohne Leerraum –
+//! unabhängig davon, ob `serde_json` im Workspace mit `preserve_order` gebaut
+//! wird oder in welcher Reihenfolge Maps befüllt wurden.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::io::Write;
+
+/// Sortiert die Schlüssel aller Objekte in `value` rekursiv.
+#[must_use]
+pub fn canonicalize(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k, canonicalize(v)))
+                    .collect::<Map<_, _>>(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(canonicalize).collect()),
+        other => other,
+    }
+}
+
+/// Serialisiert `value` als kanonischen JSON-String.
+///
+/// # Errors
+/// Schlägt fehl, wenn `value` nicht als JSON darstellbar ist.
+pub fn to_canonical_string<T: Serialize + ?Sized>(value: &T) -> serde_json::Result<String> {
+    serde_json::to_string(&canonicalize(serde_json::to_value(value)?))
+}
+
+/// Serialisiert `value` als kanonische JSON-Bytes.
+///
+/// # Errors
+/// Schlägt fehl, wenn `value` nicht als JSON darstellbar ist.
+pub fn to_canonical_vec<T: Serialize + ?Sized>(value: &T) -> serde_json::Result<Vec<u8>> {
+    serde_json::to_vec(&canonicalize(serde_json::to_value(value)?))
+}
+
+/// Schreibt `value` kanonisch in `writer`.
+///
+/// # Errors
+/// Schlägt bei Serialisierungs- oder I/O-Fehlern fehl.
+pub fn to_canonical_writer<W: Write, T: Serialize + ?Sized>(
+    writer: W,
+    value: &T,
+) -> serde_json::Result<()> {
+    serde_json::to_writer(writer, &canonicalize(serde_json::to_value(value)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    #[test]
+    fn output_is_independent_of_insertion_order() {
+        let mut a = HashMap::new();
+        let mut b = HashMap::new();
+        for (k, v) in [("zeta", 1), ("alpha", 2), ("mid", 3)] {
+            a.insert(k, v);
+        }
+        for (k, v) in [("mid", 3), ("zeta", 1), ("alpha", 2)] {
+            b.insert(k, v);
+        }
+        let (Ok(sa), Ok(sb)) = (to_canonical_string(&a), to_canonical_string(&b)) else {
+            panic!("serialization failed");
+        };
+        assert_eq!(sa, sb);
+        assert_eq!(sa, r#"{"alpha":2,"mid":3,"zeta":1}"#);
+    }
+
+    #[test]
+    fn nested_objects_inside_arrays_are_sorted() {
+        let value = json!({"b": [{"y": 1, "x": 2}], "a": null});
+        match to_canonical_string(&value) {
+            Ok(s) => assert_eq!(s, r#"{"a":null,"b":[{"x":2,"y":1}]}"#),
+            Err(e) => panic!("serialization failed: {e}"),
+        }
+    }
+}
@@ -0,0 +1,192 @@
+//! Kompatibilität mit Altformaten.
+//!
+//! Lesepfade akzeptieren weiterhin ältere Artefaktformen – die direkt
+//! serialisierte Bandit-Struktur statt des Contract-Snapshots, `why` als
+//! einzelner String, Entscheidungen ohne `chosen`. Statt solche Fälle nur zu
+//! loggen, halten sie eine [`Deprecation`] in einem [`CompatReport`] fest, der
+//! sich auswerten oder als JSON ablegen lässt. `heimlern migrate all` nutzt
+//! denselben Bericht, um ein Datenverzeichnis in einem Durchgang anzuheben.
+
+use crate::Decision;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// Snapshot in der direkt serialisierten Struct-Form statt als Contract-Snapshot.
+pub const LEGACY_SNAPSHOT: &str = "snapshot.legacy_struct";
+/// `why` als einzelner String statt als Liste.
+pub const WHY_STRING: &str = "decision.why_string";
+/// Entscheidung ohne `chosen`-Objekt.
+pub const MISSING_CHOSEN: &str = "decision.missing_chosen";
+
+/// Ein beim Lesen erkanntes Altformat.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Deprecation {
+    /// Stabiler Kennzeichner, z. B. [`WHY_STRING`].
+    pub code: String,
+    pub message: String,
+    /// Fundstelle (Datei, Zeile, …), falls bekannt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
+impl Deprecation {
+    pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            code: code.into(),
+            message: message.into(),
+            location: None,
+        }
+    }
+
+    /// Setzt die Fundstelle.
+    #[must_use]
+    pub fn at(mut self, location: impl Into<String>) -> Self {
+        self.location = Some(location.into());
+        self
+    }
+}
+
+/// Gesammelte Deprecations eines Lese- oder Migrationslaufs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompatReport {
+    pub deprecations: Vec<Deprecation>,
+}
+
+impl CompatReport {
+    pub fn record(&mut self, deprecation: Deprecation) {
+        self.deprecations.push(deprecation);
+    }
+
+    /// Übernimmt alle Einträge von `other`; Einträge ohne Fundstelle erhalten `location`.
+    pub fn absorb(&mut self, other: CompatReport, location: &str) {
+        self.deprecations
+            .extend(other.deprecations.into_iter().map(|d| match d.location {
+                Some(_) => d,
+                None => d.at(location),
+            }));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.deprecations.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.deprecations.len()
+    }
+
+    /// Anzahl der Einträge je Kennzeichner.
+    pub fn counts(&self) -> BTreeMap<&str, usize> {
+        let mut counts = BTreeMap::new();
+        for d in &self.deprecations {
+            *counts.entry(d.code.as_str()).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+/// Hebt eine Entscheidung (oder einen Entscheidungsdatensatz mit `decision`)
+/// auf das aktuelle Format: `why` wird zur Liste, fehlendes `chosen` ergänzt.
+///
+/// Gibt `true` zurück, wenn `record` verändert wurde.
+pub fn upgrade_decision(record: &mut Value, report: &mut CompatReport) -> bool {
+    let decision = if record.get("decision").is_some_and(Value::is_object) {
+        &mut record["decision"]
+    } else {
+        record
+    };
+    let Some(obj) = decision.as_object_mut() else {
+        return false;
+    };
+    // Nur echte Entscheidungen anfassen, keine Outcomes o. Ä. mit `action`.
+    if !(obj.contains_key("score") && obj.contains_key("why")) {
+        return false;
+    }
+    let Some(action) = obj
+        .get("action")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+    else {
+        return false;
+    };
+    let mut changed = false;
+    if let Some(Value::String(why)) = obj.get("why") {
+        let why = why.clone();
+        obj.insert("why".into(), json!([why]));
+        report.record(Deprecation::new(
+            WHY_STRING,
+            format!("decision '{action}': `why` is a string, expected a list"),
+        ));
+        changed = true;
+    }
+    if !obj.contains_key("chosen") {
+        obj.insert("chosen".into(), json!({ "action": action }));
+        report.record(Deprecation::new(
+            MISSING_CHOSEN,
+            format!("decision '{action}' has no `chosen` object"),
+        ));
+        changed = true;
+    }
+    changed
+}
+
+/// Liest eine [`Decision`] und hält dabei erkannte Altformate fest.
+///
+/// # Errors
+/// Schlägt fehl, wenn `value` auch nach dem Anheben keine gültige Entscheidung ist.
+pub fn decision_from_value(
+    mut value: Value,
+    report: &mut CompatReport,
+) -> serde_json::Result<Decision> {
+    upgrade_decision(&mut value, report);
+    serde_json::from_value(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_decision_is_upgraded_and_reported() {
+        let mut report = CompatReport::default();
+        let mut record = json!({
+            "ts": "2023-10-27T10:00:00Z",
+            "policy_id": "remind-bandit",
+            "decision": { "action": "send_reminder", "score": 0.8, "why": "high_priority" }
+        });
+        assert!(upgrade_decision(&mut record, &mut report));
+        assert_eq!(record["decision"]["why"], json!(["high_priority"]));
+        assert_eq!(record["decision"]["chosen"]["action"], "send_reminder");
+        assert_eq!(report.counts().get(WHY_STRING), Some(&1));
+        assert_eq!(report.counts().get(MISSING_CHOSEN), Some(&1));
+
+        // Ein zweiter Durchlauf ändert nichts mehr.
+        let mut again = CompatReport::default();
+        assert!(!upgrade_decision(&mut record, &mut again));
+        assert!(again.is_empty());
+
+        let mut located = CompatReport::default();
+        located.absorb(report, "decisions.jsonl:1");
+        assert!(located
+            .deprecations
+            .iter()
+            .all(|d| d.location.as_deref() == Some("decisions.jsonl:1")));
+    }
+
+    #[test]
+    fn decision_from_value_reports_string_why() {
+        let mut report = CompatReport::default();
+        let decision = match decision_from_value(
+            json!({ "action": "a", "score": 1.0, "why": "w" }),
+            &mut report,
+        ) {
+            Ok(decision) => decision,
+            Err(err) => panic!("decision should parse: {err}"),
+        };
+        assert_eq!(decision.why, vec!["w".to_string()]);
+        assert_eq!(report.len(), 2);
+
+        let mut outcome = json!({ "decision_id": "d", "action": "a", "outcome": "success" });
+        assert!(!upgrade_decision(&mut outcome, &mut report));
+    }
+}
@@ -0,0 +1,652 @@
+//! Aus den JSON-Schemas in `contracts/` erzeugte Serde-Typen.
+//!
+//! @generated by `heimlern contracts gen` – nicht von Hand bearbeiten.
+//! Nach Schemaänderungen neu erzeugen; ein Test in `heimlern-cli` schlägt
+//! fehl, solange diese Datei und die Schemas auseinanderlaufen.
+
+use serde::{Deserialize, Serialize};
+
+/// Aussensensor Event
+///
+/// Zentrales Contract-Schema für kuratierte Außensensor-Ereignisse (JSONL: 1 Objekt pro Zeile).
+///
+/// Erzeugt aus `contracts/aussen.event.schema.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AussenEvent {
+    /// Beliebige Merkmale für Scoring/Policies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub features: Option<serde_json::Map<String, serde_json::Value>>,
+    /// Stabile, optionale Event-ID (z. B. Hash über url+ts).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Transport-/Adapter-Metadaten (z. B. parser_version).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<serde_json::Map<String, serde_json::Value>>,
+    /// Kurzbezeichner der Quelle (z. B. rss:heise, hn, mastodon:@user).
+    pub source: String,
+    /// Kurze Zusammenfassung (optional gekürzt).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Zeitstempel ISO-8601 (UTC empfohlen).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ts: Option<String>,
+    /// Kategorie/Typ des Events (frei, aber konsistent halten).
+    pub r#type: String,
+    /// Primärlink zum Inhalt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl AussenEvent {
+    /// Schema-Datei relativ zu `contracts/`.
+    pub const SCHEMA_PATH: &str = "aussen.event.schema.json";
+    /// `$id` des Schemas.
+    pub const SCHEMA_ID: &str = "https://schemas.heimgewebe.org/contracts/aussen.event.schema.json";
+}
+
+/// Heimlern Learning Proposal Registration v1
+///
+/// Erzeugt aus `contracts/learning.proposal.registration.v1.schema.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LearningProposalRegistrationV1 {
+    pub boundary: LearningProposalRegistrationV1Boundary,
+    pub closure: LearningProposalRegistrationV1Closure,
+    pub consumer: LearningProposalRegistrationV1Consumer,
+    pub decision_target: LearningProposalRegistrationV1DecisionTarget,
+    pub expires_at: String,
+    pub proposal_id: String,
+    pub schema_version: String,
+    pub success_metric: LearningProposalRegistrationV1SuccessMetric,
+}
+
+impl LearningProposalRegistrationV1 {
+    /// Schema-Datei relativ zu `contracts/`.
+    pub const SCHEMA_PATH: &str = "learning.proposal.registration.v1.schema.json";
+    /// `$id` des Schemas.
+    pub const SCHEMA_ID: &str = "https://heimgewebe.local/heimlern/learning.proposal.registration.v1.schema.json";
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LearningProposalRegistrationV1Boundary {
+    pub no_auto_policy: bool,
+    pub no_auto_routing: bool,
+    pub no_queue_authority: bool,
+    pub no_runtime_authority: bool,
+    pub proposal_only: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LearningProposalRegistrationV1Closure {
+    pub allowed_outcomes: Vec<LearningProposalRegistrationV1ClosureAllowedOutcomesItem>,
+    pub archive_path: String,
+    pub review_at: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LearningProposalRegistrationV1ClosureAllowedOutcomesItem {
+    #[serde(rename = "promote")]
+    Promote,
+    #[serde(rename = "reject")]
+    Reject,
+    #[serde(rename = "archive")]
+    Archive,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LearningProposalRegistrationV1Consumer {
+    pub organ: String,
+    pub r#use: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LearningProposalRegistrationV1DecisionTarget {
+    pub owner: String,
+    pub question: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LearningProposalRegistrationV1SuccessMetric {
+    pub falsification: String,
+    pub measure: String,
+    pub name: String,
+    pub success: String,
+}
+
+/// Offline Learning Path
+///
+/// Erzeugt aus `contracts/learning_path.schema.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LearningPath {
+    pub artifact: String,
+    pub generated_by: String,
+    pub mode: String,
+    pub schema_version: u64,
+    pub steps: Vec<LearningPathStepsItem>,
+    pub writes_production: bool,
+}
+
+impl LearningPath {
+    /// Schema-Datei relativ zu `contracts/`.
+    pub const SCHEMA_PATH: &str = "learning_path.schema.json";
+    /// `$id` des Schemas.
+    pub const SCHEMA_ID: &str = "https://schemas.heimgewebe.org/contracts/heimlern.offline_learning_path.v1.schema.json";
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LearningPathStepsItem {
+    pub command: String,
+    pub id: String,
+    pub read_only: bool,
+    pub title: String,
+}
+
+/// WGX Metrics Snapshot
+///
+/// Erzeugt aus `contracts/metrics.snapshot.schema.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetricsSnapshot {
+    pub backup: MetricsSnapshotBackup,
+    pub drift: MetricsSnapshotDrift,
+    pub host: String,
+    pub ts: u64,
+    pub updates: MetricsSnapshotUpdates,
+}
+
+impl MetricsSnapshot {
+    /// Schema-Datei relativ zu `contracts/`.
+    pub const SCHEMA_PATH: &str = "metrics.snapshot.schema.json";
+    /// `$id` des Schemas.
+    pub const SCHEMA_ID: &str = "https://schemas.heimgewebe.org/contracts/metrics.snapshot.schema.json";
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetricsSnapshotBackup {
+    pub age_days: u64,
+    pub last_ok: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetricsSnapshotDrift {
+    pub templates: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetricsSnapshotUpdates {
+    pub flatpak: u64,
+    pub os: u64,
+    pub pkg: u64,
+}
+
+/// Chronik Operator Routing Outcome Export v1
+///
+/// A digest-bound Chronik transport envelope around a Heimlern-owned operator.routing_outcome.v1 payload.
+///
+/// Erzeugt aus `contracts/mirrors/chronik/operator-routing-outcome-export-v1.schema.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OperatorRoutingOutcomeExportV1 {
+    pub boundary: OperatorRoutingOutcomeExportV1Boundary,
+    pub event_id: String,
+    pub evidence_refs: Vec<OperatorRoutingOutcomeExportV1EvidenceRefsItem>,
+    pub freshness: OperatorRoutingOutcomeExportV1Freshness,
+    pub kind: String,
+    pub non_claims: Vec<OperatorRoutingOutcomeExportV1NonClaimsItem>,
+    pub payload: serde_json::Map<String, serde_json::Value>,
+    pub payload_contract: OperatorRoutingOutcomeExportV1PayloadContract,
+    pub payload_sha256: serde_json::Value,
+    pub schema_version: String,
+    pub source: OperatorRoutingOutcomeExportV1Source,
+    pub ts: serde_json::Value,
+}
+
+impl OperatorRoutingOutcomeExportV1 {
+    /// Schema-Datei relativ zu `contracts/`.
+    pub const SCHEMA_PATH: &str = "mirrors/chronik/operator-routing-outcome-export-v1.schema.json";
+    /// `$id` des Schemas.
+    pub const SCHEMA_ID: &str = "https://heimgewebe.local/chronik/operator-routing-outcome-export-v1.schema.json";
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OperatorRoutingOutcomeExportV1Boundary {
+    pub auto_apply_permitted: bool,
+    pub payload_authority: String,
+    pub raw_logs_included: bool,
+    pub routing_authority: String,
+    pub secrets_included: bool,
+    pub transport_authority: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OperatorRoutingOutcomeExportV1EvidenceRefsItem {
+    pub kind: String,
+    pub r#ref: String,
+    pub sha256: serde_json::Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OperatorRoutingOutcomeExportV1Freshness {
+    pub consumer_must_recompute: bool,
+    pub exported_at: serde_json::Value,
+    pub observed_at: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OperatorRoutingOutcomeExportV1NonClaimsItem {
+    #[serde(rename = "routing_policy_superiority")]
+    RoutingPolicySuperiority,
+    #[serde(rename = "production_sample_sufficiency")]
+    ProductionSampleSufficiency,
+    #[serde(rename = "automatic_application_permission")]
+    AutomaticApplicationPermission,
+    #[serde(rename = "chronik_payload_contract_ownership")]
+    ChronikPayloadContractOwnership,
+    #[serde(rename = "runtime_readiness")]
+    RuntimeReadiness,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OperatorRoutingOutcomeExportV1PayloadContract {
+    pub owner: String,
+    pub sha256: serde_json::Value,
+    pub source_path: String,
+    pub source_repository: String,
+    pub source_revision: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OperatorRoutingOutcomeExportV1Source {
+    pub component: String,
+    pub repo: String,
+    pub revision: String,
+    pub run_id: String,
+}
+
+/// heimlern policy weight adjustment proposal v1
+///
+/// Erzeugt aus `contracts/mirrors/metarepo/policy.weight_adjustment.v1.schema.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyWeightAdjustmentV1 {
+    /// ID/Hash der Policy, auf der diese Anpassung basiert.
+    pub basis_policy: String,
+    /// Confidence score for this adjustment.
+    pub confidence: f64,
+    /// Number of decisions that contributed to this adjustment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decisions_analyzed: Option<u64>,
+    /// Map von Policy-ID zu Delta-Objekt.
+    pub deltas: std::collections::BTreeMap<String, serde_json::Value>,
+    /// Evidence or simulation results backing this adjustment.
+    pub evidence: PolicyWeightAdjustmentV1Evidence,
+    /// Explanation for the adjustment. Required if status is not 'proposed'.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<String>,
+    /// Status of this adjustment proposal.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<PolicyWeightAdjustmentV1Status>,
+    pub ts: String,
+    pub version: String,
+}
+
+impl PolicyWeightAdjustmentV1 {
+    /// Schema-Datei relativ zu `contracts/`.
+    pub const SCHEMA_PATH: &str = "mirrors/metarepo/policy.weight_adjustment.v1.schema.json";
+    /// `$id` des Schemas.
+    pub const SCHEMA_ID: &str = "https://schemas.heimgewebe.org/contracts/policy.weight_adjustment.v1.schema.json";
+}
+
+/// Evidence or simulation results backing this adjustment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyWeightAdjustmentV1Evidence {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decisions_analyzed: Option<u64>,
+    /// Projected failure rate after applying the adjustment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_rate_after_sim: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_rate_before: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub patterns: Option<Vec<String>>,
+    /// The method used for simulation. Required if failure_rate_after_sim is present.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub simulation_method: Option<String>,
+}
+
+/// Status of this adjustment proposal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PolicyWeightAdjustmentV1Status {
+    #[serde(rename = "proposed")]
+    Proposed,
+    #[serde(rename = "accepted")]
+    Accepted,
+    #[serde(rename = "rejected")]
+    Rejected,
+    #[serde(rename = "superseded")]
+    Superseded,
+}
+
+/// Operator Routing Decision v1
+///
+/// Erzeugt aus `contracts/operator.routing_decision.v1.schema.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OperatorRoutingDecisionV1 {
+    pub candidate_routes: Vec<String>,
+    pub chosen_route: String,
+    pub context: OperatorRoutingDecisionV1Context,
+    pub decision_id: String,
+    pub does_not_establish: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evidence_refs: Option<Vec<OperatorRoutingDecisionV1EvidenceRefsItem>>,
+    pub policy_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selection_reason: Option<String>,
+    pub task_class: String,
+    pub ts: String,
+    pub version: String,
+}
+
+impl OperatorRoutingDecisionV1 {
+    /// Schema-Datei relativ zu `contracts/`.
+    pub const SCHEMA_PATH: &str = "operator.routing_decision.v1.schema.json";
+    /// `$id` des Schemas.
+    pub const SCHEMA_ID: &str = "https://heimgewebe.local/heimlern/operator.routing_decision.v1.schema.json";
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OperatorRoutingDecisionV1Context {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constraints: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_ball: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    pub risk_class: OperatorRoutingDecisionV1ContextRiskClass,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signals: Option<std::collections::BTreeMap<String, serde_json::Value>>,
+    pub surface: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OperatorRoutingDecisionV1ContextRiskClass {
+    #[serde(rename = "low")]
+    Low,
+    #[serde(rename = "medium")]
+    Medium,
+    #[serde(rename = "high")]
+    High,
+    #[serde(rename = "unknown")]
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OperatorRoutingDecisionV1EvidenceRefsItem {
+    pub kind: String,
+    pub r#ref: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+}
+
+/// Operator Routing Outcome v1
+///
+/// Erzeugt aus `contracts/operator.routing_outcome.v1.schema.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OperatorRoutingOutcomeV1 {
+    pub decision_id: String,
+    pub does_not_establish: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evidence_refs: Option<Vec<OperatorRoutingOutcomeV1EvidenceRefsItem>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub friction: Option<Vec<OperatorRoutingOutcomeV1FrictionItem>>,
+    pub metrics: OperatorRoutingOutcomeV1Metrics,
+    pub outcome: OperatorRoutingOutcomeV1Outcome,
+    pub resolved: bool,
+    pub reward: f64,
+    pub route_used: String,
+    pub task_class: String,
+    pub ts: String,
+    pub version: String,
+}
+
+impl OperatorRoutingOutcomeV1 {
+    /// Schema-Datei relativ zu `contracts/`.
+    pub const SCHEMA_PATH: &str = "operator.routing_outcome.v1.schema.json";
+    /// `$id` des Schemas.
+    pub const SCHEMA_ID: &str = "https://heimgewebe.local/heimlern/operator.routing_outcome.v1.schema.json";
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OperatorRoutingOutcomeV1EvidenceRefsItem {
+    pub kind: String,
+    pub r#ref: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OperatorRoutingOutcomeV1FrictionItem {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<String>,
+    pub kind: OperatorRoutingOutcomeV1FrictionItemKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operation: Option<String>,
+    pub resolved: bool,
+    pub surface: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OperatorRoutingOutcomeV1FrictionItemKind {
+    #[serde(rename = "ci_contract")]
+    CiContract,
+    #[serde(rename = "connector_snapshot")]
+    ConnectorSnapshot,
+    #[serde(rename = "execution_context")]
+    ExecutionContext,
+    #[serde(rename = "fail_closed_gate")]
+    FailClosedGate,
+    #[serde(rename = "network")]
+    Network,
+    #[serde(rename = "operator_bug")]
+    OperatorBug,
+    #[serde(rename = "platform_filter")]
+    PlatformFilter,
+    #[serde(rename = "unknown")]
+    Unknown,
+    #[serde(rename = "user_input")]
+    UserInput,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OperatorRoutingOutcomeV1Metrics {
+    pub blocked_by_platform_filter: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ci_state: Option<OperatorRoutingOutcomeV1MetricsCiState>,
+    pub completion_state: OperatorRoutingOutcomeV1MetricsCompletionState,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub elapsed_seconds: Option<u64>,
+    pub friction_count: u64,
+    pub manual_operator_needed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pr_state: Option<OperatorRoutingOutcomeV1MetricsPrState>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rework_count: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OperatorRoutingOutcomeV1MetricsCiState {
+    #[serde(rename = "pass")]
+    Pass,
+    #[serde(rename = "fail")]
+    Fail,
+    #[serde(rename = "pending")]
+    Pending,
+    #[serde(rename = "not_applicable")]
+    NotApplicable,
+    #[serde(rename = "unknown")]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OperatorRoutingOutcomeV1MetricsCompletionState {
+    #[serde(rename = "completed")]
+    Completed,
+    #[serde(rename = "blocked")]
+    Blocked,
+    #[serde(rename = "deferred")]
+    Deferred,
+    #[serde(rename = "failed")]
+    Failed,
+    #[serde(rename = "unknown")]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OperatorRoutingOutcomeV1MetricsPrState {
+    #[serde(rename = "merged")]
+    Merged,
+    #[serde(rename = "open")]
+    Open,
+    #[serde(rename = "closed")]
+    Closed,
+    #[serde(rename = "not_applicable")]
+    NotApplicable,
+    #[serde(rename = "unknown")]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OperatorRoutingOutcomeV1Outcome {
+    #[serde(rename = "success")]
+    Success,
+    #[serde(rename = "failure")]
+    Failure,
+    #[serde(rename = "partial")]
+    Partial,
+    #[serde(rename = "unknown")]
+    Unknown,
+}
+
+/// heimlern decision record
+///
+/// Erzeugt aus `contracts/policy.decision.schema.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyDecision {
+    /// The context provided to the policy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<serde_json::Map<String, serde_json::Value>>,
+    pub decision: PolicyDecisionDecision,
+    /// Name or type of the policy (e.g., 'heimlern-bandits')
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<String>,
+    /// Unique identifier of the policy instance (e.g., 'remind-bandit')
+    pub policy_id: String,
+    /// ISO-8601 timestamp of the decision
+    pub ts: String,
+}
+
+impl PolicyDecision {
+    /// Schema-Datei relativ zu `contracts/`.
+    pub const SCHEMA_PATH: &str = "policy.decision.schema.json";
+    /// `$id` des Schemas.
+    pub const SCHEMA_ID: &str = "https://schemas.heimgewebe.org/contracts/policy.decision.schema.json";
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyDecisionDecision {
+    pub action: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chosen: Option<PolicyDecisionDecisionChosen>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<serde_json::Map<String, serde_json::Value>>,
+    pub score: f64,
+    pub why: serde_json::Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyDecisionDecisionChosen {
+    pub action: String,
+    /// Weitere, vom Schema nicht benannte Felder.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Policy Feedback
+///
+/// Feedback zu einer dokumentierten Policy-Entscheidung. Heimlern konsumiert dieses Feedback als Evidenz; es ändert keine Live-Policy automatisch.
+///
+/// Erzeugt aus `contracts/policy.feedback.schema.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyFeedback {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    pub decision_id: String,
+    pub feedback_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<std::collections::BTreeMap<String, serde_json::Value>>,
+    pub reward: f64,
+    pub source: String,
+    pub ts: String,
+}
+
+impl PolicyFeedback {
+    /// Schema-Datei relativ zu `contracts/`.
+    pub const SCHEMA_PATH: &str = "policy.feedback.schema.json";
+    /// `$id` des Schemas.
+    pub const SCHEMA_ID: &str = "https://schemas.heimgewebe.org/contracts/policy.feedback.schema.json";
+}
+
+/// Policy Snapshot
+///
+/// Erzeugt aus `contracts/policy.snapshot.schema.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicySnapshot {
+    pub arms: Vec<String>,
+    pub counts: Vec<u64>,
+    pub epsilon: f64,
+    pub policy_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<i64>,
+    pub ts: String,
+    pub values: Vec<f64>,
+    pub version: String,
+}
+
+impl PolicySnapshot {
+    /// Schema-Datei relativ zu `contracts/`.
+    pub const SCHEMA_PATH: &str = "policy.snapshot.schema.json";
+    /// `$id` des Schemas.
+    pub const SCHEMA_ID: &str = "https://schemas.heimgewebe.org/contracts/policy.snapshot.schema.json";
+}
@@ -0,0 +1,311 @@
+//! Contracts: die aus den JSON-Schemas erzeugten Serde-Typen und die
+//! Laufzeitprüfung von Payloads gegen diese Schemas.
+//!
+//! Die Typen erzeugt `heimlern contracts gen` aus `contracts/` (Untermodul
+//! `generated`, hier re-exportiert). Sie decken nur ab, was sich als Rust-Typ
+//! ausdrücken lässt; [`validate`] und die `validate_*`-Funktionen prüfen gegen
+//! das vollständige Schema (Draft 2020-12) samt Mustern, Grenzen und
+//! `if`/`then`. Für Snapshots kommt wie in `scripts/validate_json.py` die
+//! Prüfung hinzu, dass `counts` und `values` so lang wie `arms` sind.
+//!
+//! Die geprüften Schemas liegen als Kopien in `schemas/` dieses Crates und
+//! werden beim Bauen eingebettet, damit das Crate auch ohne das umgebende
+//! Repository baut (`cargo package`). Ein Test hält die Kopien byteweise gleich
+//! mit `contracts/`. Kompiliert wird jedes Schema beim ersten Gebrauch.
+
+#[rustfmt::skip]
+mod generated;
+
+pub use generated::*;
+
+use crate::HeimlernError;
+use jsonschema::Validator;
+use serde_json::Value;
+use std::fmt;
+use std::sync::OnceLock;
+
+/// Eingebettete Contract-Schemas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Schema {
+    PolicySnapshot,
+    /// Snapshot-Version `0.2.x` mit den Erweiterungen der Policies.
+    PolicySnapshotV2,
+    PolicyDecision,
+    PolicyFeedback,
+    AussenEvent,
+    /// Gespiegelt aus dem Metarepo (`contracts/mirrors/metarepo/`).
+    WeightAdjustment,
+}
+
+impl Schema {
+    pub const ALL: [Self; 6] = [
+        Self::PolicySnapshot,
+        Self::PolicySnapshotV2,
+        Self::PolicyDecision,
+        Self::PolicyFeedback,
+        Self::AussenEvent,
+        Self::WeightAdjustment,
+    ];
+
+    /// Pfad der Schema-Datei relativ zu `schemas/` dieses Crates und – außer
+    /// beim aktiven v2-Schema der Snapshots – relativ zu `contracts/`.
+    #[must_use]
+    pub const fn path(self) -> &'static str {
+        match self {
+            Self::PolicySnapshot => "policy.snapshot.schema.json",
+            Self::PolicySnapshotV2 => "policy.snapshot.v2.schema.json",
+            Self::PolicyDecision => "policy.decision.schema.json",
+            Self::PolicyFeedback => "policy.feedback.schema.json",
+            Self::AussenEvent => "aussen.event.schema.json",
+            Self::WeightAdjustment => "mirrors/metarepo/policy.weight_adjustment.v1.schema.json",
+        }
+    }
+
+    /// Findet ein Schema über seinen Pfad oder Dateinamen.
+    #[must_use]
+    pub fn from_path(path: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|s| s.path() == path || s.path().rsplit('/').next() == Some(path))
+    }
+
+    const fn source(self) -> &'static str {
+        match self {
+            Self::PolicySnapshot => {
+                include_str!("../../schemas/policy.snapshot.schema.json")
+            }
+            Self::PolicySnapshotV2 => {
+                include_str!("../../schemas/policy.snapshot.v2.schema.json")
+            }
+            Self::PolicyDecision => {
+                include_str!("../../schemas/policy.decision.schema.json")
+            }
+            Self::PolicyFeedback => {
+                include_str!("../../schemas/policy.feedback.schema.json")
+            }
+            Self::AussenEvent => include_str!("../../schemas/aussen.event.schema.json"),
+            Self::WeightAdjustment => include_str!(
+                "../../schemas/mirrors/metarepo/policy.weight_adjustment.v1.schema.json"
+            ),
+        }
+    }
+
+    const fn index(self) -> usize {
+        match self {
+            Self::PolicySnapshot => 0,
+            Self::PolicySnapshotV2 => 1,
+            Self::PolicyDecision => 2,
+            Self::PolicyFeedback => 3,
+            Self::AussenEvent => 4,
+            Self::WeightAdjustment => 5,
+        }
+    }
+
+    fn validator(self) -> Result<&'static Validator, &'static str> {
+        static VALIDATORS: [OnceLock<Result<Validator, String>>; 6] = [
+            OnceLock::new(),
+            OnceLock::new(),
+            OnceLock::new(),
+            OnceLock::new(),
+            OnceLock::new(),
+            OnceLock::new(),
+        ];
+        VALIDATORS[self.index()]
+            .get_or_init(|| {
+                let schema: Value =
+                    serde_json::from_str(self.source()).map_err(|e| e.to_string())?;
+                jsonschema::draft202012::new(&schema).map_err(|e| e.to_string())
+            })
+            .as_ref()
+            .map_err(String::as_str)
+    }
+}
+
+impl fmt::Display for Schema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.path())
+    }
+}
+
+/// Eine Abweichung vom Schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// JSON-Pointer auf die betroffene Stelle (leer = Wurzel).
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "/"
+        } else {
+            &self.path
+        };
+        write!(f, "{path}: {}", self.message)
+    }
+}
+
+/// Alle Abweichungen von `value` gegenüber `schema`; leer, wenn gültig.
+#[must_use]
+pub fn violations(schema: Schema, value: &Value) -> Vec<Violation> {
+    let validator = match schema.validator() {
+        Ok(validator) => validator,
+        Err(e) => {
+            return vec![Violation {
+                path: String::new(),
+                message: format!("embedded schema does not compile: {e}"),
+            }]
+        }
+    };
+    let mut found: Vec<Violation> = validator
+        .iter_errors(value)
+        .map(|e| Violation {
+            path: e.instance_path.to_string(),
+            message: e.to_string(),
+        })
+        .collect();
+    if matches!(schema, Schema::PolicySnapshot | Schema::PolicySnapshotV2) {
+        found.extend(snapshot_lengths(value));
+    }
+    found
+}
+
+/// Prüft `value` gegen `schema`.
+///
+/// # Errors
+/// Contract-Fehler mit allen Abweichungen, wenn `value` nicht passt.
+pub fn validate(schema: Schema, value: &Value) -> Result<(), HeimlernError> {
+    let found = violations(schema, value);
+    if found.is_empty() {
+        return Ok(());
+    }
+    let details: Vec<String> = found.iter().map(ToString::to_string).collect();
+    Err(HeimlernError::contract(format!(
+        "payload does not match {schema}: {}",
+        details.join("; ")
+    )))
+}
+
+/// Prüft einen Snapshot gegen das Schema seiner Version: `0.2.x` gegen
+/// [`Schema::PolicySnapshotV2`], alles andere gegen [`Schema::PolicySnapshot`].
+///
+/// # Errors
+/// Siehe [`validate`].
+pub fn validate_snapshot(value: &Value) -> Result<(), HeimlernError> {
+    validate(snapshot_schema(value), value)
+}
+
+/// Schema, das zur `version` eines Snapshots passt.
+#[must_use]
+pub fn snapshot_schema(value: &Value) -> Schema {
+    let v2 = value
+        .get("version")
+        .and_then(Value::as_str)
+        .is_some_and(|v| v.starts_with("0.2."));
+    if v2 {
+        Schema::PolicySnapshotV2
+    } else {
+        Schema::PolicySnapshot
+    }
+}
+
+/// # Errors
+/// Siehe [`validate`].
+pub fn validate_decision(value: &Value) -> Result<(), HeimlernError> {
+    validate(Schema::PolicyDecision, value)
+}
+
+/// # Errors
+/// Siehe [`validate`].
+pub fn validate_feedback(value: &Value) -> Result<(), HeimlernError> {
+    validate(Schema::PolicyFeedback, value)
+}
+
+/// # Errors
+/// Siehe [`validate`].
+pub fn validate_event(value: &Value) -> Result<(), HeimlernError> {
+    validate(Schema::AussenEvent, value)
+}
+
+/// # Errors
+/// Siehe [`validate`].
+pub fn validate_weight_adjustment(value: &Value) -> Result<(), HeimlernError> {
+    validate(Schema::WeightAdjustment, value)
+}
+
+fn snapshot_lengths(value: &Value) -> Vec<Violation> {
+    let Some(arms) = value.get("arms").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    ["counts", "values"]
+        .into_iter()
+        .filter_map(|key| {
+            let len = value.get(key)?.as_array()?.len();
+            (len != arms.len()).then(|| Violation {
+                path: format!("/{key}"),
+                message: format!("{key} has {len} entries but arms has {}", arms.len()),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample(path: &str) -> Value {
+        let raw = std::fs::read_to_string(format!("../../{path}")).unwrap_or_default();
+        serde_json::from_str(&raw).unwrap_or(Value::Null)
+    }
+
+    #[test]
+    fn embedded_schemas_compile_and_accept_samples() {
+        for schema in Schema::ALL {
+            assert!(schema.validator().is_ok(), "{schema}");
+            assert_eq!(Schema::from_path(schema.path()), Some(schema));
+        }
+        assert!(validate_snapshot(&sample("data/samples/policy.snapshot.sample.json")).is_ok());
+        assert!(validate_feedback(&sample("data/samples/policy.feedback.sample.json")).is_ok());
+        assert!(validate_feedback(&sample("data/samples/policy.feedback.rejected.json")).is_err());
+        assert_eq!(
+            Schema::from_path("policy.weight_adjustment.v1.schema.json"),
+            Some(Schema::WeightAdjustment)
+        );
+    }
+
+    #[test]
+    fn embedded_copies_match_the_contracts() {
+        for schema in Schema::ALL {
+            if schema == Schema::PolicySnapshotV2 {
+                continue;
+            }
+            let archived = format!("../../contracts/{}", schema.path());
+            // Außerhalb des Repositorys (z. B. im gepackten Crate) fehlt das Archiv.
+            let Ok(raw) = std::fs::read_to_string(&archived) else {
+                continue;
+            };
+            assert_eq!(
+                schema.source(),
+                raw,
+                "schemas/{schema} differs from {archived}"
+            );
+        }
+    }
+
+    #[test]
+    fn violations_point_at_the_offending_field() {
+        let mut snapshot = sample("data/samples/policy.snapshot.sample.json");
+        snapshot["counts"] = json!([1]);
+        let found = violations(Schema::PolicySnapshot, &snapshot);
+        assert!(found.iter().any(|v| v.path == "/counts"), "{found:?}");
+
+        let event = json!({ "type": "sensor.reading" });
+        let Err(err) = validate_event(&event) else {
+            panic!("event without source must be rejected");
+        };
+        assert_eq!(err.kind(), crate::ErrorKind::Contract);
+        assert!(err.message().contains("aussen.event.schema.json"));
+    }
+}
@@ -0,0 +1,246 @@
+//! Erkennung doppelt gelieferter [`AussenEvent`]s.
+//!
+//! Chronik liefert nach einem Abbruch denselben Batch unter Umständen erneut
+//! aus. [`DedupFilter`] merkt sich die `id`s zuletzt gesehener Events –
+//! begrenzt auf `capacity` Einträge (das am längsten nicht gesehene fällt
+//! zuerst heraus) und auf `ttl` Sekunden. Events ohne `id` lassen sich nicht
+//! wiedererkennen und gelten immer als neu.
+//!
+//! Der Filter ist serialisierbar, damit er zusammen mit dem übrigen
+//! Ingest-Zustand einen Neustart übersteht. Zeitpunkte sind Unix-Sekunden;
+//! die `*_at`-Varianten nehmen sie explizit entgegen.
+
+use crate::event::AussenEvent;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Voreinstellung für [`DedupFilter::default`].
+pub const DEFAULT_DEDUP_CAPACITY: usize = 10_000;
+/// Voreinstellung für [`DedupFilter::default`]: eine Woche.
+pub const DEFAULT_DEDUP_TTL_SECONDS: u64 = 7 * 24 * 60 * 60;
+
+/// Begrenzter LRU-Speicher gesehener Event-`id`s mit Ablaufzeit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "DedupState", into = "DedupState")]
+pub struct DedupFilter {
+    capacity: usize,
+    ttl_seconds: u64,
+    /// `id` → (zuletzt gesehen, Reihenfolgenummer).
+    seen: HashMap<String, (i64, u64)>,
+    /// Reihenfolge der letzten Sichtung; der erste Eintrag fällt zuerst heraus.
+    order: BTreeMap<(i64, u64), String>,
+    next_seq: u64,
+}
+
+impl Default for DedupFilter {
+    fn default() -> Self {
+        Self::new(DEFAULT_DEDUP_CAPACITY, DEFAULT_DEDUP_TTL_SECONDS)
+    }
+}
+
+impl DedupFilter {
+    /// Filter für höchstens `capacity` `id`s, die nach `ttl_seconds` vergessen
+    /// werden. `capacity == 0` lässt alle Events durch.
+    #[must_use]
+    pub fn new(capacity: usize, ttl_seconds: u64) -> Self {
+        Self {
+            capacity,
+            ttl_seconds,
+            seen: HashMap::new(),
+            order: BTreeMap::new(),
+            next_seq: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    #[must_use]
+    pub fn ttl_seconds(&self) -> u64 {
+        self.ttl_seconds
+    }
+
+    /// Anzahl der gemerkten `id`s (einschließlich noch nicht entfernter
+    /// abgelaufener).
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+
+    /// `true`, wenn `event` neu ist; merkt sich dessen `id`.
+    pub fn admit(&mut self, event: &AussenEvent) -> bool {
+        self.admit_at(event, unix_now())
+    }
+
+    /// Wie [`DedupFilter::admit`] zum Zeitpunkt `now`.
+    pub fn admit_at(&mut self, event: &AussenEvent, now: i64) -> bool {
+        match event.id.as_deref() {
+            Some(id) if !id.is_empty() => self.admit_id_at(id, now),
+            _ => true,
+        }
+    }
+
+    /// `true`, wenn `id` innerhalb der Ablaufzeit noch nicht gesehen wurde.
+    /// Eine wiederholte Sichtung frischt den Eintrag auf.
+    pub fn admit_id_at(&mut self, id: &str, now: i64) -> bool {
+        if self.capacity == 0 {
+            return true;
+        }
+        self.expire(now);
+        let fresh = match self.seen.remove(id) {
+            Some(key) => {
+                self.order.remove(&key);
+                false
+            }
+            None => true,
+        };
+        let key = (now, self.next_seq);
+        self.next_seq += 1;
+        self.seen.insert(id.to_string(), key);
+        self.order.insert(key, id.to_string());
+        while self.seen.len() > self.capacity {
+            self.evict_oldest();
+        }
+        fresh
+    }
+
+    /// Ob `id` zum Zeitpunkt `now` als Duplikat gelten würde.
+    #[must_use]
+    pub fn contains_at(&self, id: &str, now: i64) -> bool {
+        self.seen
+            .get(id)
+            .is_some_and(|(seen_at, _)| !self.expired(*seen_at, now))
+    }
+
+    /// Entfernt alle zum Zeitpunkt `now` abgelaufenen Einträge.
+    pub fn expire(&mut self, now: i64) {
+        while let Some((&(seen_at, _), _)) = self.order.first_key_value() {
+            if !self.expired(seen_at, now) {
+                break;
+            }
+            self.evict_oldest();
+        }
+    }
+
+    fn expired(&self, seen_at: i64, now: i64) -> bool {
+        let ttl = i64::try_from(self.ttl_seconds).unwrap_or(i64::MAX);
+        now.saturating_sub(seen_at) >= ttl
+    }
+
+    fn evict_oldest(&mut self) {
+        if let Some((_, id)) = self.order.pop_first() {
+            self.seen.remove(&id);
+        }
+    }
+}
+
+/// Persistierte Form: Einträge in Reihenfolge der letzten Sichtung.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DedupState {
+    capacity: usize,
+    ttl_seconds: u64,
+    #[serde(default)]
+    seen: Vec<SeenId>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SeenId {
+    id: String,
+    seen_at: i64,
+}
+
+impl From<DedupFilter> for DedupState {
+    fn from(filter: DedupFilter) -> Self {
+        let seen = filter
+            .order
+            .into_iter()
+            .map(|((seen_at, _), id)| SeenId { id, seen_at })
+            .collect();
+        Self {
+            capacity: filter.capacity,
+            ttl_seconds: filter.ttl_seconds,
+            seen,
+        }
+    }
+}
+
+impl From<DedupState> for DedupFilter {
+    fn from(state: DedupState) -> Self {
+        let mut filter = Self::new(state.capacity, state.ttl_seconds);
+        for entry in state.seen {
+            filter.admit_id_at(&entry.id, entry.seen_at);
+        }
+        filter
+    }
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| i64::try_from(d.as_secs()).unwrap_or(i64::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(id: Option<&str>) -> AussenEvent {
+        AussenEvent {
+            id: id.map(Into::into),
+            r#type: "sensor.reading".into(),
+            source: "chronik".into(),
+            title: None,
+            summary: None,
+            url: None,
+            tags: None,
+            ts: None,
+            features: None,
+            meta: None,
+        }
+    }
+
+    #[test]
+    fn drops_repeats_until_evicted_or_expired() {
+        let mut filter = DedupFilter::new(2, 60);
+        assert!(filter.admit_at(&event(Some("a")), 0));
+        assert!(filter.admit_at(&event(Some("b")), 5));
+        assert!(!filter.admit_at(&event(Some("a")), 10));
+        assert!(filter.admit_at(&event(None), 10));
+        assert!(filter.admit_at(&event(None), 10));
+
+        // "a" wurde bei 10 aufgefrischt, also fällt "b" vor "a" heraus.
+        assert!(filter.admit_at(&event(Some("c")), 20));
+        assert!(filter.contains_at("a", 20));
+        assert!(!filter.contains_at("b", 20));
+
+        // "a" zuletzt bei 10 gesehen, läuft bei 70 ab.
+        assert!(filter.contains_at("a", 69));
+        assert!(filter.admit_at(&event(Some("a")), 70));
+        assert!(DedupFilter::new(0, 60).admit_at(&event(Some("a")), 0));
+    }
+
+    #[test]
+    fn survives_a_serialization_roundtrip() -> Result<(), serde_json::Error> {
+        let mut filter = DedupFilter::new(3, 3600);
+        for (id, at) in [("x", 1), ("y", 2), ("x", 3)] {
+            filter.admit_id_at(id, at);
+        }
+        let value = serde_json::to_value(&filter)?;
+        assert_eq!(value["seen"][0]["id"], "y");
+        assert_eq!(value["seen"][1]["id"], "x");
+
+        let mut restored: DedupFilter = serde_json::from_value(value)?;
+        assert_eq!(restored.len(), 2);
+        assert!(!restored.admit_id_at("x", 4));
+        assert!(restored.admit_id_at("z", 4));
+        Ok(())
+    }
+}
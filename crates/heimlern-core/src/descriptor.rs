@@ -0,0 +1,116 @@
+//! Selbstbeschreibung von Policies.
+//!
+//! Ein [`PolicyDescriptor`] sagt, welche Kontext-Arten eine Policy versteht,
+//! welche Parameter in welchen Grenzen justierbar sind, welche Features sie im
+//! Kontext erwartet und welche Snapshot-Version sie schreibt. Analysewerkzeuge
+//! prüfen damit vorab, ob ein vorgeschlagener Delta-Schlüssel auf der
+//! Zielpolicy überhaupt existiert.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Zulässiger Wertebereich eines justierbaren Parameters (inklusive Grenzen).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ParamRange {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl ParamRange {
+    #[must_use]
+    pub fn contains(&self, value: f64) -> bool {
+        value.is_finite() && self.min <= value && value <= self.max
+    }
+}
+
+/// Fähigkeiten einer Policy.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PolicyDescriptor {
+    /// Kennung, wie sie auch im Snapshot steht (z. B. `"remind-bandit"`).
+    pub policy_id: String,
+    /// Unterstützte `Context::kind`-Werte; leer bedeutet „alle“.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context_kinds: Vec<String>,
+    /// Justierbare Parameter mit ihrem zulässigen Bereich.
+    #[serde(default)]
+    pub tunables: BTreeMap<String, ParamRange>,
+    /// Schlüssel in `Context::features`, ohne die die Policy nicht sinnvoll entscheidet.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_features: Vec<String>,
+    /// Version des Snapshot-Formats.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot_version: Option<String>,
+}
+
+impl PolicyDescriptor {
+    #[must_use]
+    pub fn new(policy_id: impl Into<String>) -> Self {
+        Self {
+            policy_id: policy_id.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Fügt eine unterstützte Kontext-Art hinzu.
+    #[must_use]
+    pub fn kind(mut self, kind: impl Into<String>) -> Self {
+        self.context_kinds.push(kind.into());
+        self
+    }
+
+    /// Deklariert einen justierbaren Parameter.
+    #[must_use]
+    pub fn tunable(mut self, key: impl Into<String>, min: f64, max: f64) -> Self {
+        self.tunables.insert(key.into(), ParamRange { min, max });
+        self
+    }
+
+    /// Deklariert ein benötigtes Kontext-Feature.
+    #[must_use]
+    pub fn requires(mut self, feature: impl Into<String>) -> Self {
+        self.required_features.push(feature.into());
+        self
+    }
+
+    /// Setzt die Snapshot-Version.
+    #[must_use]
+    pub fn snapshot_version(mut self, version: impl Into<String>) -> Self {
+        self.snapshot_version = Some(version.into());
+        self
+    }
+
+    /// Wertebereich des Parameters `key`, falls er justierbar ist.
+    #[must_use]
+    pub fn range(&self, key: &str) -> Option<&ParamRange> {
+        self.tunables.get(key)
+    }
+
+    /// Ob die Policy Kontexte der Art `kind` versteht.
+    #[must_use]
+    pub fn supports_kind(&self, kind: &str) -> bool {
+        self.context_kinds.is_empty() || self.context_kinds.iter().any(|k| k == kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_and_lookups() {
+        let d = PolicyDescriptor::new("remind-bandit")
+            .kind("reminder")
+            .tunable("epsilon", 0.0, 1.0)
+            .snapshot_version("0.1.0");
+        assert!(d.supports_kind("reminder"));
+        assert!(!d.supports_kind("routine"));
+        assert!(PolicyDescriptor::new("any").supports_kind("routine"));
+        let Some(range) = d.range("epsilon") else {
+            panic!("epsilon must be tunable");
+        };
+        assert!(range.contains(0.3));
+        assert!(!range.contains(1.5));
+        assert!(!range.contains(f64::NAN));
+        assert!(d.range("epsi1on").is_none());
+    }
+}
@@ -0,0 +1,342 @@
+//! Anreicherung von Kontexten vor der Entscheidung.
+//!
+//! Querschnittsmerkmale wie Tageszeit, Wochentag oder Anwesenheit im Haushalt
+//! braucht fast jede Policy, aber keine sollte sie selbst beschaffen. Ein
+//! [`ContextEnricher`] ergänzt sie im [`Context`]; [`EnrichedPolicy`] schaltet
+//! beliebig viele davon vor eine Policy – für `decide` wie für `feedback`,
+//! damit die Policy beim Lernen dieselben Merkmale sieht wie beim Entscheiden.
+//!
+//! Eingebaut sind [`TimeOfDay`] (Stunde, Wochentag, Tagesabschnitt aus der
+//! Systemuhr) und [`SharedFeatures`] (von außen gepflegte Werte, etwa die
+//! Anwesenheit). Closures `Fn(&mut Context)` sind ebenfalls Enricher.
+
+use crate::{Context, Decision, Policy, PolicyDescriptor};
+use serde_json::{Map, Value};
+use std::fmt;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Ergänzt Merkmale in einem [`Context`].
+pub trait ContextEnricher {
+    fn enrich(&self, ctx: &mut Context);
+}
+
+impl<F: Fn(&mut Context)> ContextEnricher for F {
+    fn enrich(&self, ctx: &mut Context) {
+        self(ctx);
+    }
+}
+
+/// Setzt `key` in den Merkmalen; ein Nicht-Objekt wird dabei ersetzt.
+fn set_feature(ctx: &mut Context, key: &str, value: Value, overwrite: bool) {
+    if !ctx.features.is_object() {
+        ctx.features = Value::Object(Map::new());
+    }
+    if let Value::Object(map) = &mut ctx.features {
+        if overwrite || !map.contains_key(key) {
+            map.insert(key.to_string(), value);
+        }
+    }
+}
+
+/// Stunde, Wochentag und Tagesabschnitt in lokaler Zeit.
+///
+/// Core kennt keine Zeitzonen; die Verschiebung gegenüber UTC wird fest
+/// eingestellt. Bereits gesetzte Merkmale bleiben unangetastet, damit
+/// Aufrufer (und Tests) die Zeit vorgeben können; eine vorgegebene Stunde
+/// bestimmt auch den Tagesabschnitt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeOfDay {
+    pub utc_offset_minutes: i32,
+    /// Merkmal für die Stunde (0–23), Standard `"hour"` wie bei den
+    /// Ruhezeiten in [`crate::guard`].
+    pub hour_feature: String,
+    /// Merkmal für den Wochentag (0 = Montag … 6 = Sonntag).
+    pub weekday_feature: String,
+    /// Merkmal für `"night"`, `"morning"`, `"afternoon"` oder `"evening"`.
+    pub period_feature: String,
+    clock: Option<i64>,
+}
+
+impl Default for TimeOfDay {
+    fn default() -> Self {
+        Self {
+            utc_offset_minutes: 0,
+            hour_feature: "hour".into(),
+            weekday_feature: "weekday".into(),
+            period_feature: "time_of_day".into(),
+            clock: None,
+        }
+    }
+}
+
+impl TimeOfDay {
+    #[must_use]
+    pub fn utc_offset_minutes(mut self, minutes: i32) -> Self {
+        self.utc_offset_minutes = minutes;
+        self
+    }
+
+    /// Hält die Uhr auf `unix_seconds` an.
+    #[must_use]
+    pub fn at(mut self, unix_seconds: i64) -> Self {
+        self.clock = Some(unix_seconds);
+        self
+    }
+
+    fn now(&self) -> i64 {
+        self.clock.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| i64::try_from(d.as_secs()).unwrap_or(i64::MAX))
+        })
+    }
+}
+
+impl ContextEnricher for TimeOfDay {
+    fn enrich(&self, ctx: &mut Context) {
+        let local = self
+            .now()
+            .saturating_add(i64::from(self.utc_offset_minutes) * 60);
+        // 1970-01-01 war ein Donnerstag.
+        let weekday = (local.div_euclid(86_400) + 3).rem_euclid(7);
+        #[allow(clippy::cast_possible_truncation)]
+        let hour = ctx
+            .feature_f64(&self.hour_feature)
+            .filter(|h| (0.0..24.0).contains(h))
+            .map_or(local.rem_euclid(86_400) / 3600, |h| h as i64);
+        let period = match hour {
+            0..=5 => "night",
+            6..=11 => "morning",
+            12..=17 => "afternoon",
+            _ => "evening",
+        };
+        set_feature(ctx, &self.hour_feature, hour.into(), false);
+        set_feature(ctx, &self.weekday_feature, weekday.into(), false);
+        set_feature(ctx, &self.period_feature, period.into(), false);
+    }
+}
+
+/// Merkmale, die außerhalb der Policy gepflegt werden.
+///
+/// Klone teilen denselben Speicher: Die Integration behält einen Klon und
+/// aktualisiert ihn (etwa bei Anwesenheitsänderungen), der Enricher in der
+/// [`EnrichedPolicy`] liest den jeweils aktuellen Stand. Die Werte
+/// überschreiben gleichnamige Merkmale des Kontexts.
+#[derive(Debug, Clone, Default)]
+pub struct SharedFeatures {
+    values: Arc<RwLock<Map<String, Value>>>,
+}
+
+impl SharedFeatures {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&self, key: impl Into<String>, value: impl Into<Value>) {
+        self.values
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(key.into(), value.into());
+    }
+
+    pub fn remove(&self, key: &str) {
+        self.values
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(key);
+    }
+
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<Value> {
+        self.values
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(key)
+            .cloned()
+    }
+}
+
+impl ContextEnricher for SharedFeatures {
+    fn enrich(&self, ctx: &mut Context) {
+        let values = self.values.read().unwrap_or_else(PoisonError::into_inner);
+        for (key, value) in values.iter() {
+            set_feature(ctx, key, value.clone(), true);
+        }
+    }
+}
+
+/// Policy-Wrapper, der jeden Kontext durch eine Kette von Enrichern schickt.
+pub struct EnrichedPolicy<P> {
+    inner: P,
+    enrichers: Vec<Box<dyn ContextEnricher>>,
+}
+
+impl<P: fmt::Debug> fmt::Debug for EnrichedPolicy<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EnrichedPolicy")
+            .field("inner", &self.inner)
+            .field("enrichers", &self.enrichers.len())
+            .finish()
+    }
+}
+
+impl<P: Policy> EnrichedPolicy<P> {
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            enrichers: Vec::new(),
+        }
+    }
+
+    /// Hängt `enricher` an; Enricher laufen in der Reihenfolge des Anhängens.
+    #[must_use]
+    pub fn with(mut self, enricher: impl ContextEnricher + 'static) -> Self {
+        self.enrichers.push(Box::new(enricher));
+        self
+    }
+
+    /// Der Kontext, den die innere Policy für `ctx` sieht.
+    #[must_use]
+    pub fn enrich(&self, ctx: &Context) -> Context {
+        let mut enriched = ctx.clone();
+        for enricher in &self.enrichers {
+            enricher.enrich(&mut enriched);
+        }
+        enriched
+    }
+
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+}
+
+impl<P: Policy> Policy for EnrichedPolicy<P> {
+    fn decide(&mut self, ctx: &Context) -> Decision {
+        let ctx = self.enrich(ctx);
+        self.inner.decide(&ctx)
+    }
+
+    fn decide_batch(&mut self, ctxs: &[Context]) -> Vec<Decision> {
+        let enriched: Vec<Context> = ctxs.iter().map(|ctx| self.enrich(ctx)).collect();
+        self.inner.decide_batch(&enriched)
+    }
+
+    fn feedback(&mut self, ctx: &Context, action: &str, reward: f32) {
+        let ctx = self.enrich(ctx);
+        self.inner.feedback(&ctx, action, reward);
+    }
+
+    fn feedback_batch(&mut self, items: &[(Context, String, f32)]) {
+        let enriched: Vec<(Context, String, f32)> = items
+            .iter()
+            .map(|(ctx, action, reward)| (self.enrich(ctx), action.clone(), *reward))
+            .collect();
+        self.inner.feedback_batch(&enriched);
+    }
+
+    fn snapshot(&self) -> Value {
+        self.inner.snapshot()
+    }
+
+    fn load(&mut self, snapshot: Value) {
+        self.inner.load(snapshot);
+    }
+
+    fn descriptor(&self) -> PolicyDescriptor {
+        self.inner.descriptor()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Default)]
+    struct Recorder(Vec<Value>);
+
+    impl Policy for Recorder {
+        fn decide(&mut self, ctx: &Context) -> Decision {
+            self.0.push(ctx.features.clone());
+            Decision {
+                action: "remind.none".into(),
+                score: 0.0,
+                why: vec![],
+                context: None,
+                chosen: None,
+            }
+        }
+        fn feedback(&mut self, ctx: &Context, _: &str, _: f32) {
+            self.0.push(ctx.features.clone());
+        }
+        fn snapshot(&self) -> Value {
+            Value::Null
+        }
+        fn load(&mut self, _: Value) {}
+    }
+
+    #[test]
+    fn enrichers_run_in_order_for_decide_and_feedback() {
+        let occupancy = SharedFeatures::new();
+        occupancy.set("occupied", false);
+        // 2024-06-07 (Freitag) 22:30 UTC, in UTC+2 also Samstag 00:30.
+        let clock = TimeOfDay::default()
+            .utc_offset_minutes(120)
+            .at(1_717_799_400);
+        let mut policy = EnrichedPolicy::new(Recorder::default())
+            .with(clock)
+            .with(occupancy.clone())
+            .with(|ctx: &mut Context| {
+                let away = ctx.feature_bool("occupied") == Some(false);
+                ctx.kind = if away {
+                    "away".into()
+                } else {
+                    ctx.kind.clone()
+                };
+            });
+
+        let ctx = Context {
+            kind: "reminder".into(),
+            features: json!({ "occupied": true }),
+        };
+        policy.decide(&ctx);
+        occupancy.set("occupied", true);
+        policy.feedback(&ctx, "remind.none", 1.0);
+        assert_eq!(policy.enrich(&ctx).kind, "reminder");
+
+        assert_eq!(
+            policy.inner().0,
+            vec![
+                json!({ "hour": 0, "weekday": 5, "time_of_day": "night", "occupied": false }),
+                json!({ "hour": 0, "weekday": 5, "time_of_day": "night", "occupied": true }),
+            ]
+        );
+    }
+
+    #[test]
+    fn time_of_day_keeps_given_features() {
+        let mut ctx = Context {
+            kind: "reminder".into(),
+            features: json!({ "hour": 7 }),
+        };
+        TimeOfDay::default().at(0).enrich(&mut ctx);
+        assert_eq!(
+            ctx.features,
+            json!({ "hour": 7, "weekday": 3, "time_of_day": "morning" })
+        );
+
+        let mut bare = Context {
+            kind: "reminder".into(),
+            features: Value::Null,
+        };
+        TimeOfDay::default().at(-3600).enrich(&mut bare);
+        assert_eq!(bare.feature_f64("hour"), Some(23.0));
+        assert_eq!(bare.feature_f64("weekday"), Some(2.0));
+    }
+}
@@ -0,0 +1,361 @@
+//! Kombination mehrerer Policies zu einem Ensemble.
+//!
+//! [`EnsemblePolicy`] befragt bei jedem `decide` alle Mitglieder und führt
+//! deren Vorschläge nach [`Combine`] zusammen: als gewichtete Abstimmung oder
+//! über gewichtete Scores. Feedback geht an alle Mitglieder, auch an die, deren
+//! Vorschlag nicht gewonnen hat – so lernt jedes Mitglied aus jeder Runde.
+//! Der Snapshot enthält die Gewichte, die Kombinationsart und die Snapshots
+//! aller Mitglieder in ihrer Reihenfolge.
+
+use crate::{Context, Decision, Policy, PolicyDescriptor, PolicyError, TryPolicy};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Kennung im Ensemble-Snapshot.
+pub const ENSEMBLE_POLICY_ID: &str = "ensemble";
+
+/// Art, die Vorschläge der Mitglieder zusammenzuführen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Combine {
+    /// Jede Aktion erhält die Summe der Gewichte ihrer Befürworter.
+    #[default]
+    Vote,
+    /// Jede Aktion erhält die Summe aus Gewicht × Score ihrer Befürworter,
+    /// geteilt durch das Gesamtgewicht.
+    AverageScore,
+}
+
+/// Ensemble aus beliebigen Policies mit je einem Gewicht.
+pub struct EnsemblePolicy {
+    members: Vec<Box<dyn Policy>>,
+    weights: Vec<f64>,
+    combine: Combine,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct EnsembleSnapshot {
+    policy_id: String,
+    combine: Combine,
+    weights: Vec<f64>,
+    members: Vec<Value>,
+}
+
+impl EnsemblePolicy {
+    /// Leeres Ensemble; Mitglieder kommen über [`EnsemblePolicy::member`] hinzu.
+    pub fn new(combine: Combine) -> Self {
+        Self {
+            members: Vec::new(),
+            weights: Vec::new(),
+            combine,
+        }
+    }
+
+    /// Nimmt `policy` mit `weight` auf; nicht endliche oder negative Gewichte zählen als 0.
+    #[must_use]
+    pub fn member(mut self, policy: impl Policy + 'static, weight: f64) -> Self {
+        self.members.push(Box::new(policy));
+        self.weights.push(sanitize_weight(weight));
+        self
+    }
+
+    pub fn combine(&self) -> Combine {
+        self.combine
+    }
+
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Setzt das Gewicht des Mitglieds `index`; `false`, wenn es das Mitglied nicht gibt.
+    pub fn set_weight(&mut self, index: usize, weight: f64) -> bool {
+        match self.weights.get_mut(index) {
+            Some(w) => {
+                *w = sanitize_weight(weight);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    fn combined(&mut self, ctx: &Context) -> Result<Decision, PolicyError> {
+        if self.members.is_empty() {
+            return Err(PolicyError::NoDecision("ensemble has no members".into()));
+        }
+        let proposals: Vec<Decision> = self.members.iter_mut().map(|m| m.decide(ctx)).collect();
+        let total: f64 = self.weights.iter().sum();
+
+        // Stimmen je Aktion in der Reihenfolge des ersten Vorschlags; bei
+        // Gleichstand gewinnt der frühere.
+        let mut tally: Vec<(&str, f64)> = Vec::new();
+        for (proposal, weight) in proposals.iter().zip(&self.weights) {
+            let support = match self.combine {
+                Combine::Vote => *weight,
+                Combine::AverageScore => {
+                    let score = f64::from(proposal.score);
+                    if score.is_finite() {
+                        weight * score
+                    } else {
+                        0.0
+                    }
+                }
+            };
+            match tally.iter_mut().find(|(a, _)| *a == proposal.action) {
+                Some((_, sum)) => *sum += support,
+                None => tally.push((&proposal.action, support)),
+            }
+        }
+        let mut winner = 0;
+        for (i, (_, sum)) in tally.iter().enumerate() {
+            if *sum > tally[winner].1 {
+                winner = i;
+            }
+        }
+        let (action, support) = tally[winner];
+        let value = match self.combine {
+            Combine::Vote if total > 0.0 => support / total,
+            Combine::AverageScore if total > 0.0 => support / total,
+            _ => 0.0,
+        };
+
+        let mut why = vec![match self.combine {
+            Combine::Vote => format!("ensemble vote {:.0}%", value * 100.0),
+            Combine::AverageScore => format!("ensemble average score {value:.2}"),
+        }];
+        why.extend(
+            proposals
+                .iter()
+                .enumerate()
+                .map(|(i, p)| format!("member {i}: {} ({:.2})", p.action, p.score)),
+        );
+        let chosen = proposals
+            .iter()
+            .find(|p| p.action == action)
+            .and_then(|p| p.chosen.clone());
+        #[allow(clippy::cast_possible_truncation)]
+        Ok(Decision {
+            action: action.to_string(),
+            score: value as f32,
+            why,
+            context: serde_json::to_value(ctx).ok(),
+            chosen,
+        })
+    }
+}
+
+fn sanitize_weight(weight: f64) -> f64 {
+    if weight.is_finite() && weight > 0.0 {
+        weight
+    } else {
+        0.0
+    }
+}
+
+impl Policy for EnsemblePolicy {
+    /// Führt die Vorschläge aller Mitglieder zusammen; ohne Mitglieder `none`.
+    fn decide(&mut self, ctx: &Context) -> Decision {
+        self.combined(ctx).unwrap_or_else(|e| Decision {
+            action: "none".into(),
+            score: 0.0,
+            why: vec![e.to_string()],
+            context: serde_json::to_value(ctx).ok(),
+            chosen: None,
+        })
+    }
+
+    fn feedback(&mut self, ctx: &Context, action: &str, reward: f32) {
+        for member in &mut self.members {
+            member.feedback(ctx, action, reward);
+        }
+    }
+
+    fn feedback_batch(&mut self, items: &[(Context, String, f32)]) {
+        for member in &mut self.members {
+            member.feedback_batch(items);
+        }
+    }
+
+    fn snapshot(&self) -> Value {
+        let snap = EnsembleSnapshot {
+            policy_id: ENSEMBLE_POLICY_ID.into(),
+            combine: self.combine,
+            weights: self.weights.clone(),
+            members: self.members.iter().map(|m| m.snapshot()).collect(),
+        };
+        serde_json::to_value(snap).unwrap_or(Value::Null)
+    }
+
+    /// Lädt Gewichte und Mitglieder; ein unpassender Snapshot wird ignoriert
+    /// (siehe [`TryPolicy::try_load`]).
+    fn load(&mut self, snapshot: Value) {
+        let _ = self.try_load(snapshot);
+    }
+
+    fn descriptor(&self) -> PolicyDescriptor {
+        PolicyDescriptor::new(ENSEMBLE_POLICY_ID)
+    }
+}
+
+impl TryPolicy for EnsemblePolicy {
+    fn try_decide(&mut self, ctx: &Context) -> Result<Decision, PolicyError> {
+        self.combined(ctx)
+    }
+
+    fn try_feedback(
+        &mut self,
+        ctx: &Context,
+        action: &str,
+        reward: f32,
+    ) -> Result<(), PolicyError> {
+        if !reward.is_finite() {
+            return Err(PolicyError::InvalidReward {
+                action: action.to_string(),
+                reward,
+            });
+        }
+        self.feedback(ctx, action, reward);
+        Ok(())
+    }
+
+    fn try_load(&mut self, snapshot: Value) -> Result<(), PolicyError> {
+        let snap: EnsembleSnapshot = serde_json::from_value(snapshot)
+            .map_err(|e| PolicyError::InvalidSnapshot(e.to_string()))?;
+        if snap.policy_id != ENSEMBLE_POLICY_ID {
+            return Err(PolicyError::WrongPolicy {
+                expected: ENSEMBLE_POLICY_ID.into(),
+                found: snap.policy_id,
+            });
+        }
+        let n = self.members.len();
+        if snap.members.len() != n || snap.weights.len() != n {
+            return Err(PolicyError::InvalidSnapshot(format!(
+                "ensemble has {n} members, snapshot has {} members and {} weights",
+                snap.members.len(),
+                snap.weights.len()
+            )));
+        }
+        self.combine = snap.combine;
+        self.weights = snap.weights.into_iter().map(sanitize_weight).collect();
+        for (member, state) in self.members.iter_mut().zip(snap.members) {
+            member.load(state);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Schlägt immer `action` mit `score` vor und zählt Feedback.
+    struct Fixed {
+        action: &'static str,
+        score: f32,
+        feedback: u32,
+    }
+
+    impl Fixed {
+        fn new(action: &'static str, score: f32) -> Self {
+            Self {
+                action,
+                score,
+                feedback: 0,
+            }
+        }
+    }
+
+    impl Policy for Fixed {
+        fn decide(&mut self, _: &Context) -> Decision {
+            Decision {
+                action: self.action.into(),
+                score: self.score,
+                why: vec![],
+                context: None,
+                chosen: None,
+            }
+        }
+        fn feedback(&mut self, _: &Context, _: &str, _: f32) {
+            self.feedback += 1;
+        }
+        fn snapshot(&self) -> Value {
+            json!({ "feedback": self.feedback })
+        }
+        fn load(&mut self, snapshot: Value) {
+            if let Some(n) = snapshot["feedback"].as_u64() {
+                self.feedback = u32::try_from(n).unwrap_or(u32::MAX);
+            }
+        }
+    }
+
+    fn ctx() -> Context {
+        Context {
+            kind: "reminder".into(),
+            features: json!({}),
+        }
+    }
+
+    #[test]
+    fn vote_and_average_score_pick_different_winners() {
+        let build = |combine| {
+            EnsemblePolicy::new(combine)
+                .member(Fixed::new("a", 0.1), 1.0)
+                .member(Fixed::new("a", 0.1), 1.0)
+                .member(Fixed::new("b", 0.9), 1.5)
+        };
+        let mut vote = build(Combine::Vote);
+        let decision = vote.decide(&ctx());
+        assert_eq!(decision.action, "a");
+        assert_eq!(decision.why[0], "ensemble vote 57%");
+        assert_eq!(decision.why[3], "member 2: b (0.90)");
+
+        let mut average = build(Combine::AverageScore);
+        assert_eq!(average.decide(&ctx()).action, "b");
+
+        let mut empty = EnsemblePolicy::new(Combine::Vote);
+        assert!(matches!(
+            empty.try_decide(&ctx()),
+            Err(PolicyError::NoDecision(_))
+        ));
+        assert_eq!(empty.decide(&ctx()).action, "none");
+    }
+
+    #[test]
+    fn feedback_reaches_all_members_and_snapshot_round_trips() {
+        let mut ensemble = EnsemblePolicy::new(Combine::Vote)
+            .member(Fixed::new("a", 0.5), 2.0)
+            .member(Fixed::new("b", 0.5), 1.0);
+        ensemble.feedback(&ctx(), "a", 1.0);
+        ensemble.feedback(&ctx(), "b", 0.0);
+        assert!(ensemble.set_weight(1, 3.0));
+
+        let snap = ensemble.snapshot();
+        assert_eq!(snap["weights"], json!([2.0, 3.0]));
+        assert_eq!(
+            snap["members"],
+            json!([{ "feedback": 2 }, { "feedback": 2 }])
+        );
+
+        let mut restored = EnsemblePolicy::new(Combine::AverageScore)
+            .member(Fixed::new("a", 0.5), 1.0)
+            .member(Fixed::new("b", 0.5), 1.0);
+        assert!(restored.try_load(snap.clone()).is_ok());
+        assert_eq!(restored.combine(), Combine::Vote);
+        assert_eq!(restored.weights(), &[2.0, 3.0]);
+        assert_eq!(restored.snapshot()["members"], snap["members"]);
+
+        let mut smaller = EnsemblePolicy::new(Combine::Vote).member(Fixed::new("a", 0.5), 1.0);
+        assert!(matches!(
+            smaller.try_load(snap),
+            Err(PolicyError::InvalidSnapshot(_))
+        ));
+    }
+}
@@ -0,0 +1,188 @@
+//! Gemeinsame Hülle für Policy-Snapshots.
+//!
+//! Jeder Snapshot trägt dieselben Kopffelder – `version`, `policy_id`, `ts` –
+//! und daneben den policy-eigenen Zustand. [`SnapshotEnvelope`] bildet genau
+//! das ab: Die Nutzlast wird flach neben die Kopffelder serialisiert, das
+//! JSON entspricht also weiterhin `contracts/policy.snapshot.schema.json`.
+//!
+//! Die Prüfung, ob ein Snapshot zur ladenden Policy gehört, liegt zentral in
+//! [`SnapshotEnvelope::check_policy`] bzw. [`SnapshotEnvelope::open`].
+
+use crate::{HeimlernError, PolicyError};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Snapshot-Kopf plus policy-spezifische Nutzlast.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotEnvelope<T> {
+    /// Schemaversion der Nutzlast.
+    pub version: String,
+    /// Policy, die den Snapshot geschrieben hat.
+    pub policy_id: String,
+    /// Zeitpunkt der Erstellung (RFC 3339, UTC).
+    pub ts: String,
+    #[serde(flatten)]
+    pub payload: T,
+}
+
+impl<T> SnapshotEnvelope<T> {
+    /// Umhüllt `payload` mit dem aktuellen Zeitstempel.
+    pub fn new(policy_id: impl Into<String>, version: impl Into<String>, payload: T) -> Self {
+        Self {
+            version: version.into(),
+            policy_id: policy_id.into(),
+            ts: utc_now(),
+            payload,
+        }
+    }
+
+    #[must_use]
+    pub fn with_ts(mut self, ts: impl Into<String>) -> Self {
+        self.ts = ts.into();
+        self
+    }
+
+    /// Wandelt die Nutzlast um und behält den Kopf.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> SnapshotEnvelope<U> {
+        SnapshotEnvelope {
+            version: self.version,
+            policy_id: self.policy_id,
+            ts: self.ts,
+            payload: f(self.payload),
+        }
+    }
+
+    pub fn into_payload(self) -> T {
+        self.payload
+    }
+
+    /// Prüft, ob der Snapshot von einer der `accepted` Policies stammt.
+    ///
+    /// # Errors
+    /// [`PolicyError::WrongPolicy`] mit der ersten akzeptierten Kennung als
+    /// erwartetem Wert.
+    pub fn check_policy(&self, accepted: &[&str]) -> Result<(), PolicyError> {
+        if accepted.contains(&self.policy_id.as_str()) {
+            return Ok(());
+        }
+        Err(PolicyError::WrongPolicy {
+            expected: accepted.first().copied().unwrap_or_default().to_string(),
+            found: self.policy_id.clone(),
+        })
+    }
+}
+
+impl<T: Serialize> SnapshotEnvelope<T> {
+    /// Serialisiert Kopf und Nutzlast in ein flaches JSON-Objekt.
+    ///
+    /// # Errors
+    /// Contract-Fehler, wenn sich die Nutzlast nicht serialisieren lässt.
+    pub fn to_value(&self) -> Result<Value, HeimlernError> {
+        Ok(serde_json::to_value(self)?)
+    }
+}
+
+impl<T: DeserializeOwned> SnapshotEnvelope<T> {
+    /// Liest einen Snapshot und prüft die `policy_id`.
+    ///
+    /// # Errors
+    /// [`PolicyError::InvalidSnapshot`], wenn das JSON nicht passt,
+    /// [`PolicyError::WrongPolicy`] bei fremder `policy_id`.
+    pub fn open(value: Value, accepted: &[&str]) -> Result<Self, PolicyError> {
+        let envelope: Self = serde_json::from_value(value)
+            .map_err(|e| PolicyError::InvalidSnapshot(e.to_string()))?;
+        envelope.check_policy(accepted)?;
+        Ok(envelope)
+    }
+}
+
+/// Aktueller Zeitpunkt als RFC-3339-Zeitstempel in UTC mit Sekundenauflösung.
+#[must_use]
+pub fn utc_now() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    format_utc(i64::try_from(secs).unwrap_or(i64::MAX))
+}
+
+/// Formatiert Unix-Sekunden als `YYYY-MM-DDTHH:MM:SSZ`.
+fn format_utc(secs: i64) -> String {
+    let days = secs.div_euclid(86_400);
+    let rem = secs.rem_euclid(86_400);
+    // Kalenderdatum aus Tagen seit 1970-01-01 (Algorithmus von H. Hinnant).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Counts {
+        arms: Vec<String>,
+        counts: Vec<u64>,
+    }
+
+    #[test]
+    fn envelope_is_flat_and_checks_policy() -> Result<(), Box<dyn std::error::Error>> {
+        let envelope = SnapshotEnvelope::new(
+            "remind-bandit",
+            "0.2.0",
+            Counts {
+                arms: vec!["morning".into()],
+                counts: vec![3],
+            },
+        )
+        .with_ts("2026-01-01T00:00:00Z");
+        let value = envelope.to_value()?;
+        assert_eq!(
+            value,
+            json!({
+                "version": "0.2.0",
+                "policy_id": "remind-bandit",
+                "ts": "2026-01-01T00:00:00Z",
+                "arms": ["morning"],
+                "counts": [3]
+            })
+        );
+
+        let back =
+            SnapshotEnvelope::<Counts>::open(value.clone(), &["ucb-bandit", "remind-bandit"])?;
+        assert_eq!(back, envelope);
+        assert!(matches!(
+            SnapshotEnvelope::<Counts>::open(value, &["ucb-bandit"]),
+            Err(PolicyError::WrongPolicy { expected, found })
+                if expected == "ucb-bandit" && found == "remind-bandit"
+        ));
+        assert!(matches!(
+            SnapshotEnvelope::<Counts>::open(json!({ "policy_id": "x" }), &["x"]),
+            Err(PolicyError::InvalidSnapshot(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn timestamps_are_rfc3339_utc() {
+        assert_eq!(format_utc(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_utc(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(format_utc(1_767_225_599), "2025-12-31T23:59:59Z");
+        assert_eq!(utc_now().len(), 20);
+    }
+}
@@ -0,0 +1,260 @@
+//! Gemeinsame Fehler-Taxonomie für alle heimlern-Crates.
+//!
+//! Crate-lokale Fehler (z. B. `BanditError`, `SinkError`) bleiben erhalten,
+//! lassen sich aber per `From` in einen [`HeimlernError`] überführen. Die
+//! Kategorie ([`ErrorKind`]) ist stabil und für Integratoren auswertbar; die
+//! ursprüngliche Ursache bleibt über [`std::error::Error::source`] erreichbar.
+
+use crate::ola::RouteDeltaKeyError;
+use std::error::Error as StdError;
+use std::fmt;
+
+/// Boxed Fehlerursache, wie sie in [`HeimlernError`] mitgeführt wird.
+pub type BoxError = Box<dyn StdError + Send + Sync + 'static>;
+
+/// Ergebnis-Alias mit [`HeimlernError`] als Fehlertyp.
+pub type Result<T, E = HeimlernError> = std::result::Result<T, E>;
+
+/// Fehlerkategorie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Daten verletzen einen Vertrag (Schema, Snapshot-Format, Delta-Keys).
+    Contract,
+    /// Lesen oder Schreiben persistenter Daten ist fehlgeschlagen.
+    Storage,
+    /// Netzwerk- oder Zustellfehler (HTTP, Sinks).
+    Transport,
+    /// Eine Policy konnte eine Operation nicht ausführen.
+    Policy,
+    /// Die Auswertung von Outcomes oder Vorschlägen ist fehlgeschlagen.
+    Analysis,
+}
+
+impl ErrorKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Contract => "contract",
+            Self::Storage => "storage",
+            Self::Transport => "transport",
+            Self::Policy => "policy",
+            Self::Analysis => "analysis",
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Crate-übergreifender Fehlertyp.
+#[derive(Debug)]
+pub struct HeimlernError {
+    kind: ErrorKind,
+    message: String,
+    source: Option<BoxError>,
+}
+
+impl HeimlernError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    pub fn contract(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Contract, message)
+    }
+
+    pub fn storage(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Storage, message)
+    }
+
+    pub fn transport(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Transport, message)
+    }
+
+    pub fn policy(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Policy, message)
+    }
+
+    pub fn analysis(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Analysis, message)
+    }
+
+    /// Hängt die ursprüngliche Ursache an.
+    #[must_use]
+    pub fn with_source(mut self, source: impl Into<BoxError>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for HeimlernError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} error: {}", self.kind, self.message)
+    }
+}
+
+impl StdError for HeimlernError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.source
+            .as_deref()
+            .map(|e| e as &(dyn StdError + 'static))
+    }
+}
+
+/// Fehler der fehlbaren Policy-Schnittstelle [`crate::TryPolicy`].
+///
+/// Anders als [`HeimlernError`] ein geschlossener Satz von Fällen, auf die
+/// Aufrufer gezielt reagieren können – etwa einen Snapshot einer fremden
+/// Policy anders behandeln als einen beschädigten.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum PolicyError {
+    /// Keine Aktion wählbar, z. B. ohne Arme oder mit nur ungültigen Schätzwerten.
+    NoDecision(String),
+    /// Die Aktion gehört nicht zur Policy (fremder Namensraum, zu langer Name).
+    InvalidAction(String),
+    /// Der Reward ist keine endliche Zahl.
+    InvalidReward { action: String, reward: f32 },
+    /// Eine Kapazitätsgrenze, etwa die Höchstzahl an Armen, ist erreicht.
+    CapacityExceeded(String),
+    /// Der Snapshot stammt von einer anderen Policy.
+    WrongPolicy { expected: String, found: String },
+    /// Der Snapshot trägt eine Schemaversion, die dieser Stand nicht kennt.
+    UnsupportedVersion(String),
+    /// Der Snapshot ist unlesbar oder inhaltlich ungültig.
+    InvalidSnapshot(String),
+}
+
+impl fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoDecision(reason) => write!(f, "no decision possible: {reason}"),
+            Self::InvalidAction(reason) => write!(f, "invalid action: {reason}"),
+            Self::InvalidReward { action, reward } => {
+                write!(f, "invalid reward {reward} for action '{action}'")
+            }
+            Self::CapacityExceeded(reason) => write!(f, "capacity exceeded: {reason}"),
+            Self::WrongPolicy { expected, found } => write!(
+                f,
+                "snapshot belongs to policy '{found}', expected '{expected}'"
+            ),
+            Self::UnsupportedVersion(reason) => {
+                write!(f, "unsupported snapshot version: {reason}")
+            }
+            Self::InvalidSnapshot(reason) => write!(f, "invalid snapshot: {reason}"),
+        }
+    }
+}
+
+impl StdError for PolicyError {}
+
+impl From<PolicyError> for HeimlernError {
+    fn from(err: PolicyError) -> Self {
+        let kind = match err {
+            PolicyError::WrongPolicy { .. }
+            | PolicyError::UnsupportedVersion(_)
+            | PolicyError::InvalidSnapshot(_) => ErrorKind::Contract,
+            _ => ErrorKind::Policy,
+        };
+        Self::new(kind, err.to_string()).with_source(err)
+    }
+}
+
+impl From<std::io::Error> for HeimlernError {
+    fn from(err: std::io::Error) -> Self {
+        Self::storage(err.to_string()).with_source(err)
+    }
+}
+
+impl From<serde_json::Error> for HeimlernError {
+    fn from(err: serde_json::Error) -> Self {
+        let kind = if err.is_io() {
+            ErrorKind::Storage
+        } else {
+            ErrorKind::Contract
+        };
+        Self::new(kind, err.to_string()).with_source(err)
+    }
+}
+
+impl From<RouteDeltaKeyError> for HeimlernError {
+    fn from(err: RouteDeltaKeyError) -> Self {
+        Self::contract(err.to_string()).with_source(err)
+    }
+}
+
+#[cfg(feature = "scripting")]
+impl From<crate::reward_script::ScriptError> for HeimlernError {
+    fn from(err: crate::reward_script::ScriptError) -> Self {
+        Self::analysis(err.to_string()).with_source(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ola::RouteDeltaKeyErrorKind;
+
+    #[test]
+    fn json_errors_map_to_contract() {
+        let err = match serde_json::from_str::<serde_json::Value>("{") {
+            Ok(value) => panic!("unexpected value: {value}"),
+            Err(err) => HeimlernError::from(err),
+        };
+        assert_eq!(err.kind(), ErrorKind::Contract);
+        assert!(err.source().is_some());
+        assert!(err.to_string().starts_with("contract error: "));
+    }
+
+    #[test]
+    fn io_errors_map_to_storage() {
+        let io = std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+        let err = HeimlernError::from(io);
+        assert_eq!(err.kind(), ErrorKind::Storage);
+        assert_eq!(err.message(), "missing");
+    }
+
+    #[test]
+    fn policy_errors_split_into_contract_and_policy() {
+        let foreign = HeimlernError::from(PolicyError::WrongPolicy {
+            expected: "remind-bandit".into(),
+            found: "ucb-bandit".into(),
+        });
+        assert_eq!(foreign.kind(), ErrorKind::Contract);
+        assert_eq!(
+            foreign.message(),
+            "snapshot belongs to policy 'ucb-bandit', expected 'remind-bandit'"
+        );
+        let reward = HeimlernError::from(PolicyError::InvalidReward {
+            action: "remind.morning".into(),
+            reward: f32::NAN,
+        });
+        assert_eq!(reward.kind(), ErrorKind::Policy);
+        assert!(reward.source().is_some());
+    }
+
+    #[test]
+    fn route_delta_key_errors_keep_their_message() {
+        let err = HeimlernError::from(RouteDeltaKeyError::new(
+            RouteDeltaKeyErrorKind::RouteDeltaKeyInvalid,
+            "empty",
+        ));
+        assert_eq!(err.kind(), ErrorKind::Contract);
+        assert_eq!(err.message(), "route_delta_key_invalid: empty");
+    }
+}
@@ -8,16 +8,28 @@
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// Höchstlänge von `title` laut Contract (in Zeichen).
+pub const MAX_TITLE_LEN: usize = 300;
+/// Höchstlänge von `summary` laut Contract (in Zeichen).
+pub const MAX_SUMMARY_LEN: usize = 2000;
+/// Höchstzahl der Tags laut Contract.
+pub const MAX_TAGS: usize = 64;
+/// Höchstlänge eines Tags laut Contract (in Zeichen).
+pub const MAX_TAG_LEN: usize = 64;
 
 /// Repräsentiert ein externes Ereignis, das von einem Sensor, einer API oder
 /// einer anderen Datenquelle stammt.
 ///
 /// Die Struktur ist so konzipiert, dass sie mit dem JSON-Schema in
-/// `contracts/aussen.event.schema.json` kompatibel ist.
+/// `contracts/aussen.event.schema.json` kompatibel ist; das Schema kennt kein
+/// `null`, fehlende Felder werden deshalb weggelassen.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AussenEvent {
     /// Eine eindeutige Kennung für dieses Ereignis, z. B. eine UUID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
     /// Der Typ des Ereignisses, der zur Kategorisierung dient (z. B.
     /// "sensor.reading", "user.interaction").
@@ -27,24 +39,255 @@ pub struct AussenEvent {
     /// Die Quelle des Ereignisses (z. B. "haus-automation", "user-app").
     pub source: String,
     /// Ein optionaler, menschenlesbarer Titel für das Ereignis.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
     /// Eine kurze Zusammenfassung oder Beschreibung des Ereignisses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub summary: Option<String>,
     /// Eine URL, die auf weiterführende Informationen zum Ereignis verweist.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
     /// Eine Liste von Tags zur Kategorisierung oder zum Filtern des Ereignisses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
     /// Ein ISO-8601-formatierter Zeitstempel, der angibt, wann das Ereignis
     /// aufgetreten ist.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ts: Option<String>,
     /// Ein flexibles Feld für beliebige strukturierte Daten, die für die
     /// Policy-Entscheidung relevant sind.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub features: Option<BTreeMap<String, Value>>,
     /// Zusätzliche Metadaten, die nicht direkt für die Entscheidungsfindung
     /// verwendet werden, aber für Logging oder Debugging nützlich sein können.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub meta: Option<BTreeMap<String, Value>>,
 }
 
+/// Ein Verstoß gegen `contracts/aussen.event.schema.json`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventIssue {
+    /// Betroffenes Feld, bei Tags mit Index (z. B. `tags[2]`).
+    pub field: String,
+    pub message: String,
+}
+
+impl EventIssue {
+    fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for EventIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+impl AussenEvent {
+    /// Prüft das Event gegen die Regeln des Contracts.
+    ///
+    /// Anders als die Deserialisierung, die nur die Form prüft, werden hier
+    /// auch Inhalte geprüft: `ts` als RFC-3339-Zeitstempel, `url` als absolute
+    /// URI (Pflicht bei `type = "link"`), Tags nicht leer, eindeutig und
+    /// begrenzt, `type`/`source` nicht leer.
+    ///
+    /// # Errors
+    /// Alle gefundenen Verstöße, in Feldreihenfolge.
+    pub fn validate(&self) -> Result<(), Vec<EventIssue>> {
+        let mut issues = Vec::new();
+        for (field, value) in [("type", &self.r#type), ("source", &self.source)] {
+            if value.trim().is_empty() {
+                issues.push(EventIssue::new(field, "must not be empty"));
+            } else if value.trim() != value {
+                issues.push(EventIssue::new(
+                    field,
+                    "must not start or end with whitespace",
+                ));
+            }
+        }
+        if let Some(title) = &self.title {
+            let len = title.chars().count();
+            if len == 0 || len > MAX_TITLE_LEN {
+                issues.push(EventIssue::new(
+                    "title",
+                    format!("must have 1 to {MAX_TITLE_LEN} characters, has {len}"),
+                ));
+            }
+        }
+        if let Some(summary) = &self.summary {
+            let len = summary.chars().count();
+            if len > MAX_SUMMARY_LEN {
+                issues.push(EventIssue::new(
+                    "summary",
+                    format!("must have at most {MAX_SUMMARY_LEN} characters, has {len}"),
+                ));
+            }
+        }
+        match &self.url {
+            Some(url) if !is_absolute_uri(url) => {
+                issues.push(EventIssue::new(
+                    "url",
+                    format!("`{url}` is not an absolute URI"),
+                ));
+            }
+            None if self.r#type == "link" => {
+                issues.push(EventIssue::new("url", "is required for type `link`"));
+            }
+            _ => {}
+        }
+        if let Some(tags) = &self.tags {
+            if tags.len() > MAX_TAGS {
+                issues.push(EventIssue::new(
+                    "tags",
+                    format!("must have at most {MAX_TAGS} entries, has {}", tags.len()),
+                ));
+            }
+            let mut seen = BTreeSet::new();
+            for (i, tag) in tags.iter().enumerate() {
+                let field = format!("tags[{i}]");
+                if tag.is_empty() || tag.starts_with(char::is_whitespace) {
+                    issues.push(EventIssue::new(
+                        field,
+                        "must be non-empty and not start with whitespace",
+                    ));
+                } else if tag.chars().count() > MAX_TAG_LEN {
+                    issues.push(EventIssue::new(
+                        field,
+                        format!("must have at most {MAX_TAG_LEN} characters"),
+                    ));
+                } else if !seen.insert(tag.as_str()) {
+                    issues.push(EventIssue::new(field, format!("duplicate tag `{tag}`")));
+                }
+            }
+        }
+        if let Some(ts) = &self.ts {
+            if !is_rfc3339(ts) {
+                issues.push(EventIssue::new(
+                    "ts",
+                    format!("`{ts}` is not an RFC 3339 timestamp"),
+                ));
+            }
+        }
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(issues)
+        }
+    }
+
+    /// Bereinigt offensichtliche Formfehler, ohne Inhalte zu erfinden:
+    /// Leerraum an den Rändern der Textfelder und Tags wird entfernt, leere
+    /// optionale Felder werden zu `None`, leere und doppelte Tags entfallen
+    /// (die erste Nennung bleibt).
+    pub fn normalize(&mut self) {
+        fn trim_in_place(value: &mut String) {
+            let trimmed = value.trim();
+            if trimmed.len() != value.len() {
+                *value = trimmed.to_string();
+            }
+        }
+        fn trim_optional(value: &mut Option<String>) {
+            if let Some(inner) = value {
+                trim_in_place(inner);
+                if inner.is_empty() {
+                    *value = None;
+                }
+            }
+        }
+
+        trim_in_place(&mut self.r#type);
+        trim_in_place(&mut self.source);
+        trim_optional(&mut self.id);
+        trim_optional(&mut self.title);
+        trim_optional(&mut self.summary);
+        trim_optional(&mut self.url);
+        trim_optional(&mut self.ts);
+        if let Some(tags) = &mut self.tags {
+            let mut seen = BTreeSet::new();
+            tags.iter_mut().for_each(trim_in_place);
+            tags.retain(|tag| !tag.is_empty() && seen.insert(tag.clone()));
+            if tags.is_empty() {
+                self.tags = None;
+            }
+        }
+    }
+}
+
+/// Ob `value` eine absolute URI ist: Schema nach RFC 3986, gefolgt von einem
+/// nicht leeren Rest ohne Leerraum oder Steuerzeichen.
+fn is_absolute_uri(value: &str) -> bool {
+    let Some((scheme, rest)) = value.split_once(':') else {
+        return false;
+    };
+    let mut chars = scheme.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        && !rest.is_empty()
+        && !rest.chars().any(|c| c.is_whitespace() || c.is_control())
+}
+
+/// Ob `value` ein Zeitstempel nach RFC 3339 ist
+/// (`YYYY-MM-DDTHH:MM:SS[.frac](Z|±HH:MM)`).
+fn is_rfc3339(value: &str) -> bool {
+    fn number(bytes: &[u8]) -> Option<u32> {
+        bytes.iter().try_fold(0u32, |acc, b| {
+            b.is_ascii_digit().then(|| acc * 10 + u32::from(b - b'0'))
+        })
+    }
+
+    let b = value.as_bytes();
+    if b.len() < 20
+        || b[4] != b'-'
+        || b[7] != b'-'
+        || !matches!(b[10], b'T' | b't')
+        || b[13] != b':'
+        || b[16] != b':'
+    {
+        return false;
+    }
+    let (Some(year), Some(month), Some(day), Some(hour), Some(minute), Some(second)) = (
+        number(&b[0..4]),
+        number(&b[5..7]),
+        number(&b[8..10]),
+        number(&b[11..13]),
+        number(&b[14..16]),
+        number(&b[17..19]),
+    ) else {
+        return false;
+    };
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return false,
+    };
+    if day == 0 || day > days_in_month || hour > 23 || minute > 59 || second > 60 {
+        return false;
+    }
+
+    let mut rest = &b[19..];
+    if let Some(frac) = rest.strip_prefix(b".") {
+        let digits = frac.iter().take_while(|c| c.is_ascii_digit()).count();
+        if digits == 0 {
+            return false;
+        }
+        rest = &frac[digits..];
+    }
+    match rest {
+        [b'Z' | b'z'] => true,
+        [b'+' | b'-', h1, h2, b':', m1, m2] => {
+            matches!((number(&[*h1, *h2]), number(&[*m1, *m2])), (Some(h), Some(m)) if h <= 23 && m <= 59)
+        }
+        _ => false,
+    }
+}
+
 /// Validates an event domain/namespace identifier.
 ///
 /// This validates event namespace identifiers (e.g., "aussen", "sensor.v1"), not DNS domains.
@@ -153,6 +396,58 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn validate_reports_every_issue_and_normalize_repairs_form() {
+        let mut event: AussenEvent = serde_json::from_value(json!({
+            "type": " link ",
+            "source": "rss:heise",
+            "title": "",
+            "tags": ["news", " news", "", "news"],
+            "ts": "2024-02-30T10:00:00Z"
+        }))
+        .unwrap_or_else(|e| panic!("fixture: {e}"));
+        let Err(issues) = event.validate() else {
+            panic!("malformed event must be rejected");
+        };
+        let fields: Vec<&str> = issues.iter().map(|i| i.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            ["type", "title", "tags[1]", "tags[2]", "tags[3]", "ts"]
+        );
+
+        event.normalize();
+        assert_eq!(event.r#type, "link");
+        assert_eq!(event.title, None);
+        assert_eq!(event.tags, Some(vec!["news".to_string()]));
+        event.ts = Some("2024-02-29T10:00:00.250+01:00".into());
+        let Err(issues) = event.validate() else {
+            panic!("link without url must be rejected");
+        };
+        assert_eq!(issues[0].to_string(), "url: is required for type `link`");
+
+        event.url = Some("https://example.org/a?b=c".into());
+        assert_eq!(event.validate(), Ok(()));
+        event.url = Some("example.org/no scheme".into());
+        assert!(event.validate().is_err());
+
+        for ts in [
+            "2023-10-27T10:00:00Z",
+            "1990-12-31t23:59:60.5z",
+            "2023-01-01T00:00:00-05:30",
+        ] {
+            assert!(is_rfc3339(ts), "{ts}");
+        }
+        for ts in [
+            "2023-10-27",
+            "2023-10-27 10:00:00Z",
+            "2023-13-01T00:00:00Z",
+            "2023-01-01T00:00:00+5:00",
+            "2023-01-01T00:00:00.",
+        ] {
+            assert!(!is_rfc3339(ts), "{ts}");
+        }
+    }
+
     #[test]
     fn test_is_valid_event_domain() {
         assert!(is_valid_event_domain("example.com"));
@@ -0,0 +1,226 @@
+//! Rückfallkette aus zwei Policies.
+//!
+//! [`FallbackPolicy`] befragt zuerst die primäre Policy. Liefert sie keine
+//! Entscheidung (Aktion `none` bzw. `<namespace>.none`) oder liegt ihr Score
+//! unter `min_score`, entscheidet stattdessen die sekundäre Policy – typischerweise
+//! eine deterministische Regel, die immer eine brauchbare Antwort hat. Ketten
+//! mit mehr als zwei Gliedern entstehen durch Verschachteln.
+
+use crate::{Context, Decision, Policy, PolicyDescriptor};
+use serde_json::{json, Value};
+
+/// Policy-Wrapper, der bei schwachen Entscheidungen an eine zweite Policy abgibt.
+#[derive(Debug)]
+pub struct FallbackPolicy<P, S> {
+    primary: P,
+    secondary: S,
+    min_score: f32,
+    delegated: u64,
+}
+
+impl<P: Policy, S: Policy> FallbackPolicy<P, S> {
+    /// `min_score` ist die kleinste Bewertung, die von `primary` übernommen
+    /// wird; ein nicht endlicher Wert zählt als 0.
+    pub fn new(primary: P, secondary: S, min_score: f32) -> Self {
+        Self {
+            primary,
+            secondary,
+            min_score: if min_score.is_finite() {
+                min_score
+            } else {
+                0.0
+            },
+            delegated: 0,
+        }
+    }
+
+    pub fn primary(&self) -> &P {
+        &self.primary
+    }
+
+    pub fn secondary(&self) -> &S {
+        &self.secondary
+    }
+
+    pub fn min_score(&self) -> f32 {
+        self.min_score
+    }
+
+    /// Anzahl der Entscheidungen, die die sekundäre Policy getroffen hat.
+    pub fn delegated(&self) -> u64 {
+        self.delegated
+    }
+
+    pub fn into_parts(self) -> (P, S) {
+        (self.primary, self.secondary)
+    }
+
+    /// Grund, die Entscheidung der primären Policy zu verwerfen.
+    fn reject_reason(&self, decision: &Decision) -> Option<String> {
+        if decision.action == "none" || decision.action.ends_with(".none") {
+            Some(format!("fallback: primary returned {}", decision.action))
+        } else if decision.score.is_nan() || decision.score < self.min_score {
+            Some(format!(
+                "fallback: primary {} scored {:.2} < {:.2}",
+                decision.action, decision.score, self.min_score
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+impl<P: Policy, S: Policy> Policy for FallbackPolicy<P, S> {
+    fn decide(&mut self, ctx: &Context) -> Decision {
+        let decision = self.primary.decide(ctx);
+        let Some(reason) = self.reject_reason(&decision) else {
+            return decision;
+        };
+        self.delegated += 1;
+        let mut fallback = self.secondary.decide(ctx);
+        fallback.why.insert(0, reason);
+        fallback
+    }
+
+    /// Beide Policies erhalten jedes Feedback, damit die primäre auch aus
+    /// Runden lernt, in denen die sekundäre entschieden hat.
+    fn feedback(&mut self, ctx: &Context, action: &str, reward: f32) {
+        self.primary.feedback(ctx, action, reward);
+        self.secondary.feedback(ctx, action, reward);
+    }
+
+    fn feedback_batch(&mut self, items: &[(Context, String, f32)]) {
+        self.primary.feedback_batch(items);
+        self.secondary.feedback_batch(items);
+    }
+
+    fn snapshot(&self) -> Value {
+        json!({
+            "primary": self.primary.snapshot(),
+            "secondary": self.secondary.snapshot(),
+        })
+    }
+
+    /// Fehlende Teile des Snapshots lassen die jeweilige Policy unverändert.
+    fn load(&mut self, mut snapshot: Value) {
+        if let Some(primary) = snapshot.get_mut("primary").map(Value::take) {
+            self.primary.load(primary);
+        }
+        if let Some(secondary) = snapshot.get_mut("secondary").map(Value::take) {
+            self.secondary.load(secondary);
+        }
+    }
+
+    fn descriptor(&self) -> PolicyDescriptor {
+        self.primary.descriptor()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Liefert immer `action` mit `score` und merkt sich das letzte Feedback.
+    struct Fixed {
+        action: &'static str,
+        score: f32,
+        last_reward: Option<f32>,
+    }
+
+    impl Fixed {
+        fn new(action: &'static str, score: f32) -> Self {
+            Self {
+                action,
+                score,
+                last_reward: None,
+            }
+        }
+    }
+
+    impl Policy for Fixed {
+        fn decide(&mut self, _: &Context) -> Decision {
+            Decision {
+                action: self.action.into(),
+                score: self.score,
+                why: vec![self.action.into()],
+                context: None,
+                chosen: None,
+            }
+        }
+        fn feedback(&mut self, _: &Context, _: &str, reward: f32) {
+            self.last_reward = Some(reward);
+        }
+        fn snapshot(&self) -> Value {
+            json!({ "last_reward": self.last_reward })
+        }
+        fn load(&mut self, snapshot: Value) {
+            #[allow(clippy::cast_possible_truncation)]
+            {
+                self.last_reward = snapshot["last_reward"].as_f64().map(|r| r as f32);
+            }
+        }
+    }
+
+    fn ctx() -> Context {
+        Context {
+            kind: "reminder".into(),
+            features: json!({}),
+        }
+    }
+
+    #[test]
+    fn delegates_on_none_and_low_score() {
+        let mut confident = FallbackPolicy::new(
+            Fixed::new("remind.morning", 0.8),
+            Fixed::new("rule.evening", 1.0),
+            0.5,
+        );
+        assert_eq!(confident.decide(&ctx()).action, "remind.morning");
+        assert_eq!(confident.delegated(), 0);
+
+        let mut weak = FallbackPolicy::new(
+            Fixed::new("remind.morning", 0.2),
+            Fixed::new("rule.evening", 1.0),
+            0.5,
+        );
+        let decision = weak.decide(&ctx());
+        assert_eq!(decision.action, "rule.evening");
+        assert_eq!(
+            decision.why,
+            vec![
+                "fallback: primary remind.morning scored 0.20 < 0.50".to_string(),
+                "rule.evening".to_string()
+            ]
+        );
+
+        let mut empty = FallbackPolicy::new(
+            Fixed::new("remind.none", 1.0),
+            Fixed::new("rule.evening", 1.0),
+            0.0,
+        );
+        assert_eq!(empty.decide(&ctx()).action, "rule.evening");
+        assert_eq!(empty.delegated(), 1);
+    }
+
+    #[test]
+    fn feedback_and_snapshot_cover_both_policies() {
+        let mut chain = FallbackPolicy::new(
+            Fixed::new("remind.none", 0.0),
+            Fixed::new("rule.evening", 1.0),
+            0.0,
+        );
+        chain.feedback(&ctx(), "rule.evening", 1.0);
+        let snap = chain.snapshot();
+        assert_eq!(snap["primary"]["last_reward"], json!(1.0));
+        assert_eq!(snap["secondary"]["last_reward"], json!(1.0));
+
+        let mut restored = FallbackPolicy::new(
+            Fixed::new("remind.none", 0.0),
+            Fixed::new("rule.evening", 1.0),
+            0.0,
+        );
+        restored.load(snap);
+        assert_eq!(restored.primary().last_reward, Some(1.0));
+        assert_eq!(restored.secondary().last_reward, Some(1.0));
+    }
+}
@@ -0,0 +1,303 @@
+//! Serialisierbare Filterausdrücke für Events und Kontexte.
+//!
+//! Ein [`EventFilter`] beschreibt, welche Events (bzw. Kontexte) relevant
+//! sind, und lässt sich als JSON in einer Konfiguration ablegen:
+//!
+//! ```json
+//! { "and": [
+//!     { "type": "sensor.*" },
+//!     { "not": { "tag": "test" } },
+//!     { "feature": { "path": "battery.level", "op": "lt", "value": 20 } }
+//! ] }
+//! ```
+//!
+//! `type`, `source` und `kind` sind Glob-Muster wie in [`crate::kind`].
+//! Gegen einen [`Context`] gelesen stammen `type`, `source` und `tag` aus den
+//! gleichnamigen Merkmalen (`features.type`, `features.source`,
+//! `features.tags`), gegen ein [`AussenEvent`] wird `kind` mit dem
+//! [`KindMapper::default`] abgeleitet.
+
+use crate::event::AussenEvent;
+use crate::kind::{glob_match, KindMapper};
+use crate::{Context, HeimlernError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::borrow::Cow;
+
+/// Vergleich eines Merkmals mit einem Wert.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Compare {
+    /// Merkmal vorhanden (der Vergleichswert wird ignoriert).
+    Exists,
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    /// Merkmal ist eine Liste, die den Wert enthält, oder ein String, der ihn
+    /// als Teilstring enthält.
+    Contains,
+}
+
+/// Bedingung an ein einzelnes Merkmal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeatureTest {
+    /// Punktpfad wie bei [`Context::feature`].
+    pub path: String,
+    #[serde(default = "default_compare")]
+    pub op: Compare,
+    #[serde(default)]
+    pub value: Value,
+}
+
+fn default_compare() -> Compare {
+    Compare::Exists
+}
+
+/// Filterausdruck.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventFilter {
+    /// Alle Teilausdrücke treffen zu (leer: immer wahr).
+    And(Vec<EventFilter>),
+    /// Mindestens ein Teilausdruck trifft zu (leer: nie wahr).
+    Or(Vec<EventFilter>),
+    Not(Box<EventFilter>),
+    /// Event-Typ passt auf das Muster.
+    Type(String),
+    /// Quelle passt auf das Muster.
+    Source(String),
+    /// Kontext-Art passt auf das Muster.
+    Kind(String),
+    /// Tag ist gesetzt (exakter Vergleich).
+    Tag(String),
+    Feature(FeatureTest),
+}
+
+impl EventFilter {
+    /// Lädt einen Filter aus JSON.
+    ///
+    /// # Errors
+    /// Contract-Fehler bei ungültigem JSON oder unbekannten Ausdrücken.
+    pub fn from_json(json: &str) -> Result<Self, HeimlernError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Filter, der alles durchlässt.
+    #[must_use]
+    pub fn always() -> Self {
+        Self::And(Vec::new())
+    }
+
+    #[must_use]
+    pub fn and(self, other: Self) -> Self {
+        match self {
+            Self::And(mut all) => {
+                all.push(other);
+                Self::And(all)
+            }
+            first => Self::And(vec![first, other]),
+        }
+    }
+
+    #[must_use]
+    pub fn or(self, other: Self) -> Self {
+        match self {
+            Self::Or(mut any) => {
+                any.push(other);
+                Self::Or(any)
+            }
+            first => Self::Or(vec![first, other]),
+        }
+    }
+
+    #[must_use]
+    pub fn negate(self) -> Self {
+        Self::Not(Box::new(self))
+    }
+
+    /// Wertet den Filter gegen ein Event aus.
+    #[must_use]
+    pub fn matches_event(&self, event: &AussenEvent) -> bool {
+        let features = event
+            .features
+            .as_ref()
+            .map(|f| Value::Object(f.iter().map(|(k, v)| (k.clone(), v.clone())).collect()))
+            .unwrap_or(Value::Null);
+        self.eval(&Subject {
+            event_type: Some(&event.r#type),
+            source: Some(&event.source),
+            kind: KindMapper::default().infer_event(event),
+            tags: Tags::Event(event.tags.as_deref().unwrap_or_default()),
+            ctx: Cow::Owned(Context {
+                kind: String::new(),
+                features,
+            }),
+        })
+    }
+
+    /// Wertet den Filter gegen einen Kontext aus.
+    #[must_use]
+    pub fn matches_context(&self, ctx: &Context) -> bool {
+        self.eval(&Subject {
+            event_type: ctx.feature_str("type"),
+            source: ctx.feature_str("source"),
+            kind: &ctx.kind,
+            tags: Tags::Value(ctx.feature("tags")),
+            ctx: Cow::Borrowed(ctx),
+        })
+    }
+
+    fn eval(&self, subject: &Subject<'_>) -> bool {
+        match self {
+            Self::And(all) => all.iter().all(|f| f.eval(subject)),
+            Self::Or(any) => any.iter().any(|f| f.eval(subject)),
+            Self::Not(inner) => !inner.eval(subject),
+            Self::Type(pattern) => subject.event_type.is_some_and(|t| glob_match(pattern, t)),
+            Self::Source(pattern) => subject.source.is_some_and(|s| glob_match(pattern, s)),
+            Self::Kind(pattern) => glob_match(pattern, subject.kind),
+            Self::Tag(tag) => subject.tags.contains(tag),
+            Self::Feature(test) => test.eval(&subject.ctx),
+        }
+    }
+}
+
+impl FeatureTest {
+    fn eval(&self, ctx: &Context) -> bool {
+        let Some(actual) = ctx.feature(&self.path) else {
+            return false;
+        };
+        let ordered = |accept: fn(std::cmp::Ordering) -> bool| {
+            let (Some(a), Some(b)) = (ctx.feature_f64(&self.path), as_f64(&self.value)) else {
+                return false;
+            };
+            a.partial_cmp(&b).is_some_and(accept)
+        };
+        match self.op {
+            Compare::Exists => true,
+            Compare::Eq => equal(actual, &self.value),
+            Compare::Ne => !equal(actual, &self.value),
+            Compare::Gt => ordered(std::cmp::Ordering::is_gt),
+            Compare::Gte => ordered(std::cmp::Ordering::is_ge),
+            Compare::Lt => ordered(std::cmp::Ordering::is_lt),
+            Compare::Lte => ordered(std::cmp::Ordering::is_le),
+            Compare::Contains => match (actual, &self.value) {
+                (Value::Array(items), needle) => items.iter().any(|v| equal(v, needle)),
+                (Value::String(text), Value::String(needle)) => text.contains(needle.as_str()),
+                _ => false,
+            },
+        }
+    }
+}
+
+/// Zahlen werden numerisch verglichen (`1` gleich `1.0`), alles andere als JSON.
+fn equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => x.as_f64() == y.as_f64(),
+        _ => a == b,
+    }
+}
+
+fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+    .filter(|x: &f64| x.is_finite())
+}
+
+enum Tags<'a> {
+    Event(&'a [String]),
+    Value(Option<&'a Value>),
+}
+
+impl Tags<'_> {
+    fn contains(&self, tag: &str) -> bool {
+        match self {
+            Self::Event(tags) => tags.iter().any(|t| t == tag),
+            Self::Value(Some(Value::Array(tags))) => tags.iter().any(|t| t.as_str() == Some(tag)),
+            Self::Value(_) => false,
+        }
+    }
+}
+
+struct Subject<'a> {
+    event_type: Option<&'a str>,
+    source: Option<&'a str>,
+    kind: &'a str,
+    tags: Tags<'a>,
+    ctx: Cow<'a, Context>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event() -> AussenEvent {
+        serde_json::from_value(json!({
+            "type": "sensor.battery",
+            "source": "home-assistant",
+            "tags": ["urgent"],
+            "features": { "battery": { "level": 12 }, "rooms": ["flur", "bad"] }
+        }))
+        .unwrap_or_else(|e| panic!("fixture: {e}"))
+    }
+
+    #[test]
+    fn filters_load_from_json_and_match_events() -> Result<(), HeimlernError> {
+        let filter = EventFilter::from_json(
+            r#"{ "and": [
+                { "type": "sensor.*" },
+                { "not": { "tag": "test" } },
+                { "or": [
+                    { "feature": { "path": "battery.level", "op": "lt", "value": 20 } },
+                    { "source": "rss:*" }
+                ] },
+                { "feature": { "path": "rooms", "op": "contains", "value": "bad" } }
+            ] }"#,
+        )?;
+        let mut event = event();
+        assert!(filter.matches_event(&event));
+
+        event.features = Some([("battery".to_string(), json!({ "level": 80 }))].into());
+        assert!(!filter.matches_event(&event));
+
+        let missing = EventFilter::Feature(FeatureTest {
+            path: "battery.voltage".into(),
+            op: Compare::Ne,
+            value: json!(3),
+        });
+        assert!(!missing.matches_event(&event));
+        assert!(EventFilter::from_json(r#"{ "near": "flur" }"#).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn filters_read_contexts_through_features() {
+        let ctx = Context {
+            kind: "environment".into(),
+            features: json!({ "source": "home-assistant", "tags": ["urgent"], "hour": "22" }),
+        };
+        let filter = EventFilter::Kind("env*".into())
+            .and(EventFilter::Source("home-*".into()))
+            .and(EventFilter::Tag("urgent".into()))
+            .and(EventFilter::Feature(FeatureTest {
+                path: "hour".into(),
+                op: Compare::Gte,
+                value: json!(22),
+            }));
+        assert!(filter.matches_context(&ctx));
+        assert!(!EventFilter::Type("sensor.*".into()).matches_context(&ctx));
+        assert!(EventFilter::always().matches_context(&ctx));
+        assert!(!filter.negate().matches_context(&ctx));
+        assert_eq!(
+            serde_json::to_value(EventFilter::Tag("a".into()).or(EventFilter::Kind("b".into())))
+                .ok(),
+            Some(json!({ "or": [{ "tag": "a" }, { "kind": "b" }] }))
+        );
+    }
+}
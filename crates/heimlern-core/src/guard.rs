@@ -0,0 +1,349 @@
+//! Deklarative Leitplanken für beliebige Policies.
+//!
+//! [`GuardedPolicy`] prüft jede Entscheidung der inneren Policy gegen
+//! [`GuardRules`], bevor sie den Aufrufer erreicht:
+//!
+//! - **Verbotene Aktionen** je Kontext-Art (`"*"` gilt für alle Arten),
+//! - **Ruhezeiten**, in denen jede Aktion unterdrückt wird,
+//! - eine **Obergrenze** für den Score.
+//!
+//! Verstöße gegen Verbote oder Ruhezeiten werden auf `fallback_action`
+//! umgeschrieben, eine zu hohe Bewertung wird gekappt. Jeder Eingriff landet
+//! als Eintrag in `why`. Die Regeln sind (de)serialisierbar und lassen sich
+//! mit [`GuardRules::from_json`] laden.
+//!
+//! Core kennt keine Uhr: Die lokale Stunde für die Ruhezeiten liest der
+//! Wrapper aus `features[<hour_feature>]` (Standard `"hour"`). Fehlt sie, greifen
+//! die Ruhezeiten nicht.
+
+use crate::{Context, Decision, HeimlernError, Policy, PolicyDescriptor};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Schlüssel in [`GuardRules::forbidden`], der für jede Kontext-Art gilt.
+pub const ANY_KIND: &str = "*";
+
+/// Ruhezeit als halboffenes Stundenintervall `[start, end)`; `start > end`
+/// überspannt Mitternacht (z. B. 22 → 7).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub start: u8,
+    pub end: u8,
+    /// Feature mit der lokalen Stunde (0–23).
+    #[serde(default = "default_hour_feature")]
+    pub hour_feature: String,
+}
+
+fn default_hour_feature() -> String {
+    "hour".to_string()
+}
+
+impl QuietHours {
+    #[must_use]
+    pub fn new(start: u8, end: u8) -> Self {
+        Self {
+            start,
+            end,
+            hour_feature: default_hour_feature(),
+        }
+    }
+
+    /// `true`, wenn `hour` in die Ruhezeit fällt.
+    #[must_use]
+    pub fn contains(&self, hour: u8) -> bool {
+        if self.start <= self.end {
+            (self.start..self.end).contains(&hour)
+        } else {
+            hour >= self.start || hour < self.end
+        }
+    }
+
+    fn hour(&self, ctx: &Context) -> Option<u8> {
+        let hour = ctx.features.get(&self.hour_feature)?.as_u64()?;
+        u8::try_from(hour).ok().filter(|h| *h < 24)
+    }
+}
+
+/// Regelwerk eines [`GuardedPolicy`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuardRules {
+    /// Verbotene Aktionen je Kontext-Art.
+    #[serde(default)]
+    pub forbidden: BTreeMap<String, BTreeSet<String>>,
+    /// Höchster Score, den eine Entscheidung tragen darf.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_score: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quiet_hours: Option<QuietHours>,
+    /// Aktion, auf die verletzende Entscheidungen umgeschrieben werden.
+    #[serde(default = "default_fallback_action")]
+    pub fallback_action: String,
+}
+
+fn default_fallback_action() -> String {
+    "remind.none".to_string()
+}
+
+impl Default for GuardRules {
+    fn default() -> Self {
+        Self {
+            forbidden: BTreeMap::new(),
+            max_score: None,
+            quiet_hours: None,
+            fallback_action: default_fallback_action(),
+        }
+    }
+}
+
+impl GuardRules {
+    /// Liest und prüft Regeln aus JSON.
+    ///
+    /// # Errors
+    /// Contract-Fehler bei ungültigem JSON, Stunden ≥ 24, nicht endlicher
+    /// Obergrenze oder leerer `fallback_action`.
+    pub fn from_json(json: &str) -> Result<Self, HeimlernError> {
+        let rules: Self = serde_json::from_str(json)?;
+        rules.validate()?;
+        Ok(rules)
+    }
+
+    /// Verbietet `action` für Kontexte der Art `kind` ([`ANY_KIND`] für alle).
+    #[must_use]
+    pub fn forbid(mut self, kind: impl Into<String>, action: impl Into<String>) -> Self {
+        self.forbidden
+            .entry(kind.into())
+            .or_default()
+            .insert(action.into());
+        self
+    }
+
+    #[must_use]
+    pub fn max_score(mut self, max: f32) -> Self {
+        self.max_score = Some(max);
+        self
+    }
+
+    #[must_use]
+    pub fn quiet_hours(mut self, quiet: QuietHours) -> Self {
+        self.quiet_hours = Some(quiet);
+        self
+    }
+
+    #[must_use]
+    pub fn fallback_action(mut self, action: impl Into<String>) -> Self {
+        self.fallback_action = action.into();
+        self
+    }
+
+    /// # Errors
+    /// Siehe [`GuardRules::from_json`].
+    pub fn validate(&self) -> Result<(), HeimlernError> {
+        if let Some(q) = &self.quiet_hours {
+            if q.start >= 24 || q.end >= 24 {
+                return Err(HeimlernError::contract(format!(
+                    "quiet hours {}–{} outside 0..24",
+                    q.start, q.end
+                )));
+            }
+        }
+        if self.max_score.is_some_and(|m| !m.is_finite()) {
+            return Err(HeimlernError::contract("max_score must be finite"));
+        }
+        if self.fallback_action.is_empty() {
+            return Err(HeimlernError::contract("fallback_action must not be empty"));
+        }
+        Ok(())
+    }
+
+    fn is_forbidden(&self, kind: &str, action: &str) -> bool {
+        [kind, ANY_KIND]
+            .iter()
+            .filter_map(|k| self.forbidden.get(*k))
+            .any(|actions| actions.contains(action))
+    }
+}
+
+/// Policy-Wrapper, der Entscheidungen gegen [`GuardRules`] prüft.
+#[derive(Debug)]
+pub struct GuardedPolicy<P> {
+    inner: P,
+    rules: GuardRules,
+    interventions: u64,
+}
+
+impl<P: Policy> GuardedPolicy<P> {
+    pub fn new(inner: P, rules: GuardRules) -> Self {
+        Self {
+            inner,
+            rules,
+            interventions: 0,
+        }
+    }
+
+    pub fn rules(&self) -> &GuardRules {
+        &self.rules
+    }
+
+    /// Tauscht das Regelwerk zur Laufzeit aus.
+    pub fn set_rules(&mut self, rules: GuardRules) {
+        self.rules = rules;
+    }
+
+    /// Anzahl der umgeschriebenen oder gekappten Entscheidungen.
+    pub fn interventions(&self) -> u64 {
+        self.interventions
+    }
+
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+
+    fn rewrite(&self, decision: &mut Decision, reason: String) {
+        decision.why.push(format!(
+            "guard: {reason}, {} → {}",
+            decision.action, self.rules.fallback_action
+        ));
+        decision.action.clone_from(&self.rules.fallback_action);
+        decision.score = 0.0;
+        decision.chosen = None;
+    }
+}
+
+impl<P: Policy> Policy for GuardedPolicy<P> {
+    fn decide(&mut self, ctx: &Context) -> Decision {
+        let mut decision = self.inner.decide(ctx);
+        if decision.action == self.rules.fallback_action {
+            return decision;
+        }
+        let quiet = self
+            .rules
+            .quiet_hours
+            .as_ref()
+            .and_then(|q| q.hour(ctx).filter(|h| q.contains(*h)));
+        if let Some(hour) = quiet {
+            self.rewrite(&mut decision, format!("quiet hours at {hour}:00"));
+            self.interventions += 1;
+        } else if self.rules.is_forbidden(&ctx.kind, &decision.action) {
+            self.rewrite(&mut decision, format!("forbidden for {}", ctx.kind));
+            self.interventions += 1;
+        } else if let Some(max) = self.rules.max_score.filter(|m| decision.score > *m) {
+            decision.why.push(format!(
+                "guard: score {:.2} capped at {max:.2}",
+                decision.score
+            ));
+            decision.score = max;
+            self.interventions += 1;
+        }
+        decision
+    }
+
+    fn feedback(&mut self, ctx: &Context, action: &str, reward: f32) {
+        self.inner.feedback(ctx, action, reward);
+    }
+
+    fn feedback_batch(&mut self, items: &[(Context, String, f32)]) {
+        self.inner.feedback_batch(items);
+    }
+
+    fn snapshot(&self) -> Value {
+        self.inner.snapshot()
+    }
+
+    fn load(&mut self, snapshot: Value) {
+        self.inner.load(snapshot);
+    }
+
+    fn descriptor(&self) -> PolicyDescriptor {
+        self.inner.descriptor()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    struct Fixed(&'static str, f32);
+
+    impl Policy for Fixed {
+        fn decide(&mut self, _: &Context) -> Decision {
+            Decision {
+                action: self.0.into(),
+                score: self.1,
+                why: vec![],
+                context: None,
+                chosen: None,
+            }
+        }
+        fn feedback(&mut self, _: &Context, _: &str, _: f32) {}
+        fn snapshot(&self) -> Value {
+            Value::Null
+        }
+        fn load(&mut self, _: Value) {}
+    }
+
+    fn ctx(kind: &str, features: Value) -> Context {
+        Context {
+            kind: kind.into(),
+            features,
+        }
+    }
+
+    #[test]
+    fn rules_rewrite_or_cap_violating_decisions() -> Result<(), HeimlernError> {
+        let rules = GuardRules::from_json(
+            r#"{
+                "forbidden": { "routine": ["remind.night"], "*": ["remind.alarm"] },
+                "max_score": 0.5,
+                "quiet_hours": { "start": 22, "end": 7 }
+            }"#,
+        )?;
+        let mut night = GuardedPolicy::new(Fixed("remind.night", 0.9), rules.clone());
+        let decision = night.decide(&ctx("routine", json!({ "hour": 12 })));
+        assert_eq!(decision.action, "remind.none");
+        assert_eq!(
+            decision.why,
+            vec!["guard: forbidden for routine, remind.night → remind.none".to_string()]
+        );
+        let decision = night.decide(&ctx("reminder", json!({ "hour": 12 })));
+        assert_eq!(decision.action, "remind.night");
+        assert!((decision.score - 0.5).abs() < f32::EPSILON);
+
+        let mut alarm = GuardedPolicy::new(Fixed("remind.alarm", 0.1), rules.clone());
+        assert_eq!(
+            alarm.decide(&ctx("reminder", json!({}))).action,
+            "remind.none"
+        );
+
+        let mut quiet = GuardedPolicy::new(Fixed("remind.morning", 0.1), rules);
+        let decision = quiet.decide(&ctx("reminder", json!({ "hour": 23 })));
+        assert_eq!(decision.action, "remind.none");
+        assert!(decision.why[0].starts_with("guard: quiet hours at 23:00"));
+        assert_eq!(
+            quiet.decide(&ctx("reminder", json!({ "hour": 7 }))).action,
+            "remind.morning"
+        );
+        assert_eq!(quiet.interventions(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_rules_are_rejected() {
+        for bad in [
+            r#"{ "quiet_hours": { "start": 22, "end": 24 } }"#,
+            r#"{ "fallback_action": "" }"#,
+            r#"{ "forbidden": ["remind.night"] }"#,
+        ] {
+            assert!(GuardRules::from_json(bad).is_err(), "{bad}");
+        }
+        assert_eq!(
+            GuardRules::from_json("{}").ok(),
+            Some(GuardRules::default())
+        );
+    }
+}
@@ -0,0 +1,170 @@
+//! Regelbasierte Ableitung von [`Context::kind`](crate::Context) aus Event-Typen.
+//!
+//! Der [`KindMapper`] ordnet `AussenEvent.type`-Werte über Glob-Muster (`*`, `?`)
+//! einer Kontext-Kategorie zu, z. B. `sensor.*` → `"environment"`. Die Regeln
+//! werden in Reihenfolge geprüft; die erste passende gewinnt. Passt keine Regel,
+//! wird `fallback` verwendet. Die Konfiguration ist (de)serialisierbar und kann
+//! daher aus JSON geladen werden.
+
+use crate::event::AussenEvent;
+use serde::{Deserialize, Serialize};
+
+/// Kategorie für Umgebungs- und Sensordaten.
+pub const ENVIRONMENT: &str = "environment";
+/// Kategorie für Erinnerungen.
+pub const REMINDER: &str = "reminder";
+/// Kategorie für wiederkehrende Routinen.
+pub const ROUTINE: &str = "routine";
+/// Kategorie für Nutzerinteraktionen.
+pub const INTERACTION: &str = "interaction";
+/// Fallback-Kategorie, wenn keine Regel greift.
+pub const GENERIC: &str = "generic";
+
+/// Eine einzelne Zuordnungsregel `pattern` → `kind`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KindRule {
+    /// Glob-Muster über den Event-Typ (`*` = beliebig viele, `?` = genau ein Zeichen).
+    pub pattern: String,
+    /// Kontext-Kategorie, die bei Treffer vergeben wird.
+    pub kind: String,
+}
+
+/// Geordnete Regelmenge zur Ableitung der Kontext-Kategorie.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KindMapper {
+    /// Regeln in Prüfreihenfolge.
+    #[serde(default)]
+    pub rules: Vec<KindRule>,
+    /// Kategorie, wenn keine Regel passt.
+    #[serde(default = "default_fallback")]
+    pub fallback: String,
+}
+
+fn default_fallback() -> String {
+    GENERIC.to_string()
+}
+
+impl Default for KindMapper {
+    /// Standardregeln für die im Heimgewebe üblichen Event-Familien.
+    fn default() -> Self {
+        Self::new(GENERIC)
+            .rule("sensor.*", ENVIRONMENT)
+            .rule("reminder.*", REMINDER)
+            .rule("routine.*", ROUTINE)
+            .rule("user.*", INTERACTION)
+    }
+}
+
+impl KindMapper {
+    /// Leerer Mapper, der immer `fallback` liefert.
+    #[must_use]
+    pub fn new(fallback: impl Into<String>) -> Self {
+        Self {
+            rules: Vec::new(),
+            fallback: fallback.into(),
+        }
+    }
+
+    /// Hängt eine Regel an (niedrigere Priorität als bestehende Regeln).
+    #[must_use]
+    pub fn rule(mut self, pattern: impl Into<String>, kind: impl Into<String>) -> Self {
+        self.rules.push(KindRule {
+            pattern: pattern.into(),
+            kind: kind.into(),
+        });
+        self
+    }
+
+    /// Leitet die Kategorie für einen Event-Typ ab.
+    #[must_use]
+    pub fn infer(&self, event_type: &str) -> &str {
+        self.rules
+            .iter()
+            .find(|r| glob_match(&r.pattern, event_type))
+            .map_or(self.fallback.as_str(), |r| r.kind.as_str())
+    }
+
+    /// Leitet die Kategorie für ein [`AussenEvent`] ab.
+    #[must_use]
+    pub fn infer_event(&self, event: &AussenEvent) -> &str {
+        self.infer(&event.r#type)
+    }
+}
+
+/// Prüft, ob `text` auf das Glob-Muster `pattern` passt.
+///
+/// Unterstützt `*` (beliebige, auch leere Zeichenfolge) und `?` (genau ein
+/// Zeichen). Alle anderen Zeichen werden wörtlich verglichen.
+#[must_use]
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0, 0);
+    // Position des letzten `*` im Muster und der zugehörige Text-Index.
+    let mut star: Option<(usize, usize)> = None;
+
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if let Some((sp, st)) = star {
+            pi = sp + 1;
+            ti = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_matching_rules() {
+        assert!(glob_match("sensor.*", "sensor.reading"));
+        assert!(glob_match("sensor.*", "sensor."));
+        assert!(!glob_match("sensor.*", "sensors.reading"));
+        assert!(glob_match("*.reading", "sensor.reading"));
+        assert!(glob_match("s?nsor.*", "sensor.x"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("a*b*c", "a-x-b-y-c"));
+        assert!(!glob_match("a*b*c", "a-x-c"));
+        assert!(glob_match("link", "link"));
+        assert!(!glob_match("link", "links"));
+    }
+
+    #[test]
+    fn default_mapper_infers_known_families() {
+        let mapper = KindMapper::default();
+        assert_eq!(mapper.infer("sensor.reading"), ENVIRONMENT);
+        assert_eq!(mapper.infer("reminder.due"), REMINDER);
+        assert_eq!(mapper.infer("routine.started"), ROUTINE);
+        assert_eq!(mapper.infer("link"), GENERIC);
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let mapper = KindMapper::new("other")
+            .rule("sensor.door.*", "security")
+            .rule("sensor.*", ENVIRONMENT);
+        assert_eq!(mapper.infer("sensor.door.open"), "security");
+        assert_eq!(mapper.infer("sensor.temp"), ENVIRONMENT);
+        assert_eq!(mapper.infer("foo"), "other");
+    }
+
+    #[test]
+    fn mapper_loads_from_json() -> Result<(), Box<dyn std::error::Error>> {
+        let mapper: KindMapper = serde_json::from_value(serde_json::json!({
+            "rules": [{ "pattern": "calendar.*", "kind": "reminder" }]
+        }))?;
+        assert_eq!(mapper.infer("calendar.event"), REMINDER);
+        assert_eq!(mapper.fallback, GENERIC);
+        Ok(())
+    }
+}
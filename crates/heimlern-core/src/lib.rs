@@ -7,8 +7,34 @@
 //! werden. Alle Typen sind `Serialize`/`Deserialize`, damit sie in JSON-basierte
 //! APIs, Persistenzschichten oder Tests eingebettet werden können.
 
+pub mod blackboard;
+pub mod budget;
+pub mod canonical;
+pub mod compat;
+pub mod contracts;
+pub mod dedup;
+pub mod descriptor;
+pub mod enrich;
+pub mod ensemble;
+pub mod envelope;
+pub mod error;
 pub mod event;
+pub mod fallback;
+pub mod filter;
+pub mod guard;
+pub mod kind;
+pub mod mapping;
+pub mod metrics;
 pub mod ola;
+pub mod pending;
+#[cfg(feature = "scripting")]
+pub mod reward_script;
+pub mod shaping;
+#[cfg(feature = "telemetry")]
+pub mod telemetry;
+
+pub use descriptor::PolicyDescriptor;
+pub use error::{ErrorKind, HeimlernError, PolicyError};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -23,10 +49,140 @@ pub struct Context {
     pub features: Value,
 }
 
+/// Typisierte Zugriffe auf [`Context::features`].
+///
+/// Pfade sind punktgetrennt (`"weather.temp"`); ein numerisches Segment
+/// indiziert Arrays (`"readings.0"`). Umwandlungen:
+///
+/// - Zahlen: JSON-Zahlen, Wahrheitswerte als `0`/`1` und Strings, die sich
+///   als Zahl lesen lassen; nicht endliche Werte ergeben `None`.
+/// - Wahrheitswerte: JSON-Bools, Zahlen (`≠ 0`) sowie `"true"`/`"false"`
+///   und `"1"`/`"0"`.
+/// - Strings: nur JSON-Strings.
+impl Context {
+    /// Rohwert unter `path`.
+    #[must_use]
+    pub fn feature(&self, path: &str) -> Option<&Value> {
+        path.split('.').try_fold(&self.features, |v, key| match v {
+            Value::Object(map) => map.get(key),
+            Value::Array(items) => items.get(key.parse::<usize>().ok()?),
+            _ => None,
+        })
+    }
+
+    #[must_use]
+    pub fn feature_f64(&self, path: &str) -> Option<f64> {
+        let value = match self.feature(path)? {
+            Value::Number(n) => n.as_f64(),
+            Value::Bool(b) => Some(f64::from(u8::from(*b))),
+            Value::String(s) => s.trim().parse::<f64>().ok(),
+            _ => None,
+        };
+        value.filter(|x| x.is_finite())
+    }
+
+    #[must_use]
+    pub fn feature_f32(&self, path: &str) -> Option<f32> {
+        #[allow(clippy::cast_possible_truncation)]
+        self.feature_f64(path)
+            .map(|x| x as f32)
+            .filter(|x| x.is_finite())
+    }
+
+    #[must_use]
+    pub fn feature_str(&self, path: &str) -> Option<&str> {
+        self.feature(path)?.as_str()
+    }
+
+    #[must_use]
+    pub fn feature_bool(&self, path: &str) -> Option<bool> {
+        match self.feature(path)? {
+            Value::Bool(b) => Some(*b),
+            Value::Number(n) => n.as_f64().map(|x| x != 0.0),
+            Value::String(s) => match s.trim().to_ascii_lowercase().as_str() {
+                "true" | "1" => Some(true),
+                "false" | "0" => Some(false),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
 /// Struktur für das `chosen`-Feld, wie vom Schema gefordert.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Chosen {
     pub action: String,
+    /// Unsicherheit der Wertschätzung für die gewählte Aktion.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uncertainty: Option<Uncertainty>,
+    /// Kennung der Entscheidung, auf die sich spätere Outcomes beziehen.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decision_id: Option<String>,
+    /// Policy-Instanz, die entschieden hat.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy_id: Option<String>,
+    /// Event, das die Entscheidung ausgelöst hat.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_event_id: Option<String>,
+}
+
+impl Chosen {
+    #[must_use]
+    pub fn new(action: impl Into<String>) -> Self {
+        Self {
+            action: action.into(),
+            uncertainty: None,
+            decision_id: None,
+            policy_id: None,
+            parent_event_id: None,
+        }
+    }
+
+    /// Hängt eine Unsicherheitsangabe an.
+    #[must_use]
+    pub fn with_uncertainty(mut self, uncertainty: Uncertainty) -> Self {
+        self.uncertainty = Some(uncertainty);
+        self
+    }
+}
+
+/// Unsicherheit der Wertschätzung eines Arms.
+///
+/// Aufrufer können Entscheidungen mit breitem Intervall an den Menschen
+/// zurückgeben, statt automatisch zu handeln.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct Uncertainty {
+    /// Anzahl der Beobachtungen, auf denen die Schätzung beruht.
+    pub pulls: u64,
+    /// Standardfehler (bzw. Posterior-Standardabweichung) der Schätzung.
+    pub std_error: f64,
+    /// Untere Grenze des 95-%-Intervalls.
+    pub lower: f64,
+    /// Obere Grenze des 95-%-Intervalls.
+    pub upper: f64,
+}
+
+impl Uncertainty {
+    /// z-Wert für ein zweiseitiges 95-%-Intervall.
+    pub const Z_95: f64 = 1.96;
+
+    /// Normalapproximation: `mean ± 1.96 · std_error`.
+    #[must_use]
+    pub fn normal(pulls: u64, mean: f64, std_error: f64) -> Self {
+        Self {
+            pulls,
+            std_error,
+            lower: mean - Self::Z_95 * std_error,
+            upper: mean + Self::Z_95 * std_error,
+        }
+    }
+
+    /// Breite des Intervalls.
+    #[must_use]
+    pub fn width(&self) -> f64 {
+        self.upper - self.lower
+    }
 }
 
 /// Antwort einer Policy auf einen gegebenen [`Context`].
@@ -49,6 +205,81 @@ pub struct Decision {
     pub chosen: Option<Chosen>,
 }
 
+impl Decision {
+    /// Unsicherheit der gewählten Aktion, falls die Policy sie angibt.
+    #[must_use]
+    pub fn uncertainty(&self) -> Option<&Uncertainty> {
+        self.chosen.as_ref()?.uncertainty.as_ref()
+    }
+
+    /// 95-%-Konfidenzintervall `(untere, obere Grenze)` der Wertschätzung
+    /// für die gewählte Aktion.
+    ///
+    /// Das `decision`-Objekt des Contracts ist geschlossen
+    /// (`additionalProperties: false`); das Intervall reist deshalb in
+    /// `chosen.uncertainty` mit, wo zusätzliche Felder erlaubt sind.
+    #[must_use]
+    pub fn confidence_interval(&self) -> Option<(f64, f64)> {
+        self.uncertainty().map(|u| (u.lower, u.upper))
+    }
+
+    /// Kennung der Entscheidung für die Zuordnung späterer Outcomes.
+    ///
+    /// Wie die Unsicherheit stehen die Korrelationsfelder in `chosen`, weil
+    /// das `decision`-Objekt des Contracts keine weiteren Felder zulässt.
+    #[must_use]
+    pub fn decision_id(&self) -> Option<&str> {
+        self.chosen.as_ref()?.decision_id.as_deref()
+    }
+
+    /// Kennung der Policy, die entschieden hat (wie
+    /// [`PolicyDescriptor::policy_id`]).
+    #[must_use]
+    pub fn policy_id(&self) -> Option<&str> {
+        self.chosen.as_ref()?.policy_id.as_deref()
+    }
+
+    /// Kennung des Events, das die Entscheidung ausgelöst hat.
+    #[must_use]
+    pub fn parent_event_id(&self) -> Option<&str> {
+        self.chosen.as_ref()?.parent_event_id.as_deref()
+    }
+
+    /// Setzt die Kennung der Entscheidung.
+    #[must_use]
+    pub fn with_decision_id(mut self, id: impl Into<String>) -> Self {
+        self.chosen_mut().decision_id = Some(id.into());
+        self
+    }
+
+    /// Vergibt eine zufällige UUID (v4) als Kennung (Feature `ids`).
+    #[cfg(feature = "ids")]
+    #[must_use]
+    pub fn with_new_id(self) -> Self {
+        self.with_decision_id(uuid::Uuid::new_v4().to_string())
+    }
+
+    /// Setzt die Kennung der entscheidenden Policy.
+    #[must_use]
+    pub fn with_policy_id(mut self, policy_id: impl Into<String>) -> Self {
+        self.chosen_mut().policy_id = Some(policy_id.into());
+        self
+    }
+
+    /// Setzt die Kennung des auslösenden Events.
+    #[must_use]
+    pub fn with_parent_event_id(mut self, event_id: impl Into<String>) -> Self {
+        self.chosen_mut().parent_event_id = Some(event_id.into());
+        self
+    }
+
+    fn chosen_mut(&mut self) -> &mut Chosen {
+        let action = &self.action;
+        self.chosen
+            .get_or_insert_with(|| Chosen::new(action.clone()))
+    }
+}
+
 mod one_or_many {
     use serde::{Deserialize, Deserializer};
 
@@ -76,14 +307,58 @@ pub trait Policy {
     /// Wählt eine [`Decision`] für den übergebenen [`Context`].
     fn decide(&mut self, ctx: &Context) -> Decision;
 
+    /// Entscheidet für viele Kontexte auf einmal, etwa nach einem Ingest.
+    ///
+    /// Das Ergebnis entspricht wiederholtem [`Policy::decide`] in
+    /// Reihenfolge; Policies können gemeinsame Vorarbeit (Bereinigung,
+    /// Zufallsgenerator) dabei nur einmal erledigen.
+    fn decide_batch(&mut self, ctxs: &[Context]) -> Vec<Decision> {
+        ctxs.iter().map(|ctx| self.decide(ctx)).collect()
+    }
+
     /// Liefert Rückmeldung über das Ergebnis einer vorherigen Entscheidung.
     fn feedback(&mut self, ctx: &Context, action: &str, reward: f32);
 
+    /// Liefert viele Rückmeldungen auf einmal, etwa beim Import aus der Chronik.
+    ///
+    /// Die Default-Implementierung ruft [`Policy::feedback`] je Eintrag in
+    /// Reihenfolge auf; Policies können das für große Batches beschleunigen.
+    fn feedback_batch(&mut self, items: &[(Context, String, f32)]) {
+        for (ctx, action, reward) in items {
+            self.feedback(ctx, action, *reward);
+        }
+    }
+
     /// Exportiert den aktuellen internen Zustand als JSON-Snapshot.
     fn snapshot(&self) -> Value;
 
     /// Lädt einen zuvor erzeugten JSON-Snapshot wieder in die Policy.
     fn load(&mut self, snapshot: Value);
+
+    /// Beschreibt Kontext-Arten, justierbare Parameter und Snapshot-Version.
+    ///
+    /// Die Default-Implementierung liefert einen leeren Deskriptor ohne
+    /// justierbare Parameter.
+    fn descriptor(&self) -> PolicyDescriptor {
+        PolicyDescriptor::default()
+    }
+}
+
+/// Fehlbare Variante von [`Policy`] für Aufrufer, die auf Fehler reagieren wollen.
+///
+/// [`Policy`] fällt bei ungültigen Eingaben auf eine sichere Entscheidung
+/// zurück oder loggt und ignoriert sie; die `try_*`-Methoden melden denselben
+/// Fall als [`PolicyError`] und lassen den Zustand dabei unverändert.
+pub trait TryPolicy: Policy {
+    /// Wie [`Policy::decide`], aber ohne Rückfallentscheidung.
+    fn try_decide(&mut self, ctx: &Context) -> Result<Decision, PolicyError>;
+
+    /// Wie [`Policy::feedback`], meldet verworfene Rückmeldungen aber.
+    fn try_feedback(&mut self, ctx: &Context, action: &str, reward: f32)
+        -> Result<(), PolicyError>;
+
+    /// Wie [`Policy::load`], meldet abgelehnte Snapshots aber.
+    fn try_load(&mut self, snapshot: Value) -> Result<(), PolicyError>;
 }
 
 // -----------------------
@@ -106,4 +381,119 @@ mod tests {
         assert_eq!(ctx.features["key"], "value");
         Ok(())
     }
+    #[test]
+    fn feature_accessors_follow_paths_and_coerce() {
+        let ctx = Context {
+            kind: "test".to_string(),
+            features: json!({
+                "load": 0.5,
+                "user": "alex",
+                "away": "TRUE",
+                "weather": { "temp": "21.5", "rain": false },
+                "readings": [3, 4],
+                "huge": 1e300
+            }),
+        };
+        assert_eq!(ctx.feature_f32("load"), Some(0.5));
+        assert_eq!(ctx.feature_f64("weather.temp"), Some(21.5));
+        assert_eq!(ctx.feature_f64("weather.rain"), Some(0.0));
+        assert_eq!(ctx.feature_f64("readings.1"), Some(4.0));
+        assert_eq!(ctx.feature_f64("user"), None);
+        assert_eq!(ctx.feature_f32("huge"), None);
+        assert_eq!(ctx.feature_str("user"), Some("alex"));
+        assert_eq!(ctx.feature_str("load"), None);
+        assert_eq!(ctx.feature_bool("away"), Some(true));
+        assert_eq!(ctx.feature_bool("load"), Some(true));
+        assert_eq!(ctx.feature_bool("user"), None);
+        assert!(ctx.feature("weather.temp.x").is_none());
+        assert!(ctx.feature("missing").is_none());
+    }
+
+    #[test]
+    fn correlation_ids_travel_in_chosen() -> Result<(), Box<dyn std::error::Error>> {
+        let decision = Decision {
+            action: "remind.none".into(),
+            score: 0.0,
+            why: vec!["no slots".into()],
+            context: None,
+            chosen: None,
+        }
+        .with_decision_id("d-1")
+        .with_policy_id("remind-bandit")
+        .with_parent_event_id("evt-7");
+        let json = serde_json::to_value(&decision)?;
+        assert_eq!(
+            json["chosen"],
+            json!({
+                "action": "remind.none",
+                "decision_id": "d-1",
+                "policy_id": "remind-bandit",
+                "parent_event_id": "evt-7"
+            })
+        );
+        let back: Decision = serde_json::from_value(json)?;
+        assert_eq!(back.decision_id(), Some("d-1"));
+        assert_eq!(back.policy_id(), Some("remind-bandit"));
+        assert_eq!(back.parent_event_id(), Some("evt-7"));
+
+        #[cfg(feature = "ids")]
+        {
+            let id = back.with_new_id();
+            assert_eq!(id.decision_id().map(str::len), Some(36));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn chosen_uncertainty_is_optional() -> Result<(), Box<dyn std::error::Error>> {
+        let legacy: Chosen = serde_json::from_value(json!({"action": "remind.morning"}))?;
+        assert!(legacy.uncertainty.is_none());
+        assert_eq!(
+            serde_json::to_value(&legacy)?,
+            json!({"action": "remind.morning"})
+        );
+
+        let chosen =
+            Chosen::new("remind.morning").with_uncertainty(Uncertainty::normal(10, 0.5, 0.1));
+        let back: Chosen = serde_json::from_value(serde_json::to_value(&chosen)?)?;
+        assert_eq!(back.uncertainty, chosen.uncertainty);
+        assert!((chosen.uncertainty.map_or(0.0, |u| u.width()) - 0.392).abs() < 1e-9);
+
+        let decision = Decision {
+            action: "remind.morning".into(),
+            score: 0.5,
+            why: vec!["exploit".into()],
+            context: None,
+            chosen: Some(chosen),
+        };
+        let Some((lower, upper)) = decision.confidence_interval() else {
+            panic!("interval should be present");
+        };
+        assert!((lower - 0.304).abs() < 1e-9 && (upper - 0.696).abs() < 1e-9);
+        let json = serde_json::to_value(&decision)?;
+        assert!(json.get("confidence_interval").is_none());
+        assert_eq!(json["chosen"]["uncertainty"]["lower"], lower);
+        Ok(())
+    }
+
+    #[test]
+    fn confidence_interval_without_uncertainty_or_observations() {
+        let mut decision = Decision {
+            action: "remind.morning".into(),
+            score: 0.0,
+            why: vec!["fallback".into()],
+            context: None,
+            chosen: None,
+        };
+        assert_eq!(decision.confidence_interval(), None);
+        decision = decision.with_decision_id("d-1");
+        assert!(decision.chosen.is_some());
+        assert_eq!(decision.confidence_interval(), None);
+
+        // Arm ohne Ziehung und ohne Streuung: Intervall der Breite null.
+        decision.chosen =
+            Some(Chosen::new("remind.morning").with_uncertainty(Uncertainty::normal(0, 0.0, 0.0)));
+        assert_eq!(decision.confidence_interval(), Some((0.0, 0.0)));
+        assert_eq!(decision.uncertainty().map(|u| u.pulls), Some(0));
+    }
 }
@@ -0,0 +1,267 @@
+//! Ableitung eines [`Context`] aus einem [`AussenEvent`].
+//!
+//! [`ContextMapping`] bündelt, was bisher jede Integration selbst verdrahtet
+//! hat: die Kontext-Art kommt aus einem [`KindMapper`], die Merkmale aus den
+//! `features` des Events (sofern `passthrough`) plus einer Zuordnung
+//! `Merkmal → Quelle`. Quellen werden als Zeichenketten angegeben, damit die
+//! Zuordnung als JSON gepflegt werden kann:
+//!
+//! | Quelle              | Wert im Kontext                              |
+//! |---------------------|----------------------------------------------|
+//! | `source`, `type`, `title`, `summary`, `url`, `ts`, `id` | das Event-Feld (String) |
+//! | `tags`              | alle Tags als Liste                          |
+//! | `tag:<name>`        | `true`/`false`, ob der Tag gesetzt ist       |
+//! | `features.<key>`    | `features[key]` des Events                   |
+//! | `meta.<key>`        | `meta[key]` des Events                       |
+//!
+//! Fehlt ein Wert im Event, wird das Merkmal ausgelassen.
+
+use crate::event::AussenEvent;
+use crate::kind::KindMapper;
+use crate::Context;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Herkunft eines Merkmals im [`AussenEvent`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum FeatureSource {
+    Source,
+    Type,
+    Title,
+    Summary,
+    Url,
+    Ts,
+    Id,
+    Tags,
+    Tag(String),
+    Feature(String),
+    Meta(String),
+}
+
+impl FeatureSource {
+    fn extract(&self, event: &AussenEvent) -> Option<Value> {
+        let text = |v: &Option<String>| v.as_ref().map(|s| Value::from(s.as_str()));
+        match self {
+            Self::Source => Some(Value::from(event.source.as_str())),
+            Self::Type => Some(Value::from(event.r#type.as_str())),
+            Self::Title => text(&event.title),
+            Self::Summary => text(&event.summary),
+            Self::Url => text(&event.url),
+            Self::Ts => text(&event.ts),
+            Self::Id => text(&event.id),
+            Self::Tags => event.tags.as_ref().map(|t| Value::from(t.clone())),
+            Self::Tag(name) => Some(Value::Bool(
+                event.tags.as_ref().is_some_and(|t| t.contains(name)),
+            )),
+            Self::Feature(key) => event.features.as_ref()?.get(key).cloned(),
+            Self::Meta(key) => event.meta.as_ref()?.get(key).cloned(),
+        }
+    }
+}
+
+impl TryFrom<String> for FeatureSource {
+    type Error = String;
+
+    fn try_from(spec: String) -> Result<Self, Self::Error> {
+        let non_empty = |key: &str| {
+            if key.is_empty() {
+                Err(format!("feature source `{spec}` needs a key"))
+            } else {
+                Ok(key.to_string())
+            }
+        };
+        if let Some(name) = spec.strip_prefix("tag:") {
+            return non_empty(name).map(Self::Tag);
+        }
+        if let Some(key) = spec.strip_prefix("features.") {
+            return non_empty(key).map(Self::Feature);
+        }
+        if let Some(key) = spec.strip_prefix("meta.") {
+            return non_empty(key).map(Self::Meta);
+        }
+        match spec.as_str() {
+            "source" => Ok(Self::Source),
+            "type" => Ok(Self::Type),
+            "title" => Ok(Self::Title),
+            "summary" => Ok(Self::Summary),
+            "url" => Ok(Self::Url),
+            "ts" => Ok(Self::Ts),
+            "id" => Ok(Self::Id),
+            "tags" => Ok(Self::Tags),
+            _ => Err(format!("unknown feature source `{spec}`")),
+        }
+    }
+}
+
+impl fmt::Display for FeatureSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Source => f.write_str("source"),
+            Self::Type => f.write_str("type"),
+            Self::Title => f.write_str("title"),
+            Self::Summary => f.write_str("summary"),
+            Self::Url => f.write_str("url"),
+            Self::Ts => f.write_str("ts"),
+            Self::Id => f.write_str("id"),
+            Self::Tags => f.write_str("tags"),
+            Self::Tag(name) => write!(f, "tag:{name}"),
+            Self::Feature(key) => write!(f, "features.{key}"),
+            Self::Meta(key) => write!(f, "meta.{key}"),
+        }
+    }
+}
+
+impl From<FeatureSource> for String {
+    fn from(source: FeatureSource) -> Self {
+        source.to_string()
+    }
+}
+
+/// Konfiguration für [`Context::from_aussen_event_with`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextMapping {
+    /// Regeln für die Kontext-Art.
+    #[serde(default)]
+    pub kinds: KindMapper,
+    /// Zusätzliche Merkmale; überschreiben gleichnamige durchgereichte.
+    #[serde(default)]
+    pub features: BTreeMap<String, FeatureSource>,
+    /// Übernimmt `features` des Events unverändert in den Kontext.
+    #[serde(default = "default_passthrough")]
+    pub passthrough: bool,
+}
+
+fn default_passthrough() -> bool {
+    true
+}
+
+impl Default for ContextMapping {
+    fn default() -> Self {
+        Self {
+            kinds: KindMapper::default(),
+            features: BTreeMap::new(),
+            passthrough: true,
+        }
+    }
+}
+
+impl ContextMapping {
+    #[must_use]
+    pub fn kinds(mut self, kinds: KindMapper) -> Self {
+        self.kinds = kinds;
+        self
+    }
+
+    /// Legt das Merkmal `key` aus `source` an.
+    #[must_use]
+    pub fn feature(mut self, key: impl Into<String>, source: FeatureSource) -> Self {
+        self.features.insert(key.into(), source);
+        self
+    }
+
+    #[must_use]
+    pub fn passthrough(mut self, passthrough: bool) -> Self {
+        self.passthrough = passthrough;
+        self
+    }
+
+    /// Baut den Kontext für `event`.
+    #[must_use]
+    pub fn context(&self, event: &AussenEvent) -> Context {
+        let mut features = Map::new();
+        if self.passthrough {
+            if let Some(raw) = &event.features {
+                features.extend(raw.iter().map(|(k, v)| (k.clone(), v.clone())));
+            }
+        }
+        for (key, source) in &self.features {
+            if let Some(value) = source.extract(event) {
+                features.insert(key.clone(), value);
+            }
+        }
+        Context {
+            kind: self.kinds.infer_event(event).to_string(),
+            features: Value::Object(features),
+        }
+    }
+}
+
+impl Context {
+    /// Kontext mit den Standardregeln: Art per [`KindMapper::default`],
+    /// Merkmale aus `event.features`.
+    #[must_use]
+    pub fn from_aussen_event(event: &AussenEvent) -> Self {
+        ContextMapping::default().context(event)
+    }
+
+    /// Kontext nach der Zuordnung `mapping`.
+    #[must_use]
+    pub fn from_aussen_event_with(event: &AussenEvent, mapping: &ContextMapping) -> Self {
+        mapping.context(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event() -> AussenEvent {
+        AussenEvent {
+            id: None,
+            r#type: "sensor.door".into(),
+            source: "home-assistant".into(),
+            title: Some("Haustür offen".into()),
+            summary: None,
+            url: None,
+            tags: Some(vec!["urgent".into()]),
+            ts: None,
+            features: Some(BTreeMap::from([("open_minutes".into(), json!(12))])),
+            meta: Some(BTreeMap::from([("room".into(), json!("flur"))])),
+        }
+    }
+
+    #[test]
+    fn default_mapping_passes_features_through() {
+        let ctx = Context::from_aussen_event(&event());
+        assert_eq!(ctx.kind, crate::kind::ENVIRONMENT);
+        assert_eq!(ctx.features, json!({ "open_minutes": 12 }));
+    }
+
+    #[test]
+    fn mapping_loads_from_json_and_extracts_fields() -> Result<(), serde_json::Error> {
+        let mapping: ContextMapping = serde_json::from_value(json!({
+            "kinds": { "rules": [{ "pattern": "sensor.door*", "kind": "security" }] },
+            "features": {
+                "room": "meta.room",
+                "urgent": "tag:urgent",
+                "quiet": "tag:quiet",
+                "source": "source",
+                "summary": "summary"
+            },
+            "passthrough": false
+        }))?;
+        let ctx = Context::from_aussen_event_with(&event(), &mapping);
+        assert_eq!(ctx.kind, "security");
+        assert_eq!(
+            ctx.features,
+            json!({ "room": "flur", "urgent": true, "quiet": false, "source": "home-assistant" })
+        );
+        assert_eq!(
+            serde_json::to_value(&mapping)?["features"]["room"],
+            "meta.room"
+        );
+
+        for bad in ["weather", "tag:", "features."] {
+            assert!(
+                serde_json::from_value::<ContextMapping>(json!({ "features": { "x": bad } }))
+                    .is_err(),
+                "{bad}"
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,226 @@
+//! Zähler für Dashboards.
+//!
+//! [`PolicyMetrics`] ist bewusst schlicht: ein paar Zähler und die Laufzeit
+//! der letzten Entscheidung, als JSON exportierbar. Gepflegt werden sie
+//! entweder von [`MeteredPolicy`] um eine beliebige Policy herum oder direkt
+//! vom Aufrufer bzw. der Policy über die `record_*`-Methoden.
+//!
+//! Als Exploration zählt eine Entscheidung, deren `why` einen Eintrag mit
+//! `"explore"` am Anfang enthält (so kennzeichnen die Banditen ihre
+//! ε-Schritte). Ungültiges Feedback ist eines mit leerer Aktion oder nicht
+//! endlichem Reward; [`MeteredPolicy`] gibt es nicht an die Policy weiter.
+
+use crate::{Context, Decision, HeimlernError, Policy, PolicyDescriptor};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::{Duration, Instant};
+
+/// Zähler einer Policy.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyMetrics {
+    pub decisions_total: u64,
+    pub explores_total: u64,
+    /// Angenommenes Feedback (ohne `invalid_feedback_total`).
+    pub feedback_total: u64,
+    pub invalid_feedback_total: u64,
+    /// Laufzeit des letzten `decide` in Mikrosekunden.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_decide_latency_us: Option<u64>,
+}
+
+impl PolicyMetrics {
+    /// Zählt eine Entscheidung samt ihrer Laufzeit.
+    pub fn record_decision(&mut self, decision: &Decision, latency: Duration) {
+        self.decisions_total += 1;
+        if is_exploration(decision) {
+            self.explores_total += 1;
+        }
+        self.last_decide_latency_us = Some(u64::try_from(latency.as_micros()).unwrap_or(u64::MAX));
+    }
+
+    /// Zählt ein Feedback und meldet, ob es gültig war.
+    pub fn record_feedback(&mut self, action: &str, reward: f32) -> bool {
+        let valid = !action.is_empty() && reward.is_finite();
+        if valid {
+            self.feedback_total += 1;
+        } else {
+            self.invalid_feedback_total += 1;
+        }
+        valid
+    }
+
+    /// Zählt ein Feedback, das die Policy selbst verworfen hat (etwa für
+    /// eine unbekannte Aktion).
+    pub fn record_invalid_feedback(&mut self) {
+        self.invalid_feedback_total += 1;
+    }
+
+    #[must_use]
+    pub fn last_decide_latency(&self) -> Option<Duration> {
+        self.last_decide_latency_us.map(Duration::from_micros)
+    }
+
+    /// Anteil der Explorationsschritte an allen Entscheidungen.
+    #[must_use]
+    pub fn explore_rate(&self) -> Option<f64> {
+        #[allow(clippy::cast_precision_loss)]
+        (self.decisions_total > 0).then(|| self.explores_total as f64 / self.decisions_total as f64)
+    }
+
+    /// # Errors
+    /// Contract-Fehler, falls die Serialisierung scheitert.
+    pub fn to_json(&self) -> Result<Value, HeimlernError> {
+        Ok(serde_json::to_value(self)?)
+    }
+}
+
+fn is_exploration(decision: &Decision) -> bool {
+    decision.why.iter().any(|w| w.starts_with("explore"))
+}
+
+/// Policy-Wrapper, der [`PolicyMetrics`] mitführt.
+#[derive(Debug)]
+pub struct MeteredPolicy<P> {
+    inner: P,
+    metrics: PolicyMetrics,
+}
+
+impl<P: Policy> MeteredPolicy<P> {
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            metrics: PolicyMetrics::default(),
+        }
+    }
+
+    pub fn metrics(&self) -> &PolicyMetrics {
+        &self.metrics
+    }
+
+    /// Liefert die bisherigen Zähler und beginnt von vorn.
+    pub fn take_metrics(&mut self) -> PolicyMetrics {
+        std::mem::take(&mut self.metrics)
+    }
+
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+}
+
+impl<P: Policy> Policy for MeteredPolicy<P> {
+    fn decide(&mut self, ctx: &Context) -> Decision {
+        let start = Instant::now();
+        let decision = self.inner.decide(ctx);
+        self.metrics.record_decision(&decision, start.elapsed());
+        decision
+    }
+
+    fn feedback(&mut self, ctx: &Context, action: &str, reward: f32) {
+        if self.metrics.record_feedback(action, reward) {
+            self.inner.feedback(ctx, action, reward);
+        }
+    }
+
+    fn feedback_batch(&mut self, items: &[(Context, String, f32)]) {
+        let valid: Vec<(Context, String, f32)> = items
+            .iter()
+            .filter(|(_, action, reward)| self.metrics.record_feedback(action, *reward))
+            .cloned()
+            .collect();
+        self.inner.feedback_batch(&valid);
+    }
+
+    fn snapshot(&self) -> Value {
+        self.inner.snapshot()
+    }
+
+    fn load(&mut self, snapshot: Value) {
+        self.inner.load(snapshot);
+    }
+
+    fn descriptor(&self) -> PolicyDescriptor {
+        self.inner.descriptor()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    struct Alternating {
+        calls: u32,
+        rewards: Vec<f32>,
+    }
+
+    impl Policy for Alternating {
+        fn decide(&mut self, _: &Context) -> Decision {
+            self.calls += 1;
+            let why = if self.calls.is_multiple_of(2) {
+                "explore ε"
+            } else {
+                "exploit"
+            };
+            Decision {
+                action: "remind.morning".into(),
+                score: 0.5,
+                why: vec![why.into()],
+                context: None,
+                chosen: None,
+            }
+        }
+        fn feedback(&mut self, _: &Context, _: &str, reward: f32) {
+            self.rewards.push(reward);
+        }
+        fn snapshot(&self) -> Value {
+            Value::Null
+        }
+        fn load(&mut self, _: Value) {}
+    }
+
+    #[test]
+    fn metered_policy_counts_and_filters_feedback() -> Result<(), HeimlernError> {
+        let ctx = Context {
+            kind: "reminder".into(),
+            features: json!({}),
+        };
+        let mut policy = MeteredPolicy::new(Alternating {
+            calls: 0,
+            rewards: Vec::new(),
+        });
+        for _ in 0..4 {
+            policy.decide(&ctx);
+        }
+        policy.feedback(&ctx, "remind.morning", 1.0);
+        policy.feedback(&ctx, "remind.morning", f32::NAN);
+        policy.feedback_batch(&[
+            (ctx.clone(), String::new(), 1.0),
+            (ctx.clone(), "remind.evening".into(), 0.0),
+        ]);
+
+        assert_eq!(policy.inner().rewards, vec![1.0, 0.0]);
+        let metrics = policy.metrics();
+        assert_eq!(metrics.explore_rate(), Some(0.5));
+        assert!(metrics.last_decide_latency().is_some());
+
+        let mut exported = metrics.to_json()?;
+        exported["last_decide_latency_us"] = json!(0);
+        assert_eq!(
+            exported,
+            json!({
+                "decisions_total": 4,
+                "explores_total": 2,
+                "feedback_total": 2,
+                "invalid_feedback_total": 2,
+                "last_decide_latency_us": 0
+            })
+        );
+        assert_eq!(policy.take_metrics().decisions_total, 4);
+        assert_eq!(policy.metrics(), &PolicyMetrics::default());
+        Ok(())
+    }
+}
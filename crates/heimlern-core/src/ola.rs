@@ -4,6 +4,7 @@
 //! scripts may call into it as probes or wrappers, but route-key sanitizing,
 //! reward clamping and state normalization live here.
 
+use crate::kind::KindMapper;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Map, Value};
 use std::fmt;
@@ -316,6 +317,21 @@ pub fn adapt(input_record: &Value) -> Value {
 }
 
 pub fn to_decision_outcome(routing_outcome: &Value, policy_id: &str) -> Value {
+    to_decision_outcome_with(routing_outcome, policy_id, &KindMapper::default())
+}
+
+/// Like [`to_decision_outcome`], with the context `kind` inferred from the
+/// task class by `kinds` instead of the default rules.
+pub fn to_decision_outcome_with(
+    routing_outcome: &Value,
+    policy_id: &str,
+    kinds: &KindMapper,
+) -> Value {
+    let task_class = routing_outcome
+        .get("task_class")
+        .cloned()
+        .unwrap_or(Value::Null);
+    let kind = kinds.infer(task_class.as_str().unwrap_or_default());
     let outcome = string_field(routing_outcome, "outcome", "unknown");
     let success = match outcome.as_str() {
         "success" => true,
@@ -332,7 +348,8 @@ pub fn to_decision_outcome(routing_outcome: &Value, policy_id: &str) -> Value {
         "success": success,
         "reward": routing_outcome.get("reward").cloned().unwrap_or(Value::Null),
         "context": {
-            "task_class": routing_outcome.get("task_class").cloned().unwrap_or(Value::Null),
+            "kind": kind,
+            "task_class": task_class,
             "route_used": route_used
         },
         "metadata": {
@@ -455,5 +472,11 @@ mod tests {
         assert_eq!(decision["policy_id"], DEFAULT_POLICY_ID);
         assert_eq!(decision["action"], "route.direct:patch");
         assert!(decision["success"].as_bool().unwrap_or_default());
+        assert_eq!(decision["context"]["kind"], crate::kind::GENERIC);
+
+        let kinds = KindMapper::default().rule("contract_*", crate::kind::ROUTINE);
+        let decision = to_decision_outcome_with(&routing, DEFAULT_POLICY_ID, &kinds);
+        assert_eq!(decision["context"]["kind"], crate::kind::ROUTINE);
+        assert_eq!(decision["context"]["task_class"], "contract_slice");
     }
 }
@@ -0,0 +1,257 @@
+//! Zuordnung verspäteter Rewards zu ausgegebenen Entscheidungen.
+//!
+//! Outcomes treffen oft erst Stunden nach der Entscheidung ein und tragen
+//! dann nur noch die `decision_id`. [`PendingDecisions`] merkt sich zu jeder
+//! ausgegebenen Entscheidung Aktion und Kontext, sodass der Reward später
+//! über die Kennung allein an [`Policy::feedback`] gehen kann. Einträge
+//! verfallen nach `ttl_seconds`; ein danach eintreffender Reward wird nicht
+//! mehr zugeordnet.
+//!
+//! Der Kontext-Hash (FNV-1a über das kanonische JSON, siehe
+//! [`crate::canonical`]) ist stabil über Prozesse hinweg und eignet sich zum
+//! Abgleich mit Ledger-Einträgen, die den Kontext nicht vollständig führen.
+
+use crate::canonical::to_canonical_string;
+use crate::{Context, Decision, Policy};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Voreinstellung für [`PendingDecisions::default`]: ein Tag.
+pub const DEFAULT_PENDING_TTL_SECONDS: u64 = 24 * 60 * 60;
+
+/// Eine ausgegebene Entscheidung, deren Reward noch aussteht.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingDecision {
+    pub decision_id: String,
+    pub action: String,
+    pub context: Context,
+    pub context_hash: String,
+    /// Ausgabezeitpunkt in Unix-Sekunden.
+    pub ts: i64,
+}
+
+/// Offene Entscheidungen, nach `decision_id` abrufbar.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingDecisions {
+    ttl_seconds: u64,
+    #[serde(default)]
+    pending: HashMap<String, PendingDecision>,
+}
+
+impl Default for PendingDecisions {
+    fn default() -> Self {
+        Self::new(DEFAULT_PENDING_TTL_SECONDS)
+    }
+}
+
+impl PendingDecisions {
+    #[must_use]
+    pub fn new(ttl_seconds: u64) -> Self {
+        Self {
+            ttl_seconds,
+            pending: HashMap::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn ttl_seconds(&self) -> u64 {
+        self.ttl_seconds
+    }
+
+    /// Anzahl der offenen Entscheidungen (einschließlich noch nicht
+    /// entfernter abgelaufener).
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Merkt sich `decision` für `ctx`. Entscheidungen ohne `decision_id`
+    /// lassen sich nicht zuordnen und werden abgelehnt (`false`).
+    pub fn record(&mut self, ctx: &Context, decision: &Decision) -> bool {
+        self.record_at(ctx, decision, unix_now())
+    }
+
+    /// Wie [`PendingDecisions::record`] zum Zeitpunkt `now`.
+    pub fn record_at(&mut self, ctx: &Context, decision: &Decision, now: i64) -> bool {
+        let Some(id) = decision.decision_id().filter(|id| !id.is_empty()) else {
+            return false;
+        };
+        self.pending.insert(
+            id.to_string(),
+            PendingDecision {
+                decision_id: id.to_string(),
+                action: decision.action.clone(),
+                context: ctx.clone(),
+                context_hash: context_hash(ctx),
+                ts: now,
+            },
+        );
+        true
+    }
+
+    /// Die offene Entscheidung `decision_id`, sofern nicht abgelaufen.
+    #[must_use]
+    pub fn get_at(&self, decision_id: &str, now: i64) -> Option<&PendingDecision> {
+        self.pending
+            .get(decision_id)
+            .filter(|p| !self.expired(p.ts, now))
+    }
+
+    /// Entnimmt die offene Entscheidung `decision_id`; abgelaufene werden
+    /// dabei verworfen.
+    pub fn resolve(&mut self, decision_id: &str) -> Option<PendingDecision> {
+        self.resolve_at(decision_id, unix_now())
+    }
+
+    /// Wie [`PendingDecisions::resolve`] zum Zeitpunkt `now`.
+    pub fn resolve_at(&mut self, decision_id: &str, now: i64) -> Option<PendingDecision> {
+        let pending = self.pending.remove(decision_id)?;
+        (!self.expired(pending.ts, now)).then_some(pending)
+    }
+
+    /// Gibt `reward` für `decision_id` mit Aktion und Kontext der
+    /// Entscheidung an `policy` weiter. `false`, wenn die Kennung unbekannt
+    /// oder abgelaufen ist.
+    pub fn feedback<P: Policy + ?Sized>(
+        &mut self,
+        policy: &mut P,
+        decision_id: &str,
+        reward: f32,
+    ) -> bool {
+        self.feedback_at(policy, decision_id, reward, unix_now())
+    }
+
+    /// Wie [`PendingDecisions::feedback`] zum Zeitpunkt `now`.
+    pub fn feedback_at<P: Policy + ?Sized>(
+        &mut self,
+        policy: &mut P,
+        decision_id: &str,
+        reward: f32,
+        now: i64,
+    ) -> bool {
+        match self.resolve_at(decision_id, now) {
+            Some(pending) => {
+                policy.feedback(&pending.context, &pending.action, reward);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Entfernt alle zum Zeitpunkt `now` abgelaufenen Einträge und liefert
+    /// sie zurück, etwa um sie als „kein Outcome“ zu verbuchen.
+    pub fn expire(&mut self, now: i64) -> Vec<PendingDecision> {
+        let expired_ids: Vec<String> = self
+            .pending
+            .values()
+            .filter(|p| self.expired(p.ts, now))
+            .map(|p| p.decision_id.clone())
+            .collect();
+        let mut expired: Vec<PendingDecision> = expired_ids
+            .iter()
+            .filter_map(|id| self.pending.remove(id))
+            .collect();
+        expired.sort_by(|a, b| (a.ts, &a.decision_id).cmp(&(b.ts, &b.decision_id)));
+        expired
+    }
+
+    fn expired(&self, ts: i64, now: i64) -> bool {
+        let ttl = i64::try_from(self.ttl_seconds).unwrap_or(i64::MAX);
+        now.saturating_sub(ts) >= ttl
+    }
+}
+
+/// Stabiler Hash des Kontexts als 16 Hex-Ziffern.
+#[must_use]
+pub fn context_hash(ctx: &Context) -> String {
+    let canonical = to_canonical_string(ctx).unwrap_or_default();
+    let hash = canonical
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{hash:016x}")
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| i64::try_from(d.as_secs()).unwrap_or(i64::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    #[derive(Default)]
+    struct Recorder(Vec<(String, String, f32)>);
+
+    impl Policy for Recorder {
+        fn decide(&mut self, _: &Context) -> Decision {
+            Decision {
+                action: "remind.morning".into(),
+                score: 0.0,
+                why: vec![],
+                context: None,
+                chosen: None,
+            }
+        }
+        fn feedback(&mut self, ctx: &Context, action: &str, reward: f32) {
+            self.0.push((ctx.kind.clone(), action.into(), reward));
+        }
+        fn snapshot(&self) -> Value {
+            Value::Null
+        }
+        fn load(&mut self, _: Value) {}
+    }
+
+    #[test]
+    fn rewards_reach_the_policy_by_decision_id_until_expiry() {
+        let mut policy = Recorder::default();
+        let mut pending = PendingDecisions::new(60);
+        let ctx = Context {
+            kind: "reminder".into(),
+            features: json!({ "b": 1, "a": 2 }),
+        };
+        let decision = policy.decide(&ctx);
+        assert!(!pending.record_at(&ctx, &decision, 0));
+
+        assert!(pending.record_at(&ctx, &decision.clone().with_decision_id("d1"), 0));
+        assert!(pending.record_at(&ctx, &decision.with_decision_id("d2"), 10));
+        assert_eq!(
+            pending.get_at("d1", 30).map(|p| p.context_hash.len()),
+            Some(16)
+        );
+
+        assert!(pending.feedback_at(&mut policy, "d1", 1.0, 30));
+        assert!(!pending.feedback_at(&mut policy, "d1", 1.0, 31));
+        assert!(!pending.feedback_at(&mut policy, "unknown", 1.0, 31));
+        assert_eq!(
+            policy.0,
+            vec![("reminder".to_string(), "remind.morning".to_string(), 1.0)]
+        );
+
+        let expired = pending.expire(70);
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].decision_id, "d2");
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn context_hash_ignores_key_order() {
+        let ctx = |features| Context {
+            kind: "reminder".into(),
+            features,
+        };
+        let a = context_hash(&ctx(json!({ "a": 1, "b": [1, 2] })));
+        assert_eq!(a, context_hash(&ctx(json!({ "b": [1, 2], "a": 1 }))));
+        assert_ne!(a, context_hash(&ctx(json!({ "a": 2, "b": [1, 2] }))));
+    }
+}
@@ -0,0 +1,245 @@
+//! Scripted reward functions for the OLA outcome mapper (feature `scripting`).
+//!
+//! Households can replace the built-in [`crate::ola::compute_reward`] with a
+//! small [Rhai](https://rhai.rs) script instead of patching Rust code. The
+//! script sees two read-only constants and must evaluate to a number:
+//!
+//! - `record`: the raw input record passed to [`crate::ola::adapt`],
+//! - `outcome`: the adapted routing outcome, including the built-in `reward`.
+//!
+//! ```rhai
+//! let r = outcome.reward;
+//! if record.ci_state == "fail" { r -= 0.3 }
+//! r
+//! ```
+//!
+//! The engine is sandboxed: no module imports, no `eval`, no output, and
+//! bounded operations, call depth and data sizes. The result is clamped like
+//! the built-in reward. Every scripted outcome carries an `evidence_refs`
+//! entry of kind [`SCRIPT_EVIDENCE_KIND`] with the SHA-256 of the script
+//! source, so rewards can be traced back to the exact logic that produced them.
+
+use crate::ola::{adapt, clamp_reward};
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Dynamic, Engine, Scope, AST};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// `evidence_refs` kind for the script that computed a reward.
+pub const SCRIPT_EVIDENCE_KIND: &str = "reward_script";
+
+/// Upper bound on evaluated operations per call.
+const MAX_OPERATIONS: u64 = 100_000;
+/// Upper bound on nested function calls.
+const MAX_CALL_LEVELS: usize = 32;
+/// Upper bound on strings, arrays and maps created by the script.
+const MAX_DATA_SIZE: usize = 10_000;
+
+/// Errors raised while compiling or running a reward script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptError {
+    /// The script does not parse.
+    Compile(String),
+    /// The script failed at runtime or exceeded a sandbox limit.
+    Runtime(String),
+    /// The script did not evaluate to a finite number.
+    NotANumber(String),
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Compile(msg) => write!(f, "reward script does not compile: {msg}"),
+            Self::Runtime(msg) => write!(f, "reward script failed: {msg}"),
+            Self::NotANumber(got) => write!(f, "reward script returned {got}, expected a number"),
+        }
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+/// A compiled reward script.
+pub struct RewardScript {
+    engine: Engine,
+    ast: AST,
+    sha256: String,
+}
+
+impl fmt::Debug for RewardScript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RewardScript")
+            .field("sha256", &self.sha256)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RewardScript {
+    /// Compile `source` in a sandboxed engine.
+    ///
+    /// # Errors
+    /// Fails if the script does not parse.
+    pub fn compile(source: &str) -> Result<Self, ScriptError> {
+        let engine = sandboxed_engine();
+        let ast = engine
+            .compile(source)
+            .map_err(|e| ScriptError::Compile(e.to_string()))?;
+        let sha256 = Sha256::digest(source.as_bytes())
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        Ok(Self {
+            engine,
+            ast,
+            sha256,
+        })
+    }
+
+    /// Hex SHA-256 of the script source.
+    pub fn sha256(&self) -> &str {
+        &self.sha256
+    }
+
+    /// Evaluate the script for `record` and its adapted `outcome`.
+    ///
+    /// # Errors
+    /// Fails if the script errors, exceeds a sandbox limit or does not return
+    /// a finite number.
+    pub fn reward(&self, record: &Value, outcome: &Value) -> Result<f64, ScriptError> {
+        let mut scope = Scope::new();
+        scope.push_constant("record", to_dynamic(record)?);
+        scope.push_constant("outcome", to_dynamic(outcome)?);
+        let result: Dynamic = self
+            .engine
+            .eval_ast_with_scope(&mut scope, &self.ast)
+            .map_err(|e| ScriptError::Runtime(e.to_string()))?;
+        #[allow(clippy::cast_precision_loss)]
+        let reward = match (result.as_float(), result.as_int()) {
+            (Ok(x), _) => x,
+            (_, Ok(n)) => n as f64,
+            _ => return Err(ScriptError::NotANumber(result.type_name().to_string())),
+        };
+        if reward.is_finite() {
+            Ok(clamp_reward(reward))
+        } else {
+            Err(ScriptError::NotANumber(reward.to_string()))
+        }
+    }
+}
+
+/// Like [`adapt`], but with the reward computed by `script`.
+///
+/// `script_ref` names the script (e.g. its path) in the recorded evidence.
+///
+/// # Errors
+/// Fails if the script does; the record is then not adapted at all rather
+/// than silently falling back to the built-in reward.
+pub fn adapt_scripted(
+    input_record: &Value,
+    script: &RewardScript,
+    script_ref: &str,
+) -> Result<Value, ScriptError> {
+    let mut outcome = adapt(input_record);
+    let reward = script.reward(input_record, &outcome)?;
+    outcome["reward"] = json!(reward);
+    let evidence = json!({
+        "kind": SCRIPT_EVIDENCE_KIND,
+        "ref": if script_ref.is_empty() { "inline" } else { script_ref },
+        "sha256": script.sha256(),
+    });
+    match outcome
+        .get_mut("evidence_refs")
+        .and_then(Value::as_array_mut)
+    {
+        Some(refs) => refs.push(evidence),
+        None => outcome["evidence_refs"] = json!([evidence]),
+    }
+    Ok(outcome)
+}
+
+fn sandboxed_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_module_resolver(DummyModuleResolver::new());
+    engine.disable_symbol("eval");
+    engine.on_print(|_| {});
+    engine.on_debug(|_, _, _| {});
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(MAX_CALL_LEVELS);
+    engine.set_max_string_size(MAX_DATA_SIZE);
+    engine.set_max_array_size(MAX_DATA_SIZE);
+    engine.set_max_map_size(MAX_DATA_SIZE);
+    engine
+}
+
+fn to_dynamic(value: &Value) -> Result<Dynamic, ScriptError> {
+    rhai::serde::to_dynamic(value).map_err(|e| ScriptError::Runtime(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record() -> Value {
+        json!({
+            "decision_id": "d-1",
+            "completion_state": "completed",
+            "ci_state": "fail",
+            "route_used": "direct:patch",
+        })
+    }
+
+    #[test]
+    fn script_replaces_reward_and_records_hash() {
+        let source = r#"
+            let r = outcome.reward;
+            if record.ci_state == "fail" { r -= 0.3 }
+            r
+        "#;
+        let script = match RewardScript::compile(source) {
+            Ok(script) => script,
+            Err(err) => panic!("compile failed: {err}"),
+        };
+        let outcome = match adapt_scripted(&record(), &script, "rewards.rhai") {
+            Ok(outcome) => outcome,
+            Err(err) => panic!("script failed: {err}"),
+        };
+        let builtin = adapt(&record())["reward"].as_f64().unwrap_or_default();
+        let scripted = outcome["reward"].as_f64().unwrap_or_default();
+        assert!((builtin - 0.3 - scripted).abs() < 1e-9);
+        assert_eq!(outcome["evidence_refs"][0]["kind"], SCRIPT_EVIDENCE_KIND);
+        assert_eq!(outcome["evidence_refs"][0]["ref"], "rewards.rhai");
+        assert_eq!(outcome["evidence_refs"][0]["sha256"], script.sha256());
+        assert_eq!(script.sha256().len(), 64);
+    }
+
+    #[test]
+    fn sandbox_rejects_escapes_and_runaway_scripts() {
+        assert!(matches!(
+            RewardScript::compile("import \"secrets\" as s; 1.0")
+                .and_then(|s| s.reward(&record(), &json!({}))),
+            Err(ScriptError::Runtime(_))
+        ));
+        assert!(RewardScript::compile("eval(\"1\")").is_err());
+
+        let Ok(runaway) = RewardScript::compile("loop {}") else {
+            panic!("loop should compile");
+        };
+        assert!(matches!(
+            runaway.reward(&record(), &json!({})),
+            Err(ScriptError::Runtime(_))
+        ));
+
+        let Ok(text) = RewardScript::compile("\"great\"") else {
+            panic!("string literal should compile");
+        };
+        assert!(matches!(
+            text.reward(&record(), &json!({})),
+            Err(ScriptError::NotANumber(_))
+        ));
+
+        let Ok(big) = RewardScript::compile("5") else {
+            panic!("integer literal should compile");
+        };
+        assert_eq!(big.reward(&record(), &json!({})), Ok(1.0));
+    }
+}
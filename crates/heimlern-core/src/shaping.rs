@@ -0,0 +1,322 @@
+//! Gemeinsame Reward-Formung für Policies und Feedback-Analyse.
+//!
+//! Ein [`RewardShaper`] macht aus einem rohen Reward samt Umfeld
+//! ([`RawReward`]) den Wert, mit dem gelernt bzw. ausgewertet wird. Damit
+//! Policies, der `FeedbackAnalyzer` und externe Dienste wie hausKI dieselbe
+//! Semantik verwenden, gibt es die eingebauten Formungen zusätzlich als
+//! serialisierbare Konfiguration [`RewardShaping`]:
+//!
+//! ```json
+//! { "type": "chain", "steps": [
+//!     { "type": "delay_discount", "half_life_seconds": 1800 },
+//!     { "type": "clip", "min": 0.0, "max": 1.0 }
+//! ] }
+//! ```
+//!
+//! [`ShapedPolicy`] formt das Feedback einer beliebigen Policy, bevor es sie
+//! erreicht.
+
+use crate::{Context, Decision, Policy, PolicyDescriptor};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Roher Reward und das, was über sein Zustandekommen bekannt ist.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RawReward<'a> {
+    pub reward: f32,
+    pub action: Option<&'a str>,
+    /// Kontext-Art der Entscheidung.
+    pub kind: Option<&'a str>,
+    /// Sekunden zwischen Entscheidung und Rückmeldung.
+    pub delay_seconds: Option<f64>,
+}
+
+impl<'a> RawReward<'a> {
+    #[must_use]
+    pub fn new(reward: f32) -> Self {
+        Self {
+            reward,
+            action: None,
+            kind: None,
+            delay_seconds: None,
+        }
+    }
+
+    #[must_use]
+    pub fn action(mut self, action: &'a str) -> Self {
+        self.action = Some(action);
+        self
+    }
+
+    #[must_use]
+    pub fn kind(mut self, kind: &'a str) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    #[must_use]
+    pub fn delay_seconds(mut self, seconds: f64) -> Self {
+        self.delay_seconds = Some(seconds);
+        self
+    }
+}
+
+/// Formt rohe Rewards.
+pub trait RewardShaper {
+    fn shape(&self, raw: &RawReward<'_>) -> f32;
+}
+
+/// Lässt den Reward unverändert.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Identity;
+
+impl RewardShaper for Identity {
+    fn shape(&self, raw: &RawReward<'_>) -> f32 {
+        raw.reward
+    }
+}
+
+/// Begrenzt den Reward auf `[min, max]`; NaN wird zu 0 (bzw. in den Bereich).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Clip {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl RewardShaper for Clip {
+    fn shape(&self, raw: &RawReward<'_>) -> f32 {
+        let value = if raw.reward.is_nan() { 0.0 } else { raw.reward };
+        value.max(self.min).min(self.max)
+    }
+}
+
+/// Halbiert den Reward je `half_life_seconds` Verzögerung. Ohne bekannte
+/// Verzögerung oder bei nicht positiver Halbwertszeit bleibt er unverändert.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DelayDiscount {
+    pub half_life_seconds: f64,
+}
+
+impl RewardShaper for DelayDiscount {
+    fn shape(&self, raw: &RawReward<'_>) -> f32 {
+        let Some(delay) = raw.delay_seconds.filter(|d| d.is_finite() && *d > 0.0) else {
+            return raw.reward;
+        };
+        if !(self.half_life_seconds.is_finite() && self.half_life_seconds > 0.0) {
+            return raw.reward;
+        }
+        let factor = 0.5_f64.powf(delay / self.half_life_seconds);
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            (f64::from(raw.reward) * factor) as f32
+        }
+    }
+}
+
+/// Serialisierbare Form der eingebauten Shaper.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RewardShaping {
+    #[default]
+    Identity,
+    Clip {
+        min: f32,
+        max: f32,
+    },
+    DelayDiscount {
+        half_life_seconds: f64,
+    },
+    /// Wendet `steps` der Reihe nach an.
+    Chain {
+        steps: Vec<RewardShaping>,
+    },
+}
+
+impl RewardShaper for RewardShaping {
+    fn shape(&self, raw: &RawReward<'_>) -> f32 {
+        match self {
+            Self::Identity => Identity.shape(raw),
+            Self::Clip { min, max } => Clip {
+                min: *min,
+                max: *max,
+            }
+            .shape(raw),
+            Self::DelayDiscount { half_life_seconds } => DelayDiscount {
+                half_life_seconds: *half_life_seconds,
+            }
+            .shape(raw),
+            Self::Chain { steps } => steps.iter().fold(raw.reward, |reward, step| {
+                step.shape(&RawReward { reward, ..*raw })
+            }),
+        }
+    }
+}
+
+/// Policy-Wrapper, der jedes Feedback durch einen [`RewardShaper`] schickt.
+///
+/// `Policy::feedback` kennt keine Verzögerung; [`DelayDiscount`] wirkt hier
+/// daher nur, wenn der Aufrufer [`ShapedPolicy::feedback_delayed`] verwendet.
+#[derive(Debug)]
+pub struct ShapedPolicy<P, S> {
+    inner: P,
+    shaper: S,
+}
+
+impl<P: Policy, S: RewardShaper> ShapedPolicy<P, S> {
+    pub fn new(inner: P, shaper: S) -> Self {
+        Self { inner, shaper }
+    }
+
+    pub fn shaper(&self) -> &S {
+        &self.shaper
+    }
+
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+
+    /// Feedback mit bekannter Verzögerung zwischen Entscheidung und Rückmeldung.
+    pub fn feedback_delayed(
+        &mut self,
+        ctx: &Context,
+        action: &str,
+        reward: f32,
+        delay_seconds: f64,
+    ) {
+        let raw = RawReward::new(reward)
+            .action(action)
+            .kind(&ctx.kind)
+            .delay_seconds(delay_seconds);
+        let shaped = self.shaper.shape(&raw);
+        self.inner.feedback(ctx, action, shaped);
+    }
+}
+
+impl<P: Policy, S: RewardShaper> Policy for ShapedPolicy<P, S> {
+    fn decide(&mut self, ctx: &Context) -> Decision {
+        self.inner.decide(ctx)
+    }
+
+    fn decide_batch(&mut self, ctxs: &[Context]) -> Vec<Decision> {
+        self.inner.decide_batch(ctxs)
+    }
+
+    fn feedback(&mut self, ctx: &Context, action: &str, reward: f32) {
+        let raw = RawReward::new(reward).action(action).kind(&ctx.kind);
+        let shaped = self.shaper.shape(&raw);
+        self.inner.feedback(ctx, action, shaped);
+    }
+
+    fn feedback_batch(&mut self, items: &[(Context, String, f32)]) {
+        let shaped: Vec<(Context, String, f32)> = items
+            .iter()
+            .map(|(ctx, action, reward)| {
+                let raw = RawReward::new(*reward).action(action).kind(&ctx.kind);
+                (ctx.clone(), action.clone(), self.shaper.shape(&raw))
+            })
+            .collect();
+        self.inner.feedback_batch(&shaped);
+    }
+
+    fn snapshot(&self) -> Value {
+        self.inner.snapshot()
+    }
+
+    fn load(&mut self, snapshot: Value) {
+        self.inner.load(snapshot);
+    }
+
+    fn descriptor(&self) -> PolicyDescriptor {
+        self.inner.descriptor()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn built_in_shapers_and_their_config_agree() -> Result<(), serde_json::Error> {
+        let late = RawReward::new(1.5).delay_seconds(1800.0);
+        assert!((Identity.shape(&late) - 1.5).abs() < 1e-6);
+        assert!((Clip { min: 0.0, max: 1.0 }.shape(&late) - 1.0).abs() < 1e-6);
+        assert!(
+            (DelayDiscount {
+                half_life_seconds: 1800.0
+            }
+            .shape(&late)
+                - 0.75)
+                .abs()
+                < 1e-6
+        );
+        assert!(
+            (Clip {
+                min: -1.0,
+                max: 1.0
+            }
+            .shape(&RawReward::new(f32::NAN)))
+            .abs()
+                < f32::EPSILON
+        );
+
+        let chain: RewardShaping = serde_json::from_value(json!({
+            "type": "chain",
+            "steps": [
+                { "type": "delay_discount", "half_life_seconds": 1800.0 },
+                { "type": "clip", "min": 0.0, "max": 0.5 }
+            ]
+        }))?;
+        assert!((chain.shape(&late) - 0.5).abs() < 1e-6);
+        assert!((chain.shape(&RawReward::new(0.4)) - 0.4).abs() < 1e-6);
+        assert_eq!(RewardShaping::default(), RewardShaping::Identity);
+        Ok(())
+    }
+
+    #[test]
+    fn shaped_policy_forwards_shaped_rewards() {
+        #[derive(Default)]
+        struct Recorder(Vec<f32>);
+
+        impl Policy for Recorder {
+            fn decide(&mut self, _: &Context) -> Decision {
+                Decision {
+                    action: "remind.none".into(),
+                    score: 0.0,
+                    why: vec![],
+                    context: None,
+                    chosen: None,
+                }
+            }
+            fn feedback(&mut self, _: &Context, _: &str, reward: f32) {
+                self.0.push(reward);
+            }
+            fn snapshot(&self) -> Value {
+                Value::Null
+            }
+            fn load(&mut self, _: Value) {}
+        }
+
+        let ctx = Context {
+            kind: "reminder".into(),
+            features: json!({}),
+        };
+        let shaping = RewardShaping::Chain {
+            steps: vec![
+                RewardShaping::DelayDiscount {
+                    half_life_seconds: 60.0,
+                },
+                RewardShaping::Clip { min: 0.0, max: 1.0 },
+            ],
+        };
+        let mut policy = ShapedPolicy::new(Recorder::default(), shaping);
+        policy.feedback(&ctx, "remind.morning", 3.0);
+        policy.feedback_delayed(&ctx, "remind.morning", 1.0, 60.0);
+        policy.feedback_batch(&[(ctx.clone(), "remind.evening".into(), -2.0)]);
+        assert_eq!(policy.inner().0, vec![1.0, 0.5, 0.0]);
+    }
+}
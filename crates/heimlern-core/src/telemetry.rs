@@ -0,0 +1,246 @@
+//! Einheitliche Telemetrie für beliebige Policies (Feature `telemetry`).
+//!
+//! [`InstrumentedPolicy`] legt um jeden Aufruf von `decide`, `feedback`,
+//! `feedback_batch` und `load` einen `tracing`-Span und schließt ihn mit einem
+//! Event ab. Die Felder sind für alle Policies gleich, sodass Dashboards und
+//! Log-Filter nicht von der konkreten Policy abhängen:
+//!
+//! | Span                     | Felder                                            |
+//! |--------------------------|---------------------------------------------------|
+//! | `heimlern.decide`        | `policy_id`, `kind`, `action`, `score`, `latency_us` |
+//! | `heimlern.feedback`      | `policy_id`, `kind`, `action`, `reward`, `latency_us` |
+//! | `heimlern.feedback_batch`| `policy_id`, `items`, `latency_us`                |
+//! | `heimlern.load`          | `policy_id`, `latency_us`                         |
+//!
+//! Die `policy_id` stammt aus dem [`PolicyDescriptor`] der inneren Policy.
+
+use crate::{Context, Decision, Policy, PolicyDescriptor};
+use serde_json::Value;
+use std::time::Instant;
+use tracing::field::Empty;
+
+/// Policy-Wrapper, der jeden Aufruf als `tracing`-Span meldet.
+#[derive(Debug)]
+pub struct InstrumentedPolicy<P> {
+    inner: P,
+    policy_id: String,
+}
+
+impl<P: Policy> InstrumentedPolicy<P> {
+    pub fn new(inner: P) -> Self {
+        let policy_id = inner.descriptor().policy_id;
+        Self { inner, policy_id }
+    }
+
+    /// Überschreibt die gemeldete `policy_id`, etwa um mehrere Instanzen
+    /// derselben Policy zu unterscheiden.
+    #[must_use]
+    pub fn policy_id(mut self, policy_id: impl Into<String>) -> Self {
+        self.policy_id = policy_id.into();
+        self
+    }
+
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+}
+
+fn micros(start: Instant) -> u64 {
+    u64::try_from(start.elapsed().as_micros()).unwrap_or(u64::MAX)
+}
+
+impl<P: Policy> Policy for InstrumentedPolicy<P> {
+    fn decide(&mut self, ctx: &Context) -> Decision {
+        let span = tracing::info_span!(
+            "heimlern.decide",
+            policy_id = %self.policy_id,
+            kind = %ctx.kind,
+            action = Empty,
+            score = Empty,
+            latency_us = Empty,
+        );
+        let _entered = span.enter();
+        let start = Instant::now();
+        let decision = self.inner.decide(ctx);
+        let latency_us = micros(start);
+        span.record("action", tracing::field::display(&decision.action));
+        span.record("score", decision.score);
+        span.record("latency_us", latency_us);
+        tracing::info!(
+            action = %decision.action,
+            score = decision.score,
+            latency_us,
+            "decision"
+        );
+        decision
+    }
+
+    fn feedback(&mut self, ctx: &Context, action: &str, reward: f32) {
+        let span = tracing::info_span!(
+            "heimlern.feedback",
+            policy_id = %self.policy_id,
+            kind = %ctx.kind,
+            action = %action,
+            reward,
+            latency_us = Empty,
+        );
+        let _entered = span.enter();
+        let start = Instant::now();
+        self.inner.feedback(ctx, action, reward);
+        let latency_us = micros(start);
+        span.record("latency_us", latency_us);
+        tracing::debug!(latency_us, "feedback applied");
+    }
+
+    fn feedback_batch(&mut self, items: &[(Context, String, f32)]) {
+        let span = tracing::info_span!(
+            "heimlern.feedback_batch",
+            policy_id = %self.policy_id,
+            items = items.len(),
+            latency_us = Empty,
+        );
+        let _entered = span.enter();
+        let start = Instant::now();
+        self.inner.feedback_batch(items);
+        let latency_us = micros(start);
+        span.record("latency_us", latency_us);
+        tracing::debug!(latency_us, "feedback batch applied");
+    }
+
+    fn snapshot(&self) -> Value {
+        self.inner.snapshot()
+    }
+
+    fn load(&mut self, snapshot: Value) {
+        let span = tracing::info_span!(
+            "heimlern.load",
+            policy_id = %self.policy_id,
+            latency_us = Empty,
+        );
+        let _entered = span.enter();
+        let start = Instant::now();
+        self.inner.load(snapshot);
+        let latency_us = micros(start);
+        span.record("latency_us", latency_us);
+        tracing::info!(latency_us, "snapshot loaded");
+    }
+
+    fn descriptor(&self) -> PolicyDescriptor {
+        self.inner.descriptor()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::fmt::Debug;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Span-Name samt (nachträglich) gesetzten Feldern.
+    type Spans = Vec<(String, Vec<String>)>;
+
+    #[derive(Clone, Default)]
+    struct Collector(Arc<Mutex<Spans>>);
+
+    struct Names<'a>(&'a mut Vec<String>);
+
+    impl Visit for Names<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0.push(format!("{}={value:?}", field.name()));
+        }
+    }
+
+    impl Subscriber for Collector {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+            let mut spans = self
+                .0
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            let mut fields = Vec::new();
+            attrs.record(&mut Names(&mut fields));
+            spans.push((attrs.metadata().name().to_string(), fields));
+            Id::from_u64(spans.len() as u64)
+        }
+        fn record(&self, span: &Id, values: &Record<'_>) {
+            let mut spans = self
+                .0
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            let index = usize::try_from(span.into_u64()).unwrap_or(usize::MAX) - 1;
+            if let Some((_, fields)) = spans.get_mut(index) {
+                values.record(&mut Names(fields));
+            }
+        }
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, _: &Event<'_>) {}
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    struct Fixed;
+
+    impl Policy for Fixed {
+        fn decide(&mut self, _: &Context) -> Decision {
+            Decision {
+                action: "remind.morning".into(),
+                score: 0.5,
+                why: vec![],
+                context: None,
+                chosen: None,
+            }
+        }
+        fn feedback(&mut self, _: &Context, _: &str, _: f32) {}
+        fn snapshot(&self) -> Value {
+            Value::Null
+        }
+        fn load(&mut self, _: Value) {}
+        fn descriptor(&self) -> PolicyDescriptor {
+            PolicyDescriptor::new("fixed")
+        }
+    }
+
+    #[test]
+    fn every_call_gets_a_span_with_common_fields() {
+        let collector = Collector::default();
+        let ctx = Context {
+            kind: "reminder".into(),
+            features: json!({}),
+        };
+        tracing::subscriber::with_default(collector.clone(), || {
+            let mut policy = InstrumentedPolicy::new(Fixed);
+            let decision = policy.decide(&ctx);
+            policy.feedback(&ctx, &decision.action, 1.0);
+            policy.load(Value::Null);
+        });
+
+        let spans = collector.0.lock().map(|s| s.clone()).unwrap_or_default();
+        let names: Vec<&str> = spans.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["heimlern.decide", "heimlern.feedback", "heimlern.load"]
+        );
+        let decide = &spans[0].1;
+        for expected in [
+            "policy_id=fixed",
+            "kind=reminder",
+            "action=remind.morning",
+            "score=0.5",
+        ] {
+            assert!(decide.contains(&expected.to_string()), "{decide:?}");
+        }
+        assert!(spans
+            .iter()
+            .all(|(_, fields)| fields.iter().any(|f| f.starts_with("latency_us="))));
+    }
+}
@@ -0,0 +1,47 @@
+use heimlern_core::contracts::{
+    AussenEvent, OperatorRoutingOutcomeExportV1, OperatorRoutingOutcomeV1, PolicyDecision,
+    PolicyFeedback, PolicySnapshot,
+};
+use serde::de::DeserializeOwned;
+use std::fs;
+
+fn read(path: &str) -> String {
+    fs::read_to_string(format!("../../{path}")).expect("Failed to read fixture file")
+}
+
+fn roundtrip<T: DeserializeOwned + serde::Serialize>(raw: &str) -> serde_json::Value {
+    let value: serde_json::Value = serde_json::from_str(raw).expect("valid JSON");
+    let typed: T = serde_json::from_value(value.clone()).expect("sample matches generated type");
+    let back = serde_json::to_value(&typed).expect("serialize");
+    assert_eq!(back, value, "generated type must round-trip the sample");
+    back
+}
+
+#[test]
+fn samples_roundtrip_through_generated_types() {
+    roundtrip::<PolicySnapshot>(&read("data/samples/policy.snapshot.sample.json"));
+    roundtrip::<PolicyFeedback>(&read("data/samples/policy.feedback.sample.json"));
+    for line in read("data/samples/policy.decision.sample.jsonl").lines() {
+        roundtrip::<PolicyDecision>(line);
+    }
+    for line in read("tests/fixtures/aussen.jsonl").lines() {
+        roundtrip::<AussenEvent>(line);
+    }
+    roundtrip::<OperatorRoutingOutcomeExportV1>(&read(
+        "tests/fixtures/chronik-outcome/operator-routing-outcome-export.v1.json",
+    ));
+}
+
+#[test]
+fn generated_types_reject_what_the_contracts_reject() {
+    let rejected = read("data/samples/policy.feedback.rejected.json");
+    assert!(serde_json::from_str::<PolicyFeedback>(&rejected).is_err());
+
+    let adapted = heimlern_core::ola::adapt(&serde_json::json!({
+        "decision_id": "d-1",
+        "completion_state": "completed",
+    }));
+    let typed: OperatorRoutingOutcomeV1 =
+        serde_json::from_value(adapted).expect("adapter output matches contract");
+    assert!(serde_json::to_value(&typed).is_ok());
+}
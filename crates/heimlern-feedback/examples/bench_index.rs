@@ -0,0 +1,61 @@
+//! Compares per-grouping scans with the single-pass [`OutcomeIndex`].
+//!
+//! Run with: cargo run --release -p heimlern-feedback --example bench_index -- [outcomes]
+
+use heimlern_feedback::index::OutcomeIndex;
+use heimlern_feedback::{DecisionOutcome, FeedbackAnalyzer, OutcomeType};
+use std::hint::black_box;
+use std::time::Instant;
+
+fn main() {
+    let n: usize = std::env::args()
+        .nth(1)
+        .and_then(|arg| arg.parse().ok())
+        .unwrap_or(1_000_000);
+    let outcomes: Vec<DecisionOutcome> = (0..n)
+        .map(|i| {
+            let success = i % 3 == 0;
+            DecisionOutcome {
+                decision_id: i.to_string(),
+                ts: format!("2026-01-{:02}T08:00:00Z", i % 28 + 1),
+                policy_id: None,
+                action: Some(format!("remind.slot{}", i % 12)),
+                outcome: if success {
+                    OutcomeType::Success
+                } else {
+                    OutcomeType::Failure
+                },
+                success,
+                reward: None,
+                context: Some(serde_json::json!({ "kind": "reminder" })),
+                metadata: None,
+            }
+        })
+        .collect();
+    let analyzer = FeedbackAnalyzer::default();
+
+    let start = Instant::now();
+    black_box(analyzer.aggregate_outcomes(&outcomes, |o| o.action.clone()));
+    black_box(analyzer.aggregate_outcomes(&outcomes, |o| {
+        o.context
+            .as_ref()
+            .and_then(|c| c.get("kind"))
+            .and_then(|k| k.as_str())
+            .map(str::to_string)
+    }));
+    black_box(analyzer.aggregate_outcomes(&outcomes, |o| Some(o.ts[..10].to_string())));
+    let scans = start.elapsed();
+
+    let start = Instant::now();
+    let index = black_box(OutcomeIndex::build(&outcomes));
+    let build = start.elapsed();
+
+    let start = Instant::now();
+    black_box(analyzer.analyze_index(&index));
+    let analyze = start.elapsed();
+
+    println!("outcomes:            {n}");
+    println!("three scans:         {scans:?}");
+    println!("index build:         {build:?}");
+    println!("analyze_index:       {analyze:?}");
+}
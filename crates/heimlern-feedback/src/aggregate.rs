@@ -0,0 +1,225 @@
+//! Streaming outcome aggregation.
+//!
+//! [`FeedbackAnalyzer::aggregate_outcomes`] and [`crate::index::OutcomeIndex`]
+//! want the outcomes as a slice, and the index keeps every latency sample.
+//! An [`OutcomeAggregator`] only keeps running counts: overall and per
+//! action. Outcomes are pushed one at a time, read line by line from a JSONL
+//! log with [`OutcomeAggregator::read_jsonl`], or aggregated per chunk and
+//! combined with [`OutcomeAggregator::merge`]. Memory grows with the number
+//! of distinct actions, not with the length of the log, unless
+//! [`OutcomeAggregator::deduplicating`] is on: then every decision id is
+//! remembered so redelivered outcomes count once.
+
+use crate::dedup::OutcomeDeduper;
+use crate::{DecisionOutcome, FeedbackAnalyzer, OutcomeStatistics};
+use heimlern_core::shaping::RewardShaping;
+use std::collections::BTreeMap;
+use std::io::BufRead;
+
+/// Errors raised while reading outcomes into an aggregator.
+#[derive(Debug, thiserror::Error)]
+pub enum AggregateError {
+    #[error("reading outcomes failed: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid outcome on line {line}: {source}")]
+    Parse {
+        line: usize,
+        source: serde_json::Error,
+    },
+}
+
+/// Running outcome statistics, overall and per action.
+#[derive(Debug, Clone, Default)]
+pub struct OutcomeAggregator {
+    shaping: RewardShaping,
+    records: usize,
+    overall: OutcomeStatistics,
+    by_action: BTreeMap<String, OutcomeStatistics>,
+    deduper: Option<OutcomeDeduper>,
+}
+
+impl OutcomeAggregator {
+    /// Aggregator that applies `shaping` to rewards, like
+    /// [`FeedbackAnalyzer::aggregate_outcomes`] does.
+    #[must_use]
+    pub fn new(shaping: RewardShaping) -> Self {
+        Self {
+            shaping,
+            ..Self::default()
+        }
+    }
+
+    /// Skip outcomes whose decision was already pushed, see
+    /// [`crate::dedup`].
+    #[must_use]
+    pub fn deduplicating(mut self) -> Self {
+        self.deduper.get_or_insert_with(OutcomeDeduper::new);
+        self
+    }
+
+    /// Add one outcome; a duplicate is skipped when deduplicating.
+    pub fn push(&mut self, outcome: &DecisionOutcome) {
+        if let Some(deduper) = &mut self.deduper {
+            if !deduper.admit(outcome) {
+                return;
+            }
+        }
+        self.records += 1;
+        self.overall.record_shaped(outcome, &self.shaping);
+        if let Some(action) = &outcome.action {
+            self.by_action
+                .entry(action.clone())
+                .or_default()
+                .record_shaped(outcome, &self.shaping);
+        }
+    }
+
+    /// Add a chunk of outcomes.
+    pub fn extend<'a>(&mut self, outcomes: impl IntoIterator<Item = &'a DecisionOutcome>) {
+        for outcome in outcomes {
+            self.push(outcome);
+        }
+    }
+
+    /// Read a JSONL outcome log line by line and return the number of
+    /// outcomes read. Blank lines are skipped.
+    ///
+    /// # Errors
+    /// Fails on the first unreadable or invalid line; outcomes before it stay
+    /// aggregated.
+    pub fn read_jsonl(&mut self, reader: impl BufRead) -> Result<usize, AggregateError> {
+        let mut added = 0;
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let outcome: DecisionOutcome =
+                serde_json::from_str(&line).map_err(|source| AggregateError::Parse {
+                    line: index + 1,
+                    source,
+                })?;
+            self.push(&outcome);
+            added += 1;
+        }
+        Ok(added)
+    }
+
+    /// Add the statistics of `other`, e.g. a chunk aggregated elsewhere.
+    /// Duplicates across the two are not detected.
+    pub fn merge(&mut self, other: &Self) {
+        self.records += other.records;
+        self.overall.merge(&other.overall);
+        for (action, stats) in &other.by_action {
+            self.by_action
+                .entry(action.clone())
+                .or_default()
+                .merge(stats);
+        }
+    }
+
+    /// Number of outcomes added, censored and overridden ones included.
+    #[must_use]
+    pub fn records(&self) -> usize {
+        self.records
+    }
+
+    /// Number of outcomes skipped as duplicates.
+    #[must_use]
+    pub fn duplicates(&self) -> usize {
+        self.deduper.as_ref().map_or(0, OutcomeDeduper::duplicates)
+    }
+
+    #[must_use]
+    pub fn overall(&self) -> &OutcomeStatistics {
+        &self.overall
+    }
+
+    /// Statistics per action (outcomes without an action are skipped).
+    #[must_use]
+    pub fn by_action(&self) -> &BTreeMap<String, OutcomeStatistics> {
+        &self.by_action
+    }
+}
+
+impl FeedbackAnalyzer {
+    /// Empty [`OutcomeAggregator`] using this analyzer's reward shaping.
+    #[must_use]
+    pub fn aggregator(&self) -> OutcomeAggregator {
+        OutcomeAggregator::new(self.reward_shaping().clone())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::OutcomeType;
+    use std::io::Cursor;
+
+    fn outcome(id: usize, action: &str, success: bool) -> DecisionOutcome {
+        DecisionOutcome {
+            decision_id: id.to_string(),
+            ts: "2026-01-01T00:00:00Z".into(),
+            policy_id: None,
+            action: Some(action.into()),
+            outcome: if success {
+                OutcomeType::Success
+            } else {
+                OutcomeType::Failure
+            },
+            success,
+            reward: Some(if success { 1.0 } else { 0.0 }),
+            context: None,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn streamed_chunks_match_slice_aggregation() {
+        let outcomes: Vec<DecisionOutcome> = (0..30)
+            .map(|i| {
+                let action = if i % 3 == 0 {
+                    "remind.morning"
+                } else {
+                    "remind.evening"
+                };
+                outcome(i, action, i % 4 != 0)
+            })
+            .collect();
+        let analyzer = FeedbackAnalyzer::default();
+        let expected = analyzer.aggregate_outcomes(&outcomes, |o| o.action.clone());
+
+        let log: String = outcomes[10..]
+            .iter()
+            .map(|o| serde_json::to_string(o).unwrap() + "\n\n")
+            .collect();
+        let mut streamed = analyzer.aggregator();
+        assert_eq!(streamed.read_jsonl(Cursor::new(log)).unwrap(), 20);
+        let mut first = analyzer.aggregator();
+        first.extend(&outcomes[..10]);
+        first.merge(&streamed);
+
+        assert_eq!(first.records(), 30);
+        assert_eq!(first.overall().total, 30);
+        assert_eq!(first.by_action().len(), expected.len());
+        for (action, stats) in &expected {
+            let aggregated = &first.by_action()[action];
+            assert_eq!(aggregated.successes, stats.successes);
+            assert_eq!(aggregated.failures, stats.failures);
+            assert!((aggregated.total_reward - stats.total_reward).abs() < 1e-9);
+        }
+
+        let err = analyzer
+            .aggregator()
+            .read_jsonl(Cursor::new("\n{not json}\n"))
+            .unwrap_err();
+        assert!(matches!(err, AggregateError::Parse { line: 2, .. }));
+
+        let mut deduplicating = analyzer.aggregator().deduplicating();
+        deduplicating.extend(&outcomes);
+        deduplicating.extend(&outcomes[..5]);
+        assert_eq!(deduplicating.records(), 30);
+        assert_eq!(deduplicating.duplicates(), 5);
+    }
+}
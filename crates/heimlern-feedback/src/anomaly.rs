@@ -0,0 +1,215 @@
+//! Anomalous days in an outcome stream.
+//!
+//! A logging outage, a broken integration or a holiday can make one day's
+//! failure rate jump without saying anything about the policy. An
+//! [`EwmaChart`] tracks the daily failure rate as an exponentially weighted
+//! moving average and flags a day whose rate lies more than
+//! [`EwmaChart::limit`] binomial standard deviations away from the average of
+//! the days before it. Both directions count: a day without a single failure
+//! is as suspicious as one without a single success. Flagged days do not
+//! update the average, so one bad day does not hide the next.
+//!
+//! Operators see the flagged days with `heimlern outcomes anomalies`;
+//! [`crate::FeedbackAnalyzerBuilder::exclude_anomalies`] drops their outcomes
+//! before a proposal is generated.
+
+use crate::trend::{TimeBucket, Window};
+use crate::{DecisionOutcome, FeedbackAnalyzer};
+use serde::{Deserialize, Serialize};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+/// Smoothing factor of the moving average.
+pub const DEFAULT_EWMA_LAMBDA: f64 = 0.3;
+
+/// Distance from the average, in standard deviations, that flags a day.
+pub const DEFAULT_CONTROL_LIMIT: f64 = 3.0;
+
+/// Days that only seed the average and are never flagged themselves.
+pub const DEFAULT_WARMUP_DAYS: usize = 3;
+
+/// Bounds the expected failure rate away from 0 and 1, where the binomial
+/// standard deviation vanishes and any single deviation would be infinite.
+const MIN_EXPECTED_RATE: f64 = 0.01;
+
+/// EWMA control chart over daily failure rates.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EwmaChart {
+    /// Weight of the newest day in the average, `0.0..=1.0`.
+    pub lambda: f64,
+    /// Control limit in standard deviations.
+    pub limit: f64,
+    /// Number of leading days that seed the average.
+    pub warmup: usize,
+}
+
+impl Default for EwmaChart {
+    fn default() -> Self {
+        Self {
+            lambda: DEFAULT_EWMA_LAMBDA,
+            limit: DEFAULT_CONTROL_LIMIT,
+            warmup: DEFAULT_WARMUP_DAYS,
+        }
+    }
+}
+
+/// A day outside the control limits.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Anomaly {
+    /// Start of the day (RFC 3339, UTC).
+    pub start: String,
+    pub total: usize,
+    pub failures: usize,
+    pub failure_rate: f32,
+    /// Moving average of the days before.
+    pub expected: f32,
+    /// Signed distance from `expected` in standard deviations.
+    pub z: f32,
+}
+
+impl EwmaChart {
+    /// Days of `buckets` (oldest first, as from
+    /// [`FeedbackAnalyzer::bucket_outcomes`]) outside the control limits.
+    #[must_use]
+    pub fn detect(&self, buckets: &[TimeBucket]) -> Vec<Anomaly> {
+        let warmup = self.warmup.max(1);
+        if buckets.len() <= warmup {
+            return Vec::new();
+        }
+        let lambda = self.lambda.clamp(0.0, 1.0);
+        let (seed_failures, seed_total) = buckets[..warmup]
+            .iter()
+            .fold((0, 0), |(f, t), b| (f + b.failures, t + b.total));
+        #[allow(clippy::cast_precision_loss)]
+        let mut average = seed_failures as f64 / seed_total.max(1) as f64;
+
+        let mut anomalies = Vec::new();
+        for bucket in &buckets[warmup..] {
+            let rate = f64::from(bucket.failure_rate);
+            let expected = average.clamp(MIN_EXPECTED_RATE, 1.0 - MIN_EXPECTED_RATE);
+            #[allow(clippy::cast_precision_loss)]
+            let sigma = (expected * (1.0 - expected) / bucket.total.max(1) as f64).sqrt();
+            let z = (rate - average) / sigma;
+            if z.abs() > self.limit {
+                #[allow(clippy::cast_possible_truncation)]
+                anomalies.push(Anomaly {
+                    start: bucket.start.clone(),
+                    total: bucket.total,
+                    failures: bucket.failures,
+                    failure_rate: bucket.failure_rate,
+                    expected: average as f32,
+                    z: z as f32,
+                });
+            } else {
+                average = lambda * rate + (1.0 - lambda) * average;
+            }
+        }
+        anomalies
+    }
+}
+
+/// `outcomes` without those recorded on an anomalous day. Outcomes with an
+/// unparseable timestamp are kept.
+#[must_use]
+pub fn exclude_anomalies(
+    outcomes: &[DecisionOutcome],
+    anomalies: &[Anomaly],
+) -> Vec<DecisionOutcome> {
+    let days: Vec<i64> = anomalies
+        .iter()
+        .filter_map(|a| OffsetDateTime::parse(&a.start, &Rfc3339).ok())
+        .map(|t| t.unix_timestamp())
+        .collect();
+    outcomes
+        .iter()
+        .filter(|o| {
+            OffsetDateTime::parse(&o.ts, &Rfc3339).map_or(true, |t| {
+                let day = t.unix_timestamp().div_euclid(86_400) * 86_400;
+                !days.contains(&day)
+            })
+        })
+        .cloned()
+        .collect()
+}
+
+impl FeedbackAnalyzer {
+    /// Anomalous days in `outcomes`, using the chart set with
+    /// [`crate::FeedbackAnalyzerBuilder::exclude_anomalies`] or the default.
+    #[must_use]
+    pub fn detect_anomalies(&self, outcomes: &[DecisionOutcome]) -> Vec<Anomaly> {
+        self.anomaly_chart
+            .unwrap_or_default()
+            .detect(&self.bucket_outcomes(outcomes, Window::Daily))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OutcomeType;
+
+    fn day(day: u32, total: usize, failures: usize) -> Vec<DecisionOutcome> {
+        (0..total)
+            .map(|i| {
+                let success = i >= failures;
+                DecisionOutcome {
+                    decision_id: format!("{day}-{i}"),
+                    ts: format!("2026-05-{day:02}T{:02}:00:00Z", 8 + i % 12),
+                    policy_id: None,
+                    action: Some("remind.morning".into()),
+                    outcome: if success {
+                        OutcomeType::Success
+                    } else {
+                        OutcomeType::Failure
+                    },
+                    success,
+                    reward: None,
+                    context: None,
+                    metadata: None,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn outage_day_is_flagged_and_excluded() {
+        let mut outcomes = Vec::new();
+        for d in 1..=14 {
+            let (total, failures) = match d {
+                9 => (20, 18),
+                12 => (60, 0),
+                _ => (20, 4 + d as usize % 3),
+            };
+            outcomes.extend(day(d, total, failures));
+        }
+        let analyzer = FeedbackAnalyzer::default();
+        let anomalies = analyzer.detect_anomalies(&outcomes);
+        assert_eq!(anomalies.len(), 2, "{anomalies:?}");
+        assert_eq!(anomalies[0].start, "2026-05-09T00:00:00Z");
+        assert!(anomalies[0].z > 3.0 && anomalies[0].expected < 0.3);
+        assert_eq!(anomalies[1].start, "2026-05-12T00:00:00Z");
+        assert!(anomalies[1].z < -3.0);
+
+        let kept = exclude_anomalies(&outcomes, &anomalies);
+        assert_eq!(kept.len(), outcomes.len() - 80);
+        assert!(kept.iter().all(|o| !o.ts.starts_with("2026-05-09")));
+
+        let strict = FeedbackAnalyzer::builder()
+            .exclude_anomalies(EwmaChart {
+                limit: 10.0,
+                ..EwmaChart::default()
+            })
+            .build();
+        assert!(strict.detect_anomalies(&outcomes).is_empty());
+
+        let proposal = FeedbackAnalyzer::builder()
+            .min_confidence(0.0)
+            .exclude_anomalies(EwmaChart::default())
+            .build()
+            .propose_adjustment("remind-bandit", &outcomes);
+        assert!(proposal.is_some_and(|p| {
+            p.evidence.decisions_analyzed == outcomes.len() - 80
+                && p.reasoning
+                    .is_some_and(|r| r.contains("Left out 80 outcomes from 2 anomalous day(s)"))
+        }));
+    }
+}
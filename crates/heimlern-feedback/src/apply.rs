@@ -0,0 +1,602 @@
+//! Applying proposal deltas to policy parameters.
+//!
+//! [`apply_to_params`] turns the deltas of a [`WeightAdjustmentProposal`] into
+//! concrete parameter values, checked against the target policy's
+//! [`PolicyDescriptor`]. A delta whose key the policy does not know, whose unit
+//! is not understood or whose resulting value leaves the declared range is
+//! reported as a [`DeltaViolation`] instead of being silently ignored. Nothing
+//! is applied unless every delta is valid.
+//!
+//! [`apply_proposal`] does the same directly on a stored snapshot, so
+//! consumers do not have to reimplement the delta semantics. It only applies
+//! pending proposals within [`DEFAULT_TTL_SECONDS`] of their `ts`, and knows
+//! the snapshot fields the deltas address:
+//!
+//! | Delta key                      | Snapshot field                        | Range                           |
+//! |--------------------------------|---------------------------------------|---------------------------------|
+//! | `epsilon`                      | `epsilon`                             | `[0, 1]`                        |
+//! | `recency.half_life`            | `recency.half_life`                   | `[1, 100000]`                   |
+//! | `priors.<arm>.mean`            | `priors.<arm>.mean`                   | `[0, 1]`                        |
+//! | `priors.<arm>.pseudo_count`    | `priors.<arm>.pseudo_count`           | `>= 0`, finite                  |
+//! | `timing.<slot>.offset_minutes` | `timing.slots.<slot>.offset_minutes`  | `[0, timing.window_minutes]`    |
+//!
+//! A slot without timing state fires at the start of its slot, so its offset
+//! counts as `0` and an absolute delta creates the entry.
+//!
+//! The new snapshot records the [`proposal_digest`] of the applied proposal
+//! in [`APPLIED_PROPOSAL_FIELD`].
+
+use crate::expiry::{expires_at, DEFAULT_TTL_SECONDS};
+use crate::provenance::{proposal_digest, ProvenanceError};
+use crate::{DeltaValue, ProposalStatus, WeightAdjustmentProposal};
+use heimlern_core::PolicyDescriptor;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+/// Snapshot field holding the digest of the last applied proposal.
+pub const APPLIED_PROPOSAL_FIELD: &str = "applied_proposal";
+
+/// Bounds of the recency half-life, as enforced by the remind bandit.
+const HALF_LIFE_RANGE: (f64, f64) = (1.0, 100_000.0);
+
+/// Bounds of a prior mean: rewards lie in `[0, 1]`.
+const PRIOR_MEAN_RANGE: (f64, f64) = (0.0, 1.0);
+
+/// A single reason why a proposal cannot be applied.
+#[derive(Debug, Clone, PartialEq, Serialize, thiserror::Error)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DeltaViolation {
+    /// The proposal targets a different policy than the descriptor.
+    #[error("proposal targets '{actual}', descriptor describes '{expected}'")]
+    PolicyMismatch { expected: String, actual: String },
+    /// No descriptor is known for the proposal's policy.
+    #[error("no descriptor for policy '{policy}'")]
+    UnknownPolicy { policy: String },
+    /// The key is not tunable on the target policy.
+    #[error("'{key}' is not a tunable parameter")]
+    UnknownKey { key: String },
+    /// A relative delta uses a unit other than `percent` or `factor`.
+    #[error("'{key}' uses unsupported unit '{unit}'")]
+    UnsupportedUnit { key: String, unit: String },
+    /// A relative or additive delta needs the current value, which is missing.
+    #[error("'{key}' has no current value to adjust")]
+    MissingCurrent { key: String },
+    /// The resulting value lies outside the declared range.
+    #[error("'{key}' would become {value}, outside [{min}, {max}]")]
+    OutOfRange {
+        key: String,
+        value: f64,
+        min: f64,
+        max: f64,
+    },
+}
+
+/// Errors of [`apply_proposal`].
+#[derive(Debug, thiserror::Error)]
+pub enum ApplyError {
+    #[error("snapshot is not a JSON object")]
+    NotAnObject,
+    /// Only [`ProposalStatus::Proposed`] proposals may be applied.
+    #[error("proposal is {0:?}, not pending")]
+    NotPending(ProposalStatus),
+    /// The proposal's time to live passed at `.0`.
+    #[error("proposal expired at {0}")]
+    Expired(String),
+    /// The proposal's `ts` does not parse, so its age is unknown.
+    #[error("proposal ts '{0}' is not RFC 3339")]
+    InvalidTs(String),
+    #[error("proposal cannot be applied: {}", join(.0))]
+    Violations(Vec<DeltaViolation>),
+    #[error(transparent)]
+    Digest(#[from] ProvenanceError),
+}
+
+fn join(violations: &[DeltaViolation]) -> String {
+    violations
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Descriptor and current parameter values of one live policy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolicyState {
+    pub descriptor: PolicyDescriptor,
+    pub params: BTreeMap<String, f64>,
+}
+
+impl PolicyState {
+    #[must_use]
+    pub fn new(descriptor: PolicyDescriptor, params: BTreeMap<String, f64>) -> Self {
+        Self { descriptor, params }
+    }
+
+    /// Validate `proposal` against this policy, see [`validate_deltas`].
+    #[must_use]
+    pub fn validate(&self, proposal: &WeightAdjustmentProposal) -> Vec<DeltaViolation> {
+        validate_deltas(proposal, &self.descriptor, &self.params)
+    }
+}
+
+/// Check every delta of `proposal` against `descriptor` and `current` values.
+///
+/// Returns all violations; an empty list means the proposal can be applied.
+#[must_use]
+pub fn validate_deltas(
+    proposal: &WeightAdjustmentProposal,
+    descriptor: &PolicyDescriptor,
+    current: &BTreeMap<String, f64>,
+) -> Vec<DeltaViolation> {
+    match resolve(proposal, descriptor, current) {
+        Ok(_) => Vec::new(),
+        Err(violations) => violations,
+    }
+}
+
+/// Compute the parameter values after applying `proposal` to `current`.
+///
+/// Keys not touched by the proposal keep their current value.
+///
+/// # Errors
+/// Returns every [`DeltaViolation`] found; `current` is never partially updated.
+pub fn apply_to_params(
+    proposal: &WeightAdjustmentProposal,
+    descriptor: &PolicyDescriptor,
+    current: &BTreeMap<String, f64>,
+) -> Result<BTreeMap<String, f64>, Vec<DeltaViolation>> {
+    let updates = resolve(proposal, descriptor, current)?;
+    let mut next = current.clone();
+    next.extend(updates);
+    Ok(next)
+}
+
+fn resolve(
+    proposal: &WeightAdjustmentProposal,
+    descriptor: &PolicyDescriptor,
+    current: &BTreeMap<String, f64>,
+) -> Result<BTreeMap<String, f64>, Vec<DeltaViolation>> {
+    let mut violations = Vec::new();
+    if proposal.basis_policy != descriptor.policy_id {
+        violations.push(DeltaViolation::PolicyMismatch {
+            expected: descriptor.policy_id.clone(),
+            actual: proposal.basis_policy.clone(),
+        });
+    }
+
+    let mut updates = BTreeMap::new();
+    for (key, delta) in &proposal.deltas {
+        let Some(range) = descriptor.range(key) else {
+            violations.push(DeltaViolation::UnknownKey { key: key.clone() });
+            continue;
+        };
+        match delta_value(
+            key,
+            delta,
+            current.get(key).copied(),
+            (range.min, range.max),
+        ) {
+            Ok(value) => {
+                updates.insert(key.clone(), value);
+            }
+            Err(violation) => violations.push(violation),
+        }
+    }
+
+    if violations.is_empty() {
+        Ok(updates)
+    } else {
+        Err(violations)
+    }
+}
+
+/// Value of `key` after applying `delta` to `base`, checked against `(min, max)`.
+fn delta_value(
+    key: &str,
+    delta: &DeltaValue,
+    base: Option<f64>,
+    (min, max): (f64, f64),
+) -> Result<f64, DeltaViolation> {
+    let value = match (delta, base) {
+        (DeltaValue::Absolute { value }, _) => f64::from(*value),
+        (DeltaValue::Additive { value }, Some(base)) => base + f64::from(*value),
+        (DeltaValue::Relative { value, unit }, Some(base)) => match unit.as_str() {
+            "percent" => base * (1.0 + f64::from(*value) / 100.0),
+            "factor" => base * f64::from(*value),
+            _ => {
+                return Err(DeltaViolation::UnsupportedUnit {
+                    key: key.to_string(),
+                    unit: unit.clone(),
+                })
+            }
+        },
+        (DeltaValue::Additive { .. } | DeltaValue::Relative { .. }, None) => {
+            return Err(DeltaViolation::MissingCurrent {
+                key: key.to_string(),
+            })
+        }
+    };
+    if (min..=max).contains(&value) {
+        Ok(value)
+    } else {
+        Err(DeltaViolation::OutOfRange {
+            key: key.to_string(),
+            value,
+            min,
+            max,
+        })
+    }
+}
+
+/// Apply `proposal` to a stored policy `snapshot` and return the new snapshot.
+///
+/// Only the fields listed in the [module docs](self) can be adjusted; apart
+/// from slot offsets every addressed field must already exist in the
+/// snapshot. A `policy_id` in the snapshot must match the proposal's
+/// `basis_policy`.
+///
+/// # Errors
+/// [`ApplyError::NotPending`], [`ApplyError::Expired`] or
+/// [`ApplyError::InvalidTs`] if the proposal may no longer be applied;
+/// otherwise [`ApplyError::Violations`] with every problem found. The
+/// snapshot is never partially updated.
+pub fn apply_proposal(
+    snapshot: &Value,
+    proposal: &WeightAdjustmentProposal,
+) -> Result<Value, ApplyError> {
+    apply_proposal_at(snapshot, proposal, OffsetDateTime::now_utc())
+}
+
+/// Like [`apply_proposal`], with the expiry judged at `now`.
+///
+/// # Errors
+/// See [`apply_proposal`].
+pub fn apply_proposal_at(
+    snapshot: &Value,
+    proposal: &WeightAdjustmentProposal,
+    now: OffsetDateTime,
+) -> Result<Value, ApplyError> {
+    if proposal.status != ProposalStatus::Proposed {
+        return Err(ApplyError::NotPending(proposal.status));
+    }
+    let Some(expiry) = expires_at(proposal, DEFAULT_TTL_SECONDS) else {
+        return Err(ApplyError::InvalidTs(proposal.ts.clone()));
+    };
+    if OffsetDateTime::parse(&expiry, &Rfc3339).is_ok_and(|t| now >= t) {
+        return Err(ApplyError::Expired(expiry));
+    }
+
+    let mut next = snapshot.clone();
+    let Some(fields) = next.as_object_mut() else {
+        return Err(ApplyError::NotAnObject);
+    };
+
+    let mut violations = Vec::new();
+    if let Some(policy_id) = fields.get("policy_id").and_then(Value::as_str) {
+        if policy_id != proposal.basis_policy {
+            violations.push(DeltaViolation::PolicyMismatch {
+                expected: policy_id.to_string(),
+                actual: proposal.basis_policy.clone(),
+            });
+        }
+    }
+
+    let mut updates = Vec::new();
+    for (key, delta) in &proposal.deltas {
+        let Some(target) = snapshot_field(fields, key) else {
+            violations.push(DeltaViolation::UnknownKey { key: key.clone() });
+            continue;
+        };
+        let current = field(fields, &target.path).and_then(Value::as_f64);
+        let Some(base) = current.or(target.unset) else {
+            violations.push(DeltaViolation::MissingCurrent { key: key.clone() });
+            continue;
+        };
+        match delta_value(key, delta, Some(base), target.range) {
+            Ok(value) => updates.push((target.path, value)),
+            Err(violation) => violations.push(violation),
+        }
+    }
+    if !violations.is_empty() {
+        return Err(ApplyError::Violations(violations));
+    }
+
+    for (path, value) in updates {
+        set_field(fields, &path, Value::from(value));
+    }
+    fields.insert(
+        APPLIED_PROPOSAL_FIELD.to_string(),
+        Value::String(proposal_digest(proposal)?),
+    );
+    Ok(next)
+}
+
+/// Snapshot field a delta key addresses.
+struct SnapshotField<'a> {
+    path: Vec<&'a str>,
+    range: (f64, f64),
+    /// Value the policy assumes while the field is absent.
+    unset: Option<f64>,
+}
+
+/// The field `key` addresses in the snapshot `fields`, if the policy has it.
+fn snapshot_field<'a>(
+    fields: &serde_json::Map<String, Value>,
+    key: &'a str,
+) -> Option<SnapshotField<'a>> {
+    let known = |path: Vec<&'a str>, range| SnapshotField {
+        path,
+        range,
+        unset: None,
+    };
+    match key {
+        "epsilon" => Some(known(vec!["epsilon"], (0.0, 1.0))),
+        "recency.half_life" => Some(known(vec!["recency", "half_life"], HALF_LIFE_RANGE)),
+        _ => {
+            if let Some(rest) = key.strip_prefix("timing.") {
+                let slot = rest.strip_suffix(".offset_minutes")?;
+                let window = fields.get("timing")?.get("window_minutes")?.as_f64()?;
+                return (!slot.is_empty()).then(|| SnapshotField {
+                    path: vec!["timing", "slots", slot, "offset_minutes"],
+                    range: (0.0, window),
+                    unset: Some(0.0),
+                });
+            }
+            let (arm, name) = key.strip_prefix("priors.")?.rsplit_once('.')?;
+            let range = match name {
+                "mean" => PRIOR_MEAN_RANGE,
+                "pseudo_count" => (0.0, f64::MAX),
+                _ => return None,
+            };
+            (!arm.is_empty()).then(|| known(vec!["priors", arm, name], range))
+        }
+    }
+}
+
+fn field<'a>(fields: &'a serde_json::Map<String, Value>, path: &[&str]) -> Option<&'a Value> {
+    let (first, rest) = path.split_first()?;
+    rest.iter()
+        .try_fold(fields.get(*first)?, |value, key| value.get(key))
+}
+
+/// Set the field at `path`, creating missing objects on the way.
+fn set_field(fields: &mut serde_json::Map<String, Value>, path: &[&str], value: Value) {
+    let Some((last, parents)) = path.split_last() else {
+        return;
+    };
+    let mut object = fields;
+    for key in parents {
+        let entry = object
+            .entry((*key).to_string())
+            .or_insert_with(|| Value::Object(serde_json::Map::new()));
+        let Some(next) = entry.as_object_mut() else {
+            return;
+        };
+        object = next;
+    }
+    object.insert((*last).to_string(), value);
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::{Evidence, ProposalStatus};
+
+    fn proposal(deltas: Vec<(&str, DeltaValue)>) -> WeightAdjustmentProposal {
+        WeightAdjustmentProposal {
+            version: "v1".into(),
+            basis_policy: "remind-bandit".into(),
+            ts: "2026-01-01T00:00:00Z".into(),
+            deltas: deltas
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+            confidence: 0.7,
+            evidence: Evidence::default(),
+            reasoning: None,
+            status: ProposalStatus::Proposed,
+        }
+    }
+
+    fn now() -> OffsetDateTime {
+        OffsetDateTime::parse("2026-01-02T00:00:00Z", &Rfc3339).unwrap()
+    }
+
+    fn descriptor() -> PolicyDescriptor {
+        PolicyDescriptor::new("remind-bandit").tunable("epsilon", 0.0, 1.0)
+    }
+
+    #[test]
+    fn applies_relative_delta_to_current_value() {
+        let p = proposal(vec![(
+            "epsilon",
+            DeltaValue::Relative {
+                value: -50.0,
+                unit: "percent".into(),
+            },
+        )]);
+        let current = BTreeMap::from([("epsilon".to_string(), 0.2)]);
+        let next = apply_to_params(&p, &descriptor(), &current).unwrap();
+        assert!((next["epsilon"] - 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn applies_deltas_to_snapshot_fields_and_records_the_proposal() {
+        let snapshot = serde_json::json!({
+            "version": "v1",
+            "policy_id": "remind-bandit",
+            "epsilon": 0.2,
+            "recency": { "half_life": 50.0 },
+            "priors": { "morning": { "pseudo_count": 10.0, "mean": 0.5 } }
+        });
+        let p = proposal(vec![
+            (
+                "epsilon",
+                DeltaValue::Relative {
+                    value: -50.0,
+                    unit: "percent".into(),
+                },
+            ),
+            ("recency.half_life", DeltaValue::Additive { value: 25.0 }),
+            ("priors.morning.mean", DeltaValue::Absolute { value: 0.75 }),
+        ]);
+        let next = apply_proposal_at(&snapshot, &p, now()).unwrap();
+        assert!((next["epsilon"].as_f64().unwrap() - 0.1).abs() < 1e-9);
+        assert_eq!(next["recency"]["half_life"], 75.0);
+        assert_eq!(next["priors"]["morning"]["mean"], 0.75);
+        assert_eq!(next["priors"]["morning"]["pseudo_count"], 10.0);
+        assert_eq!(
+            next[APPLIED_PROPOSAL_FIELD].as_str(),
+            Some(proposal_digest(&p).unwrap().as_str())
+        );
+
+        let bad = proposal(vec![
+            ("priors.evening.mean", DeltaValue::Absolute { value: 0.5 }),
+            (
+                "priors.morning.pseudo_count",
+                DeltaValue::Additive { value: -20.0 },
+            ),
+            ("window.size", DeltaValue::Absolute { value: 5.0 }),
+        ]);
+        let Err(ApplyError::Violations(violations)) = apply_proposal_at(&snapshot, &bad, now())
+        else {
+            panic!("invalid deltas must be rejected");
+        };
+        assert_eq!(violations.len(), 3);
+        assert!(violations.contains(&DeltaViolation::MissingCurrent {
+            key: "priors.evening.mean".into()
+        }));
+        assert!(violations.contains(&DeltaViolation::UnknownKey {
+            key: "window.size".into()
+        }));
+    }
+
+    #[test]
+    fn reports_every_violation_and_applies_nothing() {
+        let p = proposal(vec![
+            ("epsi1on", DeltaValue::Absolute { value: 0.1 }),
+            ("epsilon", DeltaValue::Additive { value: 0.9 }),
+        ]);
+        let current = BTreeMap::from([("epsilon".to_string(), 0.2)]);
+        let violations = apply_to_params(&p, &descriptor(), &current).unwrap_err();
+        assert_eq!(violations.len(), 2);
+        assert!(violations.contains(&DeltaViolation::UnknownKey {
+            key: "epsi1on".into()
+        }));
+        assert!(matches!(
+            violations.iter().find(|v| matches!(v, DeltaViolation::OutOfRange { .. })),
+            Some(DeltaViolation::OutOfRange { key, .. }) if key == "epsilon"
+        ));
+
+        let missing = validate_deltas(&p, &descriptor(), &BTreeMap::new());
+        assert!(missing.contains(&DeltaViolation::MissingCurrent {
+            key: "epsilon".into()
+        }));
+    }
+
+    #[test]
+    fn refuses_settled_and_expired_proposals() {
+        let snapshot = serde_json::json!({ "policy_id": "remind-bandit", "epsilon": 0.2 });
+        let mut p = proposal(vec![("epsilon", DeltaValue::Absolute { value: 0.1 })]);
+        assert!(apply_proposal_at(&snapshot, &p, now()).is_ok());
+
+        let late = OffsetDateTime::parse("2026-02-01T00:00:00Z", &Rfc3339).unwrap();
+        assert!(matches!(
+            apply_proposal_at(&snapshot, &p, late),
+            Err(ApplyError::Expired(at)) if at == "2026-01-31T00:00:00Z"
+        ));
+
+        p.status = ProposalStatus::Rejected;
+        assert!(matches!(
+            apply_proposal_at(&snapshot, &p, now()),
+            Err(ApplyError::NotPending(ProposalStatus::Rejected))
+        ));
+
+        p.status = ProposalStatus::Proposed;
+        p.ts = "yesterday".into();
+        assert!(matches!(
+            apply_proposal_at(&snapshot, &p, now()),
+            Err(ApplyError::InvalidTs(_))
+        ));
+    }
+
+    #[test]
+    fn bounds_prior_means_and_slot_offsets() {
+        let snapshot = serde_json::json!({
+            "policy_id": "remind-bandit",
+            "priors": { "morning": { "pseudo_count": 10.0, "mean": 0.5 } },
+            "timing": { "window_minutes": 60, "slots": {} }
+        });
+        let p = proposal(vec![
+            ("priors.morning.mean", DeltaValue::Absolute { value: 1.5 }),
+            (
+                "timing.evening.offset_minutes",
+                DeltaValue::Absolute { value: 90.0 },
+            ),
+        ]);
+        let Err(ApplyError::Violations(violations)) = apply_proposal_at(&snapshot, &p, now())
+        else {
+            panic!("out-of-range deltas must be rejected");
+        };
+        assert_eq!(violations.len(), 2);
+
+        let p = proposal(vec![(
+            "timing.evening.offset_minutes",
+            DeltaValue::Absolute { value: 10.0 },
+        )]);
+        let next = apply_proposal_at(&snapshot, &p, now()).unwrap();
+        assert_eq!(next["timing"]["slots"]["evening"]["offset_minutes"], 10.0);
+
+        let untimed = serde_json::json!({ "policy_id": "remind-bandit" });
+        let Err(ApplyError::Violations(violations)) = apply_proposal_at(&untimed, &p, now()) else {
+            panic!("a snapshot without timing has no slot offsets");
+        };
+        assert!(violations.contains(&DeltaViolation::UnknownKey {
+            key: "timing.evening.offset_minutes".into()
+        }));
+    }
+
+    #[test]
+    fn applies_an_analyzer_proposal_to_a_bandit_snapshot() {
+        use heimlern_bandits::{RemindBandit, TimingConfig};
+        use heimlern_core::Policy;
+
+        // Evening reminders fire 50 minutes into the slot and are acknowledged 40 minutes late.
+        let outcomes: Vec<crate::DecisionOutcome> = (0..12)
+            .map(|i| {
+                serde_json::from_value(serde_json::json!({
+                    "decision_id": i.to_string(),
+                    "ts": format!("2026-01-{:02}T19:40:00Z", i + 1),
+                    "action": "remind.evening",
+                    "outcome": "success",
+                    "success": true,
+                    "reward": 1.0,
+                    "metadata": {
+                        "decision_ts": format!("2026-01-{:02}T19:00:00Z", i + 1),
+                        "offset_minutes": 50.0
+                    }
+                }))
+                .unwrap()
+            })
+            .collect();
+        let mut bandit = RemindBandit::builder()
+            .timing(TimingConfig::default())
+            .build()
+            .unwrap();
+        let proposal = crate::FeedbackAnalyzer::new(10, 0.3)
+            .propose_for(&bandit.descriptor(), &outcomes)
+            .expect("late acknowledgements yield a proposal");
+
+        let next = apply_proposal(&bandit.snapshot(), &proposal).unwrap();
+        bandit.try_load(next).unwrap();
+        assert!((bandit.tunable_params()["timing.evening.offset_minutes"] - 10.0).abs() < 1e-9);
+        assert_eq!(
+            bandit.applied_proposal(),
+            Some(proposal_digest(&proposal).unwrap().as_str())
+        );
+    }
+}
@@ -0,0 +1,351 @@
+//! Tamper-evident audit trail of proposals.
+//!
+//! Every time a proposal is stored or changes status, an [`AuditEntry`] is
+//! appended to a JSONL log. The entry records the proposal's
+//! [`proposal_digest`] and status, and carries the hash of the previous
+//! entry; its own hash covers all of that. Editing, dropping or reordering an
+//! entry breaks the chain from that point on, and a proposal file changed
+//! without going through the log no longer matches its last entry.
+//! [`AuditLog::verify`] and [`AuditLog::check_proposal`] report both.
+//!
+//! Entry hash: `sha256(canonical_json(entry without "hash"))`, hex. The first
+//! entry links to [`GENESIS_HASH`].
+
+use crate::provenance::{proposal_digest, to_hex, ProvenanceError};
+use crate::{ProposalStatus, WeightAdjustmentProposal};
+use heimlern_core::canonical;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// `prev_hash` of the first entry.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Errors raised while reading or writing an audit log.
+#[derive(Debug, thiserror::Error)]
+pub enum AuditError {
+    #[error("audit log I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid audit entry on line {line}: {source}")]
+    Parse {
+        line: usize,
+        source: serde_json::Error,
+    },
+    #[error("hashing audit entry failed: {0}")]
+    Hash(#[from] ProvenanceError),
+}
+
+/// What happened to the proposal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEvent {
+    Stored,
+    StatusChanged,
+}
+
+/// One link of the chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Position in the log, starting at 1.
+    pub seq: u64,
+    /// When the entry was written (RFC 3339).
+    pub ts: String,
+    pub event: AuditEvent,
+    pub proposal_id: String,
+    pub basis_policy: String,
+    /// [`proposal_digest`] of the proposal at this point.
+    pub proposal_digest: String,
+    pub status: ProposalStatus,
+    /// `hash` of the previous entry, [`GENESIS_HASH`] for the first.
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+impl AuditEntry {
+    /// Hash of this entry's fields other than `hash`.
+    ///
+    /// # Errors
+    /// Fails if the entry cannot be serialized.
+    pub fn compute_hash(&self) -> Result<String, ProvenanceError> {
+        let mut value = serde_json::to_value(self)?;
+        if let Some(map) = value.as_object_mut() {
+            map.remove("hash");
+        }
+        Ok(to_hex(&Sha256::digest(canonical::to_canonical_vec(
+            &value,
+        )?)))
+    }
+}
+
+/// A break found by [`AuditLog::verify`] or [`AuditLog::check_proposal`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditIssue {
+    /// Entry `seq` does not hash to its recorded `hash`.
+    HashMismatch {
+        seq: u64,
+        expected: String,
+        actual: String,
+    },
+    /// Entry `seq` does not link to the entry before it.
+    BrokenLink {
+        seq: u64,
+        expected: String,
+        actual: String,
+    },
+    /// The proposal differs from its last audited state.
+    ProposalChanged {
+        id: String,
+        expected_digest: String,
+        actual_digest: String,
+    },
+    /// The proposal's status differs from its last audited status.
+    StatusChanged {
+        id: String,
+        expected: ProposalStatus,
+        actual: ProposalStatus,
+    },
+    /// The proposal has no audit entry.
+    Unaudited { id: String },
+}
+
+/// Append-only, hash-chained audit log in a JSONL file.
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    path: PathBuf,
+    entries: Vec<AuditEntry>,
+}
+
+impl AuditLog {
+    /// Open the log at `path`; a missing file is an empty log.
+    ///
+    /// # Errors
+    /// Fails on I/O errors or malformed lines.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, AuditError> {
+        let path = path.into();
+        let entries = read_entries(&path)?;
+        Ok(Self { path, entries })
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// All entries, oldest first.
+    #[must_use]
+    pub fn entries(&self) -> &[AuditEntry] {
+        &self.entries
+    }
+
+    /// Hash the next entry would link to.
+    #[must_use]
+    pub fn head(&self) -> &str {
+        self.entries
+            .last()
+            .map_or(GENESIS_HASH, |e| e.hash.as_str())
+    }
+
+    /// Append an entry for `proposal` stored as `id`.
+    ///
+    /// # Errors
+    /// Fails if the proposal cannot be hashed or the file cannot be written.
+    pub fn record(
+        &mut self,
+        event: AuditEvent,
+        id: &str,
+        proposal: &WeightAdjustmentProposal,
+    ) -> Result<&AuditEntry, AuditError> {
+        let mut entry = AuditEntry {
+            seq: self.entries.last().map_or(1, |e| e.seq + 1),
+            ts: crate::iso8601_now(),
+            event,
+            proposal_id: id.to_string(),
+            basis_policy: proposal.basis_policy.clone(),
+            proposal_digest: proposal_digest(proposal)?,
+            status: proposal.status,
+            prev_hash: self.head().to_string(),
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash()?;
+        let line = serde_json::to_string(&entry).map_err(ProvenanceError::from)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{line}")?;
+        self.entries.push(entry);
+        Ok(&self.entries[self.entries.len() - 1])
+    }
+
+    /// Recompute every hash and link; empty if the chain is intact.
+    ///
+    /// # Errors
+    /// Fails if an entry cannot be serialized.
+    pub fn verify(&self) -> Result<Vec<AuditIssue>, AuditError> {
+        let mut issues = Vec::new();
+        let mut prev = GENESIS_HASH;
+        for entry in &self.entries {
+            if entry.prev_hash != prev {
+                issues.push(AuditIssue::BrokenLink {
+                    seq: entry.seq,
+                    expected: prev.to_string(),
+                    actual: entry.prev_hash.clone(),
+                });
+            }
+            let actual = entry.compute_hash()?;
+            if actual != entry.hash {
+                issues.push(AuditIssue::HashMismatch {
+                    seq: entry.seq,
+                    expected: entry.hash.clone(),
+                    actual,
+                });
+            }
+            prev = &entry.hash;
+        }
+        Ok(issues)
+    }
+
+    /// Compare `proposal`, stored as `id`, with its last audit entry.
+    ///
+    /// # Errors
+    /// Fails if the proposal cannot be hashed.
+    pub fn check_proposal(
+        &self,
+        id: &str,
+        proposal: &WeightAdjustmentProposal,
+    ) -> Result<Option<AuditIssue>, AuditError> {
+        let Some(last) = self.entries.iter().rev().find(|e| e.proposal_id == id) else {
+            return Ok(Some(AuditIssue::Unaudited { id: id.to_string() }));
+        };
+        let actual_digest = proposal_digest(proposal)?;
+        if actual_digest != last.proposal_digest {
+            return Ok(Some(AuditIssue::ProposalChanged {
+                id: id.to_string(),
+                expected_digest: last.proposal_digest.clone(),
+                actual_digest,
+            }));
+        }
+        if proposal.status != last.status {
+            return Ok(Some(AuditIssue::StatusChanged {
+                id: id.to_string(),
+                expected: last.status,
+                actual: proposal.status,
+            }));
+        }
+        Ok(None)
+    }
+}
+
+fn read_entries(path: &Path) -> Result<Vec<AuditEntry>, AuditError> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut entries = Vec::new();
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = serde_json::from_str(&line).map_err(|source| AuditError::Parse {
+            line: i + 1,
+            source,
+        })?;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::{DeltaValue, Evidence};
+    use std::collections::BTreeMap;
+    use std::fs;
+
+    fn proposal(epsilon: f32) -> WeightAdjustmentProposal {
+        WeightAdjustmentProposal {
+            version: "v1".into(),
+            basis_policy: "remind-bandit".into(),
+            ts: "2026-01-01T00:00:00Z".into(),
+            deltas: BTreeMap::from([("epsilon".into(), DeltaValue::Absolute { value: epsilon })]),
+            confidence: 0.8,
+            evidence: Evidence::default(),
+            reasoning: None,
+            status: ProposalStatus::Proposed,
+        }
+    }
+
+    fn rewrite(path: &Path, lines: &[String]) -> Vec<AuditIssue> {
+        fs::write(path, lines.join("\n") + "\n").unwrap();
+        AuditLog::open(path).unwrap().verify().unwrap()
+    }
+
+    #[test]
+    fn tampered_chain_is_reported() {
+        let path =
+            std::env::temp_dir().join(format!("heimlern-audit-chain-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut log = AuditLog::open(&path).unwrap();
+        for (id, epsilon) in [("p1", 0.1), ("p2", 0.2), ("p3", 0.3)] {
+            log.record(AuditEvent::Stored, id, &proposal(epsilon))
+                .unwrap();
+        }
+        assert_eq!(log.entries()[0].prev_hash, GENESIS_HASH);
+        assert!(AuditLog::open(&path).unwrap().verify().unwrap().is_empty());
+        let lines: Vec<String> = fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect();
+
+        // Edited field, hash left alone: only that entry's hash mismatches.
+        let mut edited = lines.clone();
+        let mut entry: AuditEntry = serde_json::from_str(&edited[1]).unwrap();
+        entry.status = ProposalStatus::Accepted;
+        edited[1] = serde_json::to_string(&entry).unwrap();
+        assert!(matches!(
+            rewrite(&path, &edited).as_slice(),
+            [AuditIssue::HashMismatch { seq: 2, .. }]
+        ));
+
+        // Dropped entry: the next one links to a hash that is gone.
+        let dropped = vec![lines[0].clone(), lines[2].clone()];
+        assert!(matches!(
+            rewrite(&path, &dropped).as_slice(),
+            [AuditIssue::BrokenLink { seq: 3, .. }]
+        ));
+
+        // Reordered entries: both swapped links break.
+        let swapped = vec![lines[1].clone(), lines[0].clone(), lines[2].clone()];
+        assert!(matches!(
+            rewrite(&path, &swapped).as_slice(),
+            [
+                AuditIssue::BrokenLink { seq: 2, .. },
+                AuditIssue::BrokenLink { seq: 1, .. },
+                AuditIssue::BrokenLink { seq: 3, .. },
+            ]
+        ));
+
+        // A proposal that never went through the log, or changed status outside it.
+        let log = AuditLog::open(&path).unwrap();
+        assert_eq!(
+            log.check_proposal("p9", &proposal(0.1)).unwrap(),
+            Some(AuditIssue::Unaudited { id: "p9".into() })
+        );
+        let mut accepted = proposal(0.1);
+        accepted.status = ProposalStatus::Accepted;
+        assert!(matches!(
+            log.check_proposal("p1", &accepted).unwrap(),
+            Some(AuditIssue::StatusChanged { .. })
+        ));
+        assert_eq!(log.check_proposal("p1", &proposal(0.1)).unwrap(), None);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
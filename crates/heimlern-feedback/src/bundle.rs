@@ -0,0 +1,308 @@
+//! Proposal bundles spanning multiple policies.
+//!
+//! Household-level changes often need coordinated adjustment of several
+//! policies (e.g. all per-person reminder bandits). A [`ProposalBundle`] groups
+//! such proposals with shared evidence and gives them an all-or-nothing
+//! lifecycle: status transitions either succeed for every member or leave the
+//! whole bundle unchanged.
+//!
+//! Member proposals stay valid `policy.weight_adjustment.v1` documents; the
+//! bundle is a wrapper artifact and does not add fields to them.
+
+use crate::apply::{DeltaViolation, PolicyState};
+use crate::{iso8601_now, Evidence, ProposalStatus, WeightAdjustmentProposal};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Errors raised by bundle operations.
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum BundleError {
+    /// A bundle needs at least one proposal.
+    #[error("bundle contains no proposals")]
+    Empty,
+    /// Two members target the same basis policy.
+    #[error("bundle contains more than one proposal for policy '{0}'")]
+    DuplicatePolicy(String),
+    /// A member is not in the state required for the transition.
+    #[error("proposal for '{policy}' is {status:?}, expected Proposed")]
+    NotPending {
+        policy: String,
+        status: ProposalStatus,
+    },
+    /// A member's deltas do not fit the target policy.
+    #[error("proposal for '{policy}' has {} invalid delta(s)", violations.len())]
+    InvalidDeltas {
+        policy: String,
+        violations: Vec<DeltaViolation>,
+    },
+}
+
+/// Group of proposals that are accepted or rejected together.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalBundle {
+    /// Version of the bundle format.
+    pub version: String,
+    /// Stable identifier of the bundle.
+    pub bundle_id: String,
+    /// Timestamp when the bundle was created.
+    pub ts: String,
+    /// Member proposals, at most one per basis policy.
+    pub proposals: Vec<WeightAdjustmentProposal>,
+    /// Evidence shared by all members (e.g. household-wide analysis).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shared_evidence: Option<Evidence>,
+    /// Human-readable explanation for the coordinated change.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<String>,
+}
+
+impl ProposalBundle {
+    /// Create a bundle from pending proposals.
+    ///
+    /// # Errors
+    /// Fails if `proposals` is empty, contains two proposals for the same
+    /// basis policy, or contains a proposal that is not [`ProposalStatus::Proposed`].
+    pub fn new(
+        bundle_id: impl Into<String>,
+        proposals: Vec<WeightAdjustmentProposal>,
+    ) -> Result<Self, BundleError> {
+        let bundle = Self {
+            version: "v1".to_string(),
+            bundle_id: bundle_id.into(),
+            ts: iso8601_now(),
+            proposals,
+            shared_evidence: None,
+            reasoning: None,
+        };
+        bundle.validate()?;
+        bundle.ensure_pending()?;
+        Ok(bundle)
+    }
+
+    /// Attach shared evidence.
+    #[must_use]
+    pub fn with_shared_evidence(mut self, evidence: Evidence) -> Self {
+        self.shared_evidence = Some(evidence);
+        self
+    }
+
+    /// Attach a reasoning text.
+    #[must_use]
+    pub fn with_reasoning(mut self, reasoning: impl Into<String>) -> Self {
+        self.reasoning = Some(reasoning.into());
+        self
+    }
+
+    /// Check structural invariants (non-empty, one proposal per policy).
+    ///
+    /// # Errors
+    /// Returns the first violated invariant.
+    pub fn validate(&self) -> Result<(), BundleError> {
+        if self.proposals.is_empty() {
+            return Err(BundleError::Empty);
+        }
+        let mut seen = HashSet::new();
+        for p in &self.proposals {
+            if !seen.insert(p.basis_policy.as_str()) {
+                return Err(BundleError::DuplicatePolicy(p.basis_policy.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Aggregate status: the common status if all members agree, else `None`.
+    #[must_use]
+    pub fn status(&self) -> Option<ProposalStatus> {
+        let first = self.proposals.first()?.status;
+        self.proposals
+            .iter()
+            .all(|p| p.status == first)
+            .then_some(first)
+    }
+
+    /// Basis policies touched by this bundle.
+    pub fn policies(&self) -> impl Iterator<Item = &str> {
+        self.proposals.iter().map(|p| p.basis_policy.as_str())
+    }
+
+    /// Lowest member confidence; a bundle is only as strong as its weakest part.
+    #[must_use]
+    pub fn confidence(&self) -> f32 {
+        self.proposals
+            .iter()
+            .map(|p| p.confidence)
+            .reduce(f32::min)
+            .unwrap_or(0.0)
+    }
+
+    /// Accept every member proposal, or none.
+    ///
+    /// # Errors
+    /// Fails without modification if any member is not pending.
+    pub fn accept(&mut self) -> Result<(), BundleError> {
+        self.transition(ProposalStatus::Accepted)
+    }
+
+    /// Accept every member after validating its deltas against the live
+    /// policy in `states`, or accept none.
+    ///
+    /// # Errors
+    /// Fails without modification if any member is not pending, targets a
+    /// policy missing from `states`, or has deltas that violate the policy's
+    /// descriptor.
+    pub fn accept_validated(&mut self, states: &[PolicyState]) -> Result<(), BundleError> {
+        self.validate()?;
+        self.ensure_pending()?;
+        for p in &self.proposals {
+            let violations = match states
+                .iter()
+                .find(|s| s.descriptor.policy_id == p.basis_policy)
+            {
+                Some(state) => state.validate(p),
+                None => vec![DeltaViolation::UnknownPolicy {
+                    policy: p.basis_policy.clone(),
+                }],
+            };
+            if !violations.is_empty() {
+                return Err(BundleError::InvalidDeltas {
+                    policy: p.basis_policy.clone(),
+                    violations,
+                });
+            }
+        }
+        self.transition(ProposalStatus::Accepted)
+    }
+
+    /// Reject every member proposal, or none.
+    ///
+    /// # Errors
+    /// Fails without modification if any member is not pending.
+    pub fn reject(&mut self) -> Result<(), BundleError> {
+        self.transition(ProposalStatus::Rejected)
+    }
+
+    fn ensure_pending(&self) -> Result<(), BundleError> {
+        match self
+            .proposals
+            .iter()
+            .find(|p| p.status != ProposalStatus::Proposed)
+        {
+            Some(p) => Err(BundleError::NotPending {
+                policy: p.basis_policy.clone(),
+                status: p.status,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn transition(&mut self, to: ProposalStatus) -> Result<(), BundleError> {
+        self.validate()?;
+        self.ensure_pending()?;
+        for p in &mut self.proposals {
+            p.status = to;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn proposal(policy: &str, confidence: f32) -> WeightAdjustmentProposal {
+        WeightAdjustmentProposal {
+            version: "v1".into(),
+            basis_policy: policy.into(),
+            ts: "2026-01-01T00:00:00Z".into(),
+            deltas: BTreeMap::new(),
+            confidence,
+            evidence: Evidence::default(),
+            reasoning: None,
+            status: ProposalStatus::Proposed,
+        }
+    }
+
+    #[test]
+    fn accept_transitions_all_members() {
+        let mut bundle = ProposalBundle::new(
+            "household-1",
+            vec![proposal("remind-alice", 0.8), proposal("remind-bob", 0.6)],
+        )
+        .unwrap();
+        assert_eq!(bundle.status(), Some(ProposalStatus::Proposed));
+        assert!((bundle.confidence() - 0.6).abs() < f32::EPSILON);
+
+        bundle.accept().unwrap();
+        assert_eq!(bundle.status(), Some(ProposalStatus::Accepted));
+    }
+
+    #[test]
+    fn accept_is_all_or_nothing() {
+        let mut bundle =
+            ProposalBundle::new("b", vec![proposal("a", 0.8), proposal("b", 0.8)]).unwrap();
+        bundle.proposals[1].status = ProposalStatus::Superseded;
+
+        let err = bundle.accept().unwrap_err();
+        assert_eq!(
+            err,
+            BundleError::NotPending {
+                policy: "b".into(),
+                status: ProposalStatus::Superseded
+            }
+        );
+        assert_eq!(bundle.proposals[0].status, ProposalStatus::Proposed);
+        assert_eq!(bundle.status(), None);
+    }
+
+    #[test]
+    fn accept_validated_rejects_typoed_delta_keys() {
+        use crate::DeltaValue;
+        use heimlern_core::PolicyDescriptor;
+        use std::collections::BTreeMap;
+
+        let mut bad = proposal("b", 0.8);
+        bad.deltas
+            .insert("epsi1on".into(), DeltaValue::Absolute { value: 0.1 });
+        let mut bundle = ProposalBundle::new("x", vec![proposal("a", 0.8), bad]).unwrap();
+        let states: Vec<PolicyState> = ["a", "b"]
+            .into_iter()
+            .map(|id| {
+                PolicyState::new(
+                    PolicyDescriptor::new(id).tunable("epsilon", 0.0, 1.0),
+                    BTreeMap::new(),
+                )
+            })
+            .collect();
+
+        let err = bundle.accept_validated(&states).unwrap_err();
+        assert_eq!(
+            err,
+            BundleError::InvalidDeltas {
+                policy: "b".into(),
+                violations: vec![DeltaViolation::UnknownKey {
+                    key: "epsi1on".into()
+                }],
+            }
+        );
+        assert_eq!(bundle.status(), Some(ProposalStatus::Proposed));
+
+        bundle.proposals[1].deltas.clear();
+        bundle.accept_validated(&states).unwrap();
+        assert_eq!(bundle.status(), Some(ProposalStatus::Accepted));
+    }
+
+    #[test]
+    fn rejects_duplicate_policies_and_empty_bundles() {
+        assert_eq!(
+            ProposalBundle::new("x", vec![proposal("a", 0.5), proposal("a", 0.5)]).unwrap_err(),
+            BundleError::DuplicatePolicy("a".into())
+        );
+        assert_eq!(
+            ProposalBundle::new("x", Vec::new()).unwrap_err(),
+            BundleError::Empty
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//! Champion/challenger evaluation.
+//!
+//! Before a proposal is accepted, the policy it would produce (the
+//! challenger) should beat the running one (the champion) on the same
+//! history. [`compare_policies`] replays the recorded outcomes through both,
+//! exactly as [`replay`] does for a single policy, and compares the success
+//! rates over matched decisions with a two-proportion z-test. Both policies
+//! learn from the matched outcomes during the replay, so they are compared
+//! under the same conditions.
+//!
+//! The challenger only wins when it is better *and* the difference is
+//! significant at the requested confidence; otherwise the report says
+//! [`Verdict::Inconclusive`] and the champion stays.
+
+use crate::replay::{replay, ReplayResult};
+use crate::{two_proportion_z, DecisionOutcome, OutcomeStatistics};
+use heimlern_core::Policy;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Replay result of one side, with the Wilson interval of its success rate.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PolicyEvaluation {
+    #[serde(flatten)]
+    pub replay: ReplayResult,
+    /// Success rate over matched decisions (0.0 if nothing matched).
+    pub success_rate: f32,
+    /// Wilson score interval of `success_rate` at the report's confidence.
+    pub interval: (f32, f32),
+}
+
+impl PolicyEvaluation {
+    fn new(replay: ReplayResult, confidence: f32) -> Self {
+        let stats = OutcomeStatistics {
+            total: replay.matched,
+            successes: replay.successes,
+            failures: replay.matched - replay.successes,
+            ..OutcomeStatistics::default()
+        };
+        Self {
+            success_rate: replay.success_rate().unwrap_or(0.0),
+            interval: stats.success_rate_interval(confidence),
+            replay,
+        }
+    }
+}
+
+/// Outcome of a comparison.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    /// The champion is significantly better.
+    Champion,
+    /// The challenger is significantly better.
+    Challenger,
+    /// No significant difference, or too little data.
+    Inconclusive,
+}
+
+/// Comparison of champion and challenger on the same outcomes.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ComparisonReport {
+    pub champion: PolicyEvaluation,
+    pub challenger: PolicyEvaluation,
+    /// Challenger success rate minus champion success rate.
+    pub difference: f32,
+    /// Two-proportion z statistic of `difference`.
+    pub z: f32,
+    /// Two-sided p-value of `z`.
+    pub p_value: f32,
+    /// Confidence level the verdict was taken at.
+    pub confidence: f32,
+    pub verdict: Verdict,
+}
+
+/// Replay `outcomes` through `champion` and `challenger` and compare them at
+/// the two-sided `confidence` level (e.g. `0.95`).
+pub fn compare_policies<C: Policy + ?Sized, H: Policy + ?Sized>(
+    champion: &mut C,
+    challenger: &mut H,
+    outcomes: &[DecisionOutcome],
+    confidence: f32,
+) -> ComparisonReport {
+    let champion = PolicyEvaluation::new(replay(champion, outcomes), confidence);
+    let challenger = PolicyEvaluation::new(replay(challenger, outcomes), confidence);
+    let (z, p_value) = two_proportion_test(&champion.replay, &challenger.replay);
+    #[allow(clippy::cast_possible_truncation)]
+    let p_value = p_value as f32;
+    let verdict = if p_value >= 1.0 - confidence.clamp(0.0, 1.0) {
+        Verdict::Inconclusive
+    } else if z > 0.0 {
+        Verdict::Challenger
+    } else {
+        Verdict::Champion
+    };
+    #[allow(clippy::cast_possible_truncation)]
+    ComparisonReport {
+        difference: challenger.success_rate - champion.success_rate,
+        z: z as f32,
+        p_value,
+        confidence,
+        verdict,
+        champion,
+        challenger,
+    }
+}
+
+/// Like [`compare_policies`], with both sides loaded from snapshots into
+/// fresh policies built by `make_policy`.
+pub fn compare_snapshots<P: Policy>(
+    mut make_policy: impl FnMut() -> P,
+    champion: &Value,
+    challenger: &Value,
+    outcomes: &[DecisionOutcome],
+    confidence: f32,
+) -> ComparisonReport {
+    let mut champion_policy = make_policy();
+    champion_policy.load(champion.clone());
+    let mut challenger_policy = make_policy();
+    challenger_policy.load(challenger.clone());
+    compare_policies(
+        &mut champion_policy,
+        &mut challenger_policy,
+        outcomes,
+        confidence,
+    )
+}
+
+/// Pooled two-proportion z-test of `b` against `a`: `(z, two-sided p)`.
+fn two_proportion_test(a: &ReplayResult, b: &ReplayResult) -> (f64, f64) {
+    if a.matched == 0 || b.matched == 0 {
+        return (0.0, 1.0);
+    }
+    let z = two_proportion_z(a.successes, a.matched, b.successes, b.matched);
+    (z, 2.0 * (1.0 - normal_cdf(z.abs())))
+}
+
+/// Standard normal CDF via the error function approximation of
+/// Abramowitz–Stegun 7.1.26 (absolute error below 1.5e-7).
+fn normal_cdf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.327_591_1 * x.abs() / std::f64::consts::SQRT_2);
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let erf = 1.0 - poly * (-(x * x) / 2.0).exp();
+    if x >= 0.0 {
+        0.5 * (1.0 + erf)
+    } else {
+        0.5 * (1.0 - erf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OutcomeType;
+    use heimlern_core::{Context, Decision};
+    use serde_json::json;
+
+    /// Always picks the slot stored in its snapshot.
+    #[derive(Default)]
+    struct Fixed(String);
+
+    impl Policy for Fixed {
+        fn decide(&mut self, _: &Context) -> Decision {
+            Decision {
+                action: format!("remind.{}", self.0),
+                score: 0.0,
+                why: vec![],
+                context: None,
+                chosen: None,
+            }
+        }
+        fn feedback(&mut self, _: &Context, _: &str, _: f32) {}
+        fn snapshot(&self) -> Value {
+            json!({ "slot": self.0 })
+        }
+        fn load(&mut self, snapshot: Value) {
+            self.0 = snapshot["slot"].as_str().unwrap_or_default().to_string();
+        }
+    }
+
+    fn outcomes(slot: &str, total: usize, successes: usize) -> Vec<DecisionOutcome> {
+        (0..total)
+            .map(|i| {
+                let success = i < successes;
+                DecisionOutcome {
+                    decision_id: format!("{slot}-{i}"),
+                    ts: "2026-01-01T00:00:00Z".into(),
+                    policy_id: None,
+                    action: Some(format!("remind.{slot}")),
+                    outcome: if success {
+                        OutcomeType::Success
+                    } else {
+                        OutcomeType::Failure
+                    },
+                    success,
+                    reward: None,
+                    context: Some(json!({ "kind": "reminder", "features": {} })),
+                    metadata: None,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn challenger_wins_only_with_a_significant_lead() {
+        let mut history = outcomes("morning", 100, 40);
+        history.extend(outcomes("evening", 100, 70));
+        let champion = json!({ "slot": "morning" });
+        let challenger = json!({ "slot": "evening" });
+
+        let report = compare_snapshots(Fixed::default, &champion, &challenger, &history, 0.95);
+        assert_eq!(report.champion.replay.matched, 100);
+        assert_eq!(report.challenger.replay.successes, 70);
+        assert!((report.difference - 0.3).abs() < 1e-6);
+        assert!(report.z > 4.0 && report.p_value < 0.001, "{report:?}");
+        assert!(report.challenger.interval.0 > report.champion.interval.1);
+        assert_eq!(report.verdict, Verdict::Challenger);
+
+        let reverse = compare_snapshots(Fixed::default, &challenger, &champion, &history, 0.95);
+        assert_eq!(reverse.verdict, Verdict::Champion);
+
+        let mut small = outcomes("morning", 10, 4);
+        small.extend(outcomes("evening", 10, 6));
+        let report = compare_snapshots(Fixed::default, &champion, &challenger, &small, 0.95);
+        assert_eq!(report.verdict, Verdict::Inconclusive);
+        assert!(report.p_value > 0.05);
+    }
+
+    #[test]
+    fn normal_cdf_matches_reference_values() {
+        assert!((normal_cdf(0.0) - 0.5).abs() < 1e-7);
+        assert!((normal_cdf(1.96) - 0.975).abs() < 1e-4);
+        assert!((normal_cdf(-1.0) - 0.158_655).abs() < 1e-5);
+    }
+}
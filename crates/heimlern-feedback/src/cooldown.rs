@@ -0,0 +1,288 @@
+//! Rate limiting of proposals per basis policy.
+//!
+//! Proposing after every handful of outcomes lets the analyzer chase noise:
+//! one proposal lowers epsilon, the next few unlucky decisions raise it
+//! again. A [`CooldownLedger`] remembers when the last proposal for each
+//! `basis_policy` was emitted and how many outcomes had been seen by then,
+//! and refuses a new one until the [`Cooldown`] has elapsed *and* enough new
+//! outcomes have arrived.
+//!
+//! The file-backed ledger rewrites its JSON file on every recorded proposal,
+//! so the cooldown survives restarts.
+
+use crate::{DecisionOutcome, FeedbackAnalyzer, WeightAdjustmentProposal};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+/// Default minimum time between two proposals for the same policy.
+pub const DEFAULT_COOLDOWN_SECONDS: i64 = 86_400;
+
+/// Default number of new outcomes required before the next proposal.
+pub const DEFAULT_MIN_NEW_OUTCOMES: usize = 20;
+
+/// Why a proposal was refused.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum Refusal {
+    #[error("last proposal for '{policy}' was emitted {elapsed_seconds}s ago, cooldown is {cooldown_seconds}s")]
+    TooSoon {
+        policy: String,
+        elapsed_seconds: i64,
+        cooldown_seconds: i64,
+    },
+    #[error("only {new} new outcomes for '{policy}' since the last proposal, {required} required")]
+    TooFewOutcomes {
+        policy: String,
+        new: usize,
+        required: usize,
+    },
+}
+
+/// Errors raised by a [`CooldownLedger`].
+#[derive(Debug, thiserror::Error)]
+pub enum CooldownError {
+    #[error("cooldown ledger I/O failed: {0}")]
+    Io(#[from] io::Error),
+    #[error("invalid cooldown ledger: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("proposal refused: {0}")]
+    Refused(#[from] Refusal),
+}
+
+/// Minimum distance between two proposals for the same policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cooldown {
+    pub min_interval_seconds: i64,
+    pub min_new_outcomes: usize,
+}
+
+impl Default for Cooldown {
+    fn default() -> Self {
+        Self {
+            min_interval_seconds: DEFAULT_COOLDOWN_SECONDS,
+            min_new_outcomes: DEFAULT_MIN_NEW_OUTCOMES,
+        }
+    }
+}
+
+/// The last emitted proposal of one policy.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LastProposal {
+    /// `ts` of the proposal (RFC 3339).
+    pub ts: String,
+    /// Outcomes seen for the policy when it was emitted.
+    pub outcome_count: usize,
+}
+
+/// Last proposal per basis policy, checked against a [`Cooldown`].
+#[derive(Debug, Default)]
+pub struct CooldownLedger {
+    cooldown: Cooldown,
+    path: Option<PathBuf>,
+    last: BTreeMap<String, LastProposal>,
+}
+
+impl CooldownLedger {
+    /// Ledger that only lives as long as the process.
+    #[must_use]
+    pub fn in_memory(cooldown: Cooldown) -> Self {
+        Self {
+            cooldown,
+            ..Self::default()
+        }
+    }
+
+    /// Open a file-backed ledger, loading previously recorded proposals.
+    ///
+    /// A missing file is an empty ledger; it is created on the first record.
+    ///
+    /// # Errors
+    /// Fails if the file exists but cannot be read or parsed.
+    pub fn open(path: impl AsRef<Path>, cooldown: Cooldown) -> Result<Self, CooldownError> {
+        let path = path.as_ref();
+        let last = match fs::read_to_string(path) {
+            Ok(raw) => serde_json::from_str(&raw)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self {
+            cooldown,
+            path: Some(path.to_path_buf()),
+            last,
+        })
+    }
+
+    #[must_use]
+    pub fn cooldown(&self) -> Cooldown {
+        self.cooldown
+    }
+
+    /// The last recorded proposal for `basis_policy`.
+    #[must_use]
+    pub fn last(&self, basis_policy: &str) -> Option<&LastProposal> {
+        self.last.get(basis_policy)
+    }
+
+    /// Whether a proposal for `basis_policy` may be emitted now, with
+    /// `outcome_count` outcomes seen for it.
+    ///
+    /// # Errors
+    /// Returns the [`Refusal`] if the cooldown is still active.
+    pub fn check(&self, basis_policy: &str, outcome_count: usize) -> Result<(), Refusal> {
+        self.check_at(basis_policy, outcome_count, OffsetDateTime::now_utc())
+    }
+
+    /// Like [`Self::check`], at `now`.
+    ///
+    /// # Errors
+    /// Returns the [`Refusal`] if the cooldown is still active at `now`.
+    pub fn check_at(
+        &self,
+        basis_policy: &str,
+        outcome_count: usize,
+        now: OffsetDateTime,
+    ) -> Result<(), Refusal> {
+        let Some(last) = self.last.get(basis_policy) else {
+            return Ok(());
+        };
+        // An unparseable timestamp cannot hold the policy back forever.
+        if let Ok(ts) = OffsetDateTime::parse(&last.ts, &Rfc3339) {
+            let elapsed_seconds = (now - ts).whole_seconds();
+            if elapsed_seconds < self.cooldown.min_interval_seconds {
+                return Err(Refusal::TooSoon {
+                    policy: basis_policy.to_string(),
+                    elapsed_seconds,
+                    cooldown_seconds: self.cooldown.min_interval_seconds,
+                });
+            }
+        }
+        // A shorter log than last time was rotated: everything in it is new.
+        let new = if outcome_count >= last.outcome_count {
+            outcome_count - last.outcome_count
+        } else {
+            outcome_count
+        };
+        if new < self.cooldown.min_new_outcomes {
+            return Err(Refusal::TooFewOutcomes {
+                policy: basis_policy.to_string(),
+                new,
+                required: self.cooldown.min_new_outcomes,
+            });
+        }
+        Ok(())
+    }
+
+    /// Record that `proposal` was emitted with `outcome_count` outcomes seen.
+    ///
+    /// # Errors
+    /// Fails if the file-backed ledger cannot be written.
+    pub fn record(
+        &mut self,
+        proposal: &WeightAdjustmentProposal,
+        outcome_count: usize,
+    ) -> Result<(), CooldownError> {
+        self.last.insert(
+            proposal.basis_policy.clone(),
+            LastProposal {
+                ts: proposal.ts.clone(),
+                outcome_count,
+            },
+        );
+        if let Some(path) = &self.path {
+            fs::write(path, serde_json::to_string_pretty(&self.last)?)?;
+        }
+        Ok(())
+    }
+}
+
+impl FeedbackAnalyzer {
+    /// [`Self::propose_adjustment`], unless `ledger` refuses a proposal for
+    /// `basis_policy` yet. An emitted proposal is recorded in the ledger.
+    ///
+    /// # Errors
+    /// [`CooldownError::Refused`] while the cooldown is active, or an I/O
+    /// error from the ledger.
+    pub fn propose_with_cooldown(
+        &self,
+        basis_policy: &str,
+        outcomes: &[DecisionOutcome],
+        ledger: &mut CooldownLedger,
+    ) -> Result<Option<WeightAdjustmentProposal>, CooldownError> {
+        ledger.check(basis_policy, outcomes.len())?;
+        let proposal = self.propose_adjustment(basis_policy, outcomes);
+        if let Some(proposal) = &proposal {
+            ledger.record(proposal, outcomes.len())?;
+        }
+        Ok(proposal)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::OutcomeType;
+    use time::Duration;
+
+    fn failures(count: usize) -> Vec<DecisionOutcome> {
+        (0..count)
+            .map(|i| DecisionOutcome {
+                decision_id: i.to_string(),
+                ts: "2026-01-01T00:00:00Z".into(),
+                policy_id: None,
+                action: Some("remind.morning".into()),
+                outcome: OutcomeType::Failure,
+                success: false,
+                reward: None,
+                context: None,
+                metadata: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn cooldown_survives_restarts() {
+        let path =
+            std::env::temp_dir().join(format!("heimlern-cooldown-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let cooldown = Cooldown {
+            min_interval_seconds: 3_600,
+            min_new_outcomes: 10,
+        };
+        let analyzer = FeedbackAnalyzer::default();
+        let mut ledger = CooldownLedger::open(&path, cooldown).unwrap();
+        let first = analyzer
+            .propose_with_cooldown("remind-bandit", &failures(20), &mut ledger)
+            .unwrap();
+        assert!(first.is_some());
+
+        let mut reopened = CooldownLedger::open(&path, cooldown).unwrap();
+        let err = analyzer
+            .propose_with_cooldown("remind-bandit", &failures(40), &mut reopened)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            CooldownError::Refused(Refusal::TooSoon { .. })
+        ));
+        assert!(reopened.check("ucb", 5).is_ok());
+
+        let emitted =
+            OffsetDateTime::parse(&reopened.last("remind-bandit").unwrap().ts, &Rfc3339).unwrap();
+        let later = emitted + Duration::hours(2);
+        assert_eq!(
+            reopened.check_at("remind-bandit", 25, later),
+            Err(Refusal::TooFewOutcomes {
+                policy: "remind-bandit".into(),
+                new: 5,
+                required: 10,
+            })
+        );
+        assert!(reopened.check_at("remind-bandit", 30, later).is_ok());
+        assert!(reopened.check_at("remind-bandit", 12, later).is_ok());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
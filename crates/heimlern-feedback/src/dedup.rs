@@ -0,0 +1,174 @@
+//! Deduplication of outcomes by decision id.
+//!
+//! Chronik delivers at least once, so the same outcome can reach the
+//! analyzer twice, without the idempotency key that [`crate::idempotency`]
+//! relies on. Counted twice, it skews rates and narrows the confidence
+//! interval as if there were more evidence than there is.
+//!
+//! Outcomes are identified like in [`crate::merge`]: by `decision_id` and
+//! `metadata.source`. An [`OutcomeDeduper`] admits the first outcome per key
+//! while streaming; [`deduplicate`] works on a whole slice and keeps, per
+//! key, the record [`crate::merge::merge_outcomes`] would keep, at the
+//! position of the first one. The analyzer deduplicates its input before
+//! aggregating, detecting patterns, proposing and simulating.
+
+use crate::merge::{outcome_source, supersedes};
+use crate::DecisionOutcome;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+
+fn key(outcome: &DecisionOutcome) -> (String, String) {
+    (
+        outcome.decision_id.clone(),
+        outcome_source(outcome).unwrap_or_default().to_string(),
+    )
+}
+
+/// Streaming filter that admits one outcome per decision.
+#[derive(Debug, Clone, Default)]
+pub struct OutcomeDeduper {
+    seen: HashSet<(String, String)>,
+    duplicates: usize,
+}
+
+impl OutcomeDeduper {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether `outcome` is the first one seen for its decision.
+    pub fn admit(&mut self, outcome: &DecisionOutcome) -> bool {
+        let fresh = self.seen.insert(key(outcome));
+        if !fresh {
+            self.duplicates += 1;
+        }
+        fresh
+    }
+
+    /// Number of outcomes rejected so far.
+    #[must_use]
+    pub fn duplicates(&self) -> usize {
+        self.duplicates
+    }
+
+    /// Number of distinct decisions admitted so far.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+}
+
+/// `outcomes` with one record per decision; borrowed if there are no
+/// duplicates.
+#[must_use]
+pub fn deduplicate(outcomes: &[DecisionOutcome]) -> Cow<'_, [DecisionOutcome]> {
+    let mut position: HashMap<(String, String), usize> = HashMap::with_capacity(outcomes.len());
+    let mut kept: Vec<&DecisionOutcome> = Vec::with_capacity(outcomes.len());
+    for outcome in outcomes {
+        match position.get(&key(outcome)) {
+            Some(&at) => {
+                if supersedes(outcome, kept[at]) {
+                    kept[at] = outcome;
+                }
+            }
+            None => {
+                position.insert(key(outcome), kept.len());
+                kept.push(outcome);
+            }
+        }
+    }
+    if kept.len() == outcomes.len() {
+        Cow::Borrowed(outcomes)
+    } else {
+        Cow::Owned(kept.into_iter().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        DeltaValue, Evidence, FeedbackAnalyzer, OutcomeType, ProposalStatus,
+        WeightAdjustmentProposal,
+    };
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    fn outcome(id: &str, kind: OutcomeType, source: Option<&str>) -> DecisionOutcome {
+        DecisionOutcome {
+            decision_id: id.into(),
+            ts: "2026-01-01T00:00:00Z".into(),
+            policy_id: None,
+            action: Some("remind.morning".into()),
+            success: kind == OutcomeType::Success,
+            outcome: kind,
+            reward: None,
+            context: None,
+            metadata: source.map(|s| json!({ "source": s })),
+        }
+    }
+
+    #[test]
+    fn redelivered_outcomes_count_once() {
+        let outcomes = vec![
+            outcome("d1", OutcomeType::Failure, None),
+            outcome("d2", OutcomeType::Censored, None),
+            outcome("d1", OutcomeType::Failure, None),
+            outcome("d2", OutcomeType::Success, None),
+            outcome("d1", OutcomeType::Failure, Some("laptop")),
+        ];
+        let unique = deduplicate(&outcomes);
+        let ids: Vec<(&str, OutcomeType)> = unique
+            .iter()
+            .map(|o| (o.decision_id.as_str(), o.outcome))
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                ("d1", OutcomeType::Failure),
+                ("d2", OutcomeType::Success),
+                ("d1", OutcomeType::Failure)
+            ]
+        );
+        assert!(matches!(deduplicate(&outcomes[..2]), Cow::Borrowed(_)));
+
+        let mut deduper = OutcomeDeduper::new();
+        let admitted = outcomes.iter().filter(|o| deduper.admit(o)).count();
+        assert_eq!((admitted, deduper.duplicates(), deduper.len()), (3, 2, 3));
+
+        let stats = FeedbackAnalyzer::default().aggregate_outcomes(&outcomes, |o| o.action.clone());
+        assert_eq!(stats["remind.morning"].total, 3);
+        assert_eq!(stats["remind.morning"].censored, 0);
+    }
+
+    #[test]
+    fn simulation_baseline_counts_redelivered_outcomes_once() {
+        let mut outcomes = vec![
+            outcome("d1", OutcomeType::Success, None),
+            outcome("d2", OutcomeType::Failure, None),
+        ];
+        outcomes.extend((0..3).map(|_| outcome("d2", OutcomeType::Failure, None)));
+        let proposal = WeightAdjustmentProposal {
+            version: "v1".into(),
+            basis_policy: "remind-bandit".into(),
+            ts: "2026-01-02T00:00:00Z".into(),
+            deltas: BTreeMap::from([(
+                "timing.morning.offset_minutes".to_string(),
+                DeltaValue::Absolute { value: 10.0 },
+            )]),
+            confidence: 0.5,
+            evidence: Evidence::default(),
+            reasoning: None,
+            status: ProposalStatus::Proposed,
+        };
+
+        let rate = FeedbackAnalyzer::default().simulate_adjustment(&proposal, &outcomes);
+        assert!((rate - 0.5).abs() < 1e-6, "rate = {rate}");
+    }
+}
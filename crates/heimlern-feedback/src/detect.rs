@@ -0,0 +1,400 @@
+//! Pluggable pattern detection.
+//!
+//! [`FeedbackAnalyzer::analyze_patterns`] runs every [`PatternDetector`] in
+//! its [`PatternRegistry`] and concatenates what they find. The registry
+//! starts out with the built-in heuristics, in this order:
+//!
+//! * [`ActionRates`] – per-action failure, ignore and override rates,
+//! * [`OverallFailures`] – the overall failure rate,
+//! * [`Vetoes`] – how often constraints overrule the learner,
+//! * [`LateAcknowledgements`] – actions acknowledged long after they fired,
+//! * [`Seasonality`] – actions that fail at certain hours or weekdays.
+//!
+//! Downstream detectors are registered with
+//! [`crate::FeedbackAnalyzerBuilder::detector`] and usually report
+//! [`Pattern::Custom`]:
+//!
+//! ```
+//! use heimlern_feedback::detect::{DetectionInput, PatternDetector};
+//! use heimlern_feedback::explain::Pattern;
+//! use heimlern_feedback::FeedbackAnalyzer;
+//!
+//! struct SnoozedThreeTimes;
+//!
+//! impl PatternDetector for SnoozedThreeTimes {
+//!     fn name(&self) -> &str {
+//!         "snoozed_three_times"
+//!     }
+//!
+//!     fn detect(&self, input: &DetectionInput<'_>) -> Vec<Pattern> {
+//!         let snoozed = input
+//!             .outcomes
+//!             .windows(3)
+//!             .any(|w| w.iter().all(|o| o.metadata.as_ref().and_then(|m| m.get("snoozed")).is_some()));
+//!         snoozed
+//!             .then(|| Pattern::Custom {
+//!                 detector: self.name().into(),
+//!                 message: "Snoozed three times in a row".into(),
+//!                 details: serde_json::Value::Null,
+//!             })
+//!             .into_iter()
+//!             .collect()
+//!     }
+//! }
+//!
+//! let analyzer = FeedbackAnalyzer::builder().detector(SnoozedThreeTimes).build();
+//! assert!(analyzer.detectors().names().contains(&"snoozed_three_times"));
+//! ```
+
+use crate::explain::Pattern;
+use crate::index::OutcomeIndex;
+use crate::season::detect_seasonality;
+use crate::{
+    slow_actions, DecisionOutcome, FeedbackAnalyzer, PATTERN_HIGH_FAILURE_THRESHOLD,
+    PATTERN_HIGH_IGNORE_THRESHOLD, PATTERN_HIGH_OVERRIDE_THRESHOLD, PATTERN_HIGH_VETO_THRESHOLD,
+    PATTERN_MIN_DECISIONS_PER_ACTION, PATTERN_OVERALL_FAILURE_THRESHOLD,
+};
+use std::fmt;
+use std::sync::Arc;
+
+/// What a detector gets to look at.
+#[derive(Debug, Clone, Copy)]
+pub struct DetectionInput<'a> {
+    /// Outcomes in recorded order. Empty when the analysis runs on an index
+    /// alone (see [`FeedbackAnalyzer::analyze_index`]).
+    pub outcomes: &'a [DecisionOutcome],
+    /// Groupings of the same outcomes.
+    pub index: &'a OutcomeIndex,
+    /// The analyzer's minimum number of decisions.
+    pub min_decisions: usize,
+}
+
+/// A heuristic that finds patterns in outcomes.
+pub trait PatternDetector: Send + Sync {
+    /// Stable name, listed by [`PatternRegistry::names`] and reported as
+    /// `detector` of [`Pattern::Custom`].
+    fn name(&self) -> &str;
+
+    fn detect(&self, input: &DetectionInput<'_>) -> Vec<Pattern>;
+}
+
+/// Actions that fail, are ignored or are overridden by hand too often.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ActionRates;
+
+impl PatternDetector for ActionRates {
+    fn name(&self) -> &str {
+        "action_rates"
+    }
+
+    fn detect(&self, input: &DetectionInput<'_>) -> Vec<Pattern> {
+        let mut patterns = Vec::new();
+        for (action, stats) in &input.index.by_action {
+            if stats.total >= PATTERN_MIN_DECISIONS_PER_ACTION
+                && stats.failure_rate() > PATTERN_HIGH_FAILURE_THRESHOLD
+            {
+                patterns.push(Pattern::ActionFailures {
+                    action: action.clone(),
+                    rate: stats.failure_rate(),
+                });
+            }
+            if stats.observed() >= PATTERN_MIN_DECISIONS_PER_ACTION
+                && stats.ignore_rate() > PATTERN_HIGH_IGNORE_THRESHOLD
+            {
+                patterns.push(Pattern::ActionIgnored {
+                    action: action.clone(),
+                    rate: stats.ignore_rate(),
+                });
+            }
+            if stats.observed() >= PATTERN_MIN_DECISIONS_PER_ACTION
+                && stats.override_rate() > PATTERN_HIGH_OVERRIDE_THRESHOLD
+            {
+                patterns.push(Pattern::ActionOverridden {
+                    action: action.clone(),
+                    rate: stats.override_rate(),
+                });
+            }
+        }
+        patterns
+    }
+}
+
+/// A high failure rate across all actions.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OverallFailures;
+
+impl PatternDetector for OverallFailures {
+    fn name(&self) -> &str {
+        "overall_failures"
+    }
+
+    fn detect(&self, input: &DetectionInput<'_>) -> Vec<Pattern> {
+        let overall = &input.index.overall;
+        if overall.total >= input.min_decisions
+            && overall.failure_rate() > PATTERN_OVERALL_FAILURE_THRESHOLD
+        {
+            vec![Pattern::OverallFailures {
+                rate: overall.failure_rate(),
+            }]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Constraints that frequently overrule the learner.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Vetoes;
+
+impl PatternDetector for Vetoes {
+    fn name(&self) -> &str {
+        "vetoes"
+    }
+
+    fn detect(&self, input: &DetectionInput<'_>) -> Vec<Pattern> {
+        let vetoes = input.index.veto_summary();
+        if vetoes.veto_rate() > PATTERN_HIGH_VETO_THRESHOLD {
+            vec![Pattern::Vetoes {
+                rate: vetoes.veto_rate(),
+                constraint: vetoes
+                    .dominant_constraint()
+                    .map(|(name, _)| name.to_string()),
+            }]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Actions whose median acknowledgement comes long after they fired.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LateAcknowledgements;
+
+impl PatternDetector for LateAcknowledgements {
+    fn name(&self) -> &str {
+        "late_acknowledgements"
+    }
+
+    fn detect(&self, input: &DetectionInput<'_>) -> Vec<Pattern> {
+        slow_actions(input.index)
+            .into_iter()
+            .map(|(action, minutes)| Pattern::LateAcknowledgement { action, minutes })
+            .collect()
+    }
+}
+
+/// Hours and weekdays in which an action fails significantly more or less
+/// often, see [`crate::season`]. Needs the outcomes, so it finds nothing on
+/// an index alone.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Seasonality;
+
+impl PatternDetector for Seasonality {
+    fn name(&self) -> &str {
+        "seasonality"
+    }
+
+    fn detect(&self, input: &DetectionInput<'_>) -> Vec<Pattern> {
+        detect_seasonality(input.outcomes)
+            .into_iter()
+            .map(|slot| Pattern::Seasonal {
+                action: slot.action,
+                season: slot.season,
+                bucket: slot.bucket,
+                rate: slot.failure_rate,
+                baseline: slot.baseline_failure_rate,
+            })
+            .collect()
+    }
+}
+
+/// Ordered set of detectors; cloning shares the detectors.
+#[derive(Clone, Default)]
+pub struct PatternRegistry {
+    detectors: Vec<Arc<dyn PatternDetector>>,
+}
+
+impl fmt::Debug for PatternRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
+
+impl PatternRegistry {
+    /// Registry without any detector.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Registry with the built-in heuristics.
+    #[must_use]
+    pub fn builtin() -> Self {
+        Self::empty()
+            .with(ActionRates)
+            .with(OverallFailures)
+            .with(Vetoes)
+            .with(LateAcknowledgements)
+            .with(Seasonality)
+    }
+
+    /// Append `detector`; detectors run in registration order.
+    pub fn register(&mut self, detector: impl PatternDetector + 'static) {
+        self.detectors.push(Arc::new(detector));
+    }
+
+    /// Like [`Self::register`], for chaining.
+    #[must_use]
+    pub fn with(mut self, detector: impl PatternDetector + 'static) -> Self {
+        self.register(detector);
+        self
+    }
+
+    /// Remove every detector called `name`; `true` if one was removed.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.detectors.len();
+        self.detectors.retain(|d| d.name() != name);
+        self.detectors.len() != before
+    }
+
+    /// Names of the registered detectors, in order.
+    #[must_use]
+    pub fn names(&self) -> Vec<&str> {
+        self.detectors.iter().map(|d| d.name()).collect()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.detectors.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.detectors.is_empty()
+    }
+
+    /// Run every detector on `input`.
+    #[must_use]
+    pub fn detect(&self, input: &DetectionInput<'_>) -> Vec<Pattern> {
+        self.detectors
+            .iter()
+            .flat_map(|d| d.detect(input))
+            .collect()
+    }
+}
+
+impl FeedbackAnalyzer {
+    /// Run the registered detectors, unless fewer than `min_decisions`
+    /// outcomes are indexed.
+    pub(crate) fn run_detectors(
+        &self,
+        outcomes: &[DecisionOutcome],
+        index: &OutcomeIndex,
+    ) -> Vec<Pattern> {
+        if index.records < self.min_decisions {
+            return Vec::new();
+        }
+        self.detectors.detect(&DetectionInput {
+            outcomes,
+            index,
+            min_decisions: self.min_decisions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OutcomeType;
+    use serde_json::json;
+
+    /// Flags `count` consecutive outcomes whose metadata marks them snoozed.
+    struct Snoozed {
+        count: usize,
+    }
+
+    impl PatternDetector for Snoozed {
+        fn name(&self) -> &str {
+            "snoozed"
+        }
+
+        fn detect(&self, input: &DetectionInput<'_>) -> Vec<Pattern> {
+            input
+                .outcomes
+                .windows(self.count)
+                .find(|w| {
+                    w.iter().all(|o| {
+                        o.metadata.as_ref().and_then(|m| m.get("snoozed")) == Some(&json!(true))
+                    })
+                })
+                .map(|w| Pattern::Custom {
+                    detector: self.name().into(),
+                    message: format!("Snoozed {} times in a row", self.count),
+                    details: json!({ "first": w[0].decision_id }),
+                })
+                .into_iter()
+                .collect()
+        }
+    }
+
+    fn outcome(id: usize, snoozed: bool) -> DecisionOutcome {
+        DecisionOutcome {
+            decision_id: id.to_string(),
+            ts: "2026-01-01T00:00:00Z".into(),
+            policy_id: None,
+            action: Some("remind.morning".into()),
+            outcome: OutcomeType::Failure,
+            success: false,
+            reward: None,
+            context: None,
+            metadata: snoozed.then(|| json!({ "snoozed": true })),
+        }
+    }
+
+    #[test]
+    fn custom_detectors_run_after_the_builtin_ones() {
+        let outcomes: Vec<DecisionOutcome> =
+            (0..12).map(|i| outcome(i, (4..7).contains(&i))).collect();
+        let analyzer = FeedbackAnalyzer::builder()
+            .detector(Snoozed { count: 3 })
+            .build();
+        assert_eq!(
+            analyzer.detectors().names(),
+            vec![
+                "action_rates",
+                "overall_failures",
+                "vetoes",
+                "late_acknowledgements",
+                "seasonality",
+                "snoozed"
+            ]
+        );
+
+        let patterns = analyzer.analyze_patterns(&outcomes);
+        assert!(matches!(
+            patterns.first(),
+            Some(Pattern::ActionFailures { action, .. }) if action == "remind.morning"
+        ));
+        assert_eq!(
+            patterns.last(),
+            Some(&Pattern::Custom {
+                detector: "snoozed".into(),
+                message: "Snoozed 3 times in a row".into(),
+                details: json!({ "first": "4" }),
+            })
+        );
+        let proposal = analyzer.propose_adjustment("remind-bandit", &outcomes);
+        assert!(proposal.is_some_and(|p| p
+            .evidence
+            .patterns
+            .is_some_and(|p| p.contains(&"Snoozed 3 times in a row".to_string()))));
+
+        let mut registry = PatternRegistry::builtin();
+        assert!(registry.remove("action_rates"));
+        let only_overall = FeedbackAnalyzer::builder().detectors(registry).build();
+        assert_eq!(
+            only_overall.analyze_patterns(&outcomes),
+            vec![Pattern::OverallFailures { rate: 1.0 }]
+        );
+    }
+}
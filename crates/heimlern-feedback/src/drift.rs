@@ -0,0 +1,272 @@
+//! Concept drift between outcome windows.
+//!
+//! The household a policy learned from changes: routines shift, reminders
+//! that used to land get ignored. [`DriftReport::compare`] contrasts a recent
+//! window of outcomes with the history before it, using the Population
+//! Stability Index (PSI) on two distributions:
+//!
+//! * which actions were taken, and
+//! * the rewards received, as a histogram over [`REWARD_BINS`] equal-width
+//!   bins spanning both windows (outcomes without a reward count as 1.0 on
+//!   success and 0.0 otherwise; censored and overridden outcomes are left
+//!   out).
+//!
+//! `PSI = Σ (recent − historical) · ln(recent / historical)` over the shares
+//! per bucket. By the usual rule of thumb, values below 0.1 are stable and
+//! values above [`DEFAULT_DRIFT_THRESHOLD`] (0.2) mark a shift worth a look.
+
+use crate::{outcome_is_success, DecisionOutcome};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+/// PSI above which a distribution counts as drifted.
+pub const DEFAULT_DRIFT_THRESHOLD: f64 = 0.2;
+
+/// Number of bins of the reward histogram.
+pub const REWARD_BINS: usize = 10;
+
+/// Floor for empty buckets, keeping the logarithm finite.
+const MIN_SHARE: f64 = 1e-4;
+
+/// Share of one action in both windows and its PSI term.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ActionDrift {
+    pub action: String,
+    pub historical_share: f64,
+    pub recent_share: f64,
+    pub psi: f64,
+}
+
+/// Drift of the recent window against the historical one.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DriftReport {
+    /// Number of outcomes in the historical window.
+    pub historical: usize,
+    /// Number of outcomes in the recent window.
+    pub recent: usize,
+    /// PSI of the action distribution.
+    pub action_psi: f64,
+    /// PSI of the reward histogram.
+    pub reward_psi: f64,
+    /// Per-action terms, largest contribution first.
+    pub actions: Vec<ActionDrift>,
+    pub threshold: f64,
+    /// Whether either PSI exceeds `threshold`.
+    pub drifted: bool,
+}
+
+impl DriftReport {
+    /// Compare `recent` outcomes against `historical` ones.
+    #[must_use]
+    pub fn compare(
+        historical: &[DecisionOutcome],
+        recent: &[DecisionOutcome],
+        threshold: f64,
+    ) -> Self {
+        let historical_actions = action_counts(historical);
+        let recent_actions = action_counts(recent);
+        let keys: Vec<&String> = historical_actions
+            .keys()
+            .chain(recent_actions.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let historical_shares = shares(keys.iter().map(|k| historical_actions.get(*k)));
+        let recent_shares = shares(keys.iter().map(|k| recent_actions.get(*k)));
+        let mut actions: Vec<ActionDrift> = keys
+            .iter()
+            .zip(historical_shares.iter().zip(&recent_shares))
+            .map(|(action, (&h, &r))| ActionDrift {
+                action: (*action).clone(),
+                historical_share: h,
+                recent_share: r,
+                psi: psi_term(h, r),
+            })
+            .collect();
+        actions.sort_by(|a, b| b.psi.total_cmp(&a.psi).then(a.action.cmp(&b.action)));
+        let action_psi = actions.iter().map(|a| a.psi).sum();
+
+        let reward_psi = reward_psi(&rewards(historical), &rewards(recent));
+
+        Self {
+            historical: historical.len(),
+            recent: recent.len(),
+            action_psi,
+            reward_psi,
+            actions,
+            threshold,
+            drifted: action_psi > threshold || reward_psi > threshold,
+        }
+    }
+
+    /// Compare the last `recent_seconds` before the newest outcome against
+    /// everything earlier. Outcomes with unparseable timestamps are ignored.
+    #[must_use]
+    pub fn compare_recent(
+        outcomes: &[DecisionOutcome],
+        recent_seconds: i64,
+        threshold: f64,
+    ) -> Self {
+        let (historical, recent) = split_recent(outcomes, recent_seconds);
+        Self::compare(&historical, &recent, threshold)
+    }
+}
+
+/// Split `outcomes` into `(historical, recent)`, where recent covers the last
+/// `recent_seconds` up to the newest timestamp.
+#[must_use]
+pub fn split_recent(
+    outcomes: &[DecisionOutcome],
+    recent_seconds: i64,
+) -> (Vec<DecisionOutcome>, Vec<DecisionOutcome>) {
+    let stamped: Vec<(i64, &DecisionOutcome)> = outcomes
+        .iter()
+        .filter_map(|o| {
+            let ts = OffsetDateTime::parse(&o.ts, &Rfc3339).ok()?;
+            Some((ts.unix_timestamp(), o))
+        })
+        .collect();
+    let Some(newest) = stamped.iter().map(|(ts, _)| *ts).max() else {
+        return (Vec::new(), Vec::new());
+    };
+    let cutoff = newest.saturating_sub(recent_seconds);
+    let (recent, historical): (Vec<_>, Vec<_>) =
+        stamped.into_iter().partition(|(ts, _)| *ts > cutoff);
+    let owned = |v: Vec<(i64, &DecisionOutcome)>| v.into_iter().map(|(_, o)| o.clone()).collect();
+    (owned(historical), owned(recent))
+}
+
+fn action_counts(outcomes: &[DecisionOutcome]) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for action in outcomes.iter().filter_map(|o| o.action.clone()) {
+        *counts.entry(action).or_insert(0) += 1;
+    }
+    counts
+}
+
+fn rewards(outcomes: &[DecisionOutcome]) -> Vec<f64> {
+    outcomes
+        .iter()
+        .filter(|o| !o.is_censored() && !o.is_override())
+        .map(|o| {
+            o.reward
+                .filter(|r| r.is_finite())
+                .map_or_else(|| if outcome_is_success(o) { 1.0 } else { 0.0 }, f64::from)
+        })
+        .collect()
+}
+
+/// Shares of each count in the total; all zero if the total is zero.
+fn shares<'a>(counts: impl Iterator<Item = Option<&'a usize>> + Clone) -> Vec<f64> {
+    let total: usize = counts.clone().map(|c| c.copied().unwrap_or(0)).sum();
+    #[allow(clippy::cast_precision_loss)]
+    counts
+        .map(|c| {
+            if total == 0 {
+                0.0
+            } else {
+                c.copied().unwrap_or(0) as f64 / total as f64
+            }
+        })
+        .collect()
+}
+
+fn psi_term(historical: f64, recent: f64) -> f64 {
+    let h = historical.max(MIN_SHARE);
+    let r = recent.max(MIN_SHARE);
+    (r - h) * (r / h).ln()
+}
+
+fn reward_psi(historical: &[f64], recent: &[f64]) -> f64 {
+    if historical.is_empty() || recent.is_empty() {
+        return 0.0;
+    }
+    let (min, max) = historical
+        .iter()
+        .chain(recent)
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), r| {
+            (lo.min(*r), hi.max(*r))
+        });
+    if max <= min {
+        return 0.0;
+    }
+    let histogram = |values: &[f64]| {
+        let mut bins = [0_usize; REWARD_BINS];
+        for r in values {
+            #[allow(
+                clippy::cast_possible_truncation,
+                clippy::cast_sign_loss,
+                clippy::cast_precision_loss
+            )]
+            let bin = (((r - min) / (max - min)) * REWARD_BINS as f64) as usize;
+            bins[bin.min(REWARD_BINS - 1)] += 1;
+        }
+        bins
+    };
+    let h = histogram(historical);
+    let r = histogram(recent);
+    shares(h.iter().map(Some))
+        .into_iter()
+        .zip(shares(r.iter().map(Some)))
+        .map(|(h, r)| psi_term(h, r))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OutcomeType;
+
+    fn outcome(day: u32, action: &str, success: bool) -> DecisionOutcome {
+        DecisionOutcome {
+            decision_id: format!("{day}-{action}-{success}"),
+            ts: format!("2026-04-{day:02}T09:00:00Z"),
+            policy_id: None,
+            action: Some(action.into()),
+            outcome: if success {
+                OutcomeType::Success
+            } else {
+                OutcomeType::Failure
+            },
+            success,
+            reward: None,
+            context: None,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn shifted_actions_and_rewards_are_reported() {
+        let mut outcomes = Vec::new();
+        for day in 1..=20 {
+            let recent = day > 14;
+            outcomes.push(outcome(day, "remind.morning", !recent));
+            outcomes.push(outcome(
+                day,
+                if recent {
+                    "remind.evening"
+                } else {
+                    "remind.morning"
+                },
+                !recent,
+            ));
+        }
+
+        let report = DriftReport::compare_recent(&outcomes, 6 * 86_400, DEFAULT_DRIFT_THRESHOLD);
+        assert_eq!((report.historical, report.recent), (28, 12));
+        assert!(report.drifted);
+        assert!(report.action_psi > DEFAULT_DRIFT_THRESHOLD);
+        assert!(report.reward_psi > DEFAULT_DRIFT_THRESHOLD);
+        assert_eq!(report.actions[0].action, "remind.evening");
+
+        let (historical, _) = split_recent(&outcomes, 6 * 86_400);
+        let stable = DriftReport::compare(
+            &historical[..14],
+            &historical[14..],
+            DEFAULT_DRIFT_THRESHOLD,
+        );
+        assert!(!stable.drifted);
+        assert!(stable.action_psi.abs() < 1e-9 && stable.reward_psi.abs() < 1e-9);
+    }
+}
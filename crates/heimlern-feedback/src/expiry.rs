@@ -0,0 +1,173 @@
+//! Expiry of proposals whose evidence is out of date.
+//!
+//! A proposal describes the behavior it was derived from. Three months later
+//! that behavior may be gone, and applying the proposal would tune the policy
+//! for a household that no longer exists. A proposal is stale when
+//!
+//! * its time to live has passed (`expires_at`, derived from `ts`), or
+//! * more outcomes arrived after it than it was derived from: the data it
+//!   saw is now the minority.
+//!
+//! `policy.weight_adjustment.v1` is closed and has no expiry field, so
+//! `expires_at` is kept in the [`crate::store::ProposalRecord`] of a
+//! [`crate::store::ProposalStore`] opened with a TTL.
+//! [`crate::store::ProposalStore::expire_stale`] moves stale proposals that
+//! are still pending to [`crate::ProposalStatus::Superseded`].
+
+use crate::{DecisionOutcome, WeightAdjustmentProposal};
+use serde::Serialize;
+use time::{format_description::well_known::Rfc3339, Duration, OffsetDateTime};
+
+/// Default time to live of a proposal.
+pub const DEFAULT_TTL_SECONDS: i64 = 30 * 86_400;
+
+/// Whether a proposal may still be applied.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Staleness {
+    Fresh,
+    /// The time to live passed at `expires_at`.
+    Expired {
+        expires_at: String,
+    },
+    /// `newer_outcomes` arrived after the proposal, more than the
+    /// `decisions_analyzed` it was derived from.
+    Outdated {
+        newer_outcomes: usize,
+        decisions_analyzed: usize,
+    },
+}
+
+impl Staleness {
+    #[must_use]
+    pub fn is_stale(&self) -> bool {
+        !matches!(self, Self::Fresh)
+    }
+}
+
+/// `ts` of `proposal` plus `ttl_seconds` (RFC 3339), or `None` if `ts` does
+/// not parse.
+#[must_use]
+pub fn expires_at(proposal: &WeightAdjustmentProposal, ttl_seconds: i64) -> Option<String> {
+    let ts = OffsetDateTime::parse(&proposal.ts, &Rfc3339).ok()?;
+    (ts + Duration::seconds(ttl_seconds)).format(&Rfc3339).ok()
+}
+
+/// Staleness of `proposal` at `now`, given its `expires_at` and the
+/// outcomes recorded so far.
+#[must_use]
+pub fn staleness(
+    proposal: &WeightAdjustmentProposal,
+    expires_at: Option<&str>,
+    outcomes: &[DecisionOutcome],
+    now: OffsetDateTime,
+) -> Staleness {
+    if let Some(expires_at) = expires_at {
+        if OffsetDateTime::parse(expires_at, &Rfc3339).is_ok_and(|t| now >= t) {
+            return Staleness::Expired {
+                expires_at: expires_at.to_string(),
+            };
+        }
+    }
+    let Ok(proposed) = OffsetDateTime::parse(&proposal.ts, &Rfc3339) else {
+        return Staleness::Fresh;
+    };
+    let newer_outcomes = outcomes
+        .iter()
+        .filter(|o| OffsetDateTime::parse(&o.ts, &Rfc3339).is_ok_and(|t| t > proposed))
+        .count();
+    let decisions_analyzed = proposal.evidence.decisions_analyzed;
+    if newer_outcomes > decisions_analyzed {
+        Staleness::Outdated {
+            newer_outcomes,
+            decisions_analyzed,
+        }
+    } else {
+        Staleness::Fresh
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::{DeltaValue, Evidence, OutcomeType, ProposalStatus};
+    use std::collections::BTreeMap;
+
+    fn proposal(decisions_analyzed: usize) -> WeightAdjustmentProposal {
+        WeightAdjustmentProposal {
+            version: "v1".into(),
+            basis_policy: "remind-bandit".into(),
+            ts: "2026-01-01T00:00:00Z".into(),
+            deltas: BTreeMap::from([("epsilon".into(), DeltaValue::Absolute { value: 0.1 })]),
+            confidence: 0.8,
+            evidence: Evidence {
+                decisions_analyzed,
+                ..Evidence::default()
+            },
+            reasoning: None,
+            status: ProposalStatus::Proposed,
+        }
+    }
+
+    fn outcome(ts: &str) -> DecisionOutcome {
+        DecisionOutcome {
+            decision_id: ts.into(),
+            ts: ts.into(),
+            policy_id: None,
+            action: None,
+            outcome: OutcomeType::Success,
+            success: true,
+            reward: None,
+            context: None,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn expires_exactly_at_the_ttl_boundary() {
+        let proposal = proposal(10);
+        let expires = expires_at(&proposal, 3_600).unwrap();
+        assert_eq!(expires, "2026-01-01T01:00:00Z");
+        let boundary = OffsetDateTime::parse(&expires, &Rfc3339).unwrap();
+
+        let just_before = boundary - Duration::seconds(1);
+        assert_eq!(
+            staleness(&proposal, Some(&expires), &[], just_before),
+            Staleness::Fresh
+        );
+        assert_eq!(
+            staleness(&proposal, Some(&expires), &[], boundary),
+            Staleness::Expired {
+                expires_at: expires.clone()
+            }
+        );
+        // Without an expiry only the outcomes count.
+        let much_later = boundary + Duration::days(365);
+        assert_eq!(
+            staleness(&proposal, None, &[], much_later),
+            Staleness::Fresh
+        );
+    }
+
+    #[test]
+    fn outdated_once_newer_outcomes_outnumber_the_evidence() {
+        let proposal = proposal(2);
+        let now = OffsetDateTime::parse("2026-01-02T00:00:00Z", &Rfc3339).unwrap();
+        // An outcome at the proposal's own `ts` is not newer.
+        let mut outcomes = vec![
+            outcome("2026-01-01T00:00:00Z"),
+            outcome("2026-01-01T00:00:01Z"),
+            outcome("2026-01-01T12:00:00Z"),
+        ];
+        assert_eq!(staleness(&proposal, None, &outcomes, now), Staleness::Fresh);
+        outcomes.push(outcome("2026-01-01T18:00:00Z"));
+        assert_eq!(
+            staleness(&proposal, None, &outcomes, now),
+            Staleness::Outdated {
+                newer_outcomes: 3,
+                decisions_analyzed: 2
+            }
+        );
+    }
+}
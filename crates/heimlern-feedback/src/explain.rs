@@ -0,0 +1,392 @@
+//! Natural-language explanations for proposals.
+//!
+//! The analyzer records *why* it proposes something as structured values —
+//! detected [`Pattern`]s and the [`Reason`]s behind each delta, including the
+//! simulated effect — and renders them into the proposal's `reasoning` and the
+//! evidence patterns only at the end. Rendering is a pure function of those
+//! values and the [`Language`]: fixed templates, fixed number formats, no
+//! locale lookups, so the same analysis always produces the same text.
+
+use crate::season::Season;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Output language of rendered explanations.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    En,
+    De,
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "en" => Ok(Self::En),
+            "de" => Ok(Self::De),
+            other => Err(format!(
+                "unsupported language '{other}' (expected en or de)"
+            )),
+        }
+    }
+}
+
+/// A pattern detected in decision outcomes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Pattern {
+    /// An action fails more often than the per-action threshold.
+    ActionFailures { action: String, rate: f32 },
+    /// An action goes unanswered within its TTL.
+    ActionIgnored { action: String, rate: f32 },
+    /// Humans replace an action by hand.
+    ActionOverridden { action: String, rate: f32 },
+    /// The overall failure rate is high.
+    OverallFailures { rate: f32 },
+    /// Constraints veto the learner, optionally dominated by one constraint.
+    Vetoes {
+        rate: f32,
+        constraint: Option<String>,
+    },
+    /// An action is acknowledged long after it fired (median, minutes).
+    LateAcknowledgement { action: String, minutes: f64 },
+    /// An action fails at `rate` in one hour or weekday `bucket`, against
+    /// `baseline` at all other times.
+    Seasonal {
+        action: String,
+        season: Season,
+        bucket: u8,
+        rate: f32,
+        baseline: f32,
+    },
+    /// Reported by a downstream [`crate::detect::PatternDetector`]; `message`
+    /// is used as is in every language.
+    Custom {
+        detector: String,
+        message: String,
+        #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+        details: serde_json::Value,
+    },
+}
+
+/// Why a delta was proposed or dropped, or what the simulation predicts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Reason {
+    /// Exploration is reduced by `percent` because `failure_rate` of decisions failed.
+    ReduceExploration { percent: f32, failure_rate: f32 },
+    /// `action` should fire `minutes` earlier because it is acknowledged that late.
+    FireEarlier { action: String, minutes: f64 },
+    /// The weight of `action` in one hour or weekday `bucket` changes by `percent`.
+    Reweight {
+        action: String,
+        season: Season,
+        bucket: u8,
+        percent: f32,
+    },
+    /// Decisions are ignored more often than declined.
+    MissedNotDeclined { ignore_rate: f32, reject_rate: f32 },
+    /// A delta was dropped because `policy` does not expose `key`.
+    DroppedUntunable { key: String, policy: String },
+    /// A delta was dropped because its value leaves the declared range.
+    DroppedOutOfRange {
+        key: String,
+        value: f32,
+        min: f64,
+        max: f64,
+    },
+    /// Outcomes of anomalous days were left out of the analysis.
+    ExcludedAnomalies { days: usize, outcomes: usize },
+    /// Failure rate before and after the simulated adjustment.
+    Simulated { before: f32, after: f32 },
+}
+
+/// A rate (`0.0..=1.0`) as a percentage with one decimal.
+fn percent(rate: f32, lang: Language) -> String {
+    let value = format!("{:.1}", rate * 100.0);
+    match lang {
+        Language::En => format!("{value}%"),
+        Language::De => format!("{} %", value.replace('.', ",")),
+    }
+}
+
+impl Pattern {
+    /// Render this pattern as one sentence.
+    #[must_use]
+    pub fn render(&self, lang: Language) -> String {
+        match (self, lang) {
+            (Self::ActionFailures { action, rate }, Language::En) => format!(
+                "High failure rate ({}) for action '{action}'",
+                percent(*rate, lang)
+            ),
+            (Self::ActionFailures { action, rate }, Language::De) => format!(
+                "Hohe Fehlerquote ({}) für Aktion '{action}'",
+                percent(*rate, lang)
+            ),
+            (Self::ActionIgnored { action, rate }, Language::En) => format!(
+                "High ignore rate ({}) for action '{action}' (no response within TTL)",
+                percent(*rate, lang)
+            ),
+            (Self::ActionIgnored { action, rate }, Language::De) => format!(
+                "Hohe Ignorierquote ({}) für Aktion '{action}' (keine Reaktion innerhalb der TTL)",
+                percent(*rate, lang)
+            ),
+            (Self::ActionOverridden { action, rate }, Language::En) => format!(
+                "High override rate ({}) for action '{action}' (changed by hand)",
+                percent(*rate, lang)
+            ),
+            (Self::ActionOverridden { action, rate }, Language::De) => format!(
+                "Hohe Übersteuerungsquote ({}) für Aktion '{action}' (von Hand geändert)",
+                percent(*rate, lang)
+            ),
+            (Self::OverallFailures { rate }, Language::En) => {
+                format!("Overall failure rate is high ({})", percent(*rate, lang))
+            }
+            (Self::OverallFailures { rate }, Language::De) => {
+                format!("Die Gesamt-Fehlerquote ist hoch ({})", percent(*rate, lang))
+            }
+            (Self::Vetoes { rate, constraint }, Language::En) => format!(
+                "Constraints vetoed the learner in {} of decisions{}",
+                percent(*rate, lang),
+                constraint
+                    .as_ref()
+                    .map(|name| format!(", mostly '{name}'"))
+                    .unwrap_or_default()
+            ),
+            (Self::Vetoes { rate, constraint }, Language::De) => format!(
+                "Constraints haben den Lerner in {} der Entscheidungen überstimmt{}",
+                percent(*rate, lang),
+                constraint
+                    .as_ref()
+                    .map(|name| format!(", meist '{name}'"))
+                    .unwrap_or_default()
+            ),
+            (Self::LateAcknowledgement { action, minutes }, Language::En) => {
+                format!("Action '{action}' is acknowledged {minutes:.0} minutes late (median)")
+            }
+            (Self::LateAcknowledgement { action, minutes }, Language::De) => {
+                format!("Aktion '{action}' wird im Median {minutes:.0} Minuten zu spät quittiert")
+            }
+            (
+                Self::Seasonal {
+                    action,
+                    season,
+                    bucket,
+                    rate,
+                    baseline,
+                },
+                Language::En,
+            ) => format!(
+                "Action '{action}' fails more often {} ({} vs. {} otherwise)",
+                season.label(*bucket, lang),
+                percent(*rate, lang),
+                percent(*baseline, lang)
+            ),
+            (
+                Self::Seasonal {
+                    action,
+                    season,
+                    bucket,
+                    rate,
+                    baseline,
+                },
+                Language::De,
+            ) => format!(
+                "Aktion '{action}' scheitert {} häufiger ({} statt sonst {})",
+                season.label(*bucket, lang),
+                percent(*rate, lang),
+                percent(*baseline, lang)
+            ),
+            (Self::Custom { message, .. }, _) => message.clone(),
+        }
+    }
+}
+
+impl Reason {
+    /// Render this reason as one sentence.
+    #[must_use]
+    pub fn render(&self, lang: Language) -> String {
+        match (self, lang) {
+            (
+                Self::ReduceExploration {
+                    percent: p,
+                    failure_rate,
+                },
+                Language::En,
+            ) => format!(
+                "Reduce exploration by {:.0}% because {} of decisions failed",
+                p.abs(),
+                percent(*failure_rate, lang)
+            ),
+            (
+                Self::ReduceExploration {
+                    percent: p,
+                    failure_rate,
+                },
+                Language::De,
+            ) => format!(
+                "Exploration um {:.0} % senken, weil {} der Entscheidungen scheiterten",
+                p.abs(),
+                percent(*failure_rate, lang)
+            ),
+            (Self::FireEarlier { action, minutes }, Language::En) => {
+                format!("Fire '{action}' {minutes:.0} minutes earlier: it is acknowledged that late")
+            }
+            (Self::FireEarlier { action, minutes }, Language::De) => format!(
+                "'{action}' {minutes:.0} Minuten früher auslösen: so spät wird es quittiert"
+            ),
+            (
+                Self::Reweight {
+                    action,
+                    season,
+                    bucket,
+                    percent: p,
+                },
+                Language::En,
+            ) => format!(
+                "Change the weight of '{action}' {} by {p:+.0}%",
+                season.label(*bucket, lang)
+            ),
+            (
+                Self::Reweight {
+                    action,
+                    season,
+                    bucket,
+                    percent: p,
+                },
+                Language::De,
+            ) => format!(
+                "Gewicht von '{action}' {} um {p:+.0} % ändern",
+                season.label(*bucket, lang)
+            ),
+            (
+                Self::MissedNotDeclined {
+                    ignore_rate,
+                    reject_rate,
+                },
+                Language::En,
+            ) => format!(
+                "Ignore rate ({}) exceeds reject rate ({}): decisions are missed rather than declined",
+                percent(*ignore_rate, lang),
+                percent(*reject_rate, lang)
+            ),
+            (
+                Self::MissedNotDeclined {
+                    ignore_rate,
+                    reject_rate,
+                },
+                Language::De,
+            ) => format!(
+                "Ignorierquote ({}) übersteigt Ablehnungsquote ({}): Entscheidungen werden übersehen statt abgelehnt",
+                percent(*ignore_rate, lang),
+                percent(*reject_rate, lang)
+            ),
+            (Self::DroppedUntunable { key, policy }, Language::En) => {
+                format!("Dropped delta '{key}': not tunable on '{policy}'")
+            }
+            (Self::DroppedUntunable { key, policy }, Language::De) => {
+                format!("Delta '{key}' verworfen: auf '{policy}' nicht justierbar")
+            }
+            (
+                Self::DroppedOutOfRange {
+                    key,
+                    value,
+                    min,
+                    max,
+                },
+                Language::En,
+            ) => format!("Dropped delta '{key}': {value} outside [{min}, {max}]"),
+            (
+                Self::DroppedOutOfRange {
+                    key,
+                    value,
+                    min,
+                    max,
+                },
+                Language::De,
+            ) => format!("Delta '{key}' verworfen: {value} außerhalb von [{min}, {max}]"),
+            (Self::ExcludedAnomalies { days, outcomes }, Language::En) => format!(
+                "Left out {outcomes} outcomes from {days} anomalous day(s)"
+            ),
+            (Self::ExcludedAnomalies { days, outcomes }, Language::De) => format!(
+                "{outcomes} Ergebnisse von {days} auffälligen Tag(en) nicht berücksichtigt"
+            ),
+            (Self::Simulated { before, after }, Language::En) => format!(
+                "Simulated failure rate: {} -> {}",
+                percent(*before, lang),
+                percent(*after, lang)
+            ),
+            (Self::Simulated { before, after }, Language::De) => format!(
+                "Simulierte Fehlerquote: {} -> {}",
+                percent(*before, lang),
+                percent(*after, lang)
+            ),
+        }
+    }
+}
+
+/// Render `reasons` into a single `reasoning` text.
+#[must_use]
+pub fn render(reasons: &[Reason], lang: Language) -> String {
+    reasons
+        .iter()
+        .map(|r| r.render(lang))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(Language::En))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_both_languages_deterministically() {
+        let reasons = vec![
+            Reason::ReduceExploration {
+                percent: -5.0,
+                failure_rate: 0.625,
+            },
+            Reason::Simulated {
+                before: 0.625,
+                after: 0.6,
+            },
+        ];
+        assert_eq!(
+            render(&reasons, Language::En),
+            "Reduce exploration by 5% because 62.5% of decisions failed; \
+             Simulated failure rate: 62.5% -> 60.0%"
+        );
+        assert_eq!(
+            render(&reasons, Language::De),
+            "Exploration um 5 % senken, weil 62,5 % der Entscheidungen scheiterten; \
+             Simulierte Fehlerquote: 62,5 % -> 60,0 %"
+        );
+        assert_eq!(
+            render(&reasons, Language::De),
+            render(&reasons, Language::De)
+        );
+
+        let pattern = Pattern::Vetoes {
+            rate: 0.5,
+            constraint: Some("quiet_hours".into()),
+        };
+        assert_eq!(
+            pattern.to_string(),
+            "Constraints vetoed the learner in 50.0% of decisions, mostly 'quiet_hours'"
+        );
+        assert_eq!("DE".parse::<Language>(), Ok(Language::De));
+        assert!("fr".parse::<Language>().is_err());
+    }
+}
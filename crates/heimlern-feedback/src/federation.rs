@@ -0,0 +1,232 @@
+//! Consensus priors from other households.
+//!
+//! A fresh installation knows nothing about its arms and has to explore from
+//! scratch. Installations that opt in publish [`SharedStatistics`]: per-arm
+//! pull counts and mean rewards for a task, noised with
+//! [`crate::privacy`] before they leave the house. [`consensus`] combines
+//! such documents into a [`ConsensusPrior`] per task, which a new install can
+//! use as starting values ("most households do best with evening reminders
+//! for this task").
+//!
+//! Every household counts once: arm means are averaged across households, not
+//! pooled by pulls, so one very active installation cannot dominate the prior.
+//! Seeded snapshots must be recorded as external priors in the snapshot
+//! lineage, see [`crate::lineage`].
+
+use crate::privacy::{LaplaceNoise, Privatize};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Format identifier of [`SharedStatistics`].
+pub const SHARED_STATS_VERSION: &str = "heimlern.shared_stats.v1";
+/// Lineage operation name for snapshots seeded from a consensus prior.
+pub const EXTERNAL_PRIOR_OPERATION: &str = "external_prior";
+
+/// Anonymized per-arm statistics of one household.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SharedStatistics {
+    pub version: String,
+    /// What the arms are chosen for, e.g. `"reminder.chores"`.
+    pub task: String,
+    pub arms: BTreeMap<String, SharedArm>,
+}
+
+/// Statistics of one arm.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SharedArm {
+    pub pulls: usize,
+    /// Mean reward in `[0, 1]`.
+    pub mean: f64,
+}
+
+impl SharedStatistics {
+    /// Extract statistics from a `policy.snapshot` (`arms`/`counts`/`values`).
+    ///
+    /// Arms that were never pulled are left out. Returns `None` if the
+    /// snapshot lacks the contract fields or their lengths differ.
+    #[must_use]
+    pub fn from_snapshot(task: impl Into<String>, snapshot: &serde_json::Value) -> Option<Self> {
+        let arms: Vec<String> = serde_json::from_value(snapshot.get("arms")?.clone()).ok()?;
+        let counts: Vec<usize> = serde_json::from_value(snapshot.get("counts")?.clone()).ok()?;
+        let values: Vec<f64> = serde_json::from_value(snapshot.get("values")?.clone()).ok()?;
+        if counts.len() != arms.len() || values.len() != arms.len() {
+            return None;
+        }
+        let arms = arms
+            .into_iter()
+            .zip(counts.into_iter().zip(values))
+            .filter(|(_, (pulls, mean))| *pulls > 0 && mean.is_finite())
+            .map(|(arm, (pulls, mean))| {
+                (
+                    arm,
+                    SharedArm {
+                        pulls,
+                        mean: mean.clamp(0.0, 1.0),
+                    },
+                )
+            })
+            .collect();
+        Some(Self {
+            version: SHARED_STATS_VERSION.to_string(),
+            task: task.into(),
+            arms,
+        })
+    }
+
+    /// Arm with the highest mean, if any.
+    #[must_use]
+    pub fn best_arm(&self) -> Option<&str> {
+        self.arms
+            .iter()
+            .max_by(|(ka, a), (kb, b)| a.mean.total_cmp(&b.mean).then(kb.cmp(ka)))
+            .map(|(k, _)| k.as_str())
+    }
+}
+
+impl Privatize for SharedStatistics {
+    fn privatize<R: Rng + ?Sized>(&mut self, noise: &LaplaceNoise, rng: &mut R) {
+        for arm in self.arms.values_mut() {
+            #[allow(clippy::cast_possible_truncation)]
+            let mean = noise.rate(rng, arm.mean as f32, arm.pulls);
+            arm.mean = f64::from(mean);
+            arm.pulls = noise.count(rng, arm.pulls);
+        }
+        self.arms.retain(|_, arm| arm.pulls > 0);
+    }
+}
+
+/// Consensus over one arm.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArmConsensus {
+    /// Households that reported this arm.
+    pub households: usize,
+    /// Mean reward averaged across those households.
+    pub mean: f64,
+    /// Households for which this arm performed best.
+    pub best_in: usize,
+}
+
+/// Consensus prior for one task.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsensusPrior {
+    pub task: String,
+    /// Households that contributed.
+    pub households: usize,
+    pub arms: BTreeMap<String, ArmConsensus>,
+}
+
+impl ConsensusPrior {
+    /// Arm that most households do best with (ties: higher mean, then name).
+    #[must_use]
+    pub fn favorite(&self) -> Option<(&str, &ArmConsensus)> {
+        self.arms
+            .iter()
+            .max_by(|(ka, a), (kb, b)| {
+                a.best_in
+                    .cmp(&b.best_in)
+                    .then(a.mean.total_cmp(&b.mean))
+                    .then(kb.cmp(ka))
+            })
+            .map(|(k, a)| (k.as_str(), a))
+    }
+
+    /// Contract fields `(arms, counts, values)` for seeding a snapshot.
+    ///
+    /// Each arm gets `weight` pseudo-pulls at its consensus mean, so local
+    /// feedback outweighs the prior after a handful of decisions.
+    #[must_use]
+    pub fn seed(&self, weight: u64) -> (Vec<String>, Vec<u64>, Vec<f64>) {
+        let arms: Vec<String> = self.arms.keys().cloned().collect();
+        let counts = vec![weight; arms.len()];
+        let values = self.arms.values().map(|a| a.mean).collect();
+        (arms, counts, values)
+    }
+}
+
+/// Combine shared statistics into one prior per task.
+///
+/// Documents with an unknown `version` are skipped; tasks reported by fewer
+/// than `min_households` households are left out.
+#[must_use]
+pub fn consensus(shared: &[SharedStatistics], min_households: usize) -> Vec<ConsensusPrior> {
+    let mut by_task: BTreeMap<&str, Vec<&SharedStatistics>> = BTreeMap::new();
+    for stats in shared.iter().filter(|s| s.version == SHARED_STATS_VERSION) {
+        by_task.entry(stats.task.as_str()).or_default().push(stats);
+    }
+    by_task
+        .into_iter()
+        .filter(|(_, households)| households.len() >= min_households.max(1))
+        .map(|(task, households)| {
+            let mut arms: BTreeMap<String, ArmConsensus> = BTreeMap::new();
+            for stats in &households {
+                for (arm, shared) in &stats.arms {
+                    let entry = arms.entry(arm.clone()).or_default();
+                    entry.households += 1;
+                    entry.mean += shared.mean;
+                }
+                if let Some(best) = stats.best_arm() {
+                    if let Some(entry) = arms.get_mut(best) {
+                        entry.best_in += 1;
+                    }
+                }
+            }
+            for entry in arms.values_mut() {
+                #[allow(clippy::cast_precision_loss)]
+                {
+                    entry.mean /= entry.households as f64;
+                }
+            }
+            ConsensusPrior {
+                task: task.to_string(),
+                households: households.len(),
+                arms,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn household(task: &str, morning: f64, evening: f64) -> SharedStatistics {
+        SharedStatistics::from_snapshot(
+            task,
+            &json!({
+                "arms": ["morning", "evening", "never"],
+                "counts": [10, 10, 0],
+                "values": [morning, evening, 0.0],
+            }),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn consensus_counts_each_household_once() {
+        let shared = vec![
+            household("chores", 0.2, 0.8),
+            household("chores", 0.3, 0.7),
+            household("chores", 0.9, 0.1),
+            household("meds", 0.9, 0.1),
+        ];
+        let priors = consensus(&shared, 2);
+        assert_eq!(priors.len(), 1);
+
+        let chores = &priors[0];
+        assert_eq!(chores.households, 3);
+        assert!(!chores.arms.contains_key("never"));
+        let (favorite, stats) = chores.favorite().unwrap();
+        assert_eq!(favorite, "evening");
+        assert_eq!(stats.best_in, 2);
+        assert!((stats.mean - 1.6 / 3.0).abs() < 1e-9);
+
+        let (arms, counts, values) = chores.seed(2);
+        assert_eq!(arms, vec!["evening".to_string(), "morning".to_string()]);
+        assert_eq!(counts, vec![2, 2]);
+        assert_eq!(values.len(), 2);
+    }
+}
@@ -0,0 +1,319 @@
+//! Long-horizon projection of policy performance.
+//!
+//! Outcomes are bucketed by day and fitted with a weighted linear trend plus
+//! additive weekday offsets. The fitted model is extrapolated week by week to
+//! project the success rate under the current weights. For a pending proposal,
+//! the lift estimated by [`FeedbackAnalyzer::simulate_adjustment`] is added on
+//! top, so reviewers can compare both trajectories before accepting.
+//!
+//! Regret is measured against the best-performing action in the history: a
+//! week at success rate `r` with `n` expected decisions accrues
+//! `(best - r) * n` regret.
+
+use crate::{
+    DecisionOutcome, FeedbackAnalyzer, OutcomeStatistics, WeightAdjustmentProposal,
+    PATTERN_MIN_DECISIONS_PER_ACTION,
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Projection for a single future week.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WeekProjection {
+    /// Week offset from the end of the history (1 = next week).
+    pub week: usize,
+    /// Projected success rate (0.0 to 1.0).
+    pub success_rate: f32,
+    /// Regret accrued during this week.
+    pub regret: f32,
+    /// Regret accrued up to and including this week.
+    pub cumulative_regret: f32,
+}
+
+/// Forecast under current weights and, optionally, a pending proposal.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PerformanceForecast {
+    /// Number of days covered by the history.
+    pub history_days: i64,
+    /// Average number of explicit outcomes per week in the history.
+    pub decisions_per_week: f32,
+    /// Success rate of the best action, used as regret baseline.
+    pub best_success_rate: f32,
+    /// Fitted change of the success rate per week.
+    pub trend_per_week: f32,
+    /// Projection under the current weights.
+    pub current: Vec<WeekProjection>,
+    /// Projection under the pending proposal, if one was given.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proposed: Option<Vec<WeekProjection>>,
+}
+
+impl PerformanceForecast {
+    /// Total regret saved by the proposal over the horizon (negative if it costs).
+    #[must_use]
+    pub fn regret_saved(&self) -> Option<f32> {
+        let current = self.current.last()?.cumulative_regret;
+        let proposed = self.proposed.as_ref()?.last()?.cumulative_regret;
+        Some(current - proposed)
+    }
+}
+
+/// Weighted linear trend with additive weekday offsets.
+struct TrendModel {
+    intercept: f64,
+    slope: f64,
+    weekday: [f64; 7],
+}
+
+impl TrendModel {
+    /// Fit from `(day, weekday, rate, weight)` points.
+    fn fit(points: &[(f64, usize, f64, f64)]) -> Option<Self> {
+        let w_sum: f64 = points.iter().map(|p| p.3).sum();
+        if w_sum <= 0.0 {
+            return None;
+        }
+        let x_mean = points.iter().map(|p| p.0 * p.3).sum::<f64>() / w_sum;
+        let y_mean = points.iter().map(|p| p.2 * p.3).sum::<f64>() / w_sum;
+        let sxx: f64 = points.iter().map(|p| p.3 * (p.0 - x_mean).powi(2)).sum();
+        let sxy: f64 = points
+            .iter()
+            .map(|p| p.3 * (p.0 - x_mean) * (p.2 - y_mean))
+            .sum();
+        let slope = if sxx > 0.0 { sxy / sxx } else { 0.0 };
+        let intercept = y_mean - slope * x_mean;
+
+        // Weekday offsets from residuals, centered so they do not shift the trend.
+        let mut sums = [0.0; 7];
+        let mut weights = [0.0; 7];
+        for &(x, wd, y, w) in points {
+            sums[wd] += w * (y - (intercept + slope * x));
+            weights[wd] += w;
+        }
+        let mut weekday = [0.0; 7];
+        for wd in 0..7 {
+            if weights[wd] > 0.0 {
+                weekday[wd] = sums[wd] / weights[wd];
+            }
+        }
+        let populated = weights.iter().filter(|w| **w > 0.0).count();
+        #[allow(clippy::cast_precision_loss)]
+        let mean_offset = weekday.iter().sum::<f64>() / populated.max(1) as f64;
+        for wd in 0..7 {
+            if weights[wd] > 0.0 {
+                weekday[wd] -= mean_offset;
+            }
+        }
+
+        Some(Self {
+            intercept,
+            slope,
+            weekday,
+        })
+    }
+
+    fn predict(&self, day: f64, weekday: usize) -> f64 {
+        self.intercept + self.slope * day + self.weekday[weekday]
+    }
+}
+
+impl FeedbackAnalyzer {
+    /// Project success rate and regret over the next `weeks` weeks.
+    ///
+    /// Outcomes with unparseable timestamps and censored outcomes are ignored.
+    /// Returns `None` if no usable outcomes remain or `weeks` is zero.
+    #[must_use]
+    pub fn forecast(
+        &self,
+        outcomes: &[DecisionOutcome],
+        weeks: usize,
+        proposal: Option<&WeightAdjustmentProposal>,
+    ) -> Option<PerformanceForecast> {
+        if weeks == 0 {
+            return None;
+        }
+
+        let mut daily: BTreeMap<i64, (usize, OutcomeStatistics)> = BTreeMap::new();
+        for outcome in outcomes.iter().filter(|o| !o.is_censored()) {
+            let Ok(ts) = OffsetDateTime::parse(&outcome.ts, &Rfc3339) else {
+                continue;
+            };
+            let day = ts.unix_timestamp().div_euclid(SECONDS_PER_DAY);
+            let weekday = usize::from(ts.weekday().number_days_from_monday());
+            daily
+                .entry(day)
+                .or_insert_with(|| (weekday, OutcomeStatistics::default()))
+                .1
+                .record(outcome);
+        }
+        let first_day = *daily.keys().next()?;
+        let last_day = *daily.keys().next_back()?;
+
+        #[allow(clippy::cast_precision_loss)]
+        let points: Vec<(f64, usize, f64, f64)> = daily
+            .iter()
+            .filter(|(_, (_, stats))| stats.total > 0)
+            .map(|(day, (weekday, stats))| {
+                (
+                    (day - first_day) as f64,
+                    *weekday,
+                    f64::from(stats.success_rate()),
+                    stats.total as f64,
+                )
+            })
+            .collect();
+        let model = TrendModel::fit(&points)?;
+
+        let history_days = last_day - first_day + 1;
+        let explicit: f64 = points.iter().map(|p| p.3).sum();
+        #[allow(clippy::cast_precision_loss)]
+        let decisions_per_week = explicit * 7.0 / history_days as f64;
+
+        let by_action = self.aggregate_outcomes(outcomes, |o| o.action.clone());
+        let best_action_rate = by_action
+            .values()
+            .filter(|s| s.total >= PATTERN_MIN_DECISIONS_PER_ACTION)
+            .map(OutcomeStatistics::success_rate)
+            .fold(0.0_f32, f32::max);
+
+        // Weekly mean of the daily model, in history-relative day units.
+        #[allow(clippy::cast_precision_loss)]
+        let weekly_rates: Vec<f64> = (1..=weeks)
+            .map(|week| {
+                let start = history_days + (week as i64 - 1) * 7;
+                (start..start + 7)
+                    .map(|d| {
+                        let weekday = (first_day + d + 3).rem_euclid(7) as usize;
+                        model.predict(d as f64, weekday).clamp(0.0, 1.0)
+                    })
+                    .sum::<f64>()
+                    / 7.0
+            })
+            .collect();
+
+        let lift = proposal.map(|p| {
+            let baseline = self.summarize_outcomes(outcomes).success_rate();
+            f64::from(self.simulate_adjustment(p, outcomes) - baseline)
+        });
+
+        let upside = lift.unwrap_or(0.0).max(0.0);
+        let best = weekly_rates
+            .iter()
+            .map(|rate| (rate + upside).min(1.0))
+            .fold(f64::from(best_action_rate), f64::max);
+
+        let project = |shift: f64| {
+            let mut cumulative = 0.0_f64;
+            weekly_rates
+                .iter()
+                .enumerate()
+                .map(|(i, rate)| {
+                    let rate = (rate + shift).clamp(0.0, 1.0);
+                    let regret = (best - rate).max(0.0) * decisions_per_week;
+                    cumulative += regret;
+                    #[allow(clippy::cast_possible_truncation)]
+                    WeekProjection {
+                        week: i + 1,
+                        success_rate: rate as f32,
+                        regret: regret as f32,
+                        cumulative_regret: cumulative as f32,
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+
+        #[allow(clippy::cast_possible_truncation)]
+        Some(PerformanceForecast {
+            history_days,
+            decisions_per_week: decisions_per_week as f32,
+            best_success_rate: best as f32,
+            trend_per_week: (model.slope * 7.0) as f32,
+            current: project(0.0),
+            proposed: lift.map(project),
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::{DeltaValue, Evidence, OutcomeType, ProposalStatus};
+    use std::collections::BTreeMap;
+
+    /// `days` days of history, 10 decisions per day, success rate rising
+    /// linearly from 30% to about 30% + `days`%.
+    fn history(days: u32) -> Vec<DecisionOutcome> {
+        let mut out = Vec::new();
+        for day in 0..days {
+            let successes = 3 + day / 10;
+            for i in 0..10 {
+                let success = i < successes;
+                out.push(DecisionOutcome {
+                    decision_id: format!("{day}-{i}"),
+                    ts: format!("2026-01-{:02}T12:00:00Z", day + 1),
+                    policy_id: None,
+                    action: Some(if i % 2 == 0 { "a" } else { "b" }.into()),
+                    outcome: if success {
+                        OutcomeType::Success
+                    } else {
+                        OutcomeType::Failure
+                    },
+                    success,
+                    reward: None,
+                    context: None,
+                    metadata: Some(serde_json::json!({
+                        "why": if i == 9 { "explore" } else { "exploit" }
+                    })),
+                });
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn rising_history_projects_rising_success_and_shrinking_regret() {
+        let analyzer = FeedbackAnalyzer::default();
+        let forecast = analyzer.forecast(&history(28), 4, None).unwrap();
+        assert_eq!(forecast.history_days, 28);
+        assert!((forecast.decisions_per_week - 70.0).abs() < 1e-3);
+        assert!(forecast.trend_per_week > 0.0);
+        assert_eq!(forecast.current.len(), 4);
+        assert!(forecast.current[3].success_rate > forecast.current[0].success_rate);
+        assert!(forecast.current[3].regret <= forecast.current[0].regret);
+        assert!(forecast.proposed.is_none());
+    }
+
+    #[test]
+    fn proposal_shifts_projection_by_simulated_lift() {
+        let analyzer = FeedbackAnalyzer::default();
+        let proposal = WeightAdjustmentProposal {
+            version: "v1".into(),
+            basis_policy: "p".into(),
+            ts: String::new(),
+            deltas: BTreeMap::from([("epsilon".to_string(), DeltaValue::Absolute { value: 0.0 })]),
+            confidence: 0.7,
+            evidence: Evidence::default(),
+            reasoning: None,
+            status: ProposalStatus::Proposed,
+        };
+        let forecast = analyzer.forecast(&history(28), 2, Some(&proposal)).unwrap();
+        let proposed = forecast.proposed.as_ref().unwrap();
+        assert!(proposed[0].success_rate > forecast.current[0].success_rate);
+        assert!(forecast.regret_saved().unwrap() > 0.0);
+    }
+
+    #[test]
+    fn no_parseable_timestamps_yields_none() {
+        let mut outcomes = history(1);
+        for o in &mut outcomes {
+            o.ts = "yesterday".into();
+        }
+        assert!(FeedbackAnalyzer::default()
+            .forecast(&outcomes, 4, None)
+            .is_none());
+    }
+}
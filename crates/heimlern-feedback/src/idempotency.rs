@@ -0,0 +1,180 @@
+//! Idempotent outcome ingestion.
+//!
+//! Webhook senders retry deliveries, so the same outcome can arrive more than
+//! once. Clients attach an idempotency key under `metadata.idempotency_key`;
+//! an [`IdempotencyStore`] remembers every admitted key and rejects repeats,
+//! so retries never inflate evidence counts.
+//!
+//! The file-backed store appends one key per line and reloads them on open,
+//! which keeps deduplication intact across restarts. Outcomes without a key
+//! are always admitted.
+
+use crate::DecisionOutcome;
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// Metadata key carrying the client-supplied idempotency key.
+pub const IDEMPOTENCY_METADATA_KEY: &str = "idempotency_key";
+
+/// Client-supplied idempotency key of an outcome, if any.
+#[must_use]
+pub fn idempotency_key(outcome: &DecisionOutcome) -> Option<&str> {
+    outcome
+        .metadata
+        .as_ref()?
+        .get(IDEMPOTENCY_METADATA_KEY)?
+        .as_str()
+        .filter(|key| !key.is_empty())
+}
+
+/// Set of idempotency keys that have already been admitted.
+#[derive(Debug, Default)]
+pub struct IdempotencyStore {
+    path: Option<PathBuf>,
+    seen: HashSet<String>,
+}
+
+impl IdempotencyStore {
+    /// Store that only lives as long as the process.
+    #[must_use]
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Opens a file-backed store, loading previously admitted keys.
+    ///
+    /// A missing file is treated as an empty store; it is created on the first
+    /// admitted key.
+    ///
+    /// # Errors
+    /// Returns an I/O error if the file exists but cannot be read.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut seen = HashSet::new();
+        match File::open(path) {
+            Ok(file) => {
+                for line in BufReader::new(file).lines() {
+                    let line = line?;
+                    let key = line.trim();
+                    if !key.is_empty() {
+                        seen.insert(key.to_string());
+                    }
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        Ok(Self {
+            path: Some(path.to_path_buf()),
+            seen,
+        })
+    }
+
+    /// Number of known keys.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    /// Whether no key has been admitted yet.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+
+    /// Whether `key` has already been admitted.
+    #[must_use]
+    pub fn contains(&self, key: &str) -> bool {
+        self.seen.contains(key)
+    }
+
+    /// Records `key`; returns `false` if it was seen before.
+    ///
+    /// # Errors
+    /// Returns an I/O error if a file-backed store cannot persist the key. The
+    /// key is not recorded in that case.
+    pub fn check_and_record(&mut self, key: &str) -> io::Result<bool> {
+        if self.seen.contains(key) {
+            return Ok(false);
+        }
+        if let Some(path) = &self.path {
+            if key.contains('\n') {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "idempotency key must not contain newlines",
+                ));
+            }
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(file, "{key}")?;
+        }
+        self.seen.insert(key.to_string());
+        Ok(true)
+    }
+
+    /// Decides whether `outcome` should be ingested.
+    ///
+    /// Outcomes without a key are always admitted; keyed outcomes only once.
+    ///
+    /// # Errors
+    /// See [`IdempotencyStore::check_and_record`].
+    pub fn admit(&mut self, outcome: &DecisionOutcome) -> io::Result<bool> {
+        match idempotency_key(outcome) {
+            Some(key) => self.check_and_record(key),
+            None => Ok(true),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::OutcomeType;
+
+    fn outcome(key: Option<&str>) -> DecisionOutcome {
+        DecisionOutcome {
+            decision_id: "d1".into(),
+            ts: "2026-01-01T00:00:00Z".into(),
+            policy_id: None,
+            action: None,
+            outcome: OutcomeType::Success,
+            success: true,
+            reward: None,
+            context: None,
+            metadata: key.map(|k| serde_json::json!({ IDEMPOTENCY_METADATA_KEY: k })),
+        }
+    }
+
+    #[test]
+    fn keyed_outcomes_are_admitted_once_unkeyed_always() {
+        let mut store = IdempotencyStore::in_memory();
+        assert!(store.admit(&outcome(Some("k1"))).unwrap());
+        assert!(!store.admit(&outcome(Some("k1"))).unwrap());
+        assert!(store.admit(&outcome(None)).unwrap());
+        assert!(store.admit(&outcome(None)).unwrap());
+        assert!(store.admit(&outcome(Some(""))).unwrap());
+        assert_eq!(store.len(), 1);
+    }
+
+    #[test]
+    fn file_store_survives_reopen() {
+        let dir = std::env::temp_dir().join(format!("heimlern-idem-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("keys");
+        let _ = std::fs::remove_file(&path);
+
+        let mut store = IdempotencyStore::open(&path).unwrap();
+        assert!(store.check_and_record("delivery-1").unwrap());
+        drop(store);
+
+        let mut reopened = IdempotencyStore::open(&path).unwrap();
+        assert!(reopened.contains("delivery-1"));
+        assert!(!reopened.check_and_record("delivery-1").unwrap());
+        assert!(reopened.check_and_record("delivery-2").unwrap());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,174 @@
+//! Pre-grouped outcome index.
+//!
+//! Pattern analysis used to rescan the full outcome list once per grouping
+//! (per action, overall, vetoes). An [`OutcomeIndex`] collects all groupings
+//! in a single pass and can be extended incrementally with
+//! [`OutcomeIndex::insert`], so long-running analyzers only pay for new
+//! outcomes. [`FeedbackAnalyzer::analyze_index`] runs the pattern heuristics
+//! directly on the index.
+
+use crate::latency::{decision_latency, LatencyReport};
+use crate::veto::{VetoRecord, VetoReport};
+use crate::{DecisionOutcome, OutcomeStatistics};
+use std::collections::BTreeMap;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Outcome statistics grouped by action, kind and day.
+#[derive(Debug, Default, Clone)]
+pub struct OutcomeIndex {
+    /// Number of indexed outcomes, censored ones included.
+    pub records: usize,
+    /// Statistics over all outcomes.
+    pub overall: OutcomeStatistics,
+    /// Statistics per action (outcomes without an action are skipped).
+    pub by_action: BTreeMap<String, OutcomeStatistics>,
+    /// Statistics per `context.kind` (outcomes without a kind are skipped).
+    pub by_kind: BTreeMap<String, OutcomeStatistics>,
+    /// Statistics per UTC day since the Unix epoch (unparseable timestamps are skipped).
+    pub by_day: BTreeMap<i64, OutcomeStatistics>,
+    /// Number of outcomes carrying a veto record.
+    pub vetoed: usize,
+    /// Veto count per constraint (`"unknown"` if unnamed).
+    pub vetoes_by_constraint: BTreeMap<String, usize>,
+    /// Decision-to-outcome latencies in seconds, see [`crate::latency`].
+    pub latencies: Vec<f64>,
+    /// Latencies per action.
+    pub latencies_by_action: BTreeMap<String, Vec<f64>>,
+}
+
+impl OutcomeIndex {
+    /// Index `outcomes` in a single pass.
+    #[must_use]
+    pub fn build(outcomes: &[DecisionOutcome]) -> Self {
+        let mut index = Self::default();
+        index.extend(outcomes);
+        index
+    }
+
+    /// Add one outcome to every grouping.
+    pub fn insert(&mut self, outcome: &DecisionOutcome) {
+        self.records += 1;
+        self.overall.record(outcome);
+        if let Some(action) = &outcome.action {
+            self.by_action
+                .entry(action.clone())
+                .or_default()
+                .record(outcome);
+        }
+        if let Some(kind) = outcome
+            .context
+            .as_ref()
+            .and_then(|c| c.get("kind"))
+            .and_then(serde_json::Value::as_str)
+        {
+            self.by_kind
+                .entry(kind.to_string())
+                .or_default()
+                .record(outcome);
+        }
+        if let Ok(ts) = OffsetDateTime::parse(&outcome.ts, &Rfc3339) {
+            self.by_day
+                .entry(ts.unix_timestamp().div_euclid(SECONDS_PER_DAY))
+                .or_default()
+                .record(outcome);
+        }
+        if let Some(veto) = VetoRecord::from_outcome(outcome) {
+            self.vetoed += 1;
+            let constraint = veto.constraint.unwrap_or_else(|| "unknown".to_string());
+            *self.vetoes_by_constraint.entry(constraint).or_default() += 1;
+        }
+        if let Some(latency) = decision_latency(outcome) {
+            self.latencies.push(latency);
+            if let Some(action) = &outcome.action {
+                self.latencies_by_action
+                    .entry(action.clone())
+                    .or_default()
+                    .push(latency);
+            }
+        }
+    }
+
+    /// Add several outcomes.
+    pub fn extend<'a>(&mut self, outcomes: impl IntoIterator<Item = &'a DecisionOutcome>) {
+        for outcome in outcomes {
+            self.insert(outcome);
+        }
+    }
+
+    /// Veto summary derived from the index (success rates are not tracked).
+    #[must_use]
+    pub fn veto_summary(&self) -> VetoReport {
+        VetoReport {
+            decisions: self.records,
+            vetoed: self.vetoed,
+            by_constraint: self.vetoes_by_constraint.clone(),
+            ..VetoReport::default()
+        }
+    }
+
+    /// Latency statistics derived from the index.
+    #[must_use]
+    pub fn latency_report(&self) -> LatencyReport {
+        LatencyReport::from_samples(&self.latencies_by_action, &self.latencies)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::{FeedbackAnalyzer, OutcomeType};
+
+    fn outcome(i: usize) -> DecisionOutcome {
+        let success = i.is_multiple_of(3);
+        DecisionOutcome {
+            decision_id: i.to_string(),
+            ts: format!("2026-01-{:02}T08:00:00Z", i % 5 + 1),
+            policy_id: None,
+            action: Some(format!("remind.{}", ["morning", "evening"][i % 2])),
+            outcome: if success {
+                OutcomeType::Success
+            } else {
+                OutcomeType::Failure
+            },
+            success,
+            reward: None,
+            context: Some(serde_json::json!({ "kind": "reminder" })),
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn index_matches_separate_aggregations() {
+        let outcomes: Vec<_> = (0..40).map(outcome).collect();
+        let analyzer = FeedbackAnalyzer::default();
+        let index = OutcomeIndex::build(&outcomes);
+
+        let by_action = analyzer.aggregate_outcomes(&outcomes, |o| o.action.clone());
+        assert_eq!(index.by_action.len(), by_action.len());
+        for (action, stats) in &by_action {
+            assert_eq!(index.by_action[action].successes, stats.successes);
+            assert_eq!(index.by_action[action].total, stats.total);
+        }
+        assert_eq!(index.by_kind["reminder"].total, 40);
+        assert_eq!(index.by_day.len(), 5);
+        assert_eq!(
+            analyzer.analyze_index(&index),
+            analyzer.analyze_patterns(&outcomes)
+        );
+    }
+
+    #[test]
+    fn incremental_inserts_equal_batch_build() {
+        let outcomes: Vec<_> = (0..20).map(outcome).collect();
+        let mut index = OutcomeIndex::build(&outcomes[..10]);
+        index.extend(&outcomes[10..]);
+        let batch = OutcomeIndex::build(&outcomes);
+        assert_eq!(index.records, batch.records);
+        assert_eq!(index.overall.failures, batch.overall.failures);
+        assert_eq!(index.by_day.len(), batch.by_day.len());
+    }
+}
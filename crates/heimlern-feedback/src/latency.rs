@@ -0,0 +1,192 @@
+//! Decision-to-outcome latency.
+//!
+//! An outcome's `ts` is when the response arrived. Clients that also record
+//! when the decision was made (`metadata.decision_ts`) make the delay between
+//! the two measurable. A reminder that is always acknowledged forty minutes
+//! late is technically a success, yet it fires at the wrong time; latency
+//! statistics per action surface that and let [`FeedbackAnalyzer`] propose
+//! slot-timing shifts instead of only reacting to success and failure counts.
+//!
+//! [`FeedbackAnalyzer`]: crate::FeedbackAnalyzer
+
+use crate::DecisionOutcome;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+/// Metadata key carrying the timestamp of the original decision.
+pub const DECISION_TS_METADATA_KEY: &str = "decision_ts";
+
+/// Metadata key carrying the minute within its slot at which the decision fired.
+pub const OFFSET_MINUTES_METADATA_KEY: &str = "offset_minutes";
+
+/// Median minute within the slot at which `action` fired.
+///
+/// Outcomes without [`OFFSET_MINUTES_METADATA_KEY`] are ignored; with none at
+/// all the decision is taken to have fired at the start of its slot (`0.0`).
+#[must_use]
+pub fn fired_offset(outcomes: &[DecisionOutcome], action: &str) -> f64 {
+    let mut offsets: Vec<f64> = outcomes
+        .iter()
+        .filter(|o| o.action.as_deref() == Some(action))
+        .filter_map(|o| {
+            o.metadata
+                .as_ref()?
+                .get(OFFSET_MINUTES_METADATA_KEY)?
+                .as_f64()
+        })
+        .filter(|m| m.is_finite())
+        .collect();
+    if offsets.is_empty() {
+        return 0.0;
+    }
+    offsets.sort_by(f64::total_cmp);
+    offsets[offsets.len() / 2]
+}
+
+/// Seconds between decision and outcome, if both timestamps are known.
+///
+/// Negative delays (an outcome before its decision) point to clock skew and
+/// are discarded.
+#[must_use]
+pub fn decision_latency(outcome: &DecisionOutcome) -> Option<f64> {
+    let decided = outcome
+        .metadata
+        .as_ref()?
+        .get(DECISION_TS_METADATA_KEY)?
+        .as_str()?;
+    let decided = OffsetDateTime::parse(decided, &Rfc3339).ok()?;
+    let observed = OffsetDateTime::parse(&outcome.ts, &Rfc3339).ok()?;
+    let seconds = (observed - decided).as_seconds_f64();
+    (seconds >= 0.0).then_some(seconds)
+}
+
+/// Distribution summary of latencies in seconds.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LatencyStats {
+    pub count: usize,
+    pub mean_seconds: f64,
+    pub p50_seconds: f64,
+    pub p90_seconds: f64,
+    pub max_seconds: f64,
+}
+
+impl LatencyStats {
+    /// Summarize `samples`; `None` if there are none.
+    #[must_use]
+    pub fn from_samples(samples: &[f64]) -> Option<Self> {
+        let mut sorted: Vec<f64> = samples.iter().copied().filter(|s| s.is_finite()).collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_by(f64::total_cmp);
+        let rank = |q: f64| {
+            #[allow(
+                clippy::cast_possible_truncation,
+                clippy::cast_sign_loss,
+                clippy::cast_precision_loss
+            )]
+            let idx = ((sorted.len() - 1) as f64 * q).round() as usize;
+            sorted[idx]
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let mean_seconds = sorted.iter().sum::<f64>() / sorted.len() as f64;
+        Some(Self {
+            count: sorted.len(),
+            mean_seconds,
+            p50_seconds: rank(0.5),
+            p90_seconds: rank(0.9),
+            max_seconds: sorted[sorted.len() - 1],
+        })
+    }
+}
+
+/// Latency statistics overall and per action.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LatencyReport {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overall: Option<LatencyStats>,
+    pub by_action: BTreeMap<String, LatencyStats>,
+}
+
+impl LatencyReport {
+    /// Build the report from raw per-action samples.
+    #[must_use]
+    pub fn from_samples(by_action: &BTreeMap<String, Vec<f64>>, overall: &[f64]) -> Self {
+        Self {
+            overall: LatencyStats::from_samples(overall),
+            by_action: by_action
+                .iter()
+                .filter_map(|(action, samples)| {
+                    LatencyStats::from_samples(samples).map(|s| (action.clone(), s))
+                })
+                .collect(),
+        }
+    }
+
+    /// Compute the report for `outcomes`.
+    #[must_use]
+    pub fn compute(outcomes: &[DecisionOutcome]) -> Self {
+        crate::index::OutcomeIndex::build(outcomes).latency_report()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn outcome(action: &str, decided: &str, observed: &str) -> DecisionOutcome {
+        serde_json::from_value(json!({
+            "decision_id": "d",
+            "ts": observed,
+            "action": action,
+            "outcome": "success",
+            "success": true,
+            "metadata": { "decision_ts": decided },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn summarizes_latency_per_action() {
+        let outcomes = vec![
+            outcome(
+                "remind.evening",
+                "2026-01-01T19:00:00Z",
+                "2026-01-01T19:40:00Z",
+            ),
+            outcome(
+                "remind.evening",
+                "2026-01-02T19:00:00Z",
+                "2026-01-02T19:30:00Z",
+            ),
+            outcome(
+                "remind.evening",
+                "2026-01-03T19:00:00Z",
+                "2026-01-03T20:00:00Z",
+            ),
+            outcome(
+                "remind.morning",
+                "2026-01-01T08:00:00Z",
+                "2026-01-01T08:02:00Z",
+            ),
+            // Outcome before decision: clock skew, not a latency.
+            outcome(
+                "remind.morning",
+                "2026-01-02T08:00:00Z",
+                "2026-01-02T07:59:00Z",
+            ),
+        ];
+        let report = LatencyReport::compute(&outcomes);
+
+        let evening = &report.by_action["remind.evening"];
+        assert_eq!(evening.count, 3);
+        assert!((evening.p50_seconds - 2400.0).abs() < 1e-9);
+        assert!((evening.max_seconds - 3600.0).abs() < 1e-9);
+        assert_eq!(report.by_action["remind.morning"].count, 1);
+        assert_eq!(report.overall.as_ref().map(|o| o.count), Some(4));
+    }
+}
@@ -23,13 +23,50 @@
 //! whether they were "explore" or "exploit" decisions. Simulation is supported for
 //! [`DeltaValue::Relative`], [`DeltaValue::Additive`], and [`DeltaValue::Absolute`] adjustments to `epsilon`.
 
+pub mod aggregate;
+pub mod anomaly;
+pub mod apply;
+pub mod audit;
+pub mod bundle;
+pub mod challenger;
+pub mod cooldown;
+pub mod dedup;
+pub mod detect;
+pub mod drift;
+pub mod expiry;
+pub mod explain;
+pub mod federation;
+pub mod forecast;
+pub mod idempotency;
+pub mod index;
+pub mod latency;
+pub mod lineage;
+pub mod merge;
+pub mod meta;
+pub mod overrides;
+pub mod portfolio;
+pub mod privacy;
+pub mod provenance;
+pub mod replay;
+pub mod season;
+pub mod sink;
+pub mod skew;
+pub mod store;
+pub mod support;
+pub mod trend;
+pub mod veto;
+
+use detect::{PatternDetector, PatternRegistry};
+use explain::{Language, Pattern, Reason};
+use heimlern_core::shaping::{RawReward, RewardShaper, RewardShaping};
+use heimlern_core::PolicyDescriptor;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use time::{format_description::well_known::Rfc3339, OffsetDateTime};
 
 // Confidence calculation constants
-/// Sample size at which confidence plateaus (smaller = more generous)
-const CONFIDENCE_SAMPLE_SIZE_PLATEAU: f32 = 50.0;
+/// Confidence level of the Wilson interval whose width drives the sample component
+const CONFIDENCE_INTERVAL_LEVEL: f32 = 0.95;
 /// Confidence level when 2+ patterns detected (high confidence)
 const CONFIDENCE_HIGH_PATTERN: f32 = 0.7;
 /// Confidence level when <2 patterns detected (moderate confidence)
@@ -46,6 +83,14 @@ const PATTERN_MIN_DECISIONS_PER_ACTION: usize = 5;
 const PATTERN_HIGH_FAILURE_THRESHOLD: f32 = 0.6;
 /// Overall failure rate threshold (50%) for system-wide issues
 const PATTERN_OVERALL_FAILURE_THRESHOLD: f32 = 0.5;
+/// Ignore rate threshold (60%) above which an action is flagged as being ignored
+const PATTERN_HIGH_IGNORE_THRESHOLD: f32 = 0.6;
+/// Override rate threshold (20%) above which an action is flagged as overridden by hand
+const PATTERN_HIGH_OVERRIDE_THRESHOLD: f32 = 0.2;
+/// Veto rate threshold (30%) above which constraints are flagged as fighting the learner
+const PATTERN_HIGH_VETO_THRESHOLD: f32 = 0.3;
+/// Median decision-to-outcome latency (30 minutes) above which an action is flagged as mistimed
+const PATTERN_SLOW_RESPONSE_SECONDS: f64 = 1800.0;
 
 // Adjustment thresholds
 /// Failure rate threshold (50%) that triggers exploration reduction
@@ -78,6 +123,7 @@ pub struct DecisionOutcome {
     /// For [`OutcomeType::Success`] and [`OutcomeType::Failure`], this should be
     /// consistent with `outcome`. For [`OutcomeType::Partial`] and
     /// [`OutcomeType::Unknown`], this flag drives success classification.
+    /// It is ignored for [`OutcomeType::Censored`] and [`OutcomeType::Override`].
     pub success: bool,
     /// Numeric reward signal
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -98,6 +144,43 @@ pub enum OutcomeType {
     Failure,
     Partial,
     Unknown,
+    /// No outcome was received within the feedback TTL (e.g. a reminder that
+    /// was ignored rather than declined). Censored outcomes are counted
+    /// separately and never treated as explicit failures.
+    Censored,
+    /// A human replaced the decision by hand. The replacement action, if
+    /// known, is stored in the metadata (see [`overrides`]). Overrides are
+    /// counted separately and never treated as plain failures.
+    Override,
+}
+
+impl DecisionOutcome {
+    /// Build a censored outcome for a decision that received no feedback
+    /// within its TTL.
+    #[must_use]
+    pub fn censored(
+        decision_id: impl Into<String>,
+        ts: impl Into<String>,
+        action: Option<String>,
+    ) -> Self {
+        Self {
+            decision_id: decision_id.into(),
+            ts: ts.into(),
+            policy_id: None,
+            action,
+            outcome: OutcomeType::Censored,
+            success: false,
+            reward: None,
+            context: None,
+            metadata: None,
+        }
+    }
+
+    /// Whether this outcome is censored (no response within TTL).
+    #[must_use]
+    pub fn is_censored(&self) -> bool {
+        self.outcome == OutcomeType::Censored
+    }
 }
 
 /// Evidence supporting a weight adjustment proposal.
@@ -129,7 +212,7 @@ pub struct WeightAdjustmentProposal {
     /// Timestamp when the proposal was generated
     pub ts: String,
     /// Proposed weight adjustments as key-value pairs
-    pub deltas: HashMap<String, DeltaValue>,
+    pub deltas: BTreeMap<String, DeltaValue>,
     /// Confidence in the proposed adjustments (0.0 to 1.0)
     pub confidence: f32,
     /// Evidence supporting the proposal
@@ -178,11 +261,15 @@ pub enum ProposalStatus {
 /// Statistics aggregated from decision outcomes.
 #[derive(Debug, Default, Clone)]
 pub struct OutcomeStatistics {
-    /// Total number of outcomes (successes + failures).
+    /// Total number of explicit outcomes (successes + failures).
     pub total: usize,
     pub successes: usize,
     pub failures: usize,
     pub total_reward: f64,
+    /// Number of censored outcomes (no response within TTL), kept out of `total`.
+    pub censored: usize,
+    /// Number of decisions a human overrode by hand, kept out of `total`.
+    pub overrides: usize,
 }
 
 impl OutcomeStatistics {
@@ -192,6 +279,31 @@ impl OutcomeStatistics {
         ratio(self.successes, self.total)
     }
 
+    /// Wilson score interval `(lower, upper)` of the success rate at the
+    /// given two-sided `confidence` (e.g. `0.95`).
+    ///
+    /// Unlike the point estimate, the interval reflects the sample size: five
+    /// failures out of five give roughly `(0.0, 0.43)`, five hundred give
+    /// `(0.0, 0.008)`. Without explicit outcomes the interval is `(0.0, 1.0)`.
+    #[must_use]
+    pub fn success_rate_interval(&self, confidence: f32) -> (f32, f32) {
+        if self.total == 0 {
+            return (0.0, 1.0);
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let n = self.total as f64;
+        let p = f64::from(self.success_rate());
+        let z = normal_quantile(0.5 + f64::from(confidence.clamp(0.0, 0.999_999)) / 2.0);
+        let z2 = z * z;
+        let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+        let half_width = z / (1.0 + z2 / n) * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+        #[allow(clippy::cast_possible_truncation)]
+        (
+            (center - half_width).clamp(0.0, 1.0) as f32,
+            (center + half_width).clamp(0.0, 1.0) as f32,
+        )
+    }
+
     /// Calculate failure rate (0.0 to 1.0).
     #[must_use]
     pub fn failure_rate(&self) -> f32 {
@@ -205,8 +317,51 @@ impl OutcomeStatistics {
         1.0 - self.success_rate()
     }
 
+    /// Number of decisions including censored and overridden ones.
+    #[must_use]
+    pub fn observed(&self) -> usize {
+        self.total + self.censored + self.overrides
+    }
+
+    /// Share of all observed decisions that received no response (0.0 to 1.0).
+    #[must_use]
+    pub fn ignore_rate(&self) -> f32 {
+        ratio(self.censored, self.observed())
+    }
+
+    /// Share of all observed decisions that a human overrode (0.0 to 1.0).
+    #[must_use]
+    pub fn override_rate(&self) -> f32 {
+        ratio(self.overrides, self.observed())
+    }
+
+    /// Share of all observed decisions that were explicitly rejected (0.0 to 1.0).
+    ///
+    /// Unlike [`Self::failure_rate`], the denominator includes censored outcomes,
+    /// so ignore rate and reject rate can be compared directly.
+    #[must_use]
+    pub fn reject_rate(&self) -> f32 {
+        ratio(self.failures, self.observed())
+    }
+
     /// Record one decision outcome into this aggregate.
     pub fn record(&mut self, outcome: &DecisionOutcome) {
+        self.record_shaped(outcome, &heimlern_core::shaping::Identity);
+    }
+
+    /// Record one decision outcome, passing its reward through `shaper` first.
+    ///
+    /// The shaper sees the action, the context `kind` and the decision latency
+    /// (see [`latency::decision_latency`]) when the outcome carries them.
+    pub fn record_shaped(&mut self, outcome: &DecisionOutcome, shaper: &impl RewardShaper) {
+        if outcome.is_censored() {
+            self.censored += 1;
+            return;
+        }
+        if outcome.is_override() {
+            self.overrides += 1;
+            return;
+        }
         self.total += 1;
         if outcome_is_success(outcome) {
             self.successes += 1;
@@ -214,12 +369,31 @@ impl OutcomeStatistics {
             self.failures += 1;
         }
         if let Some(reward) = outcome.reward {
+            let mut raw = RawReward::new(reward);
+            raw.action = outcome.action.as_deref();
+            raw.kind = outcome
+                .context
+                .as_ref()
+                .and_then(|c| c.get("kind"))
+                .and_then(serde_json::Value::as_str);
+            raw.delay_seconds = latency::decision_latency(outcome);
+            let reward = shaper.shape(&raw);
             if reward.is_finite() {
                 self.total_reward += f64::from(reward);
             }
         }
     }
 
+    /// Add the counts of `other`, e.g. from another chunk of the same log.
+    pub fn merge(&mut self, other: &Self) {
+        self.total += other.total;
+        self.successes += other.successes;
+        self.failures += other.failures;
+        self.total_reward += other.total_reward;
+        self.censored += other.censored;
+        self.overrides += other.overrides;
+    }
+
     /// Calculate average reward.
     #[must_use]
     pub fn average_reward(&self) -> f32 {
@@ -233,6 +407,40 @@ impl OutcomeStatistics {
     }
 }
 
+/// Quantile of the standard normal distribution for `p` in `(0, 1)`.
+///
+/// Rational approximation from Abramowitz & Stegun 26.2.23 (absolute error
+/// below 4.5e-4), which is plenty for interval bounds.
+fn normal_quantile(p: f64) -> f64 {
+    let p = p.clamp(1e-12, 1.0 - 1e-12);
+    let tail = p.min(1.0 - p);
+    let t = (-2.0 * tail.ln()).sqrt();
+    let x = t
+        - (2.515_517 + 0.802_853 * t + 0.010_328 * t * t)
+            / (1.0 + 1.432_788 * t + 0.189_269 * t * t + 0.001_308 * t * t * t);
+    if p < 0.5 {
+        -x
+    } else {
+        x
+    }
+}
+
+/// Pooled two-proportion z statistic of `s2 / n2` against `s1 / n1`;
+/// 0.0 when a side is empty or both rates are 0 or 1.
+fn two_proportion_z(s1: usize, n1: usize, s2: usize, n2: usize) -> f64 {
+    if n1 == 0 || n2 == 0 {
+        return 0.0;
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let (s1, n1, s2, n2) = (s1 as f64, n1 as f64, s2 as f64, n2 as f64);
+    let pooled = (s1 + s2) / (n1 + n2);
+    let se = (pooled * (1.0 - pooled) * (1.0 / n1 + 1.0 / n2)).sqrt();
+    if se <= 0.0 {
+        return 0.0;
+    }
+    (s2 / n2 - s1 / n1) / se
+}
+
 /// Helper to calculate ratio of two numbers with precision loss handling.
 fn ratio(num: usize, den: usize) -> f32 {
     if den == 0 {
@@ -245,12 +453,77 @@ fn ratio(num: usize, den: usize) -> f32 {
 }
 
 /// Analyzes decision outcomes and generates weight adjustment proposals.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct FeedbackAnalyzer {
     /// Minimum number of decisions required before proposing adjustments
     min_decisions: usize,
     /// Minimum confidence threshold for proposals
     min_confidence: f32,
+    /// Language of patterns and reasoning in generated proposals
+    language: Language,
+    /// Shaping applied to outcome rewards before they are aggregated
+    reward_shaping: RewardShaping,
+    /// Detectors run by the pattern analysis
+    detectors: PatternRegistry,
+    /// Chart whose anomalous days are left out of proposals, if any
+    anomaly_chart: Option<anomaly::EwmaChart>,
+}
+
+/// Builder for a [`FeedbackAnalyzer`], see [`FeedbackAnalyzer::builder`].
+#[derive(Debug, Clone, Default)]
+#[must_use]
+pub struct FeedbackAnalyzerBuilder {
+    analyzer: FeedbackAnalyzer,
+}
+
+impl FeedbackAnalyzerBuilder {
+    /// Minimum number of decisions before any proposal is made.
+    pub fn min_decisions(mut self, min_decisions: usize) -> Self {
+        self.analyzer.min_decisions = min_decisions;
+        self
+    }
+
+    /// Minimum confidence of emitted proposals, clamped to `0.0..=1.0`.
+    pub fn min_confidence(mut self, min_confidence: f32) -> Self {
+        self.analyzer.min_confidence = min_confidence.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Language of patterns and reasoning.
+    pub fn language(mut self, language: Language) -> Self {
+        self.analyzer.language = language;
+        self
+    }
+
+    /// Reward shaping shared with the policies that produced the outcomes.
+    pub fn reward_shaping(mut self, shaping: RewardShaping) -> Self {
+        self.analyzer.reward_shaping = shaping;
+        self
+    }
+
+    /// Register an additional pattern detector, run after those already set.
+    pub fn detector(mut self, detector: impl PatternDetector + 'static) -> Self {
+        self.analyzer.detectors.register(detector);
+        self
+    }
+
+    /// Replace the pattern detectors (default: [`PatternRegistry::builtin`]).
+    pub fn detectors(mut self, detectors: PatternRegistry) -> Self {
+        self.analyzer.detectors = detectors;
+        self
+    }
+
+    /// Leave outcomes of days flagged by `chart` out of proposals (default:
+    /// keep every day), see [`anomaly`].
+    pub fn exclude_anomalies(mut self, chart: anomaly::EwmaChart) -> Self {
+        self.analyzer.anomaly_chart = Some(chart);
+        self
+    }
+
+    #[must_use]
+    pub fn build(self) -> FeedbackAnalyzer {
+        self.analyzer
+    }
 }
 
 impl Default for FeedbackAnalyzer {
@@ -258,6 +531,10 @@ impl Default for FeedbackAnalyzer {
         Self {
             min_decisions: 10,
             min_confidence: 0.5,
+            language: Language::En,
+            reward_shaping: RewardShaping::Identity,
+            detectors: PatternRegistry::builtin(),
+            anomaly_chart: None,
         }
     }
 }
@@ -269,21 +546,54 @@ impl FeedbackAnalyzer {
         Self {
             min_decisions,
             min_confidence: min_confidence.clamp(0.0, 1.0),
+            language: Language::En,
+            reward_shaping: RewardShaping::Identity,
+            detectors: PatternRegistry::builtin(),
+            anomaly_chart: None,
         }
     }
 
+    /// Start a builder with the default thresholds.
+    pub fn builder() -> FeedbackAnalyzerBuilder {
+        FeedbackAnalyzerBuilder::default()
+    }
+
+    /// Render patterns and reasoning in `language` (default: English).
+    #[must_use]
+    pub fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Reward shaping applied when aggregating outcomes.
+    #[must_use]
+    pub fn reward_shaping(&self) -> &RewardShaping {
+        &self.reward_shaping
+    }
+
+    /// Pattern detectors run by [`Self::analyze_patterns`].
+    #[must_use]
+    pub fn detectors(&self) -> &PatternRegistry {
+        &self.detectors
+    }
+
     /// Aggregate outcomes by a grouping key (e.g., action, context type).
+    ///
+    /// An outcome delivered more than once counts once, see [`dedup`].
     #[must_use]
     pub fn aggregate_outcomes(
         &self,
         outcomes: &[DecisionOutcome],
         key_fn: impl Fn(&DecisionOutcome) -> Option<String>,
-    ) -> HashMap<String, OutcomeStatistics> {
-        let mut stats: HashMap<String, OutcomeStatistics> = HashMap::new();
+    ) -> BTreeMap<String, OutcomeStatistics> {
+        let mut stats: BTreeMap<String, OutcomeStatistics> = BTreeMap::new();
 
-        for outcome in outcomes {
+        for outcome in dedup::deduplicate(outcomes).iter() {
             if let Some(key) = key_fn(outcome) {
-                stats.entry(key).or_default().record(outcome);
+                stats
+                    .entry(key)
+                    .or_default()
+                    .record_shaped(outcome, &self.reward_shaping);
             }
         }
 
@@ -293,8 +603,8 @@ impl FeedbackAnalyzer {
     fn summarize_outcomes(&self, outcomes: &[DecisionOutcome]) -> OutcomeStatistics {
         let mut stats = OutcomeStatistics::default();
 
-        for outcome in outcomes {
-            stats.record(outcome);
+        for outcome in dedup::deduplicate(outcomes).iter() {
+            stats.record_shaped(outcome, &self.reward_shaping);
         }
 
         stats
@@ -302,44 +612,24 @@ impl FeedbackAnalyzer {
 
     /// Analyze outcomes and identify patterns requiring weight adjustments.
     ///
-    /// This is a heuristic-based analysis (not ML-based initially).
+    /// Runs every detector of [`Self::detectors`] (see [`detect`]) on the
+    /// deduplicated outcomes; use [`Pattern::render`] for a sentence per
+    /// pattern.
     #[must_use]
-    pub fn analyze_patterns(&self, outcomes: &[DecisionOutcome]) -> Vec<String> {
-        let mut patterns = Vec::new();
-
+    pub fn analyze_patterns(&self, outcomes: &[DecisionOutcome]) -> Vec<Pattern> {
+        let outcomes = &*dedup::deduplicate(outcomes);
         if outcomes.len() < self.min_decisions {
-            return patterns;
-        }
-
-        // Aggregate by action
-        let by_action = self.aggregate_outcomes(outcomes, |o| o.action.clone());
-
-        // Pattern 1: Repeated failures for specific actions
-        for (action, stats) in &by_action {
-            if stats.total >= PATTERN_MIN_DECISIONS_PER_ACTION
-                && stats.failure_rate() > PATTERN_HIGH_FAILURE_THRESHOLD
-            {
-                patterns.push(format!(
-                    "High failure rate ({:.1}%) for action '{}'",
-                    stats.failure_rate() * 100.0,
-                    action
-                ));
-            }
-        }
-
-        // Pattern 2: Overall poor performance
-        let overall_stats = self.summarize_outcomes(outcomes);
-
-        if overall_stats.total >= self.min_decisions
-            && overall_stats.failure_rate() > PATTERN_OVERALL_FAILURE_THRESHOLD
-        {
-            patterns.push(format!(
-                "Overall failure rate is high ({:.1}%)",
-                overall_stats.failure_rate() * 100.0
-            ));
+            return Vec::new();
         }
+        self.run_detectors(outcomes, &index::OutcomeIndex::build(outcomes))
+    }
 
-        patterns
+    /// Run the pattern detectors on a pre-built [`index::OutcomeIndex`].
+    ///
+    /// Detectors that need the outcomes in order see an empty slice.
+    #[must_use]
+    pub fn analyze_index(&self, index: &index::OutcomeIndex) -> Vec<Pattern> {
+        self.run_detectors(&[], index)
     }
 
     /// Generate a weight adjustment proposal based on analyzed outcomes.
@@ -351,22 +641,65 @@ impl FeedbackAnalyzer {
         basis_policy: &str,
         outcomes: &[DecisionOutcome],
     ) -> Option<WeightAdjustmentProposal> {
+        self.propose_with_support(basis_policy, outcomes, 0)
+            .map(|(proposal, _)| proposal)
+    }
+
+    /// Shared body of [`Self::propose_adjustment`] and
+    /// [`Self::propose_with_samples`]; collects up to `sample_limit`
+    /// supporting decision ids per pattern.
+    fn propose_with_support(
+        &self,
+        basis_policy: &str,
+        outcomes: &[DecisionOutcome],
+        sample_limit: usize,
+    ) -> Option<(WeightAdjustmentProposal, Vec<support::PatternSupport>)> {
+        let outcomes = &*dedup::deduplicate(outcomes);
+        let anomalies = self
+            .anomaly_chart
+            .map(|chart| chart.detect(&self.bucket_outcomes(outcomes, trend::Window::Daily)))
+            .unwrap_or_default();
+        let kept;
+        let outcomes = if anomalies.is_empty() {
+            outcomes
+        } else {
+            kept = anomaly::exclude_anomalies(outcomes, &anomalies);
+            &kept
+        };
         if outcomes.len() < self.min_decisions {
             return None;
         }
 
-        let patterns = self.analyze_patterns(outcomes);
+        let index = index::OutcomeIndex::build(outcomes);
+        let mut patterns = self.run_detectors(outcomes, &index);
+
+        // Failures from a spike the policy has since recovered from say
+        // nothing about the current weights.
+        let recovered = self
+            .analyze_trend(outcomes, trend::Window::Daily)
+            .is_some_and(|t| t.recovered_below(ADJUSTMENT_FAILURE_THRESHOLD));
+        if recovered {
+            patterns.retain(|p| {
+                !matches!(
+                    p,
+                    Pattern::ActionFailures { .. }
+                        | Pattern::OverallFailures { .. }
+                        | Pattern::Seasonal { .. }
+                )
+            });
+        }
         if patterns.is_empty() {
             return None;
         }
 
-        let overall_stats = self.summarize_outcomes(outcomes);
+        let overall_stats = &index.overall;
 
         // Calculate confidence based on sample size and consistency
-        #[allow(clippy::cast_precision_loss)]
         let confidence = {
-            let sample_confidence =
-                (outcomes.len() as f32 / CONFIDENCE_SAMPLE_SIZE_PLATEAU).min(1.0);
+            // A narrow interval around the observed success rate means the
+            // sample is large enough to trust, whatever that rate is.
+            let (lower, upper) = overall_stats.success_rate_interval(CONFIDENCE_INTERVAL_LEVEL);
+            let sample_confidence = 1.0 - (upper - lower);
             let pattern_confidence = if patterns.len() >= 2 {
                 CONFIDENCE_HIGH_PATTERN
             } else {
@@ -382,11 +715,18 @@ impl FeedbackAnalyzer {
         }
 
         // Generate heuristic deltas
-        let mut deltas = HashMap::new();
-        let mut reasoning = Vec::new();
+        let mut deltas = BTreeMap::new();
+        let mut reasons = Vec::new();
+
+        if !anomalies.is_empty() {
+            reasons.push(Reason::ExcludedAnomalies {
+                days: anomalies.len(),
+                outcomes: anomalies.iter().map(|a| a.total).sum(),
+            });
+        }
 
         // If overall failure rate is high, suggest reducing exploration
-        if overall_stats.failure_rate() > ADJUSTMENT_FAILURE_THRESHOLD {
+        if !recovered && overall_stats.failure_rate() > ADJUSTMENT_FAILURE_THRESHOLD {
             deltas.insert(
                 "epsilon".to_string(),
                 DeltaValue::Relative {
@@ -394,13 +734,78 @@ impl FeedbackAnalyzer {
                     unit: "percent".to_string(),
                 },
             );
-            reasoning.push("Reduce exploration due to high failure rate".to_string());
+            reasons.push(Reason::ReduceExploration {
+                percent: ADJUSTMENT_EPSILON_DELTA_PERCENT,
+                failure_rate: overall_stats.failure_rate(),
+            });
+        }
+
+        // Shift consistently late actions earlier by their median latency; the
+        // target offset is absolute, as v1 proposals carry no additive deltas.
+        for (action, minutes) in slow_actions(&index) {
+            let target = (latency::fired_offset(outcomes, &action) - minutes.round()).max(0.0);
+            #[allow(clippy::cast_possible_truncation)]
+            deltas.insert(
+                timing_delta_key(&action),
+                DeltaValue::Absolute {
+                    value: target as f32,
+                },
+            );
+            reasons.push(Reason::FireEarlier { action, minutes });
+        }
+
+        // Reweight actions in the hours and weekdays they fail unusually.
+        for pattern in &patterns {
+            if let Pattern::Seasonal {
+                action,
+                season,
+                bucket,
+                rate,
+                baseline,
+            } = pattern
+            {
+                let percent = season::seasonal_percent(*rate, *baseline);
+                deltas.insert(
+                    season::seasonal_delta_key(action, *season, *bucket),
+                    DeltaValue::Relative {
+                        value: percent,
+                        unit: "percent".to_string(),
+                    },
+                );
+                reasons.push(Reason::Reweight {
+                    action: action.clone(),
+                    season: *season,
+                    bucket: *bucket,
+                    percent,
+                });
+            }
+        }
+
+        if overall_stats.censored > 0 && overall_stats.ignore_rate() > overall_stats.reject_rate() {
+            reasons.push(Reason::MissedNotDeclined {
+                ignore_rate: overall_stats.ignore_rate(),
+                reject_rate: overall_stats.reject_rate(),
+            });
         }
 
         let success_rate_after_sim =
             Self::simulate_delta_success_rate(&deltas, outcomes, overall_stats.success_rate());
         let failure_rate_after_sim = 1.0 - success_rate_after_sim;
-        Some(WeightAdjustmentProposal {
+        if !deltas.is_empty() {
+            reasons.push(Reason::Simulated {
+                before: overall_stats.failure_rate(),
+                after: failure_rate_after_sim,
+            });
+        }
+        let samples = if sample_limit == 0 {
+            Vec::new()
+        } else {
+            patterns
+                .iter()
+                .map(|p| support::PatternSupport::collect(p, outcomes, sample_limit, self.language))
+                .collect()
+        };
+        let proposal = WeightAdjustmentProposal {
             version: "v1".to_string(),
             basis_policy: basis_policy.to_string(),
             ts: iso8601_now(),
@@ -411,18 +816,83 @@ impl FeedbackAnalyzer {
                 failure_rate_before: Some(overall_stats.failure_rate()),
                 failure_rate_after_sim: Some(failure_rate_after_sim),
                 simulation_method: Some("reweight_epsilon_simulation".to_string()),
-                patterns: Some(patterns),
+                patterns: Some(patterns.iter().map(|p| p.render(self.language)).collect()),
             },
-            reasoning: Some(reasoning.join("; ")),
+            reasoning: (!reasons.is_empty()).then(|| explain::render(&reasons, self.language)),
             status: ProposalStatus::Proposed,
-        })
+        };
+        Some((proposal, samples))
+    }
+
+    /// Like [`Self::propose_adjustment`], plus up to
+    /// [`support::MAX_SUPPORTING_DECISIONS`] decision ids behind each
+    /// detected pattern, see [`support`].
+    #[must_use]
+    pub fn propose_with_samples(
+        &self,
+        basis_policy: &str,
+        outcomes: &[DecisionOutcome],
+    ) -> Option<(WeightAdjustmentProposal, support::EvidenceSamples)> {
+        self.propose_with_support(basis_policy, outcomes, support::MAX_SUPPORTING_DECISIONS)
+            .map(|(proposal, patterns)| (proposal, support::EvidenceSamples { patterns }))
+    }
+
+    /// Generate a proposal for the policy described by `descriptor`.
+    ///
+    /// Like [`Self::propose_adjustment`], but deltas whose key is not tunable
+    /// on the target policy, or whose absolute value lies outside the declared
+    /// range, are dropped before the proposal is emitted. Each dropped delta is
+    /// noted in the reasoning.
+    #[must_use]
+    pub fn propose_for(
+        &self,
+        descriptor: &PolicyDescriptor,
+        outcomes: &[DecisionOutcome],
+    ) -> Option<WeightAdjustmentProposal> {
+        let mut proposal = self.propose_adjustment(&descriptor.policy_id, outcomes)?;
+        let mut notes = Vec::new();
+        proposal
+            .deltas
+            .retain(|key, delta| match descriptor.range(key) {
+                None => {
+                    notes.push(Reason::DroppedUntunable {
+                        key: key.clone(),
+                        policy: descriptor.policy_id.clone(),
+                    });
+                    false
+                }
+                Some(range) => match delta {
+                    DeltaValue::Absolute { value } if !range.contains(f64::from(*value)) => {
+                        notes.push(Reason::DroppedOutOfRange {
+                            key: key.clone(),
+                            value: *value,
+                            min: range.min,
+                            max: range.max,
+                        });
+                        false
+                    }
+                    _ => true,
+                },
+            });
+        if !notes.is_empty() {
+            let mut reasoning: Vec<String> = proposal
+                .reasoning
+                .take()
+                .filter(|r| !r.is_empty())
+                .into_iter()
+                .collect();
+            reasoning.push(explain::render(&notes, self.language));
+            proposal.reasoning = Some(reasoning.join("; "));
+        }
+        Some(proposal)
     }
 
     /// Simulate applying proposed adjustments to historical outcomes.
     ///
     /// Returns estimated success rate with the proposed adjustments.
-    /// This is a simplified simulation - a real implementation would replay
-    /// decisions with modified weights.
+    /// This reweights the recorded explore/exploit outcomes and needs no
+    /// policy; when the policy's snapshot is at hand,
+    /// [`FeedbackAnalyzer::simulate_replay`] replays the decisions instead.
     ///
     /// # Parameter Semantics
     ///
@@ -434,7 +904,7 @@ impl FeedbackAnalyzer {
     ///     *   `DeltaValue::Relative { unit: "factor" }`: Scales the current exploration fraction
     ///         by `value`.
     fn simulate_delta_success_rate(
-        deltas: &HashMap<String, DeltaValue>,
+        deltas: &BTreeMap<String, DeltaValue>,
         outcomes: &[DecisionOutcome],
         baseline_success_rate: f32,
     ) -> f32 {
@@ -459,17 +929,42 @@ impl FeedbackAnalyzer {
         }
     }
 
+    /// Estimated success rate of `outcomes` under `proposal`. Like the
+    /// proposal itself, the estimate counts each decision once.
     #[must_use]
     pub fn simulate_adjustment(
         &self,
         proposal: &WeightAdjustmentProposal,
         outcomes: &[DecisionOutcome],
     ) -> f32 {
+        let outcomes = &*dedup::deduplicate(outcomes);
         let baseline_stats = self.summarize_outcomes(outcomes);
         Self::simulate_delta_success_rate(&proposal.deltas, outcomes, baseline_stats.success_rate())
     }
 }
 
+/// Actions with enough latency samples whose median exceeds
+/// [`PATTERN_SLOW_RESPONSE_SECONDS`], with that median in minutes.
+fn slow_actions(index: &index::OutcomeIndex) -> Vec<(String, f64)> {
+    index
+        .latency_report()
+        .by_action
+        .into_iter()
+        .filter(|(_, stats)| {
+            stats.count >= PATTERN_MIN_DECISIONS_PER_ACTION
+                && stats.p50_seconds > PATTERN_SLOW_RESPONSE_SECONDS
+        })
+        .map(|(action, stats)| (action, stats.p50_seconds / 60.0))
+        .collect()
+}
+
+/// Tunable timing offset of the slot behind `action`: the action without its
+/// namespace, as in `timing.evening.offset_minutes` for `remind.evening`.
+fn timing_delta_key(action: &str) -> String {
+    let slot = action.split_once('.').map_or(action, |(_, slot)| slot);
+    format!("timing.{slot}.offset_minutes")
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum Strategy {
     Explore,
@@ -635,7 +1130,7 @@ fn simulate_epsilon_absolute(outcomes: &[DecisionOutcome], target_epsilon: f32)
     )
 }
 
-fn outcome_is_success(outcome: &DecisionOutcome) -> bool {
+pub(crate) fn outcome_is_success(outcome: &DecisionOutcome) -> bool {
     match outcome.outcome {
         OutcomeType::Success => {
             debug_assert!(outcome.success, "Success outcome marked as unsuccessful");
@@ -646,6 +1141,7 @@ fn outcome_is_success(outcome: &DecisionOutcome) -> bool {
             false
         }
         OutcomeType::Partial | OutcomeType::Unknown => outcome.success,
+        OutcomeType::Censored | OutcomeType::Override => false,
     }
 }
 
@@ -698,6 +1194,7 @@ mod tests {
             successes: 7,
             failures: 3,
             total_reward: 5.0,
+            ..OutcomeStatistics::default()
         };
 
         #[allow(clippy::float_cmp)]
@@ -720,6 +1217,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn wilson_interval_narrows_with_sample_size() {
+        let failures = |n| OutcomeStatistics {
+            total: n,
+            failures: n,
+            ..OutcomeStatistics::default()
+        };
+        let (lower, small) = failures(5).success_rate_interval(0.95);
+        assert!(lower.abs() < 1e-6);
+        assert!((small - 0.434).abs() < 0.01, "{small}");
+        let (_, large) = failures(500).success_rate_interval(0.95);
+        assert!(large < 0.01, "{large}");
+        let (_, wider) = failures(5).success_rate_interval(0.99);
+        assert!(wider > small);
+        assert_eq!(
+            OutcomeStatistics::default().success_rate_interval(0.95),
+            (0.0, 1.0)
+        );
+    }
+
     #[test]
     fn outcome_statistics_record_counts_outcome() {
         let mut stats = OutcomeStatistics::default();
@@ -736,6 +1253,148 @@ mod tests {
         }
     }
 
+    #[test]
+    fn analyzer_shapes_rewards_with_decision_latency() {
+        let mut late = create_outcome("1", "remind.morning", true, 1.0, None);
+        late.ts = "2026-01-01T08:30:00Z".into();
+        late.metadata = Some(serde_json::json!({ "decision_ts": "2026-01-01T08:00:00Z" }));
+        let high = create_outcome("2", "remind.morning", true, 3.0, None);
+
+        let analyzer = FeedbackAnalyzer::builder()
+            .reward_shaping(RewardShaping::Chain {
+                steps: vec![
+                    RewardShaping::DelayDiscount {
+                        half_life_seconds: 1800.0,
+                    },
+                    RewardShaping::Clip { min: 0.0, max: 1.0 },
+                ],
+            })
+            .build();
+        let stats = analyzer.aggregate_outcomes(&[late, high], |o| o.action.clone());
+        let shaped = &stats["remind.morning"];
+        assert!((shaped.total_reward - 1.5).abs() < 1e-6);
+        assert!((shaped.average_reward() - 0.75).abs() < 1e-6);
+    }
+
+    #[test]
+    fn censored_outcomes_are_counted_separately() {
+        let mut stats = OutcomeStatistics::default();
+        stats.record(&create_outcome("1", "a", true, 1.0, None));
+        stats.record(&create_outcome("2", "a", false, 0.0, None));
+        stats.record(&DecisionOutcome::censored(
+            "3",
+            iso8601_now(),
+            Some("a".into()),
+        ));
+        stats.record(&DecisionOutcome::censored(
+            "4",
+            iso8601_now(),
+            Some("a".into()),
+        ));
+
+        assert_eq!(stats.total, 2);
+        assert_eq!(stats.censored, 2);
+        assert_eq!(stats.observed(), 4);
+        #[allow(clippy::float_cmp)]
+        {
+            assert_eq!(stats.failure_rate(), 0.5);
+            assert_eq!(stats.ignore_rate(), 0.5);
+            assert_eq!(stats.reject_rate(), 0.25);
+        }
+    }
+
+    #[test]
+    fn analyzer_flags_ignored_actions_and_explains_in_reasoning() {
+        let analyzer = FeedbackAnalyzer::new(5, 0.0);
+        let mut outcomes: Vec<DecisionOutcome> = (0..8)
+            .map(|i| {
+                DecisionOutcome::censored(
+                    i.to_string(),
+                    iso8601_now(),
+                    Some("remind.morning".into()),
+                )
+            })
+            .collect();
+        outcomes.push(create_outcome("8", "remind.morning", false, 0.0, None));
+        outcomes.push(create_outcome("9", "remind.morning", true, 1.0, None));
+
+        let patterns = analyzer.analyze_patterns(&outcomes);
+        assert!(patterns
+            .iter()
+            .any(|p| p.to_string().contains("High ignore rate (80.0%)")));
+        assert!(!patterns
+            .iter()
+            .any(|p| p.to_string().contains("failure rate")));
+
+        let proposal = analyzer
+            .propose_adjustment("remind-bandit", &outcomes)
+            .expect("ignore pattern should yield a proposal");
+        assert!(proposal.deltas.is_empty());
+        assert!(proposal
+            .reasoning
+            .as_deref()
+            .unwrap()
+            .contains("exceeds reject rate"));
+    }
+
+    #[test]
+    fn analyzer_reports_frequent_vetoes() {
+        let analyzer = FeedbackAnalyzer::new(4, 0.0);
+        let outcomes: Vec<DecisionOutcome> = (0..4)
+            .map(|i| {
+                let mut o = create_outcome(&i.to_string(), "remind.none", true, 1.0, None);
+                if i < 2 {
+                    veto::VetoRecord {
+                        wanted: "remind.evening".into(),
+                        emitted: "remind.none".into(),
+                        constraint: Some("quiet_hours".into()),
+                    }
+                    .attach(&mut o);
+                }
+                o
+            })
+            .collect();
+
+        let patterns = analyzer.analyze_patterns(&outcomes);
+        assert!(patterns.iter().any(|p| p.to_string()
+            == "Constraints vetoed the learner in 50.0% of decisions, mostly 'quiet_hours'"));
+    }
+
+    #[test]
+    fn overrides_are_counted_separately_and_flagged() {
+        let mut outcomes: Vec<DecisionOutcome> = (0..8)
+            .map(|i| create_outcome(&format!("s{i}"), "remind.morning", true, 1.0, None))
+            .collect();
+        for i in 0..4 {
+            outcomes.push(DecisionOutcome::overridden(
+                format!("o{i}"),
+                iso8601_now(),
+                "remind.evening",
+                Some("remind.morning".into()),
+            ));
+        }
+        outcomes.push(create_outcome("e", "remind.evening", true, 1.0, None));
+
+        let index = index::OutcomeIndex::build(&outcomes);
+        let evening = &index.by_action["remind.evening"];
+        assert_eq!(evening.overrides, 4);
+        assert_eq!(evening.total, 1);
+        assert_eq!(evening.failures, 0);
+        assert!((evening.override_rate() - 0.8).abs() < 1e-6);
+
+        let patterns = FeedbackAnalyzer::new(10, 0.5).analyze_patterns(&outcomes);
+        assert!(patterns.iter().any(
+            |p| matches!(p, Pattern::ActionOverridden { action, .. } if action == "remind.evening")
+        ));
+    }
+
+    #[test]
+    fn censored_outcome_roundtrips_as_lowercase() {
+        let outcome = DecisionOutcome::censored("x", "2026-01-01T00:00:00Z", None);
+        let json = serde_json::to_value(&outcome).unwrap();
+        assert_eq!(json["outcome"], "censored");
+    }
+
     #[test]
     fn analyzer_aggregates_outcomes_by_action() {
         let analyzer = FeedbackAnalyzer::default();
@@ -765,7 +1424,9 @@ mod tests {
         let patterns = analyzer.analyze_patterns(&outcomes);
 
         assert!(!patterns.is_empty());
-        assert!(patterns.iter().any(|p| p.contains("High failure rate")));
+        assert!(patterns
+            .iter()
+            .any(|p| p.to_string().contains("High failure rate")));
     }
 
     #[test]
@@ -791,7 +1452,7 @@ mod tests {
 
         assert!(patterns
             .iter()
-            .any(|p| p.contains("Overall failure rate is high")));
+            .any(|p| p.to_string().contains("Overall failure rate is high")));
     }
 
     #[test]
@@ -831,6 +1492,55 @@ mod tests {
         assert!(proposal.confidence >= 0.5);
     }
 
+    #[test]
+    fn propose_for_drops_deltas_unknown_to_the_descriptor() {
+        let analyzer = FeedbackAnalyzer::new(10, 0.5);
+        let outcomes: Vec<DecisionOutcome> = (0..15)
+            .map(|i| create_outcome(&i.to_string(), "remind.morning", i % 3 == 0, 0.0, None))
+            .collect();
+
+        let bandit = PolicyDescriptor::new("remind-bandit").tunable("epsilon", 0.0, 1.0);
+        let proposal = analyzer.propose_for(&bandit, &outcomes).unwrap();
+        assert_eq!(proposal.basis_policy, "remind-bandit");
+        assert!(proposal.deltas.contains_key("epsilon"));
+
+        let fixed = PolicyDescriptor::new("fixed-schedule").tunable("offset_minutes", 0.0, 60.0);
+        let proposal = analyzer.propose_for(&fixed, &outcomes).unwrap();
+        assert!(proposal.deltas.is_empty());
+        assert!(proposal
+            .reasoning
+            .unwrap()
+            .contains("Dropped delta 'epsilon': not tunable on 'fixed-schedule'"));
+    }
+
+    #[test]
+    fn reasoning_is_rendered_from_structured_evidence_in_both_languages() {
+        let outcomes: Vec<DecisionOutcome> = (0..15)
+            .map(|i| create_outcome(&i.to_string(), "remind.morning", i % 3 == 0, 0.0, None))
+            .collect();
+
+        let english = FeedbackAnalyzer::new(10, 0.5)
+            .propose_adjustment("remind-bandit", &outcomes)
+            .unwrap();
+        let reasoning = english.reasoning.unwrap();
+        assert!(reasoning.starts_with("Reduce exploration by 5% because 66.7% of decisions failed"));
+        assert!(reasoning.contains("Simulated failure rate: 66.7% -> "));
+
+        let german = FeedbackAnalyzer::new(10, 0.5)
+            .with_language(Language::De)
+            .propose_adjustment("remind-bandit", &outcomes)
+            .unwrap();
+        assert!(german
+            .reasoning
+            .unwrap()
+            .starts_with("Exploration um 5 % senken, weil 66,7 % der Entscheidungen scheiterten"));
+        assert!(german
+            .evidence
+            .patterns
+            .unwrap()
+            .contains(&"Die Gesamt-Fehlerquote ist hoch (66,7 %)".to_string()));
+    }
+
     #[test]
     fn proposal_simulation_consistent() {
         let analyzer = FeedbackAnalyzer::new(10, 0.5);
@@ -862,7 +1572,7 @@ mod tests {
             basis_policy: "test-policy".to_string(),
             ts: iso8601_now(),
             deltas: {
-                let mut map = HashMap::new();
+                let mut map = BTreeMap::new();
                 map.insert(
                     "epsilon".to_string(),
                     DeltaValue::Relative {
@@ -912,7 +1622,7 @@ mod tests {
             .collect();
 
         // Suggest reducing epsilon by 0.1
-        let mut deltas = HashMap::new();
+        let mut deltas = BTreeMap::new();
         deltas.insert("epsilon".to_string(), DeltaValue::Additive { value: -0.1 });
 
         let proposal = WeightAdjustmentProposal {
@@ -960,7 +1670,7 @@ mod tests {
             })
             .collect();
 
-        let mut deltas = HashMap::new();
+        let mut deltas = BTreeMap::new();
         deltas.insert(
             "epsilon".to_string(),
             DeltaValue::Relative {
@@ -1010,7 +1720,7 @@ mod tests {
         // Current Explore (Failure) = 0.5. Current Exploit (Success) = 0.5.
         // New Explore = 0.4. New Exploit = 0.6.
         // Expected Success = 0.6 (Exploit) * 1.0 (Success Rate) + 0.4 (Explore) * 0.0 (Success Rate) = 0.6.
-        let mut deltas = HashMap::new();
+        let mut deltas = BTreeMap::new();
         deltas.insert("epsilon".to_string(), DeltaValue::Absolute { value: 0.4 });
 
         let proposal = WeightAdjustmentProposal {
@@ -1051,7 +1761,7 @@ mod tests {
             })
             .collect();
 
-        let mut deltas = HashMap::new();
+        let mut deltas = BTreeMap::new();
         // Set epsilon > 1.0, should clamp to 1.0
         // If clamped to 1.0, all weight goes to Explore (Failure) -> Rate 0.0
         deltas.insert("epsilon".to_string(), DeltaValue::Absolute { value: 1.5 });
@@ -1237,7 +1947,7 @@ mod tests {
             .collect();
 
         // Increase epsilon by 0.1 (more explore)
-        let mut deltas = HashMap::new();
+        let mut deltas = BTreeMap::new();
         deltas.insert("epsilon".to_string(), DeltaValue::Additive { value: 0.1 });
 
         let proposal = WeightAdjustmentProposal {
@@ -1280,7 +1990,7 @@ mod tests {
             .collect();
 
         // Huge delta to force clamp
-        let mut deltas = HashMap::new();
+        let mut deltas = BTreeMap::new();
         deltas.insert("epsilon".to_string(), DeltaValue::Additive { value: 10.0 });
 
         let proposal = WeightAdjustmentProposal {
@@ -1315,7 +2025,7 @@ mod tests {
             })
             .collect();
 
-        let mut deltas = HashMap::new();
+        let mut deltas = BTreeMap::new();
         deltas.insert("epsilon".to_string(), DeltaValue::Additive { value: -0.1 });
         let proposal = WeightAdjustmentProposal {
             version: "legacy-simulation".to_string(),
@@ -1365,7 +2075,7 @@ mod tests {
         // Total Success = 16.
         // Total Rate = 16 / 20 = 0.8.
 
-        let mut deltas = HashMap::new();
+        let mut deltas = BTreeMap::new();
         deltas.insert("epsilon".to_string(), DeltaValue::Additive { value: -0.1 });
         let proposal = WeightAdjustmentProposal {
             version: "legacy-simulation".to_string(),
@@ -1385,4 +2095,56 @@ mod tests {
             simulated_rate
         );
     }
+
+    #[test]
+    fn late_acknowledgements_drive_timing_proposal() {
+        use heimlern_bandits::{RemindBandit, TimingConfig};
+        use heimlern_core::Policy;
+
+        let analyzer = FeedbackAnalyzer::new(10, 0.3);
+        let outcomes: Vec<_> = (0..12)
+            .map(|i| {
+                let mut outcome = create_outcome(&i.to_string(), "remind.evening", true, 1.0, None);
+                outcome.ts = format!("2026-01-{:02}T19:40:00Z", i + 1);
+                outcome.metadata = Some(serde_json::json!({
+                    "decision_ts": format!("2026-01-{:02}T19:00:00Z", i + 1),
+                    "offset_minutes": 50.0
+                }));
+                outcome
+            })
+            .collect();
+
+        let patterns = analyzer.analyze_patterns(&outcomes);
+        assert!(patterns.iter().any(|p| p
+            .to_string()
+            .contains("'remind.evening' is acknowledged 40 minutes late")));
+
+        let proposal = analyzer
+            .propose_adjustment("remind-bandit", &outcomes)
+            .expect("latency pattern should yield a proposal");
+        // Fired 50 minutes into the slot and acknowledged 40 minutes later.
+        match proposal.deltas.get("timing.evening.offset_minutes") {
+            Some(DeltaValue::Absolute { value }) => assert!((value - 10.0).abs() < 1e-6),
+            other => panic!("unexpected timing delta: {other:?}"),
+        }
+        assert!(!proposal.deltas.contains_key("epsilon"));
+        let value = serde_json::to_value(&proposal).expect("proposal json");
+        heimlern_core::contracts::validate(
+            heimlern_core::contracts::Schema::WeightAdjustment,
+            &value,
+        )
+        .expect("timing proposal matches the weight_adjustment contract");
+
+        let bandit = RemindBandit::builder()
+            .timing(TimingConfig::default())
+            .build()
+            .expect("default timing is valid");
+        let tuned = analyzer
+            .propose_for(&bandit.descriptor(), &outcomes)
+            .expect("the timing delta is tunable on the remind bandit");
+        assert!(matches!(
+            tuned.deltas.get("timing.evening.offset_minutes"),
+            Some(DeltaValue::Absolute { value }) if (value - 10.0).abs() < 1e-6
+        ));
+    }
 }
@@ -0,0 +1,144 @@
+//! Snapshot lineage: an append-only record of what happened to a snapshot.
+//!
+//! `policy.snapshot` is a closed schema, so history cannot live inside the
+//! snapshot. Like proposal provenance it travels as a sidecar: every
+//! operation that rewrites a snapshot outside of normal learning (compaction,
+//! imported priors, …) appends one [`LineageEntry`] to
+//! `<snapshot>.lineage.jsonl`.
+
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// Errors raised while reading or writing a lineage file.
+#[derive(Debug, thiserror::Error)]
+pub enum LineageError {
+    #[error("lineage I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid lineage entry on line {line}: {source}")]
+    Parse {
+        line: usize,
+        source: serde_json::Error,
+    },
+    #[error("lineage entry serialization failed: {0}")]
+    Serialize(#[from] serde_json::Error),
+}
+
+/// One operation applied to a snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LineageEntry {
+    /// When the operation ran (RFC 3339).
+    pub ts: String,
+    /// Operation name, e.g. `"compact"`.
+    pub operation: String,
+    /// Policy whose snapshot was changed.
+    pub policy_id: String,
+    /// Operation-specific details.
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub details: serde_json::Value,
+}
+
+impl LineageEntry {
+    /// Entry for `operation` on `policy_id`, stamped with the current time.
+    #[must_use]
+    pub fn now(operation: impl Into<String>, policy_id: impl Into<String>) -> Self {
+        Self {
+            ts: crate::iso8601_now(),
+            operation: operation.into(),
+            policy_id: policy_id.into(),
+            details: serde_json::Value::Null,
+        }
+    }
+
+    /// Attach operation-specific details.
+    #[must_use]
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = details;
+        self
+    }
+}
+
+/// Sidecar path for the lineage of the snapshot at `snapshot`.
+#[must_use]
+pub fn lineage_path(snapshot: &Path) -> PathBuf {
+    let mut name = snapshot.file_name().unwrap_or_default().to_os_string();
+    name.push(".lineage.jsonl");
+    snapshot.with_file_name(name)
+}
+
+/// Append `entry` to the lineage file at `path`, creating it if needed.
+///
+/// # Errors
+/// Fails if the entry cannot be serialized or the file cannot be written.
+pub fn append_lineage(path: &Path, entry: &LineageEntry) -> Result<(), LineageError> {
+    let line = serde_json::to_string(entry)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")?;
+    Ok(())
+}
+
+/// Read all entries of the lineage file at `path`, oldest first.
+///
+/// A missing file is an empty lineage.
+///
+/// # Errors
+/// Fails on I/O errors or malformed lines.
+pub fn read_lineage(path: &Path) -> Result<Vec<LineageEntry>, LineageError> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut entries = Vec::new();
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = serde_json::from_str(&line).map_err(|source| LineageError::Parse {
+            line: i + 1,
+            source,
+        })?;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::fs;
+
+    #[test]
+    fn sidecar_round_trips_entries_in_order() {
+        let dir = std::env::temp_dir().join(format!("heimlern-lineage-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let snapshot = dir.join("remind.snapshot.json");
+        let path = lineage_path(&snapshot);
+        assert_eq!(path, dir.join("remind.snapshot.json.lineage.jsonl"));
+        assert!(read_lineage(&path).unwrap().is_empty());
+
+        let compact = LineageEntry::now("compact", "remind-bandit")
+            .with_details(json!({ "arms_before": 5, "arms_after": 3 }));
+        let priors = LineageEntry::now("import_priors", "remind-bandit");
+        append_lineage(&path, &compact).unwrap();
+        append_lineage(&path, &priors).unwrap();
+        assert_eq!(read_lineage(&path).unwrap(), vec![compact, priors]);
+        // Entries without details do not write a `details: null`.
+        assert!(!fs::read_to_string(&path)
+            .unwrap()
+            .contains("details\":null"));
+
+        fs::write(&path, "{\"ts\": 1}\n").unwrap();
+        assert!(matches!(
+            read_lineage(&path),
+            Err(LineageError::Parse { line: 1, .. })
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
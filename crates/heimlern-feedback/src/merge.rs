@@ -0,0 +1,204 @@
+//! Merging outcome logs collected on several machines.
+//!
+//! When a laptop and the home server both record outcomes, their logs overlap
+//! and interleave. [`merge_outcomes`] combines any number of logs into one,
+//! ordered by timestamp, keeping a single record per `(decision_id, source)`.
+//! The source is read from `metadata.source`; outcomes without one share the
+//! empty source and are therefore deduplicated by decision id alone.
+//!
+//! When two records for the same key differ, precedence decides which one is
+//! kept:
+//! 1. a human `override` beats a resolved outcome (success, failure,
+//!    partial), which beats `unknown`, which beats `censored`;
+//! 2. otherwise the record with the later timestamp wins;
+//! 3. otherwise the record from the earlier input wins.
+
+use crate::{DecisionOutcome, OutcomeType};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+/// Metadata key naming the machine or service that recorded an outcome.
+pub const SOURCE_METADATA_KEY: &str = "source";
+
+/// Recording source of an outcome, if any.
+#[must_use]
+pub fn outcome_source(outcome: &DecisionOutcome) -> Option<&str> {
+    outcome
+        .metadata
+        .as_ref()?
+        .get(SOURCE_METADATA_KEY)?
+        .as_str()
+        .filter(|source| !source.is_empty())
+}
+
+/// Counters describing a merge.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct MergeStats {
+    /// Number of input logs.
+    pub inputs: usize,
+    /// Outcomes read across all inputs.
+    pub read: usize,
+    /// Outcomes in the merged log.
+    pub merged: usize,
+    /// Exact copies that were dropped.
+    pub duplicates: usize,
+    /// Differing records for the same key that were resolved by precedence.
+    pub conflicts: usize,
+    /// Merged outcomes whose timestamp could not be parsed; they are placed last.
+    pub unparseable_ts: usize,
+}
+
+fn resolution_rank(outcome: &DecisionOutcome) -> u8 {
+    match outcome.outcome {
+        OutcomeType::Override => 3,
+        OutcomeType::Success | OutcomeType::Failure | OutcomeType::Partial => 2,
+        OutcomeType::Unknown => 1,
+        OutcomeType::Censored => 0,
+    }
+}
+
+fn parse_ts(outcome: &DecisionOutcome) -> Option<OffsetDateTime> {
+    OffsetDateTime::parse(&outcome.ts, &Rfc3339).ok()
+}
+
+/// Whether `candidate` takes precedence over `kept` (which came from an
+/// earlier or the same input).
+pub(crate) fn supersedes(candidate: &DecisionOutcome, kept: &DecisionOutcome) -> bool {
+    let (new_rank, old_rank) = (resolution_rank(candidate), resolution_rank(kept));
+    if new_rank != old_rank {
+        return new_rank > old_rank;
+    }
+    match (parse_ts(candidate), parse_ts(kept)) {
+        (Some(new_ts), Some(old_ts)) => new_ts > old_ts,
+        (Some(_), None) => true,
+        _ => false,
+    }
+}
+
+/// Merges `logs` into one timestamp-ordered log without duplicates.
+///
+/// Outcomes with equal timestamps keep their input order.
+#[must_use]
+pub fn merge_outcomes(logs: Vec<Vec<DecisionOutcome>>) -> (Vec<DecisionOutcome>, MergeStats) {
+    let mut stats = MergeStats {
+        inputs: logs.len(),
+        ..MergeStats::default()
+    };
+    let mut order: Vec<(String, String)> = Vec::new();
+    let mut kept: BTreeMap<(String, String), DecisionOutcome> = BTreeMap::new();
+
+    for outcome in logs.into_iter().flatten() {
+        stats.read += 1;
+        let key = (
+            outcome.decision_id.clone(),
+            outcome_source(&outcome).unwrap_or_default().to_string(),
+        );
+        let Some(existing) = kept.get_mut(&key) else {
+            order.push(key.clone());
+            kept.insert(key, outcome);
+            continue;
+        };
+        if serde_json::to_value(&*existing).ok() == serde_json::to_value(&outcome).ok() {
+            stats.duplicates += 1;
+            continue;
+        }
+        stats.conflicts += 1;
+        if supersedes(&outcome, existing) {
+            *existing = outcome;
+        }
+    }
+
+    let mut merged: Vec<(Option<OffsetDateTime>, DecisionOutcome)> = order
+        .iter()
+        .filter_map(|key| kept.remove(key))
+        .map(|outcome| (parse_ts(&outcome), outcome))
+        .collect();
+    // `None` sorts before `Some`, so unparseable timestamps are moved to the end explicitly.
+    merged.sort_by(|(a, _), (b, _)| match (a, b) {
+        (Some(a), Some(b)) => a.cmp(b),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+
+    stats.merged = merged.len();
+    stats.unparseable_ts = merged.iter().filter(|(ts, _)| ts.is_none()).count();
+    (
+        merged.into_iter().map(|(_, outcome)| outcome).collect(),
+        stats,
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn outcome(id: &str, ts: &str, kind: &str, source: Option<&str>) -> DecisionOutcome {
+        let mut value = json!({
+            "decision_id": id,
+            "ts": ts,
+            "outcome": kind,
+            "success": kind == "success",
+        });
+        if let Some(source) = source {
+            value["metadata"] = json!({ "source": source });
+        }
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn merges_sorted_and_deduplicated() {
+        let laptop = vec![
+            outcome("a", "2026-01-01T10:00:00Z", "success", Some("laptop")),
+            outcome("b", "2026-01-01T08:00:00Z", "failure", None),
+        ];
+        let server = vec![
+            outcome("a", "2026-01-01T10:00:00Z", "success", Some("laptop")),
+            outcome("a", "2026-01-01T09:00:00Z", "success", Some("server")),
+            outcome("c", "not-a-timestamp", "success", None),
+        ];
+        let (merged, stats) = merge_outcomes(vec![laptop, server]);
+
+        let ids: Vec<(&str, Option<&str>)> = merged
+            .iter()
+            .map(|o| (o.decision_id.as_str(), outcome_source(o)))
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                ("b", None),
+                ("a", Some("server")),
+                ("a", Some("laptop")),
+                ("c", None)
+            ]
+        );
+        assert_eq!(stats.read, 5);
+        assert_eq!(stats.merged, 4);
+        assert_eq!(stats.duplicates, 1);
+        assert_eq!(stats.conflicts, 0);
+        assert_eq!(stats.unparseable_ts, 1);
+    }
+
+    #[test]
+    fn conflicts_prefer_resolved_then_later_records() {
+        let first = vec![
+            outcome("a", "2026-01-01T10:00:00Z", "success", None),
+            outcome("b", "2026-01-01T10:00:00Z", "failure", None),
+        ];
+        let second = vec![
+            outcome("a", "2026-01-01T12:00:00Z", "censored", None),
+            outcome("b", "2026-01-01T11:00:00Z", "success", None),
+        ];
+        let (merged, stats) = merge_outcomes(vec![first, second]);
+
+        assert_eq!(stats.conflicts, 2);
+        assert_eq!(merged[0].outcome, OutcomeType::Success);
+        assert_eq!(merged[0].decision_id, "a");
+        assert_eq!(merged[1].decision_id, "b");
+        assert_eq!(merged[1].ts, "2026-01-01T11:00:00Z");
+    }
+}
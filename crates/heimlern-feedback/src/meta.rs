@@ -0,0 +1,373 @@
+//! Meta-feedback: tuning the analyzer by the fate of its own proposals.
+//!
+//! Every proposal eventually meets one of three fates: it is accepted and the
+//! policy improves, it is accepted and later rolled back, or a reviewer rejects
+//! it. [`MetaTuner`] treats those fates as rewards for the
+//! [`SensitivityProfile`] that produced the proposal and picks the profile for
+//! the next analysis with UCB1 — a deterministic bandit, so the same fate
+//! history always leads to the same choice.
+//!
+//! Each selection and each recorded fate is appended to the tuner's log, and
+//! [`MetaTuner::record`] returns the fate as a regular [`DecisionOutcome`] of
+//! the [`META_POLICY_ID`] policy, so it can go into the same outcome ledger as
+//! every other decision.
+
+use crate::{DecisionOutcome, FeedbackAnalyzer, OutcomeType};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Policy id under which proposal fates are recorded as outcomes.
+pub const META_POLICY_ID: &str = "feedback-analyzer";
+
+/// Default weight of the UCB1 exploration bonus.
+pub const DEFAULT_EXPLORATION: f64 = std::f64::consts::SQRT_2;
+
+/// Errors raised by the [`MetaTuner`].
+#[derive(Debug, thiserror::Error)]
+pub enum MetaError {
+    #[error("unknown sensitivity profile '{0}'")]
+    UnknownProfile(String),
+    #[error("meta tuner has no profiles")]
+    NoProfiles,
+}
+
+/// What happened to a proposal after it was emitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalFate {
+    /// Accepted, and the policy performed better afterwards.
+    Improved,
+    /// Accepted, but reverted because it made things worse.
+    RolledBack,
+    /// Rejected during review; cost attention, did no harm.
+    Rejected,
+}
+
+impl ProposalFate {
+    /// Reward credited to the profile that produced the proposal.
+    #[must_use]
+    pub fn reward(self) -> f32 {
+        match self {
+            Self::Improved => 1.0,
+            Self::Rejected => 0.25,
+            Self::RolledBack => 0.0,
+        }
+    }
+}
+
+impl fmt::Display for ProposalFate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Improved => "improved",
+            Self::RolledBack => "rolled_back",
+            Self::Rejected => "rejected",
+        })
+    }
+}
+
+impl FromStr for ProposalFate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('-', "_").as_str() {
+            "improved" => Ok(Self::Improved),
+            "rolled_back" => Ok(Self::RolledBack),
+            "rejected" => Ok(Self::Rejected),
+            other => Err(format!(
+                "unknown fate '{other}' (expected improved, rolled_back or rejected)"
+            )),
+        }
+    }
+}
+
+/// Named analyzer thresholds the meta tuner chooses between.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SensitivityProfile {
+    pub name: String,
+    /// See [`FeedbackAnalyzer::new`].
+    pub min_decisions: usize,
+    /// See [`FeedbackAnalyzer::new`].
+    pub min_confidence: f32,
+}
+
+impl SensitivityProfile {
+    #[must_use]
+    pub fn new(name: impl Into<String>, min_decisions: usize, min_confidence: f32) -> Self {
+        Self {
+            name: name.into(),
+            min_decisions,
+            min_confidence,
+        }
+    }
+
+    /// Analyzer configured with this profile's thresholds.
+    #[must_use]
+    pub fn analyzer(&self) -> FeedbackAnalyzer {
+        FeedbackAnalyzer::new(self.min_decisions, self.min_confidence)
+    }
+}
+
+/// Built-in profiles, from conservative to eager; `balanced` matches
+/// [`FeedbackAnalyzer::default`].
+#[must_use]
+pub fn default_profiles() -> Vec<SensitivityProfile> {
+    vec![
+        SensitivityProfile::new("cautious", 30, 0.7),
+        SensitivityProfile::new("balanced", 10, 0.5),
+        SensitivityProfile::new("eager", 5, 0.3),
+    ]
+}
+
+/// Accumulated fate rewards of one profile.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProfileStats {
+    pub pulls: u64,
+    pub total_reward: f64,
+}
+
+impl ProfileStats {
+    /// Mean reward (0.0 before the first fate).
+    #[must_use]
+    pub fn mean(&self) -> f64 {
+        if self.pulls == 0 {
+            return 0.0;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        {
+            self.total_reward / self.pulls as f64
+        }
+    }
+}
+
+/// One audit log entry of the meta tuner.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetaEntry {
+    /// When the entry was written (RFC 3339).
+    pub ts: String,
+    pub profile: String,
+    #[serde(flatten)]
+    pub event: MetaEvent,
+}
+
+/// What a [`MetaEntry`] records.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum MetaEvent {
+    /// The profile was chosen for the next analysis with this UCB score
+    /// (`None` while it had not been tried yet).
+    Selected { score: Option<f64> },
+    /// A proposal produced with the profile met `fate`.
+    Fate {
+        proposal: String,
+        fate: ProposalFate,
+        reward: f32,
+    },
+}
+
+/// Bandit over [`SensitivityProfile`]s, rewarded by [`ProposalFate`]s.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetaTuner {
+    pub profiles: Vec<SensitivityProfile>,
+    /// Weight of the UCB1 exploration bonus.
+    #[serde(default = "default_exploration")]
+    pub exploration: f64,
+    #[serde(default)]
+    stats: BTreeMap<String, ProfileStats>,
+    /// Append-only audit log, oldest first.
+    #[serde(default)]
+    log: Vec<MetaEntry>,
+}
+
+fn default_exploration() -> f64 {
+    DEFAULT_EXPLORATION
+}
+
+impl Default for MetaTuner {
+    fn default() -> Self {
+        Self::new(default_profiles())
+    }
+}
+
+impl MetaTuner {
+    #[must_use]
+    pub fn new(profiles: Vec<SensitivityProfile>) -> Self {
+        Self {
+            profiles,
+            exploration: DEFAULT_EXPLORATION,
+            stats: BTreeMap::new(),
+            log: Vec::new(),
+        }
+    }
+
+    /// Statistics of `profile` (zeroed if it has no fates yet).
+    #[must_use]
+    pub fn stats(&self, profile: &str) -> ProfileStats {
+        self.stats.get(profile).copied().unwrap_or_default()
+    }
+
+    /// Audit log, oldest first.
+    #[must_use]
+    pub fn log(&self) -> &[MetaEntry] {
+        &self.log
+    }
+
+    /// Choose the profile for the next analysis and log the choice.
+    ///
+    /// Untried profiles come first, in declaration order; afterwards the
+    /// profile with the highest UCB1 score wins, ties going to the earlier one.
+    ///
+    /// # Errors
+    /// [`MetaError::NoProfiles`] if the tuner has no profiles.
+    pub fn select(&mut self) -> Result<SensitivityProfile, MetaError> {
+        let untried = self
+            .profiles
+            .iter()
+            .find(|p| self.stats(&p.name).pulls == 0);
+        let (profile, score) = match untried {
+            Some(profile) => (profile.clone(), None),
+            None => {
+                let total: u64 = self
+                    .profiles
+                    .iter()
+                    .map(|p| self.stats(&p.name).pulls)
+                    .sum();
+                #[allow(clippy::cast_precision_loss)]
+                let ln_total = (total.max(1) as f64).ln();
+                let mut best: Option<(&SensitivityProfile, f64)> = None;
+                for profile in &self.profiles {
+                    let stats = self.stats(&profile.name);
+                    #[allow(clippy::cast_precision_loss)]
+                    let bonus = self.exploration * (ln_total / stats.pulls as f64).sqrt();
+                    let score = stats.mean() + bonus;
+                    if best.is_none_or(|(_, b)| score > b) {
+                        best = Some((profile, score));
+                    }
+                }
+                let (profile, score) = best.ok_or(MetaError::NoProfiles)?;
+                (profile.clone(), Some(score))
+            }
+        };
+        self.log.push(MetaEntry {
+            ts: crate::iso8601_now(),
+            profile: profile.name.clone(),
+            event: MetaEvent::Selected { score },
+        });
+        Ok(profile)
+    }
+
+    /// Credit `fate` of proposal `proposal` to `profile` and log it.
+    ///
+    /// Returns the fate as an outcome of [`META_POLICY_ID`]: the action is the
+    /// profile name, only [`ProposalFate::Improved`] counts as success.
+    ///
+    /// # Errors
+    /// [`MetaError::UnknownProfile`] if `profile` is not one of the tuner's profiles.
+    pub fn record(
+        &mut self,
+        proposal: &str,
+        profile: &str,
+        fate: ProposalFate,
+    ) -> Result<DecisionOutcome, MetaError> {
+        if !self.profiles.iter().any(|p| p.name == profile) {
+            return Err(MetaError::UnknownProfile(profile.to_string()));
+        }
+        let reward = fate.reward();
+        let stats = self.stats.entry(profile.to_string()).or_default();
+        stats.pulls = stats.pulls.saturating_add(1);
+        stats.total_reward += f64::from(reward);
+
+        let ts = crate::iso8601_now();
+        self.log.push(MetaEntry {
+            ts: ts.clone(),
+            profile: profile.to_string(),
+            event: MetaEvent::Fate {
+                proposal: proposal.to_string(),
+                fate,
+                reward,
+            },
+        });
+
+        let success = fate == ProposalFate::Improved;
+        Ok(DecisionOutcome {
+            decision_id: proposal.to_string(),
+            ts,
+            policy_id: Some(META_POLICY_ID.to_string()),
+            action: Some(profile.to_string()),
+            outcome: if success {
+                OutcomeType::Success
+            } else {
+                OutcomeType::Failure
+            },
+            success,
+            reward: Some(reward),
+            context: None,
+            metadata: Some(serde_json::json!({ "fate": fate })),
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tries_every_profile_then_prefers_the_one_that_improves() {
+        let mut tuner = MetaTuner::default();
+        assert_eq!(tuner.select().unwrap().name, "cautious");
+        tuner
+            .record("p1", "cautious", ProposalFate::Rejected)
+            .unwrap();
+        assert_eq!(tuner.select().unwrap().name, "balanced");
+        tuner
+            .record("p2", "balanced", ProposalFate::Improved)
+            .unwrap();
+        assert_eq!(tuner.select().unwrap().name, "eager");
+        let outcome = tuner
+            .record("p3", "eager", ProposalFate::RolledBack)
+            .unwrap();
+        assert_eq!(outcome.policy_id.as_deref(), Some(META_POLICY_ID));
+        assert_eq!(outcome.action.as_deref(), Some("eager"));
+        assert!(!outcome.success);
+        assert_eq!(outcome.metadata.unwrap()["fate"], "rolled_back");
+
+        for i in 0..30 {
+            let profile = tuner.select().unwrap();
+            let fate = if profile.name == "balanced" {
+                ProposalFate::Improved
+            } else {
+                ProposalFate::RolledBack
+            };
+            tuner.record(&format!("q{i}"), &profile.name, fate).unwrap();
+        }
+        let balanced = tuner.stats("balanced").pulls;
+        assert!(balanced > 2 * (tuner.stats("cautious").pulls + tuner.stats("eager").pulls));
+
+        assert!(matches!(
+            tuner.record("x", "reckless", ProposalFate::Improved),
+            Err(MetaError::UnknownProfile(_))
+        ));
+    }
+
+    #[test]
+    fn state_and_audit_log_roundtrip() {
+        let mut tuner = MetaTuner::default();
+        let profile = tuner.select().unwrap();
+        tuner
+            .record("p1", &profile.name, ProposalFate::Improved)
+            .unwrap();
+
+        let json = serde_json::to_value(&tuner).unwrap();
+        assert_eq!(json["log"][0]["event"], "selected");
+        assert_eq!(json["log"][1]["event"], "fate");
+        assert_eq!(json["log"][1]["fate"], "improved");
+
+        let restored: MetaTuner = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.log(), tuner.log());
+        assert_eq!(restored.stats("cautious").pulls, 1);
+        assert_eq!("rolled-back".parse(), Ok(ProposalFate::RolledBack));
+    }
+}
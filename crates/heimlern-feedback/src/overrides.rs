@@ -0,0 +1,311 @@
+//! Human overrides as feedback.
+//!
+//! When a person changes a decision by hand ("remind me in the morning
+//! instead"), the emitting side records an [`OutcomeType::Override`] outcome.
+//! `action` is what the policy chose; the replacement, if known, goes into
+//! `metadata.override_action`:
+//!
+//! ```json
+//! { "outcome": "override", "action": "remind.evening", "metadata": { "override_action": "remind.morning" } }
+//! ```
+//!
+//! An override is the clearest signal a home system gets, so it is translated
+//! into a strong penalty for the chosen action and a strong reward for the
+//! replacement ([`policy_signals`]). [`analyze_overrides`] reports which
+//! actions are overridden most and what people pick instead.
+
+use crate::{outcome_is_success, DecisionOutcome, OutcomeType};
+use heimlern_core::{Context, Policy};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Metadata key carrying the action the human picked instead.
+pub const OVERRIDE_METADATA_KEY: &str = "override_action";
+/// Reward fed to the policy for an action that was overridden.
+pub const OVERRIDE_PENALTY: f32 = -1.0;
+/// Reward fed to the policy for the action a human replaced it with.
+pub const REPLACEMENT_REWARD: f32 = 1.0;
+
+impl DecisionOutcome {
+    /// Build an override outcome: `action` was replaced by `replacement`.
+    #[must_use]
+    pub fn overridden(
+        decision_id: impl Into<String>,
+        ts: impl Into<String>,
+        action: impl Into<String>,
+        replacement: Option<String>,
+    ) -> Self {
+        Self {
+            decision_id: decision_id.into(),
+            ts: ts.into(),
+            policy_id: None,
+            action: Some(action.into()),
+            outcome: OutcomeType::Override,
+            success: false,
+            reward: Some(OVERRIDE_PENALTY),
+            context: None,
+            metadata: replacement.map(|r| serde_json::json!({ OVERRIDE_METADATA_KEY: r })),
+        }
+    }
+
+    /// Whether a human overrode this decision.
+    #[must_use]
+    pub fn is_override(&self) -> bool {
+        self.outcome == OutcomeType::Override
+    }
+
+    /// The action a human picked instead, for override outcomes.
+    #[must_use]
+    pub fn override_action(&self) -> Option<&str> {
+        if !self.is_override() {
+            return None;
+        }
+        self.metadata.as_ref()?.get(OVERRIDE_METADATA_KEY)?.as_str()
+    }
+}
+
+/// One `(action, reward)` update for a policy.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PolicySignal {
+    pub action: String,
+    pub reward: f32,
+}
+
+/// Policy updates implied by `outcome`.
+///
+/// Overrides yield [`OVERRIDE_PENALTY`] for the chosen action and
+/// [`REPLACEMENT_REWARD`] for the replacement. Other outcomes yield their
+/// `reward`, or `1.0`/`0.0` by success if none is set. Censored outcomes and
+/// outcomes without an action yield nothing.
+#[must_use]
+pub fn policy_signals(outcome: &DecisionOutcome) -> Vec<PolicySignal> {
+    let Some(action) = &outcome.action else {
+        return Vec::new();
+    };
+    match outcome.outcome {
+        OutcomeType::Censored => Vec::new(),
+        OutcomeType::Override => {
+            let mut signals = vec![PolicySignal {
+                action: action.clone(),
+                reward: OVERRIDE_PENALTY,
+            }];
+            if let Some(replacement) = outcome.override_action().filter(|r| r != action) {
+                signals.push(PolicySignal {
+                    action: replacement.to_string(),
+                    reward: REPLACEMENT_REWARD,
+                });
+            }
+            signals
+        }
+        _ => {
+            let fallback = if outcome_is_success(outcome) {
+                1.0
+            } else {
+                0.0
+            };
+            let reward = outcome.reward.filter(|r| r.is_finite()).unwrap_or(fallback);
+            vec![PolicySignal {
+                action: action.clone(),
+                reward,
+            }]
+        }
+    }
+}
+
+/// Feed the signals of `outcome` into `policy`; returns how many were applied.
+pub fn feed_policy<P: Policy + ?Sized>(
+    policy: &mut P,
+    ctx: &Context,
+    outcome: &DecisionOutcome,
+) -> usize {
+    let signals = policy_signals(outcome);
+    for signal in &signals {
+        policy.feedback(ctx, &signal.action, signal.reward);
+    }
+    signals.len()
+}
+
+/// Override counts for one action.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ActionOverrides {
+    /// Outcomes recorded for this action.
+    pub decisions: usize,
+    /// How many of them were overridden.
+    pub overridden: usize,
+    /// What people picked instead, with counts.
+    pub replacements: BTreeMap<String, usize>,
+}
+
+impl ActionOverrides {
+    /// Share of this action's decisions that were overridden (0.0 to 1.0).
+    #[must_use]
+    pub fn override_rate(&self) -> f32 {
+        if self.decisions == 0 {
+            return 0.0;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        {
+            self.overridden as f32 / self.decisions as f32
+        }
+    }
+
+    /// Most frequent replacement, if any was recorded.
+    #[must_use]
+    pub fn top_replacement(&self) -> Option<(&str, usize)> {
+        self.replacements
+            .iter()
+            .max_by(|(ka, a), (kb, b)| a.cmp(b).then(kb.cmp(ka)))
+            .map(|(k, n)| (k.as_str(), *n))
+    }
+}
+
+/// Summary of human overrides across a set of outcomes.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct OverrideReport {
+    /// Number of outcomes analyzed.
+    pub decisions: usize,
+    /// Number of override outcomes.
+    pub overridden: usize,
+    /// Per-action counts (outcomes without an action are skipped).
+    pub by_action: BTreeMap<String, ActionOverrides>,
+}
+
+impl OverrideReport {
+    /// Record one outcome.
+    pub fn record(&mut self, outcome: &DecisionOutcome) {
+        self.decisions += 1;
+        if outcome.is_override() {
+            self.overridden += 1;
+        }
+        let Some(action) = &outcome.action else {
+            return;
+        };
+        let entry = self.by_action.entry(action.clone()).or_default();
+        entry.decisions += 1;
+        if outcome.is_override() {
+            entry.overridden += 1;
+            if let Some(replacement) = outcome.override_action() {
+                *entry
+                    .replacements
+                    .entry(replacement.to_string())
+                    .or_default() += 1;
+            }
+        }
+    }
+
+    /// Up to `limit` actions with the most overrides, most overridden first.
+    #[must_use]
+    pub fn most_overridden(&self, limit: usize) -> Vec<(&str, &ActionOverrides)> {
+        let mut actions: Vec<_> = self
+            .by_action
+            .iter()
+            .filter(|(_, a)| a.overridden > 0)
+            .map(|(k, a)| (k.as_str(), a))
+            .collect();
+        actions.sort_by(|(ka, a), (kb, b)| b.overridden.cmp(&a.overridden).then(ka.cmp(kb)));
+        actions.truncate(limit);
+        actions
+    }
+}
+
+/// Build an [`OverrideReport`] from decision outcomes.
+#[must_use]
+pub fn analyze_overrides(outcomes: &[DecisionOutcome]) -> OverrideReport {
+    let mut report = OverrideReport::default();
+    for outcome in outcomes {
+        report.record(outcome);
+    }
+    report
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Default)]
+    struct Recorder(Vec<(String, f32)>);
+
+    impl Policy for Recorder {
+        fn decide(&mut self, _ctx: &Context) -> heimlern_core::Decision {
+            heimlern_core::Decision {
+                action: "noop".into(),
+                score: 0.0,
+                why: Vec::new(),
+                context: None,
+                chosen: None,
+            }
+        }
+        fn feedback(&mut self, _ctx: &Context, action: &str, reward: f32) {
+            self.0.push((action.to_string(), reward));
+        }
+        fn snapshot(&self) -> serde_json::Value {
+            json!(null)
+        }
+        fn load(&mut self, _snapshot: serde_json::Value) {}
+    }
+
+    #[test]
+    fn override_penalizes_choice_and_rewards_replacement() {
+        let outcome = DecisionOutcome::overridden(
+            "d1",
+            "2026-01-01T19:05:00Z",
+            "remind.evening",
+            Some("remind.morning".into()),
+        );
+        let json = serde_json::to_value(&outcome).unwrap();
+        assert_eq!(json["outcome"], "override");
+        assert_eq!(json["metadata"]["override_action"], "remind.morning");
+
+        let mut policy = Recorder::default();
+        let ctx = Context {
+            kind: "reminder".into(),
+            features: json!({}),
+        };
+        assert_eq!(feed_policy(&mut policy, &ctx, &outcome), 2);
+        assert_eq!(
+            policy.0,
+            vec![
+                ("remind.evening".to_string(), OVERRIDE_PENALTY),
+                ("remind.morning".to_string(), REPLACEMENT_REWARD),
+            ]
+        );
+
+        let censored = DecisionOutcome::censored("d2", "2026-01-01T00:00:00Z", None);
+        assert!(policy_signals(&censored).is_empty());
+    }
+
+    #[test]
+    fn report_ranks_most_overridden_actions() {
+        let mut outcomes = Vec::new();
+        for i in 0..3 {
+            outcomes.push(DecisionOutcome::overridden(
+                format!("e{i}"),
+                "2026-01-01T19:00:00Z",
+                "remind.evening",
+                Some("remind.morning".into()),
+            ));
+        }
+        outcomes.push(DecisionOutcome::overridden(
+            "m0",
+            "2026-01-01T08:00:00Z",
+            "remind.morning",
+            None,
+        ));
+        outcomes.push(DecisionOutcome::censored(
+            "m1",
+            "2026-01-02T08:00:00Z",
+            Some("remind.morning".into()),
+        ));
+
+        let report = analyze_overrides(&outcomes);
+        assert_eq!(report.overridden, 4);
+        let top = report.most_overridden(1);
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].0, "remind.evening");
+        assert_eq!(top[0].1.top_replacement(), Some(("remind.morning", 3)));
+        assert!((report.by_action["remind.morning"].override_rate() - 0.5).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,230 @@
+//! Portfolio analysis across several policies.
+//!
+//! Outcome logs usually mix decisions of several policies. Instead of
+//! filtering the log once per `policy_id` and analyzing each slice on its
+//! own, [`FeedbackAnalyzer::analyze_portfolio`] splits the outcomes itself,
+//! runs the usual pattern analysis and proposal generation per policy, and
+//! then compares the policies per `context.kind`: which one succeeds most
+//! often on reminders, which one on calendar entries, and whether its lead
+//! over the runner-up is significant.
+
+use crate::explain::Pattern;
+use crate::index::OutcomeIndex;
+use crate::{
+    two_proportion_z, DecisionOutcome, FeedbackAnalyzer, OutcomeStatistics,
+    WeightAdjustmentProposal, PATTERN_MIN_DECISIONS_PER_ACTION,
+};
+use heimlern_core::Uncertainty;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Policy id used for outcomes without `policy_id`.
+pub const UNATTRIBUTED_POLICY: &str = "unknown";
+
+/// Success of one policy on one slice of outcomes.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SliceSummary {
+    /// Explicit outcomes (censored and overridden ones excluded).
+    pub total: usize,
+    pub success_rate: f32,
+    /// 95 % Wilson interval of `success_rate`.
+    pub interval: (f32, f32),
+}
+
+impl SliceSummary {
+    fn new(stats: &OutcomeStatistics) -> Self {
+        Self {
+            total: stats.total,
+            success_rate: stats.success_rate(),
+            interval: stats.success_rate_interval(0.95),
+        }
+    }
+}
+
+/// Analysis of one policy's outcomes.
+#[derive(Debug, Clone, Serialize)]
+pub struct PolicyReport {
+    pub policy_id: String,
+    /// Outcomes of this policy, censored ones included.
+    pub decisions: usize,
+    pub overall: SliceSummary,
+    pub by_kind: BTreeMap<String, SliceSummary>,
+    pub patterns: Vec<Pattern>,
+    /// What [`FeedbackAnalyzer::propose_adjustment`] proposes for the policy.
+    pub proposal: Option<WeightAdjustmentProposal>,
+}
+
+/// Which policy handles one context kind best.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KindComparison {
+    pub kind: String,
+    /// Success rate per policy with enough outcomes of this kind.
+    pub success_rates: BTreeMap<String, f32>,
+    /// Policy with the highest success rate.
+    pub best: String,
+    /// Policy with the second-highest success rate, if any.
+    pub runner_up: Option<String>,
+    /// Whether `best` beats `runner_up` at the 95 % level.
+    pub significant: bool,
+}
+
+/// Per-policy reports and cross-policy comparisons.
+#[derive(Debug, Clone, Serialize)]
+pub struct PortfolioReport {
+    /// One report per policy, ordered by `policy_id`.
+    pub policies: Vec<PolicyReport>,
+    /// One comparison per context kind seen with enough outcomes.
+    pub by_kind: Vec<KindComparison>,
+}
+
+impl PortfolioReport {
+    /// The report for `policy_id`.
+    #[must_use]
+    pub fn policy(&self, policy_id: &str) -> Option<&PolicyReport> {
+        self.policies.iter().find(|p| p.policy_id == policy_id)
+    }
+}
+
+impl FeedbackAnalyzer {
+    /// Analyze outcomes of several policies in one pass.
+    ///
+    /// Outcomes without `policy_id` are reported under
+    /// [`UNATTRIBUTED_POLICY`]. A policy takes part in a kind comparison once
+    /// it has [`PATTERN_MIN_DECISIONS_PER_ACTION`] explicit outcomes of that
+    /// kind.
+    #[must_use]
+    pub fn analyze_portfolio(&self, outcomes: &[DecisionOutcome]) -> PortfolioReport {
+        let mut by_policy: BTreeMap<&str, Vec<DecisionOutcome>> = BTreeMap::new();
+        for outcome in outcomes {
+            by_policy
+                .entry(outcome.policy_id.as_deref().unwrap_or(UNATTRIBUTED_POLICY))
+                .or_default()
+                .push(outcome.clone());
+        }
+
+        let mut kinds: BTreeMap<String, Vec<(&str, OutcomeStatistics)>> = BTreeMap::new();
+        let mut policies = Vec::with_capacity(by_policy.len());
+        for (policy_id, outcomes) in &by_policy {
+            let index = OutcomeIndex::build(outcomes);
+            for (kind, stats) in &index.by_kind {
+                if stats.total >= PATTERN_MIN_DECISIONS_PER_ACTION {
+                    kinds
+                        .entry(kind.clone())
+                        .or_default()
+                        .push((policy_id, stats.clone()));
+                }
+            }
+            policies.push(PolicyReport {
+                policy_id: (*policy_id).to_string(),
+                decisions: outcomes.len(),
+                overall: SliceSummary::new(&index.overall),
+                by_kind: index
+                    .by_kind
+                    .iter()
+                    .map(|(kind, stats)| (kind.clone(), SliceSummary::new(stats)))
+                    .collect(),
+                patterns: self.analyze_patterns(outcomes),
+                proposal: self.propose_adjustment(policy_id, outcomes),
+            });
+        }
+
+        let by_kind = kinds
+            .into_iter()
+            .map(|(kind, mut entries)| {
+                entries.sort_by(|a, b| b.1.success_rate().total_cmp(&a.1.success_rate()));
+                let (best, best_stats) = &entries[0];
+                let runner_up = entries.get(1);
+                let significant = runner_up.is_some_and(|(_, stats)| {
+                    two_proportion_z(
+                        stats.successes,
+                        stats.total,
+                        best_stats.successes,
+                        best_stats.total,
+                    ) > Uncertainty::Z_95
+                });
+                KindComparison {
+                    success_rates: entries
+                        .iter()
+                        .map(|(policy, stats)| ((*policy).to_string(), stats.success_rate()))
+                        .collect(),
+                    best: (*best).to_string(),
+                    runner_up: runner_up.map(|(policy, _)| (*policy).to_string()),
+                    significant,
+                    kind,
+                }
+            })
+            .collect();
+
+        PortfolioReport { policies, by_kind }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OutcomeType;
+    use serde_json::json;
+
+    fn outcomes(
+        policy: Option<&str>,
+        kind: &str,
+        total: usize,
+        successes: usize,
+    ) -> Vec<DecisionOutcome> {
+        (0..total)
+            .map(|i| {
+                let success = i < successes;
+                DecisionOutcome {
+                    decision_id: format!("{}-{kind}-{i}", policy.unwrap_or("none")),
+                    ts: "2026-01-01T00:00:00Z".into(),
+                    policy_id: policy.map(String::from),
+                    action: Some(format!("remind.{kind}")),
+                    outcome: if success {
+                        OutcomeType::Success
+                    } else {
+                        OutcomeType::Failure
+                    },
+                    success,
+                    reward: None,
+                    context: Some(json!({ "kind": kind })),
+                    metadata: None,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn policies_are_compared_per_context_kind() {
+        let mut log = outcomes(Some("bandit"), "reminder", 60, 48);
+        log.extend(outcomes(Some("bandit"), "calendar", 20, 9));
+        log.extend(outcomes(Some("ucb"), "reminder", 60, 30));
+        log.extend(outcomes(Some("ucb"), "calendar", 20, 11));
+        log.extend(outcomes(None, "calendar", 3, 0));
+
+        let report = FeedbackAnalyzer::default().analyze_portfolio(&log);
+        let ids: Vec<&str> = report
+            .policies
+            .iter()
+            .map(|p| p.policy_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["bandit", "ucb", UNATTRIBUTED_POLICY]);
+
+        let ucb = report.policy("ucb").unwrap_or_else(|| panic!("ucb report"));
+        assert_eq!(ucb.decisions, 80);
+        assert!((ucb.by_kind["reminder"].success_rate - 0.5).abs() < 1e-6);
+        assert!(report
+            .policy(UNATTRIBUTED_POLICY)
+            .is_some_and(|p| p.proposal.is_none()));
+
+        let reminder = &report.by_kind[1];
+        assert_eq!(reminder.kind, "reminder");
+        assert_eq!(reminder.best, "bandit");
+        assert!(reminder.significant);
+
+        let calendar = &report.by_kind[0];
+        assert_eq!(calendar.best, "ucb");
+        assert_eq!(calendar.runner_up.as_deref(), Some("bandit"));
+        assert_eq!(calendar.success_rates.len(), 2);
+        assert!(!calendar.significant);
+    }
+}
@@ -0,0 +1,200 @@
+//! Differential privacy for artifacts that leave the household.
+//!
+//! Reports and proposals carry exact counts and rates, which can reveal when
+//! and how a household reacts to reminders. Before such an artifact is shared
+//! with the wider community, [`Privatize`] adds Laplace noise calibrated to a
+//! configurable privacy parameter `epsilon`: smaller values mean more noise
+//! and stronger privacy.
+//!
+//! Each released statistic is treated as a separate query with its own
+//! `epsilon`; the total privacy cost of an artifact is the sum over its noised
+//! fields. Counts have sensitivity 1 (one household event changes them by at
+//! most one); a rate over `n` records has sensitivity `1 / n`. Free-text
+//! fields that may quote exact figures (proposal patterns and reasoning) are
+//! removed rather than noised.
+
+use crate::overrides::OverrideReport;
+use crate::veto::VetoReport;
+use crate::WeightAdjustmentProposal;
+use rand::Rng;
+
+/// Errors raised for invalid privacy settings.
+#[derive(Debug, thiserror::Error)]
+pub enum PrivacyError {
+    #[error("privacy epsilon must be finite and positive, got {0}")]
+    InvalidEpsilon(f64),
+}
+
+/// Laplace mechanism with privacy parameter `epsilon`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LaplaceNoise {
+    epsilon: f64,
+}
+
+impl LaplaceNoise {
+    /// Mechanism with the given `epsilon` (> 0).
+    ///
+    /// # Errors
+    /// Fails if `epsilon` is not finite and positive.
+    pub fn new(epsilon: f64) -> Result<Self, PrivacyError> {
+        if epsilon.is_finite() && epsilon > 0.0 {
+            Ok(Self { epsilon })
+        } else {
+            Err(PrivacyError::InvalidEpsilon(epsilon))
+        }
+    }
+
+    #[must_use]
+    pub fn epsilon(&self) -> f64 {
+        self.epsilon
+    }
+
+    /// Laplace sample for a query with the given `sensitivity`.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R, sensitivity: f64) -> f64 {
+        let scale = sensitivity / self.epsilon;
+        // u in (-0.5, 0.5]; inverse CDF of the Laplace distribution.
+        let u: f64 = 0.5 - rng.gen::<f64>();
+        -scale * u.signum() * (1.0 - 2.0 * u.abs()).max(f64::MIN_POSITIVE).ln()
+    }
+
+    /// Noised count, rounded and kept non-negative.
+    pub fn count<R: Rng + ?Sized>(&self, rng: &mut R, count: usize) -> usize {
+        #[allow(clippy::cast_precision_loss)]
+        let noisy = count as f64 + self.sample(rng, 1.0);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        {
+            noisy.round().max(0.0) as usize
+        }
+    }
+
+    /// Noised rate over `n` records, clamped to `[0, 1]`.
+    pub fn rate<R: Rng + ?Sized>(&self, rng: &mut R, rate: f32, n: usize) -> f32 {
+        #[allow(clippy::cast_precision_loss)]
+        let sensitivity = 1.0 / n.max(1) as f64;
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            (f64::from(rate) + self.sample(rng, sensitivity)).clamp(0.0, 1.0) as f32
+        }
+    }
+}
+
+/// Artifacts that can be prepared for sharing.
+pub trait Privatize {
+    /// Replace exact statistics with noised ones.
+    fn privatize<R: Rng + ?Sized>(&mut self, noise: &LaplaceNoise, rng: &mut R);
+}
+
+impl Privatize for WeightAdjustmentProposal {
+    fn privatize<R: Rng + ?Sized>(&mut self, noise: &LaplaceNoise, rng: &mut R) {
+        let n = self.evidence.decisions_analyzed;
+        for rate in [
+            &mut self.evidence.failure_rate_before,
+            &mut self.evidence.failure_rate_after_sim,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            *rate = noise.rate(rng, *rate, n);
+        }
+        self.evidence.decisions_analyzed = noise.count(rng, n);
+        self.evidence.patterns = None;
+        self.reasoning = None;
+    }
+}
+
+impl Privatize for OverrideReport {
+    fn privatize<R: Rng + ?Sized>(&mut self, noise: &LaplaceNoise, rng: &mut R) {
+        self.decisions = noise.count(rng, self.decisions);
+        self.overridden = noise.count(rng, self.overridden).min(self.decisions);
+        for action in self.by_action.values_mut() {
+            action.decisions = noise.count(rng, action.decisions);
+            action.overridden = noise.count(rng, action.overridden).min(action.decisions);
+            for count in action.replacements.values_mut() {
+                *count = noise.count(rng, *count);
+            }
+            action.replacements.retain(|_, count| *count > 0);
+        }
+    }
+}
+
+impl Privatize for VetoReport {
+    fn privatize<R: Rng + ?Sized>(&mut self, noise: &LaplaceNoise, rng: &mut R) {
+        let (decisions, vetoed) = (self.decisions, self.vetoed);
+        self.vetoed_success_rate = noise.rate(rng, self.vetoed_success_rate, vetoed);
+        self.unvetoed_success_rate = noise.rate(
+            rng,
+            self.unvetoed_success_rate,
+            decisions.saturating_sub(vetoed),
+        );
+        self.decisions = noise.count(rng, decisions);
+        self.vetoed = noise.count(rng, vetoed).min(self.decisions);
+        for count in self
+            .by_constraint
+            .values_mut()
+            .chain(self.pairs.values_mut())
+        {
+            *count = noise.count(rng, *count);
+        }
+        self.by_constraint.retain(|_, count| *count > 0);
+        self.pairs.retain(|_, count| *count > 0);
+    }
+}
+
+/// Privatize `artifact` in place with fresh system randomness.
+///
+/// # Errors
+/// Fails if `epsilon` is not finite and positive.
+pub fn privatize<T: Privatize>(artifact: &mut T, epsilon: f64) -> Result<(), PrivacyError> {
+    let noise = LaplaceNoise::new(epsilon)?;
+    artifact.privatize(&noise, &mut rand::thread_rng());
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn laplace_noise_has_expected_scale() {
+        let noise = LaplaceNoise::new(0.5).unwrap();
+        let mut rng = StdRng::seed_from_u64(7);
+        let n = 20_000;
+        let samples: Vec<f64> = (0..n).map(|_| noise.sample(&mut rng, 1.0)).collect();
+        #[allow(clippy::cast_precision_loss)]
+        let mean_abs = samples.iter().map(|s| s.abs()).sum::<f64>() / n as f64;
+        // E|X| of Laplace(b) is b = sensitivity / epsilon = 2.
+        assert!((mean_abs - 2.0).abs() < 0.1);
+        assert!(LaplaceNoise::new(0.0).is_err());
+        assert!(LaplaceNoise::new(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn privatized_proposal_drops_free_text_and_keeps_ranges() {
+        let mut proposal: WeightAdjustmentProposal = serde_json::from_value(serde_json::json!({
+            "version": "v1",
+            "basis_policy": "remind-bandit",
+            "ts": "2026-01-01T00:00:00Z",
+            "deltas": {},
+            "confidence": 0.7,
+            "evidence": {
+                "decisions_analyzed": 40,
+                "failure_rate_before": 0.6,
+                "patterns": ["High failure rate (60.0%) for action 'remind.evening'"]
+            },
+            "reasoning": "Reduce exploration due to high failure rate"
+        }))
+        .unwrap();
+        let noise = LaplaceNoise::new(1.0).unwrap();
+        proposal.privatize(&noise, &mut StdRng::seed_from_u64(1));
+
+        assert!(proposal.evidence.patterns.is_none());
+        assert!(proposal.reasoning.is_none());
+        let rate = proposal.evidence.failure_rate_before.unwrap();
+        assert!((0.0..=1.0).contains(&rate));
+        assert!(proposal.evidence.decisions_analyzed.abs_diff(40) < 20);
+    }
+}
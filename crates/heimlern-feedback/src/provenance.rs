@@ -0,0 +1,254 @@
+//! Evidence provenance: binding proposals to the exact outcome records.
+//!
+//! A [`ProposalProvenance`] records a SHA-256 Merkle root over the outcome
+//! records a proposal was derived from, plus a digest of the proposal itself.
+//! Recomputing both from stored outcomes proves that the proposal was derived
+//! from unaltered data.
+//!
+//! The `evidence` object of `policy.weight_adjustment.v1` is closed
+//! (`additionalProperties: false`), so provenance travels as a sidecar
+//! document next to the proposal instead of inside it.
+//!
+//! Hashing scheme:
+//! * leaf = `sha256(0x00 || json(outcome))`
+//! * node = `sha256(0x01 || left || right)`; an unpaired node is promoted as-is
+//! * the root of zero outcomes is `sha256("")`
+
+use crate::{DecisionOutcome, WeightAdjustmentProposal};
+use heimlern_core::canonical;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Hash algorithm identifier stored in the sidecar.
+pub const ALGORITHM: &str = "sha256-merkle-v1";
+
+/// Errors raised while hashing or verifying provenance.
+#[derive(Debug, thiserror::Error)]
+pub enum ProvenanceError {
+    #[error("failed to serialize record for hashing: {0}")]
+    Serialize(#[from] serde_json::Error),
+    #[error("unsupported provenance algorithm '{0}'")]
+    UnsupportedAlgorithm(String),
+}
+
+/// Result of a provenance check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification {
+    /// Proposal and outcomes match the recorded hashes.
+    Valid,
+    /// The proposal differs from the one the sidecar was created for.
+    ProposalMismatch { expected: String, actual: String },
+    /// The outcomes differ from the ones the proposal was derived from.
+    OutcomesMismatch {
+        expected_root: String,
+        actual_root: String,
+        expected_count: usize,
+        actual_count: usize,
+    },
+}
+
+impl Verification {
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        matches!(self, Self::Valid)
+    }
+}
+
+/// Sidecar linking a proposal to its outcome records.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposalProvenance {
+    pub algorithm: String,
+    /// SHA-256 of the serialized proposal (hex).
+    pub proposal_digest: String,
+    /// Number of outcome records covered.
+    pub outcome_count: usize,
+    /// Merkle root over the outcome records (hex).
+    pub outcomes_root: String,
+}
+
+impl ProposalProvenance {
+    /// Compute provenance for `proposal` derived from `outcomes`.
+    ///
+    /// # Errors
+    /// Fails if a record cannot be serialized.
+    pub fn compute(
+        proposal: &WeightAdjustmentProposal,
+        outcomes: &[DecisionOutcome],
+    ) -> Result<Self, ProvenanceError> {
+        Ok(Self {
+            algorithm: ALGORITHM.to_string(),
+            proposal_digest: proposal_digest(proposal)?,
+            outcome_count: outcomes.len(),
+            outcomes_root: merkle_root(outcomes)?,
+        })
+    }
+
+    /// Recompute hashes and compare them against this sidecar.
+    ///
+    /// # Errors
+    /// Fails on unknown algorithms or serialization errors.
+    pub fn verify(
+        &self,
+        proposal: &WeightAdjustmentProposal,
+        outcomes: &[DecisionOutcome],
+    ) -> Result<Verification, ProvenanceError> {
+        if self.algorithm != ALGORITHM {
+            return Err(ProvenanceError::UnsupportedAlgorithm(
+                self.algorithm.clone(),
+            ));
+        }
+        let actual = proposal_digest(proposal)?;
+        if actual != self.proposal_digest {
+            return Ok(Verification::ProposalMismatch {
+                expected: self.proposal_digest.clone(),
+                actual,
+            });
+        }
+        let actual_root = merkle_root(outcomes)?;
+        if actual_root != self.outcomes_root || outcomes.len() != self.outcome_count {
+            return Ok(Verification::OutcomesMismatch {
+                expected_root: self.outcomes_root.clone(),
+                actual_root,
+                expected_count: self.outcome_count,
+                actual_count: outcomes.len(),
+            });
+        }
+        Ok(Verification::Valid)
+    }
+}
+
+/// SHA-256 digest (hex) of a proposal's JSON form.
+///
+/// The `status` field is excluded so that accepting or rejecting a proposal
+/// does not invalidate its provenance.
+///
+/// # Errors
+/// Fails if the proposal cannot be serialized.
+pub fn proposal_digest(proposal: &WeightAdjustmentProposal) -> Result<String, ProvenanceError> {
+    let mut value = serde_json::to_value(proposal)?;
+    if let Some(map) = value.as_object_mut() {
+        map.remove("status");
+    }
+    Ok(to_hex(&Sha256::digest(canonical::to_canonical_vec(
+        &value,
+    )?)))
+}
+
+/// Merkle root (hex) over outcome records.
+///
+/// # Errors
+/// Fails if a record cannot be serialized.
+pub fn merkle_root(outcomes: &[DecisionOutcome]) -> Result<String, ProvenanceError> {
+    let mut level = outcomes
+        .iter()
+        .map(|o| {
+            let mut h = Sha256::new();
+            h.update([0x00]);
+            h.update(serde_json::to_vec(o)?);
+            Ok(h.finalize().to_vec())
+        })
+        .collect::<Result<Vec<_>, ProvenanceError>>()?;
+
+    if level.is_empty() {
+        return Ok(to_hex(&Sha256::digest([])));
+    }
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => {
+                    let mut h = Sha256::new();
+                    h.update([0x01]);
+                    h.update(left);
+                    h.update(right);
+                    h.finalize().to_vec()
+                }
+                [single] => single.clone(),
+                _ => unreachable!("chunks(2) yields one or two elements"),
+            })
+            .collect();
+    }
+    Ok(to_hex(&level[0]))
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::{Evidence, OutcomeType, ProposalStatus};
+    use std::collections::BTreeMap;
+
+    fn outcome(id: &str, success: bool) -> DecisionOutcome {
+        DecisionOutcome {
+            decision_id: id.into(),
+            ts: "2026-01-01T00:00:00Z".into(),
+            policy_id: Some("remind-bandit".into()),
+            action: Some("remind.morning".into()),
+            outcome: if success {
+                OutcomeType::Success
+            } else {
+                OutcomeType::Failure
+            },
+            success,
+            reward: None,
+            context: None,
+            metadata: None,
+        }
+    }
+
+    fn proposal() -> WeightAdjustmentProposal {
+        WeightAdjustmentProposal {
+            version: "v1".into(),
+            basis_policy: "remind-bandit".into(),
+            ts: "2026-01-01T00:00:00Z".into(),
+            deltas: BTreeMap::new(),
+            confidence: 0.7,
+            evidence: Evidence::default(),
+            reasoning: None,
+            status: ProposalStatus::Proposed,
+        }
+    }
+
+    #[test]
+    fn merkle_root_depends_on_every_record_and_order() {
+        let a = vec![outcome("1", true), outcome("2", false), outcome("3", true)];
+        let mut b = a.clone();
+        b.swap(0, 1);
+        let mut c = a.clone();
+        c[2].success = false;
+
+        let root = merkle_root(&a).unwrap();
+        assert_eq!(root.len(), 64);
+        assert_ne!(root, merkle_root(&b).unwrap());
+        assert_ne!(root, merkle_root(&c).unwrap());
+        assert_eq!(root, merkle_root(&a).unwrap());
+    }
+
+    #[test]
+    fn verify_detects_tampering_but_ignores_status_changes() {
+        let outcomes = vec![outcome("1", true), outcome("2", false)];
+        let mut p = proposal();
+        let sidecar = ProposalProvenance::compute(&p, &outcomes).unwrap();
+
+        p.status = ProposalStatus::Accepted;
+        assert!(sidecar.verify(&p, &outcomes).unwrap().is_valid());
+
+        let mut tampered = outcomes.clone();
+        tampered[1].success = true;
+        assert!(matches!(
+            sidecar.verify(&p, &tampered).unwrap(),
+            Verification::OutcomesMismatch { .. }
+        ));
+
+        p.confidence = 0.9;
+        assert!(matches!(
+            sidecar.verify(&p, &outcomes).unwrap(),
+            Verification::ProposalMismatch { .. }
+        ));
+    }
+}
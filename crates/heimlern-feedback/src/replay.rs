@@ -0,0 +1,419 @@
+//! Replay-based simulation of proposals.
+//!
+//! The reweighting estimate of [`FeedbackAnalyzer::simulate_adjustment`] only
+//! shifts the explore/exploit mix of the history. A replay runs the policy
+//! itself: the caller reconstructs it from its snapshot (with a fixed seed,
+//! so the run is reproducible), [`FeedbackAnalyzer::simulate_replay`] applies
+//! the proposed deltas, and every recorded context is passed through
+//! [`Policy::decide`] again. Where the replayed action matches the recorded
+//! one, the recorded outcome counts and is fed back to the policy; all other
+//! decisions are skipped (rejection sampling). The success rate over matched
+//! decisions becomes the proposal's `failure_rate_after_sim`, with
+//! `simulation_method: "replay"`.
+//!
+//! Outcomes without context or action, and censored or overridden ones, are
+//! not replayed.
+//!
+//! A single replay of a stochastic policy depends on its seed.
+//! [`FeedbackAnalyzer::simulate_monte_carlo`] repeats it with consecutive
+//! seeds and reports mean, standard deviation and a 95 % interval of the
+//! failure rate as a [`MonteCarloReport`]. Since the `evidence` object is
+//! closed, only the mean lands in the proposal (`simulation_method:
+//! "monte_carlo"`); the report travels alongside it.
+
+use crate::apply::{apply_to_params, DeltaViolation, PolicyState};
+use crate::{outcome_is_success, DecisionOutcome, FeedbackAnalyzer, WeightAdjustmentProposal};
+use heimlern_core::{Context, Policy, PolicyDescriptor, Uncertainty};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Value of [`crate::Evidence::simulation_method`] for replayed proposals.
+pub const REPLAY_METHOD: &str = "replay";
+
+/// Value of [`crate::Evidence::simulation_method`] for Monte Carlo runs.
+pub const MONTE_CARLO_METHOD: &str = "monte_carlo";
+
+/// Result of one replay run.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ReplayResult {
+    /// Outcomes passed through `decide`.
+    pub replayed: usize,
+    /// Replayed decisions that chose the recorded action.
+    pub matched: usize,
+    /// Matched decisions that succeeded.
+    pub successes: usize,
+}
+
+impl ReplayResult {
+    /// Success rate over matched decisions; `None` if nothing matched.
+    #[must_use]
+    pub fn success_rate(&self) -> Option<f32> {
+        #[allow(clippy::cast_precision_loss)]
+        (self.matched > 0).then(|| self.successes as f32 / self.matched as f32)
+    }
+}
+
+/// Replays `outcomes` through `policy` in order.
+pub fn replay<P: Policy + ?Sized>(policy: &mut P, outcomes: &[DecisionOutcome]) -> ReplayResult {
+    let mut result = ReplayResult::default();
+    for outcome in outcomes {
+        if outcome.is_censored() || outcome.is_override() {
+            continue;
+        }
+        let (Some(action), Some(ctx)) = (outcome.action.as_deref(), recorded_context(outcome))
+        else {
+            continue;
+        };
+        result.replayed += 1;
+        if policy.decide(&ctx).action != action {
+            continue;
+        }
+        result.matched += 1;
+        let success = outcome_is_success(outcome);
+        if success {
+            result.successes += 1;
+        }
+        let reward = outcome
+            .reward
+            .filter(|r| r.is_finite())
+            .unwrap_or(if success { 1.0 } else { 0.0 });
+        policy.feedback(&ctx, action, reward);
+    }
+    result
+}
+
+/// Number of runs and first seed of a Monte Carlo simulation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MonteCarlo {
+    pub runs: usize,
+    /// Run `i` uses seed `seed + i`.
+    pub seed: u64,
+}
+
+impl Default for MonteCarlo {
+    fn default() -> Self {
+        Self { runs: 32, seed: 0 }
+    }
+}
+
+/// Distribution of the replayed failure rate over all Monte Carlo runs.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MonteCarloReport {
+    pub runs: usize,
+    /// Runs in which at least one decision matched; only these are counted.
+    pub effective_runs: usize,
+    pub mean: f32,
+    /// Sample standard deviation across runs.
+    pub stddev: f32,
+    /// Lower bound of the 95 % interval of the mean, clamped to `[0, 1]`.
+    pub lower: f32,
+    /// Upper bound of the 95 % interval of the mean, clamped to `[0, 1]`.
+    pub upper: f32,
+}
+
+impl MonteCarloReport {
+    fn from_rates(runs: usize, rates: &[f64]) -> Option<Self> {
+        if rates.is_empty() {
+            return None;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let n = rates.len() as f64;
+        let mean = rates.iter().sum::<f64>() / n;
+        let variance = if rates.len() > 1 {
+            rates.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+        let stddev = variance.sqrt();
+        let half_width = Uncertainty::Z_95 * stddev / n.sqrt();
+        #[allow(clippy::cast_possible_truncation)]
+        Some(Self {
+            runs,
+            effective_runs: rates.len(),
+            mean: mean as f32,
+            stddev: stddev as f32,
+            lower: (mean - half_width).clamp(0.0, 1.0) as f32,
+            upper: (mean + half_width).clamp(0.0, 1.0) as f32,
+        })
+    }
+}
+
+fn recorded_context(outcome: &DecisionOutcome) -> Option<Context> {
+    serde_json::from_value(outcome.context.clone()?).ok()
+}
+
+impl FeedbackAnalyzer {
+    /// Simulate `proposal` by replaying `outcomes` through `policy`.
+    ///
+    /// `policy` should be reconstructed from the snapshot the outcomes were
+    /// recorded against; `current` are its tunable parameters and `set_param`
+    /// writes one of them (for bandits, their `set_param` method). The
+    /// proposed deltas are resolved via [`apply_to_params`] and set before the
+    /// replay. If any decision matched, the proposal's evidence is updated
+    /// with the replayed failure rate and [`REPLAY_METHOD`]; otherwise it is
+    /// left as it was.
+    ///
+    /// # Errors
+    /// Returns the violations of [`apply_to_params`]; nothing is replayed then.
+    pub fn simulate_replay<P: Policy>(
+        &self,
+        proposal: &mut WeightAdjustmentProposal,
+        descriptor: &PolicyDescriptor,
+        current: &BTreeMap<String, f64>,
+        mut policy: P,
+        mut set_param: impl FnMut(&mut P, &str, f64) -> bool,
+        outcomes: &[DecisionOutcome],
+    ) -> Result<ReplayResult, Vec<DeltaViolation>> {
+        let params = apply_to_params(proposal, descriptor, current)?;
+        for (key, value) in &params {
+            set_param(&mut policy, key, *value);
+        }
+        let result = replay(&mut policy, outcomes);
+        if let Some(rate) = result.success_rate() {
+            proposal.evidence.failure_rate_after_sim = Some(1.0 - rate);
+            proposal.evidence.simulation_method = Some(REPLAY_METHOD.to_string());
+        }
+        Ok(result)
+    }
+
+    /// Simulate `proposal` by `config.runs` seeded replays.
+    ///
+    /// `make_policy` reconstructs the policy with the given seed for each run;
+    /// deltas are applied as in [`Self::simulate_replay`]. The proposal's
+    /// evidence receives the mean failure rate and [`MONTE_CARLO_METHOD`].
+    /// Returns `Ok(None)` if no run matched a single decision.
+    ///
+    /// # Errors
+    /// Returns the violations of [`apply_to_params`]; nothing is replayed then.
+    pub fn simulate_monte_carlo<P: Policy>(
+        &self,
+        proposal: &mut WeightAdjustmentProposal,
+        state: &PolicyState,
+        config: MonteCarlo,
+        mut make_policy: impl FnMut(u64) -> P,
+        mut set_param: impl FnMut(&mut P, &str, f64) -> bool,
+        outcomes: &[DecisionOutcome],
+    ) -> Result<Option<MonteCarloReport>, Vec<DeltaViolation>> {
+        let params = apply_to_params(proposal, &state.descriptor, &state.params)?;
+        let rates: Vec<f64> = (0..config.runs)
+            .filter_map(|run| {
+                let mut policy = make_policy(config.seed.wrapping_add(run as u64));
+                for (key, value) in &params {
+                    set_param(&mut policy, key, *value);
+                }
+                replay(&mut policy, outcomes)
+                    .success_rate()
+                    .map(|rate| 1.0 - f64::from(rate))
+            })
+            .collect();
+        let report = MonteCarloReport::from_rates(config.runs, &rates);
+        if let Some(report) = &report {
+            proposal.evidence.failure_rate_after_sim = Some(report.mean);
+            proposal.evidence.simulation_method = Some(MONTE_CARLO_METHOD.to_string());
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DeltaValue, Evidence, OutcomeType, ProposalStatus};
+    use heimlern_core::Decision;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use serde_json::{json, Value};
+
+    /// Picks `remind.evening` when `epsilon` is below one half.
+    struct Threshold {
+        epsilon: f64,
+        rewards: Vec<f32>,
+    }
+
+    impl Policy for Threshold {
+        fn decide(&mut self, _: &Context) -> Decision {
+            let slot = if self.epsilon < 0.5 {
+                "evening"
+            } else {
+                "morning"
+            };
+            Decision {
+                action: format!("remind.{slot}"),
+                score: 0.0,
+                why: vec![],
+                context: None,
+                chosen: None,
+            }
+        }
+        fn feedback(&mut self, _: &Context, _: &str, reward: f32) {
+            self.rewards.push(reward);
+        }
+        fn snapshot(&self) -> Value {
+            Value::Null
+        }
+        fn load(&mut self, _: Value) {}
+        fn descriptor(&self) -> PolicyDescriptor {
+            PolicyDescriptor::new("threshold").tunable("epsilon", 0.0, 1.0)
+        }
+    }
+
+    fn outcome(id: usize, action: &str, success: bool) -> DecisionOutcome {
+        DecisionOutcome {
+            decision_id: id.to_string(),
+            ts: "2026-01-01T00:00:00Z".into(),
+            policy_id: Some("threshold".into()),
+            action: Some(action.into()),
+            outcome: if success {
+                OutcomeType::Success
+            } else {
+                OutcomeType::Failure
+            },
+            success,
+            reward: None,
+            context: Some(json!({ "kind": "reminder", "features": {} })),
+            metadata: None,
+        }
+    }
+
+    fn proposal(epsilon: f32) -> WeightAdjustmentProposal {
+        WeightAdjustmentProposal {
+            version: "v1".into(),
+            basis_policy: "threshold".into(),
+            ts: String::new(),
+            deltas: BTreeMap::from([("epsilon".into(), DeltaValue::Absolute { value: epsilon })]),
+            confidence: 1.0,
+            evidence: Evidence::default(),
+            reasoning: None,
+            status: ProposalStatus::Proposed,
+        }
+    }
+
+    #[test]
+    fn replay_scores_the_adjusted_policy_on_matching_decisions() {
+        let mut outcomes: Vec<DecisionOutcome> = (0..4)
+            .map(|i| outcome(i, "remind.morning", i == 0))
+            .chain((4..8).map(|i| outcome(i, "remind.evening", i != 4)))
+            .collect();
+        outcomes.push(DecisionOutcome {
+            context: None,
+            ..outcome(8, "remind.evening", true)
+        });
+
+        let policy = Threshold {
+            epsilon: 0.8,
+            rewards: Vec::new(),
+        };
+        let descriptor = policy.descriptor();
+        let mut proposal = proposal(0.2);
+        let current = BTreeMap::from([("epsilon".to_string(), 0.8)]);
+        let result = FeedbackAnalyzer::default()
+            .simulate_replay(
+                &mut proposal,
+                &descriptor,
+                &current,
+                policy,
+                |p, key, value| {
+                    key == "epsilon" && {
+                        p.epsilon = value;
+                        true
+                    }
+                },
+                &outcomes,
+            )
+            .unwrap_or_default();
+
+        assert_eq!(
+            result,
+            ReplayResult {
+                replayed: 8,
+                matched: 4,
+                successes: 3,
+            }
+        );
+        assert_eq!(proposal.evidence.failure_rate_after_sim, Some(0.25));
+        assert_eq!(
+            proposal.evidence.simulation_method.as_deref(),
+            Some(REPLAY_METHOD)
+        );
+    }
+
+    /// Explores `remind.morning` with probability `epsilon`.
+    struct Coin {
+        epsilon: f64,
+        rng: StdRng,
+    }
+
+    impl Policy for Coin {
+        fn decide(&mut self, _: &Context) -> Decision {
+            let slot = if self.rng.gen::<f64>() < self.epsilon {
+                "morning"
+            } else {
+                "evening"
+            };
+            Decision {
+                action: format!("remind.{slot}"),
+                score: 0.0,
+                why: vec![],
+                context: None,
+                chosen: None,
+            }
+        }
+        fn feedback(&mut self, _: &Context, _: &str, _: f32) {}
+        fn snapshot(&self) -> Value {
+            Value::Null
+        }
+        fn load(&mut self, _: Value) {}
+    }
+
+    #[test]
+    fn monte_carlo_reports_spread_across_seeds() {
+        let outcomes: Vec<DecisionOutcome> = (0..40)
+            .map(|i| {
+                let action = if i % 2 == 0 {
+                    "remind.morning"
+                } else {
+                    "remind.evening"
+                };
+                outcome(i, action, i % 2 == 1 && i % 5 != 0)
+            })
+            .collect();
+        let state = PolicyState::new(
+            PolicyDescriptor::new("threshold").tunable("epsilon", 0.0, 1.0),
+            BTreeMap::from([("epsilon".to_string(), 0.1)]),
+        );
+        let run = |proposal: &mut WeightAdjustmentProposal| {
+            FeedbackAnalyzer::default()
+                .simulate_monte_carlo(
+                    proposal,
+                    &state,
+                    MonteCarlo { runs: 20, seed: 7 },
+                    |seed| Coin {
+                        epsilon: 0.1,
+                        rng: StdRng::seed_from_u64(seed),
+                    },
+                    |p, key, value| {
+                        key == "epsilon" && {
+                            p.epsilon = value;
+                            true
+                        }
+                    },
+                    &outcomes,
+                )
+                .ok()
+                .flatten()
+        };
+
+        let mut proposal = proposal(0.5);
+        let report = run(&mut proposal).unwrap_or_else(|| panic!("no run matched"));
+        assert_eq!(report.effective_runs, 20);
+        assert!(report.stddev > 0.0);
+        assert!(report.lower < report.mean && report.mean < report.upper);
+        assert!((0.3..0.7).contains(&report.mean), "{report:?}");
+        assert_eq!(proposal.evidence.failure_rate_after_sim, Some(report.mean));
+        assert_eq!(
+            proposal.evidence.simulation_method.as_deref(),
+            Some(MONTE_CARLO_METHOD)
+        );
+        assert_eq!(run(&mut proposal.clone()), Some(report));
+    }
+}
@@ -0,0 +1,268 @@
+//! Seasonality of failure rates by hour of day and day of week.
+//!
+//! A reminder that works on weekdays may be useless on Saturday mornings.
+//! [`detect_seasonality`] buckets each action's outcomes by UTC hour and by
+//! weekday (from `ts`) and compares every bucket's failure rate with the rest
+//! of that action's outcomes in a two-proportion z-test. Because an action
+//! has up to 31 buckets, the significance level is Bonferroni-corrected over
+//! all buckets tested.
+//!
+//! Each significant bucket becomes a [`SeasonalSlot`] and, through the
+//! [`crate::detect::Seasonality`] detector, a `seasonal` pattern whose
+//! proposal delta scales the action's weight in that bucket by the ratio of
+//! success rates (`seasonal.<action>.<hour|weekday>.<bucket>`, relative,
+//! capped at ±[`MAX_SEASONAL_PERCENT`]).
+
+use crate::explain::Language;
+use crate::{normal_quantile, two_proportion_z, DecisionOutcome, DeltaValue, OutcomeStatistics};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+/// Minimum number of explicit outcomes in a bucket and in the rest.
+pub const MIN_SEASONAL_OUTCOMES: usize = 5;
+
+/// Family-wise significance level across all tested buckets.
+pub const SEASONAL_SIGNIFICANCE: f64 = 0.05;
+
+/// Largest weight change a seasonal delta proposes, in percent.
+pub const MAX_SEASONAL_PERCENT: f32 = 50.0;
+
+const WEEKDAYS: [(&str, &str, &str); 7] = [
+    ("mon", "Monday", "montags"),
+    ("tue", "Tuesday", "dienstags"),
+    ("wed", "Wednesday", "mittwochs"),
+    ("thu", "Thursday", "donnerstags"),
+    ("fri", "Friday", "freitags"),
+    ("sat", "Saturday", "samstags"),
+    ("sun", "Sunday", "sonntags"),
+];
+
+/// Calendar cycle a bucket belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Season {
+    /// Bucket is the UTC hour, 0–23.
+    HourOfDay,
+    /// Bucket is the weekday, 0 = Monday … 6 = Sunday.
+    DayOfWeek,
+}
+
+impl Season {
+    pub(crate) fn bucket(self, ts: OffsetDateTime) -> u8 {
+        match self {
+            Self::HourOfDay => ts.hour(),
+            Self::DayOfWeek => ts.weekday().number_days_from_monday(),
+        }
+    }
+
+    /// Delta key segment, e.g. `hour.07` or `weekday.sat`.
+    #[must_use]
+    pub fn key(self, bucket: u8) -> String {
+        match self {
+            Self::HourOfDay => format!("hour.{bucket:02}"),
+            Self::DayOfWeek => format!(
+                "weekday.{}",
+                WEEKDAYS.get(usize::from(bucket)).map_or("?", |w| w.0)
+            ),
+        }
+    }
+
+    /// Human-readable bucket, e.g. `at 07:00 UTC` or `on Saturday`.
+    #[must_use]
+    pub fn label(self, bucket: u8, lang: Language) -> String {
+        match (self, lang) {
+            (Self::HourOfDay, Language::En) => format!("at {bucket:02}:00 UTC"),
+            (Self::HourOfDay, Language::De) => format!("um {bucket:02}:00 UTC"),
+            (Self::DayOfWeek, Language::En) => format!(
+                "on {}",
+                WEEKDAYS.get(usize::from(bucket)).map_or("?", |w| w.1)
+            ),
+            (Self::DayOfWeek, Language::De) => WEEKDAYS
+                .get(usize::from(bucket))
+                .map_or("?", |w| w.2)
+                .to_string(),
+        }
+    }
+}
+
+/// A bucket whose failure rate differs significantly from the rest.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SeasonalSlot {
+    pub action: String,
+    pub season: Season,
+    pub bucket: u8,
+    /// Explicit outcomes in the bucket.
+    pub total: usize,
+    pub failure_rate: f32,
+    /// Failure rate of the action's other outcomes.
+    pub baseline_failure_rate: f32,
+    /// z statistic of the bucket's success rate against the rest.
+    pub z: f32,
+}
+
+impl SeasonalSlot {
+    /// Proposal delta key, see [`seasonal_delta_key`].
+    #[must_use]
+    pub fn delta_key(&self) -> String {
+        seasonal_delta_key(&self.action, self.season, self.bucket)
+    }
+
+    /// Relative weight change for the bucket, see [`seasonal_percent`].
+    #[must_use]
+    pub fn delta(&self) -> DeltaValue {
+        DeltaValue::Relative {
+            value: seasonal_percent(self.failure_rate, self.baseline_failure_rate),
+            unit: "percent".into(),
+        }
+    }
+}
+
+/// `seasonal.<action>.<hour|weekday>.<bucket>`.
+#[must_use]
+pub fn seasonal_delta_key(action: &str, season: Season, bucket: u8) -> String {
+    format!("seasonal.{action}.{}", season.key(bucket))
+}
+
+/// Percent change of the success rate in a bucket against the baseline,
+/// capped at ±[`MAX_SEASONAL_PERCENT`].
+#[must_use]
+pub fn seasonal_percent(failure_rate: f32, baseline_failure_rate: f32) -> f32 {
+    let baseline = 1.0 - baseline_failure_rate;
+    let change = if baseline > 0.0 {
+        ((1.0 - failure_rate) / baseline - 1.0) * 100.0
+    } else {
+        MAX_SEASONAL_PERCENT
+    };
+    change.clamp(-MAX_SEASONAL_PERCENT, MAX_SEASONAL_PERCENT)
+}
+
+/// Buckets of `outcomes` with a time-dependent failure rate, most
+/// significant first. Outcomes without action or parseable `ts` are skipped.
+#[must_use]
+pub fn detect_seasonality(outcomes: &[DecisionOutcome]) -> Vec<SeasonalSlot> {
+    let mut per_action: BTreeMap<&str, OutcomeStatistics> = BTreeMap::new();
+    let mut buckets: BTreeMap<(&str, Season, u8), OutcomeStatistics> = BTreeMap::new();
+    for outcome in outcomes {
+        let Some(action) = outcome.action.as_deref() else {
+            continue;
+        };
+        let Ok(ts) = OffsetDateTime::parse(&outcome.ts, &Rfc3339) else {
+            continue;
+        };
+        per_action.entry(action).or_default().record(outcome);
+        for season in [Season::HourOfDay, Season::DayOfWeek] {
+            buckets
+                .entry((action, season, season.bucket(ts)))
+                .or_default()
+                .record(outcome);
+        }
+    }
+
+    let candidates: Vec<(&(&str, Season, u8), &OutcomeStatistics, OutcomeStatistics)> = buckets
+        .iter()
+        .filter_map(|(key, stats)| {
+            let all = &per_action[key.0];
+            let rest = OutcomeStatistics {
+                total: all.total - stats.total,
+                successes: all.successes - stats.successes,
+                failures: all.failures - stats.failures,
+                ..OutcomeStatistics::default()
+            };
+            (stats.total >= MIN_SEASONAL_OUTCOMES && rest.total >= MIN_SEASONAL_OUTCOMES)
+                .then_some((key, stats, rest))
+        })
+        .collect();
+    if candidates.is_empty() {
+        return Vec::new();
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let critical = normal_quantile(1.0 - SEASONAL_SIGNIFICANCE / (2.0 * candidates.len() as f64));
+
+    let mut slots: Vec<SeasonalSlot> = candidates
+        .into_iter()
+        .filter_map(|(&(action, season, bucket), stats, rest)| {
+            let z = two_proportion_z(rest.successes, rest.total, stats.successes, stats.total);
+            #[allow(clippy::cast_possible_truncation)]
+            (z.abs() > critical).then(|| SeasonalSlot {
+                action: action.to_string(),
+                season,
+                bucket,
+                total: stats.total,
+                failure_rate: stats.failure_rate(),
+                baseline_failure_rate: rest.failure_rate(),
+                z: z as f32,
+            })
+        })
+        .collect();
+    slots.sort_by(|a, b| b.z.abs().total_cmp(&a.z.abs()));
+    slots
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::OutcomeType;
+
+    /// Eight weeks of `remind.morning` at 07:00 and 19:00; weekend mornings fail.
+    fn history() -> Vec<DecisionOutcome> {
+        // A Monday.
+        let start = OffsetDateTime::parse("2026-03-02T00:00:00Z", &Rfc3339).unwrap();
+        let mut outcomes = Vec::new();
+        for day in 0..56_i64 {
+            for hour in [7, 19_i64] {
+                let ts = start + time::Duration::days(day) + time::Duration::hours(hour);
+                let success = hour == 19 || day % 7 < 5;
+                outcomes.push(DecisionOutcome {
+                    decision_id: format!("{day}-{hour}"),
+                    ts: ts.format(&Rfc3339).unwrap(),
+                    policy_id: None,
+                    action: Some("remind.morning".into()),
+                    outcome: if success {
+                        OutcomeType::Success
+                    } else {
+                        OutcomeType::Failure
+                    },
+                    success,
+                    reward: None,
+                    context: None,
+                    metadata: None,
+                });
+            }
+        }
+        outcomes
+    }
+
+    #[test]
+    fn weekend_failures_become_seasonal_slots() {
+        let slots = detect_seasonality(&history());
+        let keys: Vec<String> = slots.iter().map(SeasonalSlot::delta_key).collect();
+        assert!(keys.contains(&"seasonal.remind.morning.weekday.sat".to_string()));
+        assert!(keys.contains(&"seasonal.remind.morning.weekday.sun".to_string()));
+        assert!(!keys.iter().any(|k| k.contains("weekday.mon")));
+
+        let saturday = slots
+            .iter()
+            .find(|s| s.season == Season::DayOfWeek && s.bucket == 5)
+            .unwrap();
+        assert!((saturday.failure_rate - 0.5).abs() < 1e-6);
+        assert!(saturday.z < 0.0);
+        assert!(matches!(
+            saturday.delta(),
+            DeltaValue::Relative { value, .. } if value < -30.0
+        ));
+        assert_eq!(Season::DayOfWeek.label(5, Language::De), "samstags");
+        assert_eq!(seasonal_percent(0.0, 1.0), MAX_SEASONAL_PERCENT);
+
+        let proposal = crate::FeedbackAnalyzer::default()
+            .propose_adjustment("remind-bandit", &history())
+            .unwrap();
+        assert!(proposal
+            .deltas
+            .contains_key("seasonal.remind.morning.weekday.sun"));
+        assert!(proposal
+            .reasoning
+            .is_some_and(|r| r.contains("'remind.morning' on Sunday")));
+    }
+}
@@ -0,0 +1,269 @@
+//! Outcome stream tap for third-party sinks.
+//!
+//! An [`OutcomeTap`] forwards every processed [`DecisionOutcome`] to a list of
+//! registered [`OutcomeSink`]s. This lets integrators mirror learning data into
+//! their own analytics stack (files, webhooks, message brokers) without
+//! touching the analysis pipeline.
+//!
+//! Only a JSONL sink ships with this crate. Transports with extra dependencies
+//! (HTTP, MQTT) are plugged in via [`FnSink`] or a custom [`OutcomeSink`] impl.
+
+use crate::DecisionOutcome;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Errors raised by an [`OutcomeSink`].
+#[derive(Debug, thiserror::Error)]
+pub enum SinkError {
+    /// Writing to the underlying destination failed.
+    #[error("sink I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+    /// The outcome could not be serialized.
+    #[error("outcome serialization failed: {0}")]
+    Serialize(#[from] serde_json::Error),
+    /// A custom sink reported a failure.
+    #[error("{0}")]
+    Custom(String),
+}
+
+impl From<SinkError> for heimlern_core::HeimlernError {
+    fn from(err: SinkError) -> Self {
+        let base = match &err {
+            SinkError::Serialize(_) => Self::contract(err.to_string()),
+            SinkError::Io(_) | SinkError::Custom(_) => Self::transport(err.to_string()),
+        };
+        base.with_source(err)
+    }
+}
+
+/// Destination for forwarded decision outcomes.
+pub trait OutcomeSink: Send {
+    /// Human-readable name used in error reports.
+    fn name(&self) -> &str;
+
+    /// Forwards a single outcome.
+    ///
+    /// # Errors
+    /// Returns a [`SinkError`] if the outcome could not be delivered.
+    fn send(&mut self, outcome: &DecisionOutcome) -> Result<(), SinkError>;
+
+    /// Flushes buffered data. The default implementation does nothing.
+    ///
+    /// # Errors
+    /// Returns a [`SinkError`] if buffered data could not be written.
+    fn flush(&mut self) -> Result<(), SinkError> {
+        Ok(())
+    }
+}
+
+/// Writes one JSON object per line to any [`Write`] destination.
+pub struct JsonlSink<W: Write + Send> {
+    name: String,
+    writer: W,
+}
+
+impl<W: Write + Send> JsonlSink<W> {
+    /// Wraps an arbitrary writer.
+    pub fn new(name: impl Into<String>, writer: W) -> Self {
+        Self {
+            name: name.into(),
+            writer,
+        }
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl JsonlSink<BufWriter<File>> {
+    /// Opens `path` in append mode, creating it if necessary.
+    ///
+    /// # Errors
+    /// Returns an I/O error if the file cannot be opened.
+    pub fn append(path: impl AsRef<Path>) -> Result<Self, SinkError> {
+        let path = path.as_ref();
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::new(path.display().to_string(), BufWriter::new(file)))
+    }
+}
+
+impl<W: Write + Send> OutcomeSink for JsonlSink<W> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn send(&mut self, outcome: &DecisionOutcome) -> Result<(), SinkError> {
+        serde_json::to_writer(&mut self.writer, outcome)?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), SinkError> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Adapter that turns a closure into an [`OutcomeSink`].
+///
+/// Intended for transports such as webhooks or MQTT topics whose clients live
+/// in the embedding application.
+pub struct FnSink<F> {
+    name: String,
+    f: F,
+}
+
+impl<F> FnSink<F>
+where
+    F: FnMut(&DecisionOutcome) -> Result<(), SinkError> + Send,
+{
+    /// Creates a named closure sink.
+    pub fn new(name: impl Into<String>, f: F) -> Self {
+        Self {
+            name: name.into(),
+            f,
+        }
+    }
+}
+
+impl<F> OutcomeSink for FnSink<F>
+where
+    F: FnMut(&DecisionOutcome) -> Result<(), SinkError> + Send,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn send(&mut self, outcome: &DecisionOutcome) -> Result<(), SinkError> {
+        (self.f)(outcome)
+    }
+}
+
+/// Fan-out of processed outcomes to all registered sinks.
+///
+/// A failing sink never blocks the others; failures are returned to the
+/// caller together with the sink name.
+#[derive(Default)]
+pub struct OutcomeTap {
+    sinks: Vec<Box<dyn OutcomeSink>>,
+}
+
+impl std::fmt::Debug for OutcomeTap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OutcomeTap")
+            .field(
+                "sinks",
+                &self.sinks.iter().map(|s| s.name()).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl OutcomeTap {
+    /// Creates an empty tap.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an additional sink.
+    pub fn register(&mut self, sink: impl OutcomeSink + 'static) -> &mut Self {
+        self.sinks.push(Box::new(sink));
+        self
+    }
+
+    /// Number of registered sinks.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.sinks.len()
+    }
+
+    /// Returns `true` if no sink is registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.sinks.is_empty()
+    }
+
+    /// Forwards `outcome` to every sink and collects the failures.
+    pub fn forward(&mut self, outcome: &DecisionOutcome) -> Vec<(String, SinkError)> {
+        self.sinks
+            .iter_mut()
+            .filter_map(|sink| {
+                sink.send(outcome)
+                    .err()
+                    .map(|e| (sink.name().to_string(), e))
+            })
+            .collect()
+    }
+
+    /// Flushes every sink and collects the failures.
+    pub fn flush(&mut self) -> Vec<(String, SinkError)> {
+        self.sinks
+            .iter_mut()
+            .filter_map(|sink| sink.flush().err().map(|e| (sink.name().to_string(), e)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::OutcomeType;
+    use std::sync::{Arc, Mutex};
+
+    fn outcome(id: &str) -> DecisionOutcome {
+        DecisionOutcome {
+            decision_id: id.to_string(),
+            ts: "2026-01-01T00:00:00Z".to_string(),
+            policy_id: None,
+            action: None,
+            outcome: OutcomeType::Success,
+            success: true,
+            reward: Some(1.0),
+            context: None,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn jsonl_sink_writes_one_line_per_outcome() {
+        let mut sink = JsonlSink::new("mem", Vec::new());
+        sink.send(&outcome("a")).unwrap();
+        sink.send(&outcome("b")).unwrap();
+        let text = String::from_utf8(sink.into_inner()).unwrap();
+        let ids: Vec<String> = text
+            .lines()
+            .map(|l| {
+                serde_json::from_str::<DecisionOutcome>(l)
+                    .unwrap()
+                    .decision_id
+            })
+            .collect();
+        assert_eq!(ids, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn failing_sink_does_not_block_others() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = Arc::clone(&seen);
+
+        let mut tap = OutcomeTap::new();
+        tap.register(FnSink::new("broken", |_: &DecisionOutcome| {
+            Err(SinkError::Custom("offline".into()))
+        }))
+        .register(FnSink::new("collector", move |o: &DecisionOutcome| {
+            seen_clone.lock().unwrap().push(o.decision_id.clone());
+            Ok(())
+        }));
+
+        let errors = tap.forward(&outcome("x"));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, "broken");
+        assert_eq!(*seen.lock().unwrap(), vec!["x".to_string()]);
+    }
+}
@@ -0,0 +1,180 @@
+//! Clock-skew detection between outcome sources.
+//!
+//! A sensor hub whose clock runs seven minutes fast produces outcomes that
+//! appear to precede their decisions, and merged logs interleave in the wrong
+//! order. When several sources report on the same decision, the differences
+//! between their timestamps reveal the offset: [`estimate_skew`] takes the
+//! median difference per source against a reference source, and
+//! [`correct_skew`] shifts the affected timestamps back.
+//!
+//! Every corrected outcome keeps its original timestamp and the applied offset
+//! under `metadata.clock_skew`, so the adjustment stays auditable.
+
+use crate::merge::outcome_source;
+use crate::DecisionOutcome;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::BTreeMap;
+use time::{format_description::well_known::Rfc3339, Duration, OffsetDateTime};
+
+/// Metadata key under which a correction is recorded.
+pub const CLOCK_SKEW_METADATA_KEY: &str = "clock_skew";
+
+/// Estimated clock offset of one source.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SkewEstimate {
+    pub source: String,
+    /// Seconds the source's clock runs ahead of the reference (negative: behind).
+    pub offset_seconds: f64,
+    /// Number of decisions seen by both the source and the reference.
+    pub samples: usize,
+}
+
+fn median(values: &mut [f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    Some(if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    })
+}
+
+/// Estimates the offset of every source against `reference`.
+///
+/// Only decisions reported by both a source and the reference contribute;
+/// sources with fewer than `min_samples` shared decisions are left out. The
+/// median keeps a few genuinely late reports from distorting the estimate.
+#[must_use]
+pub fn estimate_skew(
+    outcomes: &[DecisionOutcome],
+    reference: &str,
+    min_samples: usize,
+) -> Vec<SkewEstimate> {
+    let mut reference_ts: BTreeMap<&str, OffsetDateTime> = BTreeMap::new();
+    for outcome in outcomes {
+        if outcome_source(outcome) == Some(reference) {
+            if let Ok(ts) = OffsetDateTime::parse(&outcome.ts, &Rfc3339) {
+                reference_ts.insert(outcome.decision_id.as_str(), ts);
+            }
+        }
+    }
+
+    let mut diffs: BTreeMap<&str, Vec<f64>> = BTreeMap::new();
+    for outcome in outcomes {
+        let Some(source) = outcome_source(outcome).filter(|s| *s != reference) else {
+            continue;
+        };
+        let Some(base) = reference_ts.get(outcome.decision_id.as_str()) else {
+            continue;
+        };
+        if let Ok(ts) = OffsetDateTime::parse(&outcome.ts, &Rfc3339) {
+            diffs
+                .entry(source)
+                .or_default()
+                .push((ts - *base).as_seconds_f64());
+        }
+    }
+
+    diffs
+        .into_iter()
+        .filter(|(_, values)| values.len() >= min_samples.max(1))
+        .filter_map(|(source, mut values)| {
+            let samples = values.len();
+            median(&mut values).map(|offset_seconds| SkewEstimate {
+                source: source.to_string(),
+                offset_seconds,
+                samples,
+            })
+        })
+        .collect()
+}
+
+/// Shifts timestamps of skewed sources by their estimated offset.
+///
+/// Estimates with an absolute offset below `tolerance_seconds` are ignored.
+/// Returns the number of corrected outcomes.
+pub fn correct_skew(
+    outcomes: &mut [DecisionOutcome],
+    estimates: &[SkewEstimate],
+    tolerance_seconds: f64,
+) -> usize {
+    let offsets: BTreeMap<&str, f64> = estimates
+        .iter()
+        .filter(|e| e.offset_seconds.abs() >= tolerance_seconds)
+        .map(|e| (e.source.as_str(), e.offset_seconds))
+        .collect();
+
+    let mut corrected = 0;
+    for outcome in outcomes.iter_mut() {
+        let Some(offset) = outcome_source(outcome)
+            .and_then(|s| offsets.get(s))
+            .copied()
+        else {
+            continue;
+        };
+        let Ok(ts) = OffsetDateTime::parse(&outcome.ts, &Rfc3339) else {
+            continue;
+        };
+        let Ok(adjusted) = (ts - Duration::seconds_f64(offset)).format(&Rfc3339) else {
+            continue;
+        };
+        let record = json!({ "offset_seconds": offset, "original_ts": outcome.ts });
+        match outcome.metadata.as_mut().and_then(|m| m.as_object_mut()) {
+            Some(map) => {
+                map.insert(CLOCK_SKEW_METADATA_KEY.to_string(), record);
+            }
+            None => outcome.metadata = Some(json!({ CLOCK_SKEW_METADATA_KEY: record })),
+        }
+        outcome.ts = adjusted;
+        corrected += 1;
+    }
+    corrected
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn outcome(id: &str, ts: &str, source: &str) -> DecisionOutcome {
+        serde_json::from_value(json!({
+            "decision_id": id,
+            "ts": ts,
+            "outcome": "success",
+            "success": true,
+            "metadata": { "source": source },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn detects_and_corrects_fast_source() {
+        let mut outcomes = vec![
+            outcome("a", "2026-01-01T10:00:00Z", "server"),
+            outcome("b", "2026-01-01T11:00:00Z", "server"),
+            outcome("c", "2026-01-01T12:00:00Z", "server"),
+            outcome("a", "2026-01-01T10:07:00Z", "hub"),
+            outcome("b", "2026-01-01T11:07:00Z", "hub"),
+            // A genuinely late report must not pull the estimate.
+            outcome("c", "2026-01-01T12:30:00Z", "hub"),
+        ];
+
+        let estimates = estimate_skew(&outcomes, "server", 3);
+        assert_eq!(estimates.len(), 1);
+        assert_eq!(estimates[0].source, "hub");
+        assert!((estimates[0].offset_seconds - 420.0).abs() < 1e-9);
+        assert!(estimate_skew(&outcomes, "server", 4).is_empty());
+
+        assert_eq!(correct_skew(&mut outcomes, &estimates, 1.0), 3);
+        assert_eq!(outcomes[3].ts, "2026-01-01T10:00:00Z");
+        let record = &outcomes[3].metadata.as_ref().unwrap()[CLOCK_SKEW_METADATA_KEY];
+        assert_eq!(record["original_ts"], "2026-01-01T10:07:00Z");
+        assert_eq!(outcomes[0].ts, "2026-01-01T10:00:00Z");
+        assert_eq!(correct_skew(&mut outcomes, &estimates, 600.0), 0);
+    }
+}
@@ -0,0 +1,525 @@
+//! Persistent proposal store with supersede chains.
+//!
+//! A [`ProposalStore`] owns a directory in the layout the CLI already uses:
+//! every proposal lives as `<dir>/<id>.json`. Since `WeightAdjustmentProposal`
+//! is a closed schema, ids and links are kept in an append-only index,
+//! `<dir>/history.jsonl`, with one [`ProposalRecord`] per stored proposal.
+//!
+//! Storing a proposal for a `basis_policy` that already has one links the
+//! new record to its predecessor. A predecessor still in
+//! [`ProposalStatus::Proposed`] is marked [`ProposalStatus::Superseded`];
+//! accepted or rejected ones keep their status, the link only records the
+//! order.
+//!
+//! A store opened with [`ProposalStore::with_ttl`] records an `expires_at`
+//! per proposal, and [`ProposalStore::expire_stale`] retires pending
+//! proposals whose evidence is out of date (see [`crate::expiry`]).
+//!
+//! Every write also goes to the hash-chained audit log `<dir>/audit.jsonl`
+//! (see [`crate::audit`]); [`ProposalStore::verify`] checks the chain and
+//! every proposal file against it.
+
+use crate::audit::{AuditError, AuditEvent, AuditIssue, AuditLog};
+use crate::expiry::{expires_at, staleness, Staleness};
+use crate::DecisionOutcome;
+use crate::{ProposalStatus, WeightAdjustmentProposal};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use time::OffsetDateTime;
+
+/// File name of the index inside the store directory.
+pub const HISTORY_FILE: &str = "history.jsonl";
+
+/// File name of the audit log inside the store directory.
+pub const AUDIT_FILE: &str = "audit.jsonl";
+
+/// Errors raised by a [`ProposalStore`].
+#[derive(Debug, thiserror::Error)]
+pub enum ProposalStoreError {
+    #[error("proposal store I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid history entry on line {line}: {source}")]
+    Parse {
+        line: usize,
+        source: serde_json::Error,
+    },
+    #[error("invalid proposal '{id}': {source}")]
+    Proposal {
+        id: String,
+        source: serde_json::Error,
+    },
+    #[error("proposal serialization failed: {0}")]
+    Serialize(#[from] serde_json::Error),
+    #[error("unknown proposal '{0}'")]
+    UnknownId(String),
+    #[error(transparent)]
+    Audit(#[from] AuditError),
+}
+
+/// Index entry of one stored proposal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposalRecord {
+    pub id: String,
+    pub basis_policy: String,
+    /// When the proposal was stored (RFC 3339).
+    pub created: String,
+    /// Previous proposal for the same `basis_policy`, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supersedes: Option<String>,
+    /// When the proposal stops being applicable (RFC 3339), if the store
+    /// has a TTL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+}
+
+/// Directory-backed store of weight adjustment proposals.
+#[derive(Debug, Clone)]
+pub struct ProposalStore {
+    dir: PathBuf,
+    records: Vec<ProposalRecord>,
+    audit: AuditLog,
+    ttl_seconds: Option<i64>,
+}
+
+impl ProposalStore {
+    /// Open the store in `dir`, creating the directory if needed.
+    ///
+    /// # Errors
+    /// Fails if the directory cannot be created or the index cannot be read.
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, ProposalStoreError> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        let records = read_history(&dir.join(HISTORY_FILE))?;
+        let audit = AuditLog::open(dir.join(AUDIT_FILE))?;
+        Ok(Self {
+            dir,
+            records,
+            audit,
+            ttl_seconds: None,
+        })
+    }
+
+    /// Give proposals stored from now on a time to live of `seconds` from
+    /// their `ts`.
+    #[must_use]
+    pub fn with_ttl(mut self, seconds: i64) -> Self {
+        self.ttl_seconds = Some(seconds);
+        self
+    }
+
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// All records in the order they were stored.
+    #[must_use]
+    pub fn records(&self) -> &[ProposalRecord] {
+        &self.records
+    }
+
+    #[must_use]
+    pub fn audit(&self) -> &AuditLog {
+        &self.audit
+    }
+
+    /// Check the audit chain and every stored proposal against its last
+    /// audit entry; empty if nothing was tampered with.
+    ///
+    /// # Errors
+    /// Fails if a proposal cannot be read or hashed.
+    pub fn verify(&self) -> Result<Vec<AuditIssue>, ProposalStoreError> {
+        let mut issues = self.audit.verify()?;
+        for record in &self.records {
+            let proposal = self.get(&record.id)?;
+            issues.extend(self.audit.check_proposal(&record.id, &proposal)?);
+        }
+        Ok(issues)
+    }
+
+    /// Path of the proposal file for `id`.
+    #[must_use]
+    pub fn path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.json"))
+    }
+
+    /// Store `proposal` under a fresh id and return the id.
+    ///
+    /// # Errors
+    /// Fails if a file cannot be written or the predecessor cannot be read.
+    pub fn add(
+        &mut self,
+        proposal: &WeightAdjustmentProposal,
+    ) -> Result<String, ProposalStoreError> {
+        let id = self.next_id();
+        let supersedes = self
+            .latest(&proposal.basis_policy)
+            .map(|record| record.id.clone());
+        if let Some(previous) = &supersedes {
+            let mut stored = self.get(previous)?;
+            if stored.status == ProposalStatus::Proposed {
+                stored.status = ProposalStatus::Superseded;
+                // The contract requires a reason for every settled status.
+                stored
+                    .reasoning
+                    .get_or_insert_with(|| format!("superseded by {id}"));
+                self.write(previous, &stored)?;
+                self.audit
+                    .record(AuditEvent::StatusChanged, previous, &stored)?;
+            }
+        }
+
+        self.write(&id, proposal)?;
+        self.audit.record(AuditEvent::Stored, &id, proposal)?;
+        let record = ProposalRecord {
+            id: id.clone(),
+            basis_policy: proposal.basis_policy.clone(),
+            created: crate::iso8601_now(),
+            supersedes,
+            expires_at: self.ttl_seconds.and_then(|ttl| expires_at(proposal, ttl)),
+        };
+        let line = serde_json::to_string(&record)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.dir.join(HISTORY_FILE))?;
+        writeln!(file, "{line}")?;
+        self.records.push(record);
+        Ok(id)
+    }
+
+    /// The stored proposal `id`.
+    ///
+    /// # Errors
+    /// Fails if `id` is unknown or its file cannot be read.
+    pub fn get(&self, id: &str) -> Result<WeightAdjustmentProposal, ProposalStoreError> {
+        let path = self.path(id);
+        if !path.exists() {
+            return Err(ProposalStoreError::UnknownId(id.to_string()));
+        }
+        serde_json::from_str(&fs::read_to_string(path)?).map_err(|source| {
+            ProposalStoreError::Proposal {
+                id: id.to_string(),
+                source,
+            }
+        })
+    }
+
+    /// Change the status of the stored proposal `id`.
+    ///
+    /// # Errors
+    /// Fails if `id` is unknown or its file cannot be rewritten.
+    pub fn set_status(
+        &mut self,
+        id: &str,
+        status: ProposalStatus,
+    ) -> Result<(), ProposalStoreError> {
+        let mut proposal = self.get(id)?;
+        proposal.status = status;
+        self.write(id, &proposal)?;
+        self.audit
+            .record(AuditEvent::StatusChanged, id, &proposal)?;
+        Ok(())
+    }
+
+    /// Staleness of the stored proposal `id` at `now`, given the outcomes
+    /// recorded so far.
+    ///
+    /// # Errors
+    /// Fails if `id` is unknown or its file cannot be read.
+    pub fn staleness(
+        &self,
+        id: &str,
+        outcomes: &[DecisionOutcome],
+        now: OffsetDateTime,
+    ) -> Result<Staleness, ProposalStoreError> {
+        let proposal = self.get(id)?;
+        let expires_at = self
+            .records
+            .iter()
+            .find(|r| r.id == id)
+            .and_then(|r| r.expires_at.as_deref());
+        Ok(staleness(&proposal, expires_at, outcomes, now))
+    }
+
+    /// Mark every pending proposal that is stale at `now` as
+    /// [`ProposalStatus::Superseded`]; returns the retired ids and why.
+    ///
+    /// # Errors
+    /// Fails if a proposal cannot be read or rewritten.
+    pub fn expire_stale(
+        &mut self,
+        outcomes: &[DecisionOutcome],
+        now: OffsetDateTime,
+    ) -> Result<Vec<(String, Staleness)>, ProposalStoreError> {
+        let mut retired = Vec::new();
+        let ids: Vec<String> = self.records.iter().map(|r| r.id.clone()).collect();
+        for id in ids {
+            if self.get(&id)?.status != ProposalStatus::Proposed {
+                continue;
+            }
+            let staleness = self.staleness(&id, outcomes, now)?;
+            if staleness.is_stale() {
+                let mut proposal = self.get(&id)?;
+                proposal.status = ProposalStatus::Superseded;
+                proposal.reasoning.get_or_insert_with(|| match staleness {
+                    Staleness::Expired { .. } => "expired".to_string(),
+                    _ => "outdated by newer outcomes".to_string(),
+                });
+                self.write(&id, &proposal)?;
+                self.audit
+                    .record(AuditEvent::StatusChanged, &id, &proposal)?;
+                retired.push((id, staleness));
+            }
+        }
+        Ok(retired)
+    }
+
+    /// The most recent record for `basis_policy`.
+    #[must_use]
+    pub fn latest(&self, basis_policy: &str) -> Option<&ProposalRecord> {
+        self.records
+            .iter()
+            .rev()
+            .find(|record| record.basis_policy == basis_policy)
+    }
+
+    /// The record that superseded `id`, if any.
+    #[must_use]
+    pub fn superseded_by(&self, id: &str) -> Option<&ProposalRecord> {
+        self.records
+            .iter()
+            .find(|record| record.supersedes.as_deref() == Some(id))
+    }
+
+    /// `id` followed by its predecessors, newest first.
+    #[must_use]
+    pub fn chain(&self, id: &str) -> Vec<&ProposalRecord> {
+        let mut chain = Vec::new();
+        let mut next = Some(id);
+        while let Some(id) = next {
+            let Some(record) = self.records.iter().find(|r| r.id == id) else {
+                break;
+            };
+            if chain.len() >= self.records.len() {
+                break;
+            }
+            chain.push(record);
+            next = record.supersedes.as_deref();
+        }
+        chain
+    }
+
+    fn next_id(&self) -> String {
+        (self.records.len() + 1..)
+            .map(|n| format!("p{n:06}"))
+            .find(|id| !self.path(id).exists())
+            .unwrap_or_default()
+    }
+
+    fn write(
+        &self,
+        id: &str,
+        proposal: &WeightAdjustmentProposal,
+    ) -> Result<(), ProposalStoreError> {
+        fs::write(self.path(id), serde_json::to_string_pretty(proposal)?)?;
+        Ok(())
+    }
+}
+
+fn read_history(path: &Path) -> Result<Vec<ProposalRecord>, ProposalStoreError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let mut records = Vec::new();
+    for (index, line) in BufReader::new(File::open(path)?).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record = serde_json::from_str(&line).map_err(|source| ProposalStoreError::Parse {
+            line: index + 1,
+            source,
+        })?;
+        records.push(record);
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::{DeltaValue, Evidence};
+    use std::collections::BTreeMap;
+
+    fn proposal(basis_policy: &str, epsilon: f32) -> WeightAdjustmentProposal {
+        WeightAdjustmentProposal {
+            version: "v1".into(),
+            basis_policy: basis_policy.into(),
+            ts: "2026-01-01T00:00:00Z".into(),
+            deltas: BTreeMap::from([("epsilon".into(), DeltaValue::Absolute { value: epsilon })]),
+            confidence: 0.8,
+            evidence: Evidence::default(),
+            reasoning: None,
+            status: ProposalStatus::Proposed,
+        }
+    }
+
+    #[test]
+    fn new_proposal_supersedes_pending_predecessor() {
+        let dir = std::env::temp_dir().join(format!("heimlern-store-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut store = ProposalStore::open(&dir).unwrap();
+        let first = store.add(&proposal("remind-bandit", 0.1)).unwrap();
+        let other = store.add(&proposal("ucb", 0.3)).unwrap();
+        let accepted = store.add(&proposal("remind-bandit", 0.2)).unwrap();
+        store
+            .set_status(&accepted, ProposalStatus::Accepted)
+            .unwrap();
+        let latest = store.add(&proposal("remind-bandit", 0.15)).unwrap();
+
+        let status = |id: &str| store.get(id).map(|p| p.status).ok();
+        assert_eq!(status(&first), Some(ProposalStatus::Superseded));
+        assert_eq!(status(&other), Some(ProposalStatus::Proposed));
+        assert_eq!(status(&accepted), Some(ProposalStatus::Accepted));
+        assert_eq!(status(&latest), Some(ProposalStatus::Proposed));
+        assert_eq!(
+            store.superseded_by(&first).map(|r| r.id.as_str()),
+            Some(accepted.as_str())
+        );
+
+        let reopened = ProposalStore::open(&dir).unwrap();
+        let chain: Vec<&str> = reopened
+            .chain(&latest)
+            .iter()
+            .map(|r| r.id.as_str())
+            .collect();
+        assert_eq!(
+            chain,
+            vec![latest.as_str(), accepted.as_str(), first.as_str()]
+        );
+        assert_eq!(
+            reopened.latest("ucb").map(|r| r.id.as_str()),
+            Some(other.as_str())
+        );
+        assert!(matches!(
+            reopened.get("p999999"),
+            Err(ProposalStoreError::UnknownId(_))
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn stale_proposals_are_retired() {
+        let dir = std::env::temp_dir().join(format!("heimlern-expiry-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut store = ProposalStore::open(&dir).unwrap().with_ttl(30 * 86_400);
+        let mut old = proposal("remind-bandit", 0.1);
+        old.evidence.decisions_analyzed = 10;
+        let old = store.add(&old).unwrap();
+        let mut busy = proposal("ucb", 0.2);
+        busy.evidence.decisions_analyzed = 10;
+        let busy = store.add(&busy).unwrap();
+        assert_eq!(
+            store.records()[0].expires_at.as_deref(),
+            Some("2026-01-31T00:00:00Z")
+        );
+
+        let outcome = |ts: &str| DecisionOutcome {
+            decision_id: ts.into(),
+            ts: ts.into(),
+            policy_id: None,
+            action: None,
+            outcome: crate::OutcomeType::Success,
+            success: true,
+            reward: None,
+            context: None,
+            metadata: None,
+        };
+        let newer: Vec<DecisionOutcome> = (10..21)
+            .map(|day| outcome(&format!("2026-01-{day}T00:00:00Z")))
+            .collect();
+        let now = OffsetDateTime::parse(
+            "2026-01-21T00:00:00Z",
+            &time::format_description::well_known::Rfc3339,
+        )
+        .unwrap();
+        assert_eq!(
+            store.staleness(&busy, &newer[..5], now).unwrap(),
+            Staleness::Fresh
+        );
+
+        let retired = store.expire_stale(&newer, now).unwrap();
+        assert_eq!(retired.len(), 2);
+        assert!(matches!(
+            retired[0].1,
+            Staleness::Outdated {
+                newer_outcomes: 11,
+                decisions_analyzed: 10
+            }
+        ));
+        assert_eq!(store.get(&old).unwrap().status, ProposalStatus::Superseded);
+
+        let fresh = store.add(&proposal("remind-bandit", 0.3)).unwrap();
+        let later = now + time::Duration::days(20);
+        let retired = store.expire_stale(&[], later).unwrap();
+        assert_eq!(
+            retired,
+            vec![(
+                fresh,
+                Staleness::Expired {
+                    expires_at: "2026-01-31T00:00:00Z".into()
+                }
+            )]
+        );
+        assert!(store.verify().unwrap().is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn audit_trail_detects_tampering() {
+        let dir = std::env::temp_dir().join(format!("heimlern-audit-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut store = ProposalStore::open(&dir).unwrap();
+        let first = store.add(&proposal("remind-bandit", 0.1)).unwrap();
+        let second = store.add(&proposal("remind-bandit", 0.2)).unwrap();
+        store.set_status(&second, ProposalStatus::Accepted).unwrap();
+        assert_eq!(store.audit().entries().len(), 4);
+        assert!(store.verify().unwrap().is_empty());
+
+        // Edit a proposal behind the store's back.
+        let mut edited = store.get(&first).unwrap();
+        edited
+            .deltas
+            .insert("epsilon".into(), DeltaValue::Absolute { value: 0.9 });
+        fs::write(store.path(&first), serde_json::to_string(&edited).unwrap()).unwrap();
+        let issues = ProposalStore::open(&dir).unwrap().verify().unwrap();
+        assert!(matches!(
+            issues.as_slice(),
+            [AuditIssue::ProposalChanged { id, .. }] if id == &first
+        ));
+
+        // Rewrite the audit entry to match: the chain breaks instead.
+        let log = dir.join(AUDIT_FILE);
+        let raw = fs::read_to_string(&log).unwrap();
+        let mut lines: Vec<String> = raw.lines().map(String::from).collect();
+        let mut entry: crate::audit::AuditEntry = serde_json::from_str(&lines[0]).unwrap();
+        entry.proposal_digest = crate::provenance::proposal_digest(&edited).unwrap();
+        entry.hash = entry.compute_hash().unwrap();
+        lines[0] = serde_json::to_string(&entry).unwrap();
+        fs::write(&log, lines.join("\n") + "\n").unwrap();
+        let issues = ProposalStore::open(&dir).unwrap().verify().unwrap();
+        assert!(matches!(
+            issues.as_slice(),
+            [AuditIssue::BrokenLink { seq: 2, .. }, ..]
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,156 @@
+//! Concrete decisions behind detected patterns.
+//!
+//! Evidence in a proposal is aggregate: "High failure rate (70.0%) for action
+//! 'remind.morning'". A reviewer who wants to see those failures needs the
+//! decision ids. [`FeedbackAnalyzer::propose_with_samples`] collects, for
+//! every pattern, the ids of the first [`MAX_SUPPORTING_DECISIONS`] outcomes
+//! that exhibit it, in recorded order, together with the number of matching
+//! outcomes in total.
+//!
+//! The `evidence` object of `policy.weight_adjustment.v1` is closed, so the
+//! samples travel as an [`EvidenceSamples`] sidecar, like provenance.
+//!
+//! [`FeedbackAnalyzer::propose_with_samples`]: crate::FeedbackAnalyzer::propose_with_samples
+
+use crate::explain::{Language, Pattern};
+use crate::latency::decision_latency;
+use crate::veto::VetoRecord;
+use crate::{outcome_is_success, DecisionOutcome, PATTERN_SLOW_RESPONSE_SECONDS};
+use serde::{Deserialize, Serialize};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+/// Largest number of decision ids kept per pattern.
+pub const MAX_SUPPORTING_DECISIONS: usize = 20;
+
+/// Decisions exhibiting one pattern.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PatternSupport {
+    /// The pattern as rendered into `evidence.patterns`.
+    pub pattern: String,
+    /// Ids of the first matching decisions, in recorded order.
+    pub decision_ids: Vec<String>,
+    /// Number of matching decisions, including those not listed.
+    pub matching: usize,
+}
+
+impl PatternSupport {
+    /// Collect up to `limit` decisions of `outcomes` exhibiting `pattern`.
+    #[must_use]
+    pub fn collect(
+        pattern: &Pattern,
+        outcomes: &[DecisionOutcome],
+        limit: usize,
+        lang: Language,
+    ) -> Self {
+        let mut decision_ids = Vec::new();
+        let mut matching = 0;
+        for outcome in outcomes.iter().filter(|o| supports(pattern, o)) {
+            matching += 1;
+            if decision_ids.len() < limit {
+                decision_ids.push(outcome.decision_id.clone());
+            }
+        }
+        Self {
+            pattern: pattern.render(lang),
+            decision_ids,
+            matching,
+        }
+    }
+}
+
+/// Sidecar listing the decisions behind a proposal's patterns, in the order
+/// of `evidence.patterns`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvidenceSamples {
+    pub patterns: Vec<PatternSupport>,
+}
+
+/// Whether `outcome` is an instance of `pattern`. Custom patterns have no
+/// known instances.
+#[must_use]
+pub fn supports(pattern: &Pattern, outcome: &DecisionOutcome) -> bool {
+    let is_action = |action: &str| outcome.action.as_deref() == Some(action);
+    let explicit_failure =
+        !outcome.is_censored() && !outcome.is_override() && !outcome_is_success(outcome);
+    match pattern {
+        Pattern::ActionFailures { action, .. } => is_action(action) && explicit_failure,
+        Pattern::ActionIgnored { action, .. } => is_action(action) && outcome.is_censored(),
+        Pattern::ActionOverridden { action, .. } => is_action(action) && outcome.is_override(),
+        Pattern::OverallFailures { .. } => explicit_failure,
+        Pattern::Vetoes { constraint, .. } => VetoRecord::from_outcome(outcome)
+            .is_some_and(|veto| constraint.is_none() || veto.constraint == *constraint),
+        Pattern::LateAcknowledgement { action, .. } => {
+            is_action(action)
+                && decision_latency(outcome).is_some_and(|s| s > PATTERN_SLOW_RESPONSE_SECONDS)
+        }
+        Pattern::Seasonal {
+            action,
+            season,
+            bucket,
+            rate,
+            baseline,
+        } => {
+            let in_bucket = OffsetDateTime::parse(&outcome.ts, &Rfc3339)
+                .is_ok_and(|ts| season.bucket(ts) == *bucket);
+            let explicit = !outcome.is_censored() && !outcome.is_override();
+            // A bucket that fails more often is shown by its failures, one
+            // that fails less often by its successes.
+            is_action(action)
+                && in_bucket
+                && explicit
+                && (rate > baseline) != outcome_is_success(outcome)
+        }
+        Pattern::Custom { .. } => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FeedbackAnalyzer, OutcomeType};
+
+    fn outcome(id: usize, action: &str, outcome: OutcomeType) -> DecisionOutcome {
+        DecisionOutcome {
+            decision_id: format!("d{id}"),
+            ts: "2026-01-01T00:00:00Z".into(),
+            policy_id: None,
+            action: Some(action.into()),
+            success: outcome == OutcomeType::Success,
+            outcome,
+            reward: None,
+            context: None,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn samples_list_the_failing_decisions() {
+        let mut outcomes: Vec<DecisionOutcome> = (0..30)
+            .map(|i| {
+                let kind = if i % 10 < 7 {
+                    OutcomeType::Failure
+                } else {
+                    OutcomeType::Success
+                };
+                outcome(i, "remind.morning", kind)
+            })
+            .collect();
+        outcomes.push(outcome(30, "remind.morning", OutcomeType::Censored));
+        outcomes.push(outcome(31, "remind.evening", OutcomeType::Success));
+
+        let (proposal, samples) = FeedbackAnalyzer::default()
+            .propose_with_samples("remind-bandit", &outcomes)
+            .unwrap_or_else(|| panic!("failures lead to a proposal"));
+        assert_eq!(
+            proposal.evidence.patterns.as_ref().map(Vec::len),
+            Some(samples.patterns.len())
+        );
+        let action = &samples.patterns[0];
+        assert!(action.pattern.contains("'remind.morning'"));
+        assert_eq!(action.matching, 21);
+        assert_eq!(action.decision_ids.len(), MAX_SUPPORTING_DECISIONS);
+        assert_eq!(action.decision_ids[..3], ["d0", "d1", "d2"]);
+        assert!(!action.decision_ids.contains(&"d7".to_string()));
+        assert!(!action.decision_ids.contains(&"d30".to_string()));
+    }
+}
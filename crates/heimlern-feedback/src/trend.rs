@@ -0,0 +1,299 @@
+//! Time-windowed trend analysis of outcomes.
+//!
+//! Outcomes are bucketed into daily or weekly windows (UTC, weeks start on
+//! Monday) and the failure rate per window is tested for a monotonic trend
+//! with the Mann-Kendall test. A trend is only reported as improving or
+//! degrading when it is significant at the 95 % level; a weighted linear fit
+//! gives its size per window.
+//!
+//! [`FeedbackAnalyzer::propose_adjustment`] uses the daily trend to ignore
+//! failure patterns the policy has already recovered from: an improving trend
+//! whose latest window is back below the adjustment threshold means the
+//! failures belong to the past, not to the current weights.
+
+use crate::{DecisionOutcome, FeedbackAnalyzer, OutcomeStatistics};
+use heimlern_core::Uncertainty;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+/// Width of one analysis window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Window {
+    Daily,
+    Weekly,
+}
+
+impl Window {
+    fn seconds(self) -> i64 {
+        match self {
+            Self::Daily => 86_400,
+            Self::Weekly => 7 * 86_400,
+        }
+    }
+
+    /// Start of the window containing `unix` (Unix seconds).
+    fn start(self, unix: i64) -> i64 {
+        // 1970-01-01 was a Thursday; shift so weekly windows start on Monday.
+        let offset = match self {
+            Self::Daily => 0,
+            Self::Weekly => 3 * 86_400,
+        };
+        (unix + offset).div_euclid(self.seconds()) * self.seconds() - offset
+    }
+}
+
+/// Direction of a trend in the failure rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Trend {
+    /// The failure rate falls.
+    Improving,
+    /// The failure rate rises.
+    Degrading,
+    /// No significant monotonic change.
+    Stable,
+}
+
+/// Explicit outcomes of one window.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TimeBucket {
+    /// Start of the window (RFC 3339, UTC).
+    pub start: String,
+    pub total: usize,
+    pub failures: usize,
+    pub failure_rate: f32,
+}
+
+/// Trend of the failure rate across windows.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TrendReport {
+    pub window: Window,
+    /// Windows with at least one explicit outcome, oldest first.
+    pub buckets: Vec<TimeBucket>,
+    pub trend: Trend,
+    /// Change of the failure rate per window (weighted least squares).
+    pub slope_per_window: f32,
+    /// Mann-Kendall statistic `S`.
+    pub mann_kendall_s: i64,
+    /// Normal approximation of `S`; `|z| > 1.96` is significant.
+    pub z: f32,
+}
+
+impl TrendReport {
+    /// Whether failures were concentrated in the past: the trend improves and
+    /// the latest window's failure rate is at most `threshold`.
+    #[must_use]
+    pub fn recovered_below(&self, threshold: f32) -> bool {
+        self.trend == Trend::Improving
+            && self
+                .buckets
+                .last()
+                .is_some_and(|b| b.failure_rate <= threshold)
+    }
+}
+
+impl FeedbackAnalyzer {
+    /// Bucket explicit outcomes by `window`, oldest first.
+    ///
+    /// Outcomes with unparseable timestamps are skipped, as are censored and
+    /// overridden ones (see [`OutcomeStatistics`]).
+    #[must_use]
+    pub fn bucket_outcomes(&self, outcomes: &[DecisionOutcome], window: Window) -> Vec<TimeBucket> {
+        let mut windows: BTreeMap<i64, OutcomeStatistics> = BTreeMap::new();
+        for outcome in outcomes {
+            let Ok(ts) = OffsetDateTime::parse(&outcome.ts, &Rfc3339) else {
+                continue;
+            };
+            windows
+                .entry(window.start(ts.unix_timestamp()))
+                .or_default()
+                .record(outcome);
+        }
+        windows
+            .into_iter()
+            .filter(|(_, stats)| stats.total > 0)
+            .map(|(start, stats)| TimeBucket {
+                start: OffsetDateTime::from_unix_timestamp(start)
+                    .ok()
+                    .and_then(|t| t.format(&Rfc3339).ok())
+                    .unwrap_or_default(),
+                total: stats.total,
+                failures: stats.failures,
+                failure_rate: stats.failure_rate(),
+            })
+            .collect()
+    }
+
+    /// Trend of the failure rate across `window`s.
+    ///
+    /// Returns `None` with fewer than two populated windows.
+    #[must_use]
+    pub fn analyze_trend(
+        &self,
+        outcomes: &[DecisionOutcome],
+        window: Window,
+    ) -> Option<TrendReport> {
+        let buckets = self.bucket_outcomes(outcomes, window);
+        if buckets.len() < 2 {
+            return None;
+        }
+        let rates: Vec<f64> = buckets.iter().map(|b| f64::from(b.failure_rate)).collect();
+        let (s, z) = mann_kendall(&rates);
+        let trend = if z.abs() <= Uncertainty::Z_95 {
+            Trend::Stable
+        } else if z < 0.0 {
+            Trend::Improving
+        } else {
+            Trend::Degrading
+        };
+        #[allow(clippy::cast_possible_truncation)]
+        Some(TrendReport {
+            window,
+            slope_per_window: weighted_slope(&buckets) as f32,
+            buckets,
+            trend,
+            mann_kendall_s: s,
+            z: z as f32,
+        })
+    }
+}
+
+/// Mann-Kendall `S` and its z-score, with tie correction.
+fn mann_kendall(values: &[f64]) -> (i64, f64) {
+    let mut s = 0_i64;
+    for (i, a) in values.iter().enumerate() {
+        for b in &values[i + 1..] {
+            s += match b.partial_cmp(a) {
+                Some(std::cmp::Ordering::Greater) => 1,
+                Some(std::cmp::Ordering::Less) => -1,
+                _ => 0,
+            };
+        }
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let mut ties = 0.0;
+    for group in sorted.chunk_by(|a, b| a == b) {
+        #[allow(clippy::cast_precision_loss)]
+        let t = group.len() as f64;
+        ties += t * (t - 1.0) * (2.0 * t + 5.0);
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let n = values.len() as f64;
+    let variance = (n * (n - 1.0) * (2.0 * n + 5.0) - ties) / 18.0;
+    if variance <= 0.0 {
+        return (s, 0.0);
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let z = match s.signum() {
+        1 => (s - 1) as f64 / variance.sqrt(),
+        -1 => (s + 1) as f64 / variance.sqrt(),
+        _ => 0.0,
+    };
+    (s, z)
+}
+
+/// Least-squares slope of the failure rate over the window index, weighted
+/// by the number of outcomes per window.
+fn weighted_slope(buckets: &[TimeBucket]) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let points: Vec<(f64, f64, f64)> = buckets
+        .iter()
+        .enumerate()
+        .map(|(i, b)| (i as f64, f64::from(b.failure_rate), b.total as f64))
+        .collect();
+    let w_sum: f64 = points.iter().map(|p| p.2).sum();
+    if w_sum <= 0.0 {
+        return 0.0;
+    }
+    let x_mean = points.iter().map(|p| p.0 * p.2).sum::<f64>() / w_sum;
+    let y_mean = points.iter().map(|p| p.1 * p.2).sum::<f64>() / w_sum;
+    let sxx: f64 = points.iter().map(|p| p.2 * (p.0 - x_mean).powi(2)).sum();
+    let sxy: f64 = points
+        .iter()
+        .map(|p| p.2 * (p.0 - x_mean) * (p.1 - y_mean))
+        .sum();
+    if sxx > 0.0 {
+        sxy / sxx
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OutcomeType;
+
+    /// Ten outcomes per day; `failures[d]` of them fail on day `d`.
+    fn days(failures: &[usize]) -> Vec<DecisionOutcome> {
+        failures
+            .iter()
+            .enumerate()
+            .flat_map(|(day, &failed)| {
+                (0..10).map(move |i| {
+                    let success = i >= failed;
+                    DecisionOutcome {
+                        decision_id: format!("{day}-{i}"),
+                        ts: format!("2026-03-{:02}T{:02}:00:00Z", day + 2, 8 + i),
+                        policy_id: None,
+                        action: Some("remind.morning".into()),
+                        outcome: if success {
+                            OutcomeType::Success
+                        } else {
+                            OutcomeType::Failure
+                        },
+                        success,
+                        reward: None,
+                        context: None,
+                        metadata: None,
+                    }
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn detects_recovery_and_degradation() {
+        let analyzer = FeedbackAnalyzer::default();
+        let recovering = days(&[9, 9, 8, 7, 5, 4, 3, 2, 1, 1]);
+        let report = analyzer
+            .analyze_trend(&recovering, Window::Daily)
+            .unwrap_or_else(|| panic!("enough windows"));
+        assert_eq!(report.buckets.len(), 10);
+        assert_eq!(report.buckets[0].start, "2026-03-02T00:00:00Z");
+        assert_eq!(report.trend, Trend::Improving);
+        assert!(report.slope_per_window < 0.0);
+        assert!(report.recovered_below(0.5));
+
+        let degrading = days(&[1, 1, 2, 3, 4, 5, 7, 8, 9, 9]);
+        let report = analyzer.analyze_trend(&degrading, Window::Daily);
+        assert_eq!(report.map(|r| r.trend), Some(Trend::Degrading));
+
+        let flat = days(&[5, 4, 5, 6, 5, 4, 5, 6, 5, 5]);
+        let report = analyzer.analyze_trend(&flat, Window::Daily);
+        assert_eq!(report.map(|r| r.trend), Some(Trend::Stable));
+
+        // 2026-03-02 is a Monday: the ten days span two weeks.
+        let weeks = analyzer.bucket_outcomes(&recovering, Window::Weekly);
+        assert_eq!(weeks.len(), 2);
+        assert_eq!(weeks[1].start, "2026-03-09T00:00:00Z");
+        assert_eq!(weeks[0].total, 70);
+    }
+
+    #[test]
+    fn recovered_failure_spike_does_not_drive_a_proposal() {
+        let analyzer = FeedbackAnalyzer::default();
+        let spike = days(&[10, 10, 10, 10, 9, 2, 1, 1, 0, 0]);
+        assert!(analyzer
+            .propose_adjustment("remind-bandit-v1", &spike)
+            .is_none());
+
+        let ongoing = days(&[0, 0, 1, 1, 2, 9, 10, 10, 10, 10]);
+        let proposal = analyzer.propose_adjustment("remind-bandit-v1", &ongoing);
+        assert!(proposal.is_some_and(|p| p.deltas.contains_key("epsilon")));
+    }
+}
@@ -0,0 +1,192 @@
+//! Analysis of guardrail vetoes.
+//!
+//! When a constraint overrides what the learner wanted, the emitting side
+//! records the pair under `metadata.veto` of the [`DecisionOutcome`]:
+//!
+//! ```json
+//! { "veto": { "wanted": "remind.evening", "emitted": "remind.none", "constraint": "quiet_hours" } }
+//! ```
+//!
+//! [`analyze_vetoes`] reports how often constraints fight the learner, which
+//! constraints do so, and how vetoed decisions performed compared to the rest.
+//! This is evidence for either changing constraints or retraining the policy.
+
+use crate::{DecisionOutcome, OutcomeStatistics};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Metadata key under which veto records are stored.
+pub const VETO_METADATA_KEY: &str = "veto";
+
+/// A single veto: the action the learner wanted vs. the one actually emitted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VetoRecord {
+    pub wanted: String,
+    pub emitted: String,
+    /// Name of the constraint that vetoed the decision.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constraint: Option<String>,
+}
+
+impl VetoRecord {
+    /// Extract a veto record from outcome metadata, if present and well-formed.
+    #[must_use]
+    pub fn from_outcome(outcome: &DecisionOutcome) -> Option<Self> {
+        let raw = outcome.metadata.as_ref()?.get(VETO_METADATA_KEY)?;
+        serde_json::from_value(raw.clone()).ok()
+    }
+
+    /// Store this record in the outcome metadata, creating the object if needed.
+    ///
+    /// Non-object metadata is left untouched.
+    pub fn attach(&self, outcome: &mut DecisionOutcome) {
+        let metadata = outcome
+            .metadata
+            .get_or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+        if let (Some(map), Ok(value)) = (metadata.as_object_mut(), serde_json::to_value(self)) {
+            map.insert(VETO_METADATA_KEY.to_string(), value);
+        }
+    }
+}
+
+/// Summary of vetoes across a set of outcomes.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct VetoReport {
+    /// Number of outcomes analyzed.
+    pub decisions: usize,
+    /// Number of outcomes carrying a veto record.
+    pub vetoed: usize,
+    /// Veto count per constraint (`"unknown"` if unnamed).
+    pub by_constraint: BTreeMap<String, usize>,
+    /// Veto count per `"wanted -> emitted"` pair.
+    pub pairs: BTreeMap<String, usize>,
+    /// Success rate of vetoed decisions (explicit outcomes only).
+    pub vetoed_success_rate: f32,
+    /// Success rate of decisions that were not vetoed (explicit outcomes only).
+    pub unvetoed_success_rate: f32,
+}
+
+impl VetoReport {
+    /// Share of decisions that were vetoed (0.0 to 1.0).
+    #[must_use]
+    pub fn veto_rate(&self) -> f32 {
+        if self.decisions == 0 {
+            return 0.0;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        {
+            self.vetoed as f32 / self.decisions as f32
+        }
+    }
+
+    /// Constraint responsible for the most vetoes, if any.
+    #[must_use]
+    pub fn dominant_constraint(&self) -> Option<(&str, usize)> {
+        self.by_constraint
+            .iter()
+            .max_by(|(ka, a), (kb, b)| a.cmp(b).then(kb.cmp(ka)))
+            .map(|(k, n)| (k.as_str(), *n))
+    }
+}
+
+/// Build a [`VetoReport`] from decision outcomes.
+#[must_use]
+pub fn analyze_vetoes(outcomes: &[DecisionOutcome]) -> VetoReport {
+    let mut report = VetoReport {
+        decisions: outcomes.len(),
+        ..VetoReport::default()
+    };
+    let mut vetoed_stats = OutcomeStatistics::default();
+    let mut unvetoed_stats = OutcomeStatistics::default();
+
+    for outcome in outcomes {
+        match VetoRecord::from_outcome(outcome) {
+            Some(veto) => {
+                report.vetoed += 1;
+                let constraint = veto.constraint.unwrap_or_else(|| "unknown".to_string());
+                *report.by_constraint.entry(constraint).or_default() += 1;
+                *report
+                    .pairs
+                    .entry(format!("{} -> {}", veto.wanted, veto.emitted))
+                    .or_default() += 1;
+                vetoed_stats.record(outcome);
+            }
+            None => unvetoed_stats.record(outcome),
+        }
+    }
+
+    report.vetoed_success_rate = vetoed_stats.success_rate();
+    report.unvetoed_success_rate = unvetoed_stats.success_rate();
+    report
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::OutcomeType;
+
+    fn outcome(id: &str, success: bool, veto: Option<(&str, &str, &str)>) -> DecisionOutcome {
+        let mut o = DecisionOutcome {
+            decision_id: id.to_string(),
+            ts: "2026-01-01T00:00:00Z".to_string(),
+            policy_id: None,
+            action: None,
+            outcome: if success {
+                OutcomeType::Success
+            } else {
+                OutcomeType::Failure
+            },
+            success,
+            reward: None,
+            context: None,
+            metadata: None,
+        };
+        if let Some((wanted, emitted, constraint)) = veto {
+            VetoRecord {
+                wanted: wanted.into(),
+                emitted: emitted.into(),
+                constraint: Some(constraint.into()),
+            }
+            .attach(&mut o);
+        }
+        o
+    }
+
+    #[test]
+    fn report_counts_pairs_and_constraints() {
+        let outcomes = vec![
+            outcome(
+                "1",
+                false,
+                Some(("remind.evening", "remind.none", "quiet_hours")),
+            ),
+            outcome(
+                "2",
+                false,
+                Some(("remind.evening", "remind.none", "quiet_hours")),
+            ),
+            outcome(
+                "3",
+                true,
+                Some(("remind.morning", "remind.afternoon", "fatigue")),
+            ),
+            outcome("4", true, None),
+        ];
+        let report = analyze_vetoes(&outcomes);
+        assert_eq!(report.vetoed, 3);
+        assert!((report.veto_rate() - 0.75).abs() < f32::EPSILON);
+        assert_eq!(report.pairs["remind.evening -> remind.none"], 2);
+        assert_eq!(report.dominant_constraint(), Some(("quiet_hours", 2)));
+        assert!((report.unvetoed_success_rate - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn malformed_veto_metadata_is_ignored() {
+        let mut o = outcome("1", true, None);
+        o.metadata = Some(serde_json::json!({ "veto": "yes" }));
+        assert!(VetoRecord::from_outcome(&o).is_none());
+        assert_eq!(analyze_vetoes(&[o]).vetoed, 0);
+    }
+}
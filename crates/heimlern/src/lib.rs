@@ -0,0 +1,116 @@
+#![warn(clippy::unwrap_used, clippy::expect_used)]
+
+//! Einstiegspunkt für Integrationen: bündelt `heimlern-core`,
+//! `heimlern-bandits` und `heimlern-feedback` hinter einer Abhängigkeit.
+//!
+//! Die Einzel-Crates bleiben unter [`core`], [`bandits`] und [`feedback`]
+//! erreichbar; für den Alltag genügt meist `use heimlern::prelude::*;`:
+//!
+//! ```text
+//! let mut bandit = RemindBandit::builder()
+//!     .epsilon(0.1)
+//!     .slots(["morning", "evening"])
+//!     .seed(7)
+//!     .build()?;
+//! let analyzer = FeedbackAnalyzer::builder().min_decisions(20).build();
+//! ```
+
+pub use heimlern_bandits as bandits;
+pub use heimlern_core as core;
+pub use heimlern_feedback as feedback;
+
+/// Die gebräuchlichsten Typen aller drei Crates.
+pub mod prelude {
+    pub use heimlern_core::dedup::DedupFilter;
+    pub use heimlern_core::enrich::{ContextEnricher, EnrichedPolicy, SharedFeatures, TimeOfDay};
+    pub use heimlern_core::ensemble::{Combine, EnsemblePolicy};
+    pub use heimlern_core::envelope::SnapshotEnvelope;
+    pub use heimlern_core::fallback::FallbackPolicy;
+    pub use heimlern_core::filter::EventFilter;
+    pub use heimlern_core::guard::{GuardRules, GuardedPolicy, QuietHours};
+    pub use heimlern_core::mapping::{ContextMapping, FeatureSource};
+    pub use heimlern_core::metrics::{MeteredPolicy, PolicyMetrics};
+    pub use heimlern_core::pending::{PendingDecision, PendingDecisions};
+    pub use heimlern_core::shaping::{RawReward, RewardShaper, RewardShaping, ShapedPolicy};
+    #[cfg(feature = "telemetry")]
+    pub use heimlern_core::telemetry::InstrumentedPolicy;
+    pub use heimlern_core::{
+        Chosen, Context, Decision, HeimlernError, Policy, PolicyDescriptor, PolicyError, TryPolicy,
+        Uncertainty,
+    };
+
+    pub use heimlern_bandits::{
+        ArmMeta, ArmPrior, AuditConfig, BanditError, CooldownConfig, DecayConfig, EvictionStrategy,
+        FatigueConfig, GaussianThompsonBandit, LinUcbBandit, RecencyConfig, RemindBandit,
+        RemindBanditBuilder, SnapshotVersion, ThompsonBandit, TimingConfig, UcbBandit,
+        WarmupConfig, WindowConfig,
+    };
+
+    pub use heimlern_feedback::apply::{apply_proposal, apply_to_params};
+    pub use heimlern_feedback::explain::Language;
+    pub use heimlern_feedback::{
+        DecisionOutcome, DeltaValue, FeedbackAnalyzer, FeedbackAnalyzerBuilder, OutcomeType,
+        ProposalStatus, WeightAdjustmentProposal,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::prelude::*;
+
+    #[test]
+    fn prelude_covers_decide_feedback_and_analysis() {
+        let Ok(mut bandit) = RemindBandit::builder()
+            .epsilon(0.0)
+            .slots(["morning", "evening"])
+            .seed(7)
+            .build()
+        else {
+            panic!("builder should accept valid slots");
+        };
+        let ctx = Context {
+            kind: crate::core::kind::REMINDER.into(),
+            features: serde_json::json!({}),
+        };
+        let items: Vec<(Context, String, f32)> = (0..20)
+            .map(|i| {
+                (
+                    ctx.clone(),
+                    "remind.evening".to_string(),
+                    (i % 4 == 0) as u8 as f32,
+                )
+            })
+            .collect();
+        bandit.feedback_batch(&items);
+        assert_eq!(bandit.decide(&ctx).action, "remind.evening");
+
+        let outcomes: Vec<DecisionOutcome> = items
+            .iter()
+            .enumerate()
+            .map(|(i, (_, action, reward))| DecisionOutcome {
+                decision_id: i.to_string(),
+                ts: "2026-01-01T00:00:00Z".into(),
+                policy_id: Some("remind-bandit".into()),
+                action: Some(action.clone()),
+                outcome: if *reward > 0.0 {
+                    OutcomeType::Success
+                } else {
+                    OutcomeType::Failure
+                },
+                success: *reward > 0.0,
+                reward: Some(*reward),
+                context: None,
+                metadata: None,
+            })
+            .collect();
+        let analyzer = FeedbackAnalyzer::builder()
+            .min_decisions(10)
+            .language(Language::De)
+            .build();
+        let Some(proposal) = analyzer.propose_for(&bandit.descriptor(), &outcomes) else {
+            panic!("high failure rate should yield a proposal");
+        };
+        assert!(proposal.deltas.contains_key("epsilon"));
+        assert!(apply_to_params(&proposal, &bandit.descriptor(), &bandit.tunable_params()).is_ok());
+    }
+}